@@ -83,6 +83,30 @@ pub struct Parameters {
     #[serde(default = "Parameters::default_commit_sync_batches_ahead")]
     pub commit_sync_batches_ahead: usize,
 
+    /// Number of most recent commits kept in the store, and served to peers syncing commits.
+    /// Older commits are deleted once handled by the commit consumer, together with the blocks
+    /// that can no longer be included in a retained commit. This requires GC to be enabled in the
+    /// protocol config for blocks to be deleted. Zero disables pruning.
+    ///
+    /// If unspecified, this will default to 0 and the store is only cleaned up between epochs.
+    #[serde(default = "Parameters::default_store_commit_retention")]
+    pub store_commit_retention: u32,
+
+    /// Percentage of the transaction count and byte limits of each proposed block reserved for
+    /// the system transaction lane. Capacity left unused by a lane is offered to the others, in
+    /// lane priority order. The quotas of all lanes should add up to at most 100.
+    #[serde(default = "Parameters::default_system_lane_block_quota_percent")]
+    pub system_lane_block_quota_percent: u8,
+
+    /// Percentage of the transaction count and byte limits of each proposed block reserved for
+    /// the user transaction lane.
+    #[serde(default = "Parameters::default_user_lane_block_quota_percent")]
+    pub user_lane_block_quota_percent: u8,
+
+    /// Compression of serialized blocks exchanged with peers and written to the store.
+    #[serde(default = "BlockCompressionParameters::default")]
+    pub block_compression: BlockCompressionParameters,
+
     /// Anemo network settings.
     #[serde(default = "AnemoParameters::default")]
     pub anemo: AnemoParameters,
@@ -174,6 +198,19 @@ impl Parameters {
         // while keeping the total number of inflight fetches and unprocessed fetched commits limited.
         32
     }
+
+    pub(crate) fn default_store_commit_retention() -> u32 {
+        0
+    }
+
+    pub(crate) fn default_system_lane_block_quota_percent() -> u8 {
+        // System transactions are few and small, but must never wait behind user traffic.
+        20
+    }
+
+    pub(crate) fn default_user_lane_block_quota_percent() -> u8 {
+        80
+    }
 }
 
 impl Default for Parameters {
@@ -194,12 +231,56 @@ impl Default for Parameters {
             commit_sync_parallel_fetches: Parameters::default_commit_sync_parallel_fetches(),
             commit_sync_batch_size: Parameters::default_commit_sync_batch_size(),
             commit_sync_batches_ahead: Parameters::default_commit_sync_batches_ahead(),
+            store_commit_retention: Parameters::default_store_commit_retention(),
+            system_lane_block_quota_percent: Parameters::default_system_lane_block_quota_percent(),
+            user_lane_block_quota_percent: Parameters::default_user_lane_block_quota_percent(),
+            block_compression: BlockCompressionParameters::default(),
             anemo: AnemoParameters::default(),
             tonic: TonicParameters::default(),
         }
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BlockCompressionParameters {
+    /// Whether blocks fetched by or streamed to peers are zstd compressed. Compression is only
+    /// applied when the receiving peer asks for it, so nodes with and without it enabled can
+    /// be mixed in a committee.
+    ///
+    /// If unspecified, this will default to false.
+    #[serde(default)]
+    pub network: bool,
+
+    /// Whether blocks are zstd compressed before being written to the store. Blocks written
+    /// uncompressed remain readable after enabling it, and vice versa.
+    ///
+    /// If unspecified, this will default to false.
+    #[serde(default)]
+    pub storage: bool,
+
+    /// zstd compression level. Higher levels trade CPU time for a better compression ratio.
+    ///
+    /// If unspecified, this will default to 3.
+    #[serde(default = "BlockCompressionParameters::default_level")]
+    pub level: i32,
+}
+
+impl BlockCompressionParameters {
+    fn default_level() -> i32 {
+        3
+    }
+}
+
+impl Default for BlockCompressionParameters {
+    fn default() -> Self {
+        Self {
+            network: false,
+            storage: false,
+            level: BlockCompressionParameters::default_level(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct AnemoParameters {
     /// Size in bytes above which network messages are considered excessively large. Excessively
@@ -21,12 +21,13 @@ use crate::{
     core::{Core, CoreSignals},
     core_thread::{ChannelCoreThreadDispatcher, CoreThreadHandle},
     dag_state::DagState,
+    health_monitor::HealthMonitor,
     leader_schedule::{LeaderSchedule, LeaderSwapTable},
     leader_timeout::{LeaderTimeoutTask, LeaderTimeoutTaskHandle},
     metrics::initialise_metrics,
     network::{
         anemo_network::AnemoManager, tonic_network::TonicManager, NetworkClient as _,
-        NetworkManager,
+        NetworkManager, NetworkService,
     },
     round_prober::{RoundProber, RoundProberHandle},
     storage::rocksdb_store::RocksDBStore,
@@ -119,6 +120,13 @@ impl ConsensusAuthority {
         }
     }
 
+    pub fn health_monitor(&self) -> Arc<HealthMonitor> {
+        match self {
+            Self::WithAnemo(authority) => authority.health_monitor(),
+            Self::WithTonic(authority) => authority.health_monitor(),
+        }
+    }
+
     #[cfg(test)]
     fn context(&self) -> &Arc<Context> {
         match self {
@@ -136,15 +144,19 @@ impl ConsensusAuthority {
     }
 }
 
-pub(crate) struct AuthorityNode<N>
+/// An authority node, serving peers with `S`. It is the `AuthorityService` of the node, unless
+/// tests wrap it to alter what peers are served.
+pub(crate) struct AuthorityNode<N, S = AuthorityService<ChannelCoreThreadDispatcher>>
 where
-    N: NetworkManager<AuthorityService<ChannelCoreThreadDispatcher>>,
+    N: NetworkManager<S>,
+    S: NetworkService,
 {
     context: Arc<Context>,
     start_time: Instant,
     transaction_client: Arc<TransactionClient>,
     synchronizer: Arc<SynchronizerHandle>,
     commit_consumer_monitor: Arc<CommitConsumerMonitor>,
+    health_monitor: Arc<HealthMonitor>,
 
     commit_syncer_handle: CommitSyncerHandle,
     round_prober_handle: Option<RoundProberHandle>,
@@ -175,6 +187,49 @@ where
         commit_consumer: CommitConsumer,
         registry: Registry,
         boot_counter: u64,
+    ) -> Self {
+        Self::start_with_service(
+            own_index,
+            committee,
+            parameters,
+            protocol_config,
+            protocol_keypair,
+            network_keypair,
+            transaction_verifier,
+            commit_consumer,
+            registry,
+            boot_counter,
+            |_core| {},
+            |_context, service| service,
+        )
+        .await
+    }
+}
+
+impl<N, S> AuthorityNode<N, S>
+where
+    N: NetworkManager<S>,
+    S: NetworkService,
+{
+    /// Starts the authority, with `configure_core` applied to its `Core` before it starts, and
+    /// `make_service` creating the service installed on the network from the `AuthorityService`
+    /// of the authority.
+    pub(crate) async fn start_with_service(
+        own_index: AuthorityIndex,
+        committee: Committee,
+        parameters: Parameters,
+        protocol_config: ProtocolConfig,
+        protocol_keypair: ProtocolKeyPair,
+        network_keypair: NetworkKeyPair,
+        transaction_verifier: Arc<dyn TransactionVerifier>,
+        commit_consumer: CommitConsumer,
+        registry: Registry,
+        boot_counter: u64,
+        configure_core: impl FnOnce(&mut Core),
+        make_service: impl FnOnce(
+            Arc<Context>,
+            Arc<AuthorityService<ChannelCoreThreadDispatcher>>,
+        ) -> Arc<S>,
     ) -> Self {
         info!(
             "Starting consensus authority {}\n{:#?}\n{:#?}\n{:?}\nBoot counter: {}",
@@ -212,7 +267,7 @@ where
         };
 
         let store_path = context.parameters.db_path.as_path().to_str().unwrap();
-        let store = Arc::new(RocksDBStore::new(store_path));
+        let store = Arc::new(RocksDBStore::new(store_path).with_context(context.clone()));
         let dag_state = Arc::new(RwLock::new(DagState::new(context.clone(), store.clone())));
 
         let highest_known_commit_at_startup = dag_state.read().last_commit_index();
@@ -259,7 +314,14 @@ where
             leader_schedule.clone(),
         );
 
-        let core = Core::new(
+        let commit_vote_monitor = Arc::new(CommitVoteMonitor::new(context.clone()));
+        let health_monitor = Arc::new(HealthMonitor::new(
+            context.clone(),
+            dag_state.clone(),
+            commit_vote_monitor.clone(),
+        ));
+
+        let mut core = Core::new(
             context.clone(),
             leader_schedule,
             tx_consumer,
@@ -271,8 +333,10 @@ where
             core_signals,
             protocol_keypair,
             dag_state.clone(),
+            health_monitor.clone(),
             sync_last_known_own_block,
         );
+        configure_core(&mut core);
 
         let (core_dispatcher, core_thread_handle) =
             ChannelCoreThreadDispatcher::start(context.clone(), &dag_state, core);
@@ -280,8 +344,6 @@ where
         let leader_timeout_handle =
             LeaderTimeoutTask::start(core_dispatcher.clone(), &signals_receivers, context.clone());
 
-        let commit_vote_monitor = Arc::new(CommitVoteMonitor::new(context.clone()));
-
         let synchronizer = Synchronizer::start(
             network_client.clone(),
             context.clone(),
@@ -309,6 +371,7 @@ where
                     context.clone(),
                     core_dispatcher.clone(),
                     dag_state.clone(),
+                    health_monitor.clone(),
                     network_client.clone(),
                 )
                 .start(),
@@ -345,7 +408,9 @@ where
             None
         };
 
-        network_manager.install_service(network_service).await;
+        network_manager
+            .install_service(make_service(context.clone(), network_service))
+            .await;
 
         info!(
             "Consensus authority started, took {:?}",
@@ -360,6 +425,7 @@ where
             commit_syncer_handle,
             round_prober_handle,
             commit_consumer_monitor,
+            health_monitor,
             leader_timeout_handle,
             core_thread_handle,
             broadcaster,
@@ -416,6 +482,10 @@ where
     pub(crate) async fn replay_complete(&self) {
         self.commit_consumer_monitor.replay_complete().await;
     }
+
+    pub(crate) fn health_monitor(&self) -> Arc<HealthMonitor> {
+        self.health_monitor.clone()
+    }
 }
 
 #[cfg(test)]
@@ -436,7 +506,11 @@ mod tests {
 
     use super::*;
     use crate::block::GENESIS_ROUND;
-    use crate::{block::BlockAPI as _, transaction::NoopTransactionVerifier, CommittedSubDag};
+    use crate::{
+        block::BlockAPI as _,
+        transaction::{NoopTransactionVerifier, TransactionLane},
+        CommittedSubDag,
+    };
 
     #[rstest]
     #[tokio::test]
@@ -523,7 +597,7 @@ mod tests {
             submitted_transactions.insert(txn.clone());
             authorities[i as usize % authorities.len()]
                 .transaction_client()
-                .submit(vec![txn])
+                .submit(TransactionLane::User, vec![txn])
                 .await
                 .unwrap();
         }
@@ -711,22 +711,68 @@ impl TestBlock {
         self
     }
 
+    pub fn set_misbehavior_reports(mut self, misbehavior_reports: Vec<MisbehaviorReport>) -> Self {
+        self.block.misbehavior_reports = misbehavior_reports;
+        self
+    }
+
     pub fn build(self) -> Block {
         Block::V1(self.block)
     }
 }
 
+/// Maximum number of misbehavior reports a block can attach.
+pub(crate) const MAX_MISBEHAVIOR_REPORTS_PER_BLOCK: usize = 10;
+
 /// A block can attach reports of misbehavior by other authorities.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct MisbehaviorReport {
     pub target: AuthorityIndex,
     pub proof: MisbehaviorProof,
 }
 
+impl MisbehaviorReport {
+    pub(crate) fn new(proof: MisbehaviorProof) -> Self {
+        Self {
+            target: proof.author(),
+            proof,
+        }
+    }
+}
+
 /// Proof of misbehavior are usually signed block(s) from the misbehaving authority.
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum MisbehaviorProof {
     InvalidBlock(BlockRef),
+    /// Two different blocks from the same slot. The refs are ordered by digest.
+    Equivocation(BlockRef, BlockRef),
+}
+
+impl MisbehaviorProof {
+    /// Creates an equivocation proof from two blocks of the same slot, in canonical order.
+    pub(crate) fn equivocation(a: BlockRef, b: BlockRef) -> Self {
+        if a <= b {
+            MisbehaviorProof::Equivocation(a, b)
+        } else {
+            MisbehaviorProof::Equivocation(b, a)
+        }
+    }
+
+    /// Returns the author of the block(s) in the proof.
+    pub fn author(&self) -> AuthorityIndex {
+        match self {
+            MisbehaviorProof::InvalidBlock(block_ref) => block_ref.author,
+            MisbehaviorProof::Equivocation(block_ref, _) => block_ref.author,
+        }
+    }
+
+    /// Returns a short name for the kind of misbehavior, used in metrics and logs.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MisbehaviorProof::InvalidBlock(_) => "invalid_block",
+            MisbehaviorProof::Equivocation(_, _) => "equivocation",
+        }
+    }
 }
 
 // TODO: add basic verification for BlockRef and BlockDigest.
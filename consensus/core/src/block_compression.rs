@@ -0,0 +1,178 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{io::Read as _, time::Instant};
+
+use bytes::Bytes;
+
+use crate::{
+    context::Context,
+    error::{ConsensusError, ConsensusResult},
+    metrics::NodeMetrics,
+};
+
+/// Magic number starting every zstd frame. A serialized `SignedBlock` starts with the BCS tag of
+/// its `Block` variant, so it can never be mistaken for a compressed block.
+const ZSTD_MAGIC_NUMBER: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Upper bound on the size of a decompressed block, so a peer cannot exhaust memory by sending
+/// a small block that decompresses into a huge one. Valid blocks are far smaller.
+const MAX_DECOMPRESSED_BLOCK_SIZE: u64 = 64 << 20;
+
+/// Where compressed blocks are sent to, for labeling metrics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CompressionPath {
+    Network,
+    Storage,
+}
+
+impl CompressionPath {
+    fn label(&self) -> &'static str {
+        match self {
+            CompressionPath::Network => "network",
+            CompressionPath::Storage => "storage",
+        }
+    }
+}
+
+/// Returns true if the data is a zstd compressed block rather than a serialized one.
+fn is_compressed_block(data: &[u8]) -> bool {
+    data.starts_with(&ZSTD_MAGIC_NUMBER)
+}
+
+/// Compresses a serialized block with zstd at the given level.
+pub(crate) fn compress_block(
+    metrics: &NodeMetrics,
+    path: CompressionPath,
+    level: i32,
+    serialized: &[u8],
+) -> ConsensusResult<Bytes> {
+    let start = Instant::now();
+    let compressed = zstd::bulk::compress(serialized, level)
+        .map_err(|e| ConsensusError::BlockCompressionFailure(e.to_string()))?;
+    metrics
+        .block_compression_latency
+        .with_label_values(&[path.label(), "compress"])
+        .observe(start.elapsed().as_secs_f64());
+    if !compressed.is_empty() {
+        metrics
+            .block_compression_ratio
+            .with_label_values(&[path.label()])
+            .observe(serialized.len() as f64 / compressed.len() as f64);
+    }
+    Ok(compressed.into())
+}
+
+/// Returns the serialized block from data produced by `compress_block()`. Data that is not
+/// compressed is returned as is, so blocks from peers or stores not using compression are
+/// handled transparently.
+pub(crate) fn decompress_block(
+    metrics: Option<&NodeMetrics>,
+    path: CompressionPath,
+    data: Bytes,
+) -> ConsensusResult<Bytes> {
+    if !is_compressed_block(&data) {
+        return Ok(data);
+    }
+    let start = Instant::now();
+    let mut decompressed = Vec::new();
+    zstd::stream::read::Decoder::new(&data[..])
+        .and_then(|decoder| {
+            decoder
+                .take(MAX_DECOMPRESSED_BLOCK_SIZE + 1)
+                .read_to_end(&mut decompressed)
+        })
+        .map_err(|e| ConsensusError::BlockCompressionFailure(e.to_string()))?;
+    if decompressed.len() as u64 > MAX_DECOMPRESSED_BLOCK_SIZE {
+        return Err(ConsensusError::BlockCompressionFailure(format!(
+            "decompressed block exceeds {MAX_DECOMPRESSED_BLOCK_SIZE} bytes"
+        )));
+    }
+    if let Some(metrics) = metrics {
+        metrics
+            .block_compression_latency
+            .with_label_values(&[path.label(), "decompress"])
+            .observe(start.elapsed().as_secs_f64());
+    }
+    Ok(decompressed.into())
+}
+
+/// Prepares a serialized block to be sent to a peer. The block is compressed only when the peer
+/// asked for compressed blocks and network compression is enabled on this authority.
+pub(crate) fn compress_block_for_peer(
+    context: &Context,
+    peer_accepts_compressed: bool,
+    serialized: Bytes,
+) -> ConsensusResult<Bytes> {
+    let config = &context.parameters.block_compression;
+    if !peer_accepts_compressed || !config.network {
+        return Ok(serialized);
+    }
+    compress_block(
+        &context.metrics.node_metrics,
+        CompressionPath::Network,
+        config.level,
+        &serialized,
+    )
+}
+
+/// Returns the serialized block from a block received from a peer, whether or not the peer
+/// compressed it.
+pub(crate) fn decompress_block_from_peer(
+    context: &Context,
+    block: Bytes,
+) -> ConsensusResult<Bytes> {
+    decompress_block(
+        Some(&context.metrics.node_metrics),
+        CompressionPath::Network,
+        block,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::{TestBlock, Transaction, VerifiedBlock};
+
+    #[test]
+    fn compress_and_decompress_block() {
+        let (context, _) = Context::new_for_test(4);
+        let metrics = &context.metrics.node_metrics;
+        let block = VerifiedBlock::new_for_test(
+            TestBlock::new(10, 1)
+                .set_transactions(vec![Transaction::new(vec![7u8; 4096]); 8])
+                .build(),
+        );
+        let serialized = block.serialized().clone();
+        assert!(!is_compressed_block(&serialized));
+
+        let compressed = compress_block(metrics, CompressionPath::Network, 3, &serialized).unwrap();
+        assert!(is_compressed_block(&compressed));
+        assert!(compressed.len() < serialized.len());
+
+        let decompressed =
+            decompress_block(Some(metrics), CompressionPath::Network, compressed).unwrap();
+        assert_eq!(decompressed, serialized);
+
+        // Uncompressed blocks are passed through.
+        let passed = decompress_block(None, CompressionPath::Storage, serialized.clone()).unwrap();
+        assert_eq!(passed, serialized);
+    }
+
+    #[test]
+    fn reject_oversized_decompressed_block() {
+        let (context, _) = Context::new_for_test(4);
+        let zeros = vec![0u8; MAX_DECOMPRESSED_BLOCK_SIZE as usize + 1];
+        let compressed = compress_block(
+            &context.metrics.node_metrics,
+            CompressionPath::Network,
+            1,
+            &zeros,
+        )
+        .unwrap();
+        assert!(matches!(
+            decompress_block(None, CompressionPath::Network, compressed),
+            Err(ConsensusError::BlockCompressionFailure(_))
+        ));
+    }
+}
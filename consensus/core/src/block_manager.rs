@@ -152,6 +152,8 @@ impl BlockManager {
                     }
                     if let Err(e) = self.block_verifier.check_ancestors(&b, &ancestor_blocks) {
                         warn!("Block {:?} failed to verify ancestors: {}", b, e);
+                        // The block is signed by its author, so it can be reported as invalid.
+                        self.dag_state.write().report_invalid_block(b.reference());
                         blocks_to_reject.insert(b.reference(), b);
                     } else {
                         blocks_to_accept.insert(b.reference(), b);
@@ -175,8 +177,6 @@ impl BlockManager {
                 warn!("Invalid block {:?} is rejected", block);
             }
 
-            // TODO: report blocks_to_reject to peers.
-
             // Insert the accepted blocks into DAG state so future blocks including them as
             // ancestors do not get suspended.
             let blocks_to_accept: Vec<_> = blocks_to_accept.into_values().collect();
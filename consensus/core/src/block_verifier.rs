@@ -5,8 +5,8 @@ use std::{collections::BTreeSet, sync::Arc};
 
 use crate::{
     block::{
-        genesis_blocks, BlockAPI, BlockRef, BlockTimestampMs, SignedBlock, VerifiedBlock,
-        GENESIS_ROUND,
+        genesis_blocks, BlockAPI, BlockRef, BlockTimestampMs, MisbehaviorProof, SignedBlock,
+        VerifiedBlock, GENESIS_ROUND, MAX_MISBEHAVIOR_REPORTS_PER_BLOCK,
     },
     context::Context,
     error::{ConsensusError, ConsensusResult},
@@ -56,6 +56,57 @@ impl SignedBlockVerifier {
             transaction_verifier,
         }
     }
+
+    /// Checks that the misbehavior reports attached to a block are well formed. The referenced
+    /// blocks may not be available locally, so whether the reported misbehavior actually happened
+    /// is not verified here. Instead, reports are only trusted once enough stake vouches for them.
+    fn verify_misbehavior_reports(&self, block: &SignedBlock) -> ConsensusResult<()> {
+        let committee = &self.context.committee;
+        let reports = block.misbehavior_reports();
+        if reports.len() > MAX_MISBEHAVIOR_REPORTS_PER_BLOCK {
+            return Err(ConsensusError::TooManyMisbehaviorReports {
+                count: reports.len(),
+                limit: MAX_MISBEHAVIOR_REPORTS_PER_BLOCK,
+            });
+        }
+        let mut seen_reports = BTreeSet::new();
+        for report in reports {
+            let invalid = |reason: &str| {
+                ConsensusError::InvalidMisbehaviorReport(format!("{report:?}: {reason}"))
+            };
+            if !committee.is_valid_index(report.target) {
+                return Err(invalid("invalid target authority"));
+            }
+            if report.target == block.author() {
+                return Err(invalid("authority reports itself"));
+            }
+            if report.proof.author() != report.target {
+                return Err(invalid("proof is not about the target authority"));
+            }
+            match &report.proof {
+                MisbehaviorProof::InvalidBlock(block_ref) => {
+                    if block_ref.round == GENESIS_ROUND {
+                        return Err(invalid("genesis block cannot be invalid"));
+                    }
+                }
+                MisbehaviorProof::Equivocation(first, second) => {
+                    if first.author != second.author || first.round != second.round {
+                        return Err(invalid("blocks are not from the same slot"));
+                    }
+                    if first >= second {
+                        return Err(invalid("blocks are identical or not in canonical order"));
+                    }
+                    if first.round == GENESIS_ROUND {
+                        return Err(invalid("genesis blocks cannot equivocate"));
+                    }
+                }
+            }
+            if !seen_reports.insert(report) {
+                return Err(invalid("duplicated report"));
+            }
+        }
+        Ok(())
+    }
 }
 
 // All block verification logic are implemented below.
@@ -142,6 +193,10 @@ impl BlockVerifier for SignedBlockVerifier {
             });
         }
 
+        if self.context.protocol_config.consensus_misbehavior_reports() {
+            self.verify_misbehavior_reports(block)?;
+        }
+
         let batch: Vec<_> = block.transactions().iter().map(|t| t.data()).collect();
 
         let max_transaction_size_limit =
@@ -227,7 +282,9 @@ mod test {
 
     use super::*;
     use crate::{
-        block::{BlockDigest, BlockRef, TestBlock, Transaction, TransactionIndex},
+        block::{
+            BlockDigest, BlockRef, MisbehaviorReport, TestBlock, Transaction, TransactionIndex,
+        },
         context::Context,
         transaction::{TransactionVerifier, ValidationError},
     };
@@ -522,6 +579,136 @@ mod test {
                 Err(ConsensusError::TooManyTransactionBytes { size: _, limit: _ })
             ));
         }
+
+        let block_ref = |author: u32, digest: BlockDigest| {
+            BlockRef::new(8, AuthorityIndex::new_for_test(author), digest)
+        };
+
+        // Block with valid misbehavior reports.
+        {
+            let block = test_block
+                .clone()
+                .set_misbehavior_reports(vec![
+                    MisbehaviorReport::new(MisbehaviorProof::equivocation(
+                        block_ref(1, BlockDigest::MAX),
+                        block_ref(1, BlockDigest::MIN),
+                    )),
+                    MisbehaviorReport::new(MisbehaviorProof::InvalidBlock(block_ref(
+                        3,
+                        BlockDigest::MIN,
+                    ))),
+                ])
+                .build();
+            let signed_block = SignedBlock::new(block, authority_2_protocol_keypair).unwrap();
+            verifier.verify(&signed_block).unwrap();
+        }
+
+        // Block with a misbehavior report against its own author.
+        {
+            let block = test_block
+                .clone()
+                .set_misbehavior_reports(vec![MisbehaviorReport::new(
+                    MisbehaviorProof::InvalidBlock(block_ref(2, BlockDigest::MIN)),
+                )])
+                .build();
+            let signed_block = SignedBlock::new(block, authority_2_protocol_keypair).unwrap();
+            assert!(matches!(
+                verifier.verify(&signed_block),
+                Err(ConsensusError::InvalidMisbehaviorReport(_))
+            ));
+        }
+
+        // Block with a misbehavior report whose target does not match the proof.
+        {
+            let block = test_block
+                .clone()
+                .set_misbehavior_reports(vec![MisbehaviorReport {
+                    target: AuthorityIndex::new_for_test(1),
+                    proof: MisbehaviorProof::InvalidBlock(block_ref(3, BlockDigest::MIN)),
+                }])
+                .build();
+            let signed_block = SignedBlock::new(block, authority_2_protocol_keypair).unwrap();
+            assert!(matches!(
+                verifier.verify(&signed_block),
+                Err(ConsensusError::InvalidMisbehaviorReport(_))
+            ));
+        }
+
+        // Block with an equivocation proof over the same block.
+        {
+            let block = test_block
+                .clone()
+                .set_misbehavior_reports(vec![MisbehaviorReport::new(
+                    MisbehaviorProof::Equivocation(
+                        block_ref(1, BlockDigest::MIN),
+                        block_ref(1, BlockDigest::MIN),
+                    ),
+                )])
+                .build();
+            let signed_block = SignedBlock::new(block, authority_2_protocol_keypair).unwrap();
+            assert!(matches!(
+                verifier.verify(&signed_block),
+                Err(ConsensusError::InvalidMisbehaviorReport(_))
+            ));
+        }
+
+        // Block with an equivocation proof over different slots.
+        {
+            let block = test_block
+                .clone()
+                .set_misbehavior_reports(vec![MisbehaviorReport::new(
+                    MisbehaviorProof::equivocation(
+                        block_ref(1, BlockDigest::MIN),
+                        BlockRef::new(9, AuthorityIndex::new_for_test(1), BlockDigest::MAX),
+                    ),
+                )])
+                .build();
+            let signed_block = SignedBlock::new(block, authority_2_protocol_keypair).unwrap();
+            assert!(matches!(
+                verifier.verify(&signed_block),
+                Err(ConsensusError::InvalidMisbehaviorReport(_))
+            ));
+        }
+
+        // Block with duplicated misbehavior reports.
+        {
+            let report = MisbehaviorReport::new(MisbehaviorProof::InvalidBlock(block_ref(
+                3,
+                BlockDigest::MIN,
+            )));
+            let block = test_block
+                .clone()
+                .set_misbehavior_reports(vec![report.clone(), report])
+                .build();
+            let signed_block = SignedBlock::new(block, authority_2_protocol_keypair).unwrap();
+            assert!(matches!(
+                verifier.verify(&signed_block),
+                Err(ConsensusError::InvalidMisbehaviorReport(_))
+            ));
+        }
+
+        // Block with too many misbehavior reports.
+        {
+            let block = test_block
+                .clone()
+                .set_misbehavior_reports(
+                    (0..=MAX_MISBEHAVIOR_REPORTS_PER_BLOCK)
+                        .map(|round| {
+                            MisbehaviorReport::new(MisbehaviorProof::InvalidBlock(BlockRef::new(
+                                round as u32 + 1,
+                                AuthorityIndex::new_for_test(3),
+                                BlockDigest::MIN,
+                            )))
+                        })
+                        .collect(),
+                )
+                .build();
+            let signed_block = SignedBlock::new(block, authority_2_protocol_keypair).unwrap();
+            assert!(matches!(
+                verifier.verify(&signed_block),
+                Err(ConsensusError::TooManyMisbehaviorReports { count: _, limit: _ })
+            ));
+        }
     }
 
     #[tokio::test]
@@ -0,0 +1,51 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Injects Byzantine behavior into the `Core` of an authority for tests. Where `ByzantineService`
+//! alters honest blocks on their way to each peer, misbehavior here happens when blocks are
+//! proposed: Core signs the conflicting blocks itself, and broadcasts them to every peer like its
+//! actual proposals.
+
+use consensus_config::ProtocolKeyPair;
+
+use crate::{
+    block::{BlockAPI as _, SignedBlock, Transaction, VerifiedBlock},
+    error::{ConsensusError, ConsensusResult},
+    network::byzantine_service::copy_block,
+};
+
+/// Misbehavior of the `Core` of a Byzantine authority.
+#[derive(Clone, Debug)]
+pub(crate) enum ByzantineCoreBehavior {
+    /// Proposes `conflicting_blocks` more blocks for each own slot, which differ from the actual
+    /// proposal by their transactions. Only the actual proposal is accepted in the own DAG and
+    /// built upon. Own conflicting blocks received back from peers are dropped, as the own DAG
+    /// can hold a single block per own slot.
+    Equivocate { conflicting_blocks: usize },
+}
+
+impl ByzantineCoreBehavior {
+    /// Returns the blocks to broadcast in addition to the own `block` just proposed.
+    pub(crate) fn extra_proposals(
+        &self,
+        block: &VerifiedBlock,
+        block_signer: &ProtocolKeyPair,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        match self {
+            ByzantineCoreBehavior::Equivocate { conflicting_blocks } => (0..*conflicting_blocks)
+                .map(|i| {
+                    let mut transactions = block.transactions().to_vec();
+                    transactions.push(Transaction::new(format!("equivocation {i}").into_bytes()));
+                    let signed = SignedBlock::new(
+                        copy_block(block).set_transactions(transactions).build(),
+                        block_signer,
+                    )?;
+                    let serialized = signed
+                        .serialize()
+                        .map_err(ConsensusError::SerializationFailure)?;
+                    Ok(VerifiedBlock::new_verified(signed, serialized))
+                })
+                .collect(),
+        }
+    }
+}
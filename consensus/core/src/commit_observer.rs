@@ -5,12 +5,13 @@ use std::{sync::Arc, time::Duration};
 
 use mysten_metrics::monitored_mpsc::UnboundedSender;
 use parking_lot::RwLock;
-use tokio::time::Instant;
-use tracing::{debug, info};
+use tokio::{task::JoinHandle, time::Instant};
+use tracing::{debug, info, warn};
 
 use crate::{
-    block::{BlockAPI, VerifiedBlock},
-    commit::{load_committed_subdag_from_store, CommitAPI, CommitIndex},
+    block::{BlockAPI, Round, VerifiedBlock, GENESIS_ROUND},
+    commit::{load_committed_subdag_from_store, CommitAPI, CommitIndex, GENESIS_COMMIT_INDEX},
+    commit_consumer::CommitConsumerMonitor,
     context::Context,
     dag_state::DagState,
     error::{ConsensusError, ConsensusResult},
@@ -42,9 +43,19 @@ pub(crate) struct CommitObserver {
     /// Persistent storage for blocks, commits and other consensus data.
     store: Arc<dyn Store>,
     leader_schedule: Arc<LeaderSchedule>,
+    dag_state: Arc<RwLock<DagState>>,
+    /// Progress of the consumer, whose unhandled commits must be kept in store.
+    commit_consumer_monitor: Arc<CommitConsumerMonitor>,
+    /// Index of the last commit when the store was pruned.
+    last_pruning_commit_index: CommitIndex,
+    /// The running or last pruning of the store.
+    pruning_task: Option<JoinHandle<()>>,
 }
 
 impl CommitObserver {
+    /// Minimum number of commits between two prunings of the store.
+    const STORE_PRUNING_INTERVAL: CommitIndex = 1000;
+
     pub(crate) fn new(
         context: Arc<Context>,
         commit_consumer: CommitConsumer,
@@ -52,12 +63,17 @@ impl CommitObserver {
         store: Arc<dyn Store>,
         leader_schedule: Arc<LeaderSchedule>,
     ) -> Self {
+        let commit_consumer_monitor = commit_consumer.monitor();
         let mut observer = Self {
             context,
             commit_interpreter: Linearizer::new(dag_state.clone(), leader_schedule.clone()),
             sender: commit_consumer.sender,
             store,
             leader_schedule,
+            dag_state,
+            commit_consumer_monitor,
+            last_pruning_commit_index: GENESIS_COMMIT_INDEX,
+            pruning_task: None,
         };
 
         observer.recover_and_send_commits(commit_consumer.last_processed_commit_index);
@@ -95,10 +111,95 @@ impl CommitObserver {
         }
 
         self.report_metrics(&sent_sub_dags);
+        if let Some(last) = sent_sub_dags.last() {
+            self.maybe_prune_store(last.commit_ref.index);
+        }
         tracing::trace!("Committed & sent {sent_sub_dags:#?}");
         Ok(sent_sub_dags)
     }
 
+    /// Deletes from store the commits outside of the retention window, and the blocks that can
+    /// no longer be part of the retained commits. Pruning runs in the background, off the core
+    /// thread. Pruning failures are not fatal, and pruning is retried after the next interval.
+    fn maybe_prune_store(&mut self, last_commit_index: CommitIndex) {
+        let retention = self.context.parameters.store_commit_retention;
+        if retention == 0
+            || last_commit_index < self.last_pruning_commit_index + Self::STORE_PRUNING_INTERVAL
+            || self
+                .pruning_task
+                .as_ref()
+                .is_some_and(|task| !task.is_finished())
+        {
+            return;
+        }
+        self.last_pruning_commit_index = last_commit_index;
+
+        let context = self.context.clone();
+        let store = self.store.clone();
+        let commit_consumer_monitor = self.commit_consumer_monitor.clone();
+        let gc_enabled = self.dag_state.read().gc_enabled();
+        self.pruning_task = Some(tokio::task::spawn_blocking(move || {
+            if let Err(e) = Self::prune_store(
+                &context,
+                store.as_ref(),
+                &commit_consumer_monitor,
+                gc_enabled,
+                last_commit_index,
+                retention,
+            ) {
+                warn!("Failed to prune consensus store: {e:?}");
+            }
+        }));
+    }
+
+    fn prune_store(
+        context: &Context,
+        store: &dyn Store,
+        commit_consumer_monitor: &CommitConsumerMonitor,
+        gc_enabled: bool,
+        last_commit_index: CommitIndex,
+        retention: CommitIndex,
+    ) -> ConsensusResult<()> {
+        // Commits not handled by the consumer yet are replayed to it after a restart, and commits
+        // from the last commit info onwards are used to recover DagState.
+        let Some((last_commit_info_ref, _)) = store.read_last_commit_info()? else {
+            return Ok(());
+        };
+        let before_commit_index = (last_commit_index + 1)
+            .saturating_sub(retention)
+            .min(commit_consumer_monitor.highest_handled_commit() + 1)
+            .min(last_commit_info_ref.index);
+        if before_commit_index <= GENESIS_COMMIT_INDEX + 1 {
+            return Ok(());
+        }
+
+        // With GC, a commit only includes blocks of rounds higher than the GC round after the
+        // previous commit. So the blocks of the retained commits, which peers may fetch while
+        // syncing them, are above the GC round of the last pruned commit.
+        let gc_depth = context.protocol_config.gc_depth();
+        let before_round: Round = if gc_enabled {
+            let last_pruned = before_commit_index - 1;
+            store
+                .scan_commits((last_pruned..=last_pruned).into())?
+                .first()
+                .map(|commit| commit.leader().round.saturating_sub(gc_depth) + 1)
+                .unwrap_or(GENESIS_ROUND)
+        } else {
+            GENESIS_ROUND
+        };
+
+        debug!(
+            "Pruning consensus store below round {before_round} and commit {before_commit_index}"
+        );
+        store.prune(before_round, before_commit_index)?;
+        let metrics = &context.metrics.node_metrics;
+        metrics.store_pruned_round.set(before_round as i64);
+        metrics
+            .store_pruned_commit_index
+            .set(before_commit_index as i64);
+        Ok(())
+    }
+
     fn recover_and_send_commits(&mut self, last_processed_commit_index: CommitIndex) {
         let now = Instant::now();
         // TODO: remove this check, to allow consensus to regenerate commits?
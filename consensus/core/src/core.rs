@@ -21,7 +21,7 @@ use tracing::{debug, info, warn};
 use crate::{
     block::{
         Block, BlockAPI, BlockRef, BlockTimestampMs, BlockV1, Round, SignedBlock, Slot,
-        VerifiedBlock, GENESIS_ROUND,
+        VerifiedBlock, GENESIS_ROUND, MAX_MISBEHAVIOR_REPORTS_PER_BLOCK,
     },
     block_manager::BlockManager,
     commit::CommittedSubDag,
@@ -29,6 +29,7 @@ use crate::{
     context::Context,
     dag_state::DagState,
     error::{ConsensusError, ConsensusResult},
+    health_monitor::HealthMonitor,
     leader_schedule::LeaderSchedule,
     round_prober::QuorumRound,
     stake_aggregator::{QuorumThreshold, StakeAggregator},
@@ -40,8 +41,8 @@ use crate::{
 };
 #[cfg(test)]
 use crate::{
-    block_verifier::NoopBlockVerifier, storage::mem_store::MemStore, CommitConsumer,
-    TransactionClient,
+    block_verifier::NoopBlockVerifier, byzantine_core::ByzantineCoreBehavior,
+    storage::mem_store::MemStore, CommitConsumer, TransactionClient,
 };
 
 // Maximum number of commit votes to include in a block.
@@ -94,10 +95,15 @@ pub(crate) struct Core {
     block_signer: ProtocolKeyPair,
     /// Keeping track of state of the DAG, including blocks, commits and last committed rounds.
     dag_state: Arc<RwLock<DagState>>,
+    /// Records the decided leaders, for health reports.
+    health_monitor: Arc<HealthMonitor>,
     /// The last known round for which the node has proposed. Any proposal should be for a round > of this.
     /// This is currently being used to avoid equivocations during a node recovering from amnesia. When value is None it means that
     /// the last block sync mechanism is enabled, but it hasn't been initialised yet.
     last_known_proposed_round: Option<Round>,
+    /// Misbehavior injected in tests, to run the authority as a Byzantine one.
+    #[cfg(test)]
+    byzantine_behavior: Option<ByzantineCoreBehavior>,
 }
 
 impl Core {
@@ -111,6 +117,7 @@ impl Core {
         signals: CoreSignals,
         block_signer: ProtocolKeyPair,
         dag_state: Arc<RwLock<DagState>>,
+        health_monitor: Arc<HealthMonitor>,
         sync_last_known_own_block: bool,
     ) -> Self {
         let last_decided_leader = dag_state.read().last_commit_leader();
@@ -168,7 +175,10 @@ impl Core {
             signals,
             block_signer,
             dag_state,
+            health_monitor,
             last_known_proposed_round: min_propose_round,
+            #[cfg(test)]
+            byzantine_behavior: None,
         }
         .recover()
     }
@@ -245,6 +255,9 @@ impl Core {
             .core_add_blocks_batch_size
             .observe(blocks.len() as f64);
 
+        #[cfg(test)]
+        let blocks = self.drop_own_conflicting_blocks(blocks);
+
         // Try to accept them via the block manager
         let (accepted_blocks, missing_blocks) = self.block_manager.try_accept_blocks(blocks);
 
@@ -324,6 +337,13 @@ impl Core {
         if let Some(block) = self.try_new_block(force) {
             self.signals.new_block(block.clone())?;
 
+            #[cfg(test)]
+            if let Some(behavior) = &self.byzantine_behavior {
+                for extra_block in behavior.extra_proposals(&block, &self.block_signer)? {
+                    self.signals.new_block(extra_block)?;
+                }
+            }
+
             fail_point!("consensus-after-propose");
 
             // The new block may help commit.
@@ -435,11 +455,14 @@ impl Core {
             .proposed_block_transactions
             .observe(transactions.len() as f64);
 
-        // Consume the commit votes to be included.
-        let commit_votes = self
-            .dag_state
-            .write()
-            .take_commit_votes(MAX_COMMIT_VOTES_PER_BLOCK);
+        // Consume the commit votes and misbehavior reports to be included.
+        let (commit_votes, misbehavior_reports) = {
+            let mut dag_state = self.dag_state.write();
+            (
+                dag_state.take_commit_votes(MAX_COMMIT_VOTES_PER_BLOCK),
+                dag_state.take_misbehavior_reports(MAX_MISBEHAVIOR_REPORTS_PER_BLOCK),
+            )
+        };
 
         // Create the block and insert to storage.
         let block = Block::V1(BlockV1::new(
@@ -450,7 +473,7 @@ impl Core {
             ancestors.iter().map(|b| b.reference()).collect(),
             transactions,
             commit_votes,
-            vec![],
+            misbehavior_reports,
         ));
         let signed_block =
             SignedBlock::new(block, &self.block_signer).expect("Block signing failed.");
@@ -520,6 +543,8 @@ impl Core {
                     .last_decided_leader_round
                     .set(self.last_decided_leader.round as i64);
             }
+            self.health_monitor
+                .record_decided_leaders(&decided_leaders, self.last_decided_leader);
 
             let committed_leaders = decided_leaders
                 .into_iter()
@@ -580,7 +605,8 @@ impl Core {
                 tracing::debug!("Decided {} leaders and {commits_until_update} commits can be made before next leader schedule change", decided_leaders.len());
 
                 let mut sequenced_leaders = decided_leaders
-                    .into_iter()
+                    .iter()
+                    .cloned()
                     .filter_map(|leader| leader.into_committed_block())
                     .collect::<Vec<_>>();
 
@@ -600,6 +626,8 @@ impl Core {
                     .node_metrics
                     .last_decided_leader_round
                     .set(self.last_decided_leader.round as i64);
+                self.health_monitor
+                    .record_decided_leaders(&decided_leaders, self.last_decided_leader);
 
                 if sequenced_leaders.is_empty() {
                     break;
@@ -656,6 +684,29 @@ impl Core {
         self.propagation_delay = delay;
     }
 
+    /// Makes the authority misbehave when proposing blocks, for tests.
+    #[cfg(test)]
+    pub(crate) fn set_byzantine_behavior(&mut self, behavior: ByzantineCoreBehavior) {
+        self.byzantine_behavior = Some(behavior);
+    }
+
+    /// Drops the own blocks that are not in the own DAG, which a Byzantine authority proposed in
+    /// conflict with its accepted ones, and received back from peers.
+    #[cfg(test)]
+    fn drop_own_conflicting_blocks(&self, blocks: Vec<VerifiedBlock>) -> Vec<VerifiedBlock> {
+        if self.byzantine_behavior.is_none() {
+            return blocks;
+        }
+        let dag_state = self.dag_state.read();
+        blocks
+            .into_iter()
+            .filter(|block| {
+                block.author() != self.context.own_index
+                    || dag_state.contains_block(&block.reference())
+            })
+            .collect()
+    }
+
     /// Sets the min propose round for the proposer allowing to propose blocks only for round numbers
     /// `> last_known_proposed_round`. At the moment is allowed to call the method only once leading to a panic
     /// if attempt to do multiple times.
@@ -951,6 +1002,7 @@ impl CoreTextFixture {
 
         let block_signer = signers.remove(own_index.value()).1;
 
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let core = Core::new(
             context,
             leader_schedule,
@@ -961,6 +1013,7 @@ impl CoreTextFixture {
             signals,
             block_signer,
             dag_state,
+            health_monitor,
             false,
         );
 
@@ -991,7 +1044,7 @@ mod test {
         leader_scoring::ReputationScores,
         storage::{mem_store::MemStore, Store, WriteBatch},
         test_dag_builder::DagBuilder,
-        transaction::TransactionClient,
+        transaction::{TransactionClient, TransactionLane},
         CommitConsumer, CommitIndex,
     };
 
@@ -1057,6 +1110,7 @@ mod test {
         let (signals, signal_receivers) = CoreSignals::new(context.clone());
         // Need at least one subscriber to the block broadcast channel.
         let mut block_receiver = signal_receivers.block_broadcast_receiver();
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let mut core = Core::new(
             context.clone(),
             leader_schedule,
@@ -1067,6 +1121,7 @@ mod test {
             signals,
             key_pairs.remove(context.own_index.value()).1,
             dag_state.clone(),
+            health_monitor,
             false,
         );
 
@@ -1175,6 +1230,7 @@ mod test {
         let (signals, signal_receivers) = CoreSignals::new(context.clone());
         // Need at least one subscriber to the block broadcast channel.
         let mut block_receiver = signal_receivers.block_broadcast_receiver();
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let mut core = Core::new(
             context.clone(),
             leader_schedule,
@@ -1185,6 +1241,7 @@ mod test {
             signals,
             key_pairs.remove(context.own_index.value()).1,
             dag_state.clone(),
+            health_monitor,
             false,
         );
 
@@ -1263,6 +1320,7 @@ mod test {
             leader_schedule.clone(),
         );
 
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let mut core = Core::new(
             context.clone(),
             leader_schedule,
@@ -1273,6 +1331,7 @@ mod test {
             signals,
             key_pairs.remove(context.own_index.value()).1,
             dag_state.clone(),
+            health_monitor,
             false,
         );
 
@@ -1285,7 +1344,7 @@ mod test {
             total += transaction.len();
             index += 1;
             let _w = transaction_client
-                .submit_no_wait(vec![transaction])
+                .submit_no_wait(TransactionLane::User, vec![transaction])
                 .await
                 .unwrap();
 
@@ -1368,6 +1427,7 @@ mod test {
             leader_schedule.clone(),
         );
 
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let mut core = Core::new(
             context.clone(),
             leader_schedule,
@@ -1378,6 +1438,7 @@ mod test {
             signals,
             key_pairs.remove(context.own_index.value()).1,
             dag_state.clone(),
+            health_monitor,
             false,
         );
 
@@ -1457,6 +1518,7 @@ mod test {
             leader_schedule.clone(),
         );
 
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let mut core = Core::new(
             context.clone(),
             leader_schedule,
@@ -1467,6 +1529,7 @@ mod test {
             signals,
             key_pairs.remove(context.own_index.value()).1,
             dag_state.clone(),
+            health_monitor,
             true,
         );
 
@@ -1645,6 +1708,7 @@ mod test {
             leader_schedule.clone(),
         );
 
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let mut core = Core::new(
             context.clone(),
             leader_schedule,
@@ -1656,6 +1720,7 @@ mod test {
             signals,
             key_pairs.remove(context.own_index.value()).1,
             dag_state.clone(),
+            health_monitor,
             false,
         );
 
@@ -1710,6 +1775,7 @@ mod test {
             leader_schedule.clone(),
         );
 
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let mut core = Core::new(
             context.clone(),
             leader_schedule,
@@ -1721,6 +1787,7 @@ mod test {
             signals,
             key_pairs.remove(context.own_index.value()).1,
             dag_state.clone(),
+            health_monitor,
             false,
         );
 
@@ -401,6 +401,7 @@ mod test {
         context::Context,
         core::CoreSignals,
         dag_state::DagState,
+        health_monitor::HealthMonitor,
         leader_schedule::LeaderSchedule,
         storage::mem_store::MemStore,
         transaction::{TransactionClient, TransactionConsumer},
@@ -439,6 +440,7 @@ mod test {
             context.clone(),
             dag_state.clone(),
         ));
+        let health_monitor = HealthMonitor::new_for_test(context.clone(), dag_state.clone());
         let core = Core::new(
             context.clone(),
             leader_schedule,
@@ -449,6 +451,7 @@ mod test {
             signals,
             key_pairs.remove(context.own_index.value()).1,
             dag_state.clone(),
+            health_monitor,
             false,
         );
 
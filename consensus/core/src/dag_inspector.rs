@@ -0,0 +1,633 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Write as _},
+    ops::RangeInclusive,
+    path::Path,
+    sync::Arc,
+};
+
+use consensus_config::{AuthorityIndex, Committee, Parameters};
+use parking_lot::RwLock;
+use prometheus::Registry;
+use serde::Serialize;
+use sui_protocol_config::ProtocolConfig;
+
+use crate::{
+    block::{BlockAPI as _, BlockRef, BlockTimestampMs, Round, VerifiedBlock},
+    commit::{CommitAPI as _, Decision, LeaderStatus, DEFAULT_WAVE_LENGTH},
+    context::{Clock, Context},
+    dag_state::DagState,
+    error::{ConsensusError, ConsensusResult},
+    leader_schedule::{LeaderSchedule, LeaderSwapTable},
+    metrics::initialise_metrics,
+    storage::{mem_store::MemStore, rocksdb_store::RocksDBStore, Store},
+    universal_committer::universal_committer_builder::UniversalCommitterBuilder,
+    CommitIndex,
+};
+
+/// Number of commits or commit info read from the store at once.
+const COMMIT_SCAN_BATCH: CommitIndex = 1_000;
+
+/// Reads a range of rounds of the DAG persisted by an authority, and evaluates the commit rule
+/// over it, for debugging.
+pub struct DagInspector {
+    context: Arc<Context>,
+    store: Arc<dyn Store>,
+}
+
+impl DagInspector {
+    /// Opens the consensus store at `db_path`, written by an authority of `committee`.
+    pub fn new(committee: Committee, protocol_config: ProtocolConfig, db_path: &Path) -> Self {
+        let context = Context::new(
+            AuthorityIndex::ZERO,
+            committee,
+            Parameters {
+                db_path: db_path.to_path_buf(),
+                ..Default::default()
+            },
+            protocol_config,
+            initialise_metrics(Registry::new()),
+            Arc::new(Clock::new()),
+        );
+        let store = Arc::new(RocksDBStore::new(&db_path.to_string_lossy()));
+        Self {
+            context: Arc::new(context),
+            store,
+        }
+    }
+
+    #[cfg(test)]
+    fn new_for_test(context: Arc<Context>, store: Arc<dyn Store>) -> Self {
+        Self { context, store }
+    }
+
+    /// Returns a view of the blocks in `rounds`, with the status of their leaders.
+    ///
+    /// Leaders are elected with the schedule in effect at the start of the range, and decided with
+    /// the blocks of up to two waves after the range. Only blocks and commits around the range are
+    /// read from the store.
+    pub fn inspect(&self, rounds: RangeInclusive<Round>) -> ConsensusResult<DagView> {
+        let (start_round, end_round) = (*rounds.start(), *rounds.end());
+        let last_loaded_round = end_round.saturating_add(2 * DEFAULT_WAVE_LENGTH);
+
+        let mut blocks = Vec::new();
+        for (authority, _) in self.context.committee.authorities() {
+            blocks.extend(
+                self.store
+                    .scan_blocks_by_author_in_rounds(authority, start_round..=last_loaded_round)?,
+            );
+        }
+        blocks.sort_by_key(|block| block.reference());
+
+        let first_commit_in_range = self.first_commit_at_or_after(start_round)?;
+        let commits = self.commits_from(first_commit_in_range, last_loaded_round)?;
+
+        let leaders = self.leader_views(
+            &blocks,
+            &commits,
+            start_round,
+            end_round,
+            first_commit_in_range.saturating_sub(1),
+        )?;
+        let blocks: Vec<_> = blocks
+            .into_iter()
+            .filter(|block| block.round() <= end_round)
+            .collect();
+        let authorities = self.authority_views(&blocks);
+        let blocks = blocks
+            .iter()
+            .map(|block| BlockView::new(block, self.store.as_ref(), start_round))
+            .collect::<ConsensusResult<_>>()?;
+
+        Ok(DagView {
+            start_round,
+            end_round,
+            authorities,
+            blocks,
+            leaders,
+        })
+    }
+
+    /// Returns a view of the rounds spanned by the leaders of the commits in `commits`.
+    pub fn inspect_commits(
+        &self,
+        commits: RangeInclusive<CommitIndex>,
+    ) -> ConsensusResult<DagView> {
+        let (first, last) = (*commits.start(), *commits.end());
+        let first_commit = self.store.scan_commits((first..=first).into())?.pop();
+        let last_commit = self.store.scan_commits((last..=last).into())?.pop();
+        let (Some(first_commit), Some(last_commit)) = (first_commit, last_commit) else {
+            return Err(ConsensusError::CommitsNotFound {
+                start: first,
+                end: last,
+            });
+        };
+        self.inspect(first_commit.leader().round..=last_commit.leader().round)
+    }
+
+    /// Returns the index of the first stored commit with a leader at or after `round`, or the index
+    /// following the last commit if there is none. Commits are searched by bisection, as their
+    /// leader rounds increase with their indices.
+    fn first_commit_at_or_after(&self, round: Round) -> ConsensusResult<CommitIndex> {
+        let Some(last_commit) = self.store.read_last_commit()? else {
+            return Ok(1);
+        };
+        let (mut low, mut high) = (1, last_commit.index() + 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.store.scan_commits((mid..=mid).into())?.pop() {
+                Some(commit) if commit.leader().round >= round => high = mid,
+                // Pruned commits all precede the range.
+                _ => low = mid + 1,
+            }
+        }
+        Ok(low)
+    }
+
+    /// Reads the commits from index `first`, until one with a leader after `last_round`.
+    fn commits_from(
+        &self,
+        first: CommitIndex,
+        last_round: Round,
+    ) -> ConsensusResult<HashMap<BlockRef, CommitIndex>> {
+        let mut commits = HashMap::new();
+        let mut start = first;
+        loop {
+            let batch = self
+                .store
+                .scan_commits((start..=start.saturating_add(COMMIT_SCAN_BATCH - 1)).into())?;
+            let Some(last) = batch.last().map(|commit| commit.index()) else {
+                return Ok(commits);
+            };
+            for commit in batch {
+                if commit.leader().round > last_round {
+                    return Ok(commits);
+                }
+                commits.insert(commit.leader(), commit.index());
+            }
+            start = last + 1;
+        }
+    }
+
+    fn leader_views(
+        &self,
+        blocks: &[VerifiedBlock],
+        commits: &HashMap<BlockRef, CommitIndex>,
+        start_round: Round,
+        end_round: Round,
+        last_commit_before_range: CommitIndex,
+    ) -> ConsensusResult<Vec<LeaderView>> {
+        // The schedule is recovered from the last commit info written before the range.
+        let mut last_commit_info = None;
+        let mut end = last_commit_before_range;
+        while end > 0 && last_commit_info.is_none() {
+            let start = end.saturating_sub(COMMIT_SCAN_BATCH - 1).max(1);
+            last_commit_info = self.store.scan_commit_info((start..=end).into())?.pop();
+            end = start - 1;
+        }
+        let leader_swap_table =
+            last_commit_info.map_or(LeaderSwapTable::default(), |(commit_ref, commit_info)| {
+                LeaderSwapTable::new(
+                    self.context.clone(),
+                    commit_ref.index,
+                    commit_info.reputation_scores,
+                )
+            });
+        let leader_schedule =
+            Arc::new(LeaderSchedule::new(self.context.clone(), leader_swap_table));
+
+        let dag_state = Arc::new(RwLock::new(DagState::new(
+            self.context.clone(),
+            Arc::new(MemStore::new()),
+        )));
+        dag_state
+            .write()
+            .accept_blocks(blocks.iter().filter(|b| b.round() > 0).cloned().collect());
+        let highest_round = dag_state.read().highest_accepted_round();
+
+        let number_of_leaders = self
+            .context
+            .protocol_config
+            .mysticeti_num_leaders_per_round()
+            .unwrap_or(1);
+        let committer =
+            UniversalCommitterBuilder::new(self.context.clone(), leader_schedule, dag_state)
+                .with_number_of_leaders(number_of_leaders)
+                .with_pipeline(true)
+                .build();
+
+        let leaders = committer
+            .leader_statuses(start_round..=highest_round.saturating_sub(2))
+            .into_iter()
+            .filter(|(status, _)| status.round() <= end_round)
+            .map(|(status, decision)| {
+                let (slot, reference, kind) = match (status, decision) {
+                    (LeaderStatus::Commit(block), Decision::Direct) => (
+                        block.slot(),
+                        Some(block.reference()),
+                        LeaderDecision::DirectCommit,
+                    ),
+                    (LeaderStatus::Commit(block), Decision::Indirect) => (
+                        block.slot(),
+                        Some(block.reference()),
+                        LeaderDecision::IndirectCommit,
+                    ),
+                    (LeaderStatus::Skip(slot), Decision::Direct) => {
+                        (slot, None, LeaderDecision::DirectSkip)
+                    }
+                    (LeaderStatus::Skip(slot), Decision::Indirect) => {
+                        (slot, None, LeaderDecision::IndirectSkip)
+                    }
+                    (LeaderStatus::Undecided(slot), _) => (slot, None, LeaderDecision::Undecided),
+                };
+                LeaderView {
+                    round: slot.round,
+                    authority: slot.authority,
+                    block: reference.map(BlockRefView::from),
+                    decision: kind,
+                    commit_index: reference.and_then(|r| commits.get(&r).copied()),
+                }
+            })
+            .collect();
+        Ok(leaders)
+    }
+
+    fn authority_views(&self, blocks: &[VerifiedBlock]) -> Vec<AuthorityView> {
+        let mut first_timestamp_per_round = BTreeMap::<Round, BlockTimestampMs>::new();
+        for block in blocks {
+            let first = first_timestamp_per_round
+                .entry(block.round())
+                .or_insert(block.timestamp_ms());
+            *first = (*first).min(block.timestamp_ms());
+        }
+
+        self.context
+            .committee
+            .authorities()
+            .map(|(index, authority)| {
+                let own_blocks: Vec<_> = blocks
+                    .iter()
+                    .filter(|block| block.author() == index)
+                    .collect();
+                let lags: Vec<_> = own_blocks
+                    .iter()
+                    .map(|block| block.timestamp_ms() - first_timestamp_per_round[&block.round()])
+                    .collect();
+                let intervals: Vec<_> = own_blocks
+                    .windows(2)
+                    .filter(|pair| pair[1].round() == pair[0].round() + 1)
+                    .map(|pair| {
+                        pair[1]
+                            .timestamp_ms()
+                            .saturating_sub(pair[0].timestamp_ms())
+                    })
+                    .collect();
+                let mut rounds: Vec<_> = own_blocks.iter().map(|block| block.round()).collect();
+                rounds.dedup();
+
+                AuthorityView {
+                    index,
+                    hostname: authority.hostname.clone(),
+                    num_blocks: own_blocks.len(),
+                    missing_rounds: first_timestamp_per_round.len() - rounds.len(),
+                    mean_lag_ms: mean(&lags),
+                    mean_round_interval_ms: mean(&intervals),
+                }
+            })
+            .collect()
+    }
+}
+
+fn mean(values: &[u64]) -> Option<u64> {
+    if values.is_empty() {
+        return None;
+    }
+    Some(values.iter().sum::<u64>() / values.len() as u64)
+}
+
+/// Blocks and leaders of a range of rounds in the DAG.
+///
+/// Its `Display` output follows the format understood by the DAG parser used in tests, which
+/// cannot represent equivocations nor more than 26 authorities.
+#[derive(Clone, Debug, Serialize)]
+pub struct DagView {
+    pub start_round: Round,
+    pub end_round: Round,
+    pub authorities: Vec<AuthorityView>,
+    pub blocks: Vec<BlockView>,
+    pub leaders: Vec<LeaderView>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct AuthorityView {
+    pub index: AuthorityIndex,
+    pub hostname: String,
+    pub num_blocks: usize,
+    /// Number of rounds in the range without a block from the authority.
+    pub missing_rounds: usize,
+    /// Mean delay of the authority's blocks behind the earliest block of the same round.
+    pub mean_lag_ms: Option<u64>,
+    /// Mean time between the authority's blocks of consecutive rounds.
+    pub mean_round_interval_ms: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BlockView {
+    #[serde(flatten)]
+    pub reference: BlockRefView,
+    pub timestamp_ms: BlockTimestampMs,
+    pub ancestors: Vec<BlockRefView>,
+    /// Ancestors in the range that are not found in the store.
+    pub missing_ancestors: Vec<BlockRefView>,
+}
+
+impl BlockView {
+    fn new(block: &VerifiedBlock, store: &dyn Store, start_round: Round) -> ConsensusResult<Self> {
+        let in_range: Vec<_> = block
+            .ancestors()
+            .iter()
+            .filter(|ancestor| ancestor.round >= start_round && ancestor.round > 0)
+            .copied()
+            .collect();
+        let missing_ancestors = in_range
+            .iter()
+            .zip(store.contains_blocks(&in_range)?)
+            .filter(|(_, found)| !found)
+            .map(|(ancestor, _)| BlockRefView::from(*ancestor))
+            .collect();
+
+        Ok(Self {
+            reference: block.reference().into(),
+            timestamp_ms: block.timestamp_ms(),
+            ancestors: block.ancestors().iter().map(|r| (*r).into()).collect(),
+            missing_ancestors,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BlockRefView {
+    pub round: Round,
+    pub author: AuthorityIndex,
+    pub digest: String,
+}
+
+impl From<BlockRef> for BlockRefView {
+    fn from(value: BlockRef) -> Self {
+        Self {
+            round: value.round,
+            author: value.author,
+            digest: value.digest.to_string(),
+        }
+    }
+}
+
+impl BlockRefView {
+    fn node_id(&self) -> String {
+        format!("{}{}_{}", self.author, self.round, self.digest)
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct LeaderView {
+    pub round: Round,
+    pub authority: AuthorityIndex,
+    /// The committed leader block, if any.
+    pub block: Option<BlockRefView>,
+    pub decision: LeaderDecision,
+    /// Index of the commit of the leader recorded in the store, if any.
+    pub commit_index: Option<CommitIndex>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderDecision {
+    DirectCommit,
+    IndirectCommit,
+    DirectSkip,
+    IndirectSkip,
+    Undecided,
+}
+
+impl LeaderDecision {
+    fn color(&self) -> &'static str {
+        match self {
+            LeaderDecision::DirectCommit => "green",
+            LeaderDecision::IndirectCommit => "palegreen",
+            LeaderDecision::DirectSkip => "red",
+            LeaderDecision::IndirectSkip => "salmon",
+            LeaderDecision::Undecided => "gold",
+        }
+    }
+}
+
+impl DagView {
+    /// Renders the DAG in the DOT language of Graphviz. Leader slots are filled according to their
+    /// decision, and links to ancestors before the range are omitted.
+    pub fn to_dot(&self) -> String {
+        let leaders: HashMap<_, _> = self
+            .leaders
+            .iter()
+            .map(|leader| ((leader.round, leader.authority), leader.decision))
+            .collect();
+
+        let mut dot = String::from("digraph DAG {\n    rankdir=BT;\n    node [shape=box];\n");
+        for round in self.start_round..=self.end_round {
+            let _ = write!(dot, "    {{ rank=same; ");
+            for block in self.blocks.iter().filter(|b| b.reference.round == round) {
+                let _ = write!(dot, "\"{}\"; ", block.reference.node_id());
+            }
+            dot.push_str("}\n");
+        }
+        for block in &self.blocks {
+            let reference = &block.reference;
+            let style = match leaders.get(&(reference.round, reference.author)) {
+                Some(decision) => format!(", style=filled, fillcolor={}", decision.color()),
+                None => String::new(),
+            };
+            let _ = writeln!(
+                dot,
+                "    \"{}\" [label=\"{}{}\\n{}\\n{}\"{}];",
+                reference.node_id(),
+                reference.author,
+                reference.round,
+                reference.digest,
+                block.timestamp_ms,
+                style
+            );
+            for ancestor in &block.ancestors {
+                if ancestor.round < self.start_round {
+                    continue;
+                }
+                let missing = block.missing_ancestors.contains(ancestor);
+                let _ = writeln!(
+                    dot,
+                    "    \"{}\" -> \"{}\"{};",
+                    reference.node_id(),
+                    ancestor.node_id(),
+                    if missing {
+                        " [style=dashed, color=red]"
+                    } else {
+                        ""
+                    }
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl fmt::Display for DagView {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "DAG {{")?;
+        writeln!(f, "    Round 0 : {{ {} }},", self.authorities.len())?;
+        for round in self.start_round.max(1)..=self.end_round {
+            writeln!(f, "    Round {round} : {{")?;
+            for block in self.blocks.iter().filter(|b| b.reference.round == round) {
+                let ancestors = block
+                    .ancestors
+                    .iter()
+                    .map(|ancestor| format!("{}{}", ancestor.author, ancestor.round))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                writeln!(f, "        {} -> [{ancestors}],", block.reference.author)?;
+            }
+            writeln!(f, "    }},")?;
+        }
+        writeln!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        commit::{CommitDigest, TrustedCommit},
+        storage::WriteBatch,
+        test_dag_builder::DagBuilder,
+        test_dag_parser::parse_dag,
+    };
+
+    #[tokio::test]
+    async fn test_inspect_dag_with_missing_leader() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=2).build();
+        dag_builder.layer(3).no_leader_block(vec![]).build();
+        dag_builder.layers(4..=10).build();
+
+        let store = Arc::new(MemStore::new());
+        store
+            .write(WriteBatch::default().blocks(dag_builder.blocks.values().cloned().collect()))
+            .unwrap();
+        let inspector = DagInspector::new_for_test(context.clone(), store);
+
+        let view = inspector.inspect(2..=6).unwrap();
+        assert_eq!(view.blocks.len(), 4 * 5 - 1);
+        assert!(view.blocks.iter().all(|b| b.missing_ancestors.is_empty()));
+        assert_eq!(view.authorities[0].missing_rounds, 0);
+
+        let decisions: Vec<_> = view
+            .leaders
+            .iter()
+            .map(|leader| (leader.round, leader.decision))
+            .collect();
+        assert_eq!(
+            decisions,
+            vec![
+                (2, LeaderDecision::DirectCommit),
+                (3, LeaderDecision::DirectSkip),
+                (4, LeaderDecision::DirectCommit),
+                (5, LeaderDecision::DirectCommit),
+                (6, LeaderDecision::DirectCommit),
+            ]
+        );
+        // No commits are recorded in the store.
+        assert!(view.leaders.iter().all(|l| l.commit_index.is_none()));
+
+        let dot = view.to_dot();
+        assert!(dot.starts_with("digraph DAG {"));
+        assert_eq!(dot.matches("fillcolor=red").count(), 0);
+        assert_eq!(dot.matches("fillcolor=green").count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_inspect_reads_commits_around_range() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=20).build();
+
+        let commits: Vec<_> = (1..=20)
+            .map(|round| {
+                let leader = dag_builder.leader_block(round).unwrap();
+                TrustedCommit::new_for_test(
+                    round as CommitIndex,
+                    CommitDigest::MIN,
+                    leader.timestamp_ms(),
+                    leader.reference(),
+                    vec![leader.reference()],
+                )
+            })
+            .collect();
+        let store = Arc::new(MemStore::new());
+        store
+            .write(
+                WriteBatch::default()
+                    .blocks(dag_builder.blocks.values().cloned().collect())
+                    .commits(commits),
+            )
+            .unwrap();
+        let inspector = DagInspector::new_for_test(context, store);
+
+        let view = inspector.inspect(8..=10).unwrap();
+        assert!(view
+            .blocks
+            .iter()
+            .all(|b| (8..=10).contains(&b.reference.round)));
+        assert_eq!(
+            view.leaders
+                .iter()
+                .map(|leader| (leader.round, leader.commit_index))
+                .collect::<Vec<_>>(),
+            vec![(8, Some(8)), (9, Some(9)), (10, Some(10))]
+        );
+
+        let view = inspector.inspect_commits(5..=6).unwrap();
+        assert_eq!((view.start_round, view.end_round), (5, 6));
+        assert_eq!(view.leaders.len(), 2);
+
+        assert!(inspector.inspect_commits(19..=21).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_dag_view_round_trips_through_parser() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=3).build();
+        dag_builder
+            .layer(4)
+            .authorities(vec![AuthorityIndex::new_for_test(3)])
+            .skip_block()
+            .build();
+
+        let store = Arc::new(MemStore::new());
+        store
+            .write(WriteBatch::default().blocks(dag_builder.blocks.values().cloned().collect()))
+            .unwrap();
+        let view = DagInspector::new_for_test(context, store)
+            .inspect(1..=4)
+            .unwrap();
+        assert_eq!(view.authorities[3].missing_rounds, 1);
+
+        let (_, parsed) = parse_dag(&view.to_string()).expect("Invalid dag");
+        assert_eq!(parsed.blocks.len(), view.blocks.len());
+        for (parsed, block) in parsed.blocks.keys().zip(view.blocks.iter()) {
+            assert_eq!(parsed.round, block.reference.round);
+            assert_eq!(parsed.author, block.reference.author);
+        }
+    }
+}
@@ -15,15 +15,16 @@ use tracing::{debug, error};
 
 use crate::{
     block::{
-        genesis_blocks, BlockAPI, BlockDigest, BlockRef, BlockTimestampMs, Round, Slot,
-        VerifiedBlock, GENESIS_ROUND,
+        genesis_blocks, BlockAPI, BlockDigest, BlockRef, BlockTimestampMs, MisbehaviorReport,
+        Round, Slot, VerifiedBlock, GENESIS_ROUND,
     },
     commit::{
         load_committed_subdag_from_store, CommitAPI as _, CommitDigest, CommitIndex, CommitInfo,
         CommitRef, CommitVote, TrustedCommit, GENESIS_COMMIT_INDEX,
     },
     context::Context,
-    leader_scoring::{ReputationScores, ScoringSubdag},
+    leader_scoring::{ReputationScores, ScoringStrategy, ScoringSubdag},
+    misbehavior_tracker::MisbehaviorTracker,
     stake_aggregator::{QuorumThreshold, StakeAggregator},
     storage::{Store, WriteBatch},
     CommittedSubDag,
@@ -76,6 +77,9 @@ pub(crate) struct DagState {
     // TODO: limit to 1st commit per round with multi-leader.
     pending_commit_votes: VecDeque<CommitVote>,
 
+    // Misbehavior detected in accepted blocks and reports pending to be included in new blocks.
+    misbehavior_tracker: MisbehaviorTracker,
+
     // Data to be flushed to storage.
     blocks_to_write: Vec<VerifiedBlock>,
     commits_to_write: Vec<TrustedCommit>,
@@ -159,6 +163,7 @@ impl DagState {
             scoring_subdag.add_subdags(std::mem::take(&mut unscored_committed_subdags));
         }
 
+        let misbehavior_tracker = MisbehaviorTracker::new(context.clone());
         let mut state = Self {
             context,
             genesis,
@@ -169,6 +174,7 @@ impl DagState {
             last_commit_round_advancement_time: None,
             last_committed_rounds: last_committed_rounds.clone(),
             pending_commit_votes: VecDeque::new(),
+            misbehavior_tracker,
             blocks_to_write: vec![],
             commits_to_write: vec![],
             commit_info_to_write: vec![],
@@ -226,6 +232,23 @@ impl DagState {
                 block(s) {existing_blocks:#?} already exists."
             );
         }
+        let slot_block_refs = self.recent_refs[block_ref.author]
+            .range((
+                Included(BlockRef::new(
+                    block_ref.round,
+                    block_ref.author,
+                    BlockDigest::MIN,
+                )),
+                Included(BlockRef::new(
+                    block_ref.round,
+                    block_ref.author,
+                    BlockDigest::MAX,
+                )),
+            ))
+            .copied()
+            .collect::<Vec<_>>();
+        self.misbehavior_tracker
+            .observe_block(&block, slot_block_refs);
         self.update_block_metadata(&block);
         self.blocks_to_write.push(block);
         let source = if self.context.own_index == block_ref.author {
@@ -677,6 +700,15 @@ impl DagState {
         votes
     }
 
+    /// Records a block that is signed by its author but has failed verification.
+    pub(crate) fn report_invalid_block(&mut self, block_ref: BlockRef) {
+        self.misbehavior_tracker.report_invalid_block(block_ref);
+    }
+
+    pub(crate) fn take_misbehavior_reports(&mut self, limit: usize) -> Vec<MisbehaviorReport> {
+        self.misbehavior_tracker.take_pending_reports(limit)
+    }
+
     /// Index of the last commit.
     pub(crate) fn last_commit_index(&self) -> CommitIndex {
         match &self.last_commit {
@@ -896,8 +928,11 @@ impl DagState {
         self.scoring_subdag.is_empty()
     }
 
-    pub(crate) fn calculate_scoring_subdag_scores(&self) -> ReputationScores {
-        self.scoring_subdag.calculate_scores()
+    pub(crate) fn calculate_scoring_subdag_scores(
+        &self,
+        strategy: &dyn ScoringStrategy,
+    ) -> ReputationScores {
+        self.scoring_subdag.calculate_scores(strategy)
     }
 
     pub(crate) fn scoring_subdag_commit_range(&self) -> CommitIndex {
@@ -940,7 +975,10 @@ mod test {
 
     use super::*;
     use crate::{
-        block::{BlockDigest, BlockRef, BlockTimestampMs, TestBlock, VerifiedBlock},
+        block::{
+            BlockDigest, BlockRef, BlockTimestampMs, MisbehaviorProof, TestBlock, VerifiedBlock,
+            MAX_MISBEHAVIOR_REPORTS_PER_BLOCK,
+        },
         storage::{mem_store::MemStore, WriteBatch},
         test_dag_builder::DagBuilder,
     };
@@ -1372,6 +1410,44 @@ mod test {
             dag_state.contains_cached_block_at_slot(Slot::new(8, AuthorityIndex::new_for_test(0)));
     }
 
+    #[tokio::test]
+    async fn test_accept_equivocating_blocks() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let store = Arc::new(MemStore::new());
+        let mut dag_state = DagState::new(context.clone(), store.clone());
+
+        // Authority 1 produces two different blocks for round 3.
+        let block = VerifiedBlock::new_for_test(TestBlock::new(3, 1).set_timestamp_ms(1).build());
+        let equivocating_block =
+            VerifiedBlock::new_for_test(TestBlock::new(3, 1).set_timestamp_ms(2).build());
+        dag_state.accept_blocks(vec![
+            block.clone(),
+            VerifiedBlock::new_for_test(TestBlock::new(3, 2).build()),
+        ]);
+        assert!(dag_state
+            .take_misbehavior_reports(MAX_MISBEHAVIOR_REPORTS_PER_BLOCK)
+            .is_empty());
+
+        // Both blocks are accepted, and the equivocation is reported.
+        dag_state.accept_block(equivocating_block.clone());
+        assert!(dag_state.contains_block(&block.reference()));
+        assert!(dag_state.contains_block(&equivocating_block.reference()));
+        assert_eq!(
+            dag_state.take_misbehavior_reports(MAX_MISBEHAVIOR_REPORTS_PER_BLOCK),
+            vec![MisbehaviorReport::new(MisbehaviorProof::equivocation(
+                block.reference(),
+                equivocating_block.reference(),
+            ))]
+        );
+
+        // Accepting the same block again is not an equivocation.
+        dag_state.accept_block(equivocating_block);
+        assert!(dag_state
+            .take_misbehavior_reports(MAX_MISBEHAVIOR_REPORTS_PER_BLOCK)
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_get_blocks_in_cache_or_store() {
         let (context, _) = Context::new_for_test(4);
@@ -18,6 +18,9 @@ pub(crate) enum ConsensusError {
     #[error("Error deserializing block: {0}")]
     MalformedBlock(bcs::Error),
 
+    #[error("Error compressing or decompressing block: {0}")]
+    BlockCompressionFailure(String),
+
     #[error("Error deserializing commit: {0}")]
     MalformedCommit(bcs::Error),
 
@@ -33,6 +36,12 @@ pub(crate) enum ConsensusError {
     #[error("Block contains too many transaction bytes: {size} > {limit}")]
     TooManyTransactionBytes { size: usize, limit: usize },
 
+    #[error("Block contains too many misbehavior reports: {count} > {limit}")]
+    TooManyMisbehaviorReports { count: usize, limit: usize },
+
+    #[error("Block contains an invalid misbehavior report: {0}")]
+    InvalidMisbehaviorReport(String),
+
     #[error("Unexpected block authority {0} from peer {1}")]
     UnexpectedAuthority(AuthorityIndex, AuthorityIndex),
 
@@ -161,6 +170,12 @@ pub(crate) enum ConsensusError {
         received: BlockRef,
     },
 
+    #[error("Commits {start} to {end} are not found in the store")]
+    CommitsNotFound {
+        start: CommitIndex,
+        end: CommitIndex,
+    },
+
     #[error("RocksDB failure: {0}")]
     RocksDBFailure(#[from] TypedStoreError),
 
@@ -0,0 +1,241 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::VecDeque, sync::Arc};
+
+use consensus_config::{AuthorityIndex, Epoch};
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+
+use crate::{
+    block::{BlockAPI as _, BlockTimestampMs, Round, Slot},
+    commit::DecidedLeader,
+    commit_vote_monitor::CommitVoteMonitor,
+    context::Context,
+    dag_state::DagState,
+    round_prober::QuorumRound,
+    CommitIndex,
+};
+
+/// Maximum number of decided leaders kept for health reports.
+const MAX_RECENT_LEADERS: usize = 100;
+
+/// Gathers the state of round propagation, commits and leader decisions of an authority, so
+/// operators can tell why it lags behind the network. Unlike metrics, a report is a consistent
+/// snapshot with per-peer details.
+pub struct HealthMonitor {
+    context: Arc<Context>,
+    dag_state: Arc<RwLock<DagState>>,
+    commit_vote_monitor: Arc<CommitVoteMonitor>,
+    // Result of the last probe of peers by RoundProber.
+    round_propagation: Mutex<Option<RoundPropagation>>,
+    // Most recently decided leaders, oldest first.
+    recent_leaders: Mutex<VecDeque<LeaderOutcome>>,
+}
+
+impl HealthMonitor {
+    pub(crate) fn new(
+        context: Arc<Context>,
+        dag_state: Arc<RwLock<DagState>>,
+        commit_vote_monitor: Arc<CommitVoteMonitor>,
+    ) -> Self {
+        Self {
+            context,
+            dag_state,
+            commit_vote_monitor,
+            round_propagation: Mutex::new(None),
+            recent_leaders: Mutex::new(VecDeque::with_capacity(MAX_RECENT_LEADERS)),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_test(
+        context: Arc<Context>,
+        dag_state: Arc<RwLock<DagState>>,
+    ) -> Arc<Self> {
+        let commit_vote_monitor = Arc::new(CommitVoteMonitor::new(context.clone()));
+        Arc::new(Self::new(context, dag_state, commit_vote_monitor))
+    }
+
+    /// Records the rounds collected by a probe of peers, replacing the previous probe.
+    pub(crate) fn record_round_probe(
+        &self,
+        highest_received_rounds: Vec<Vec<Round>>,
+        quorum_rounds: &[QuorumRound],
+        propagation_delay: Round,
+    ) {
+        *self.round_propagation.lock() = Some(RoundPropagation {
+            probed_at_ms: self.context.clock.timestamp_utc_ms(),
+            highest_received_rounds,
+            quorum_rounds: quorum_rounds.to_vec(),
+            propagation_delay,
+        });
+    }
+
+    /// Records the decided leaders up to and including `last_decided`. Leaders decided after it
+    /// are decided again later, possibly with a different leader schedule.
+    pub(crate) fn record_decided_leaders(
+        &self,
+        decided_leaders: &[DecidedLeader],
+        last_decided: Slot,
+    ) {
+        let mut recent_leaders = self.recent_leaders.lock();
+        for leader in decided_leaders {
+            let slot = leader.slot();
+            recent_leaders.push_back(LeaderOutcome {
+                round: slot.round,
+                authority: slot.authority,
+                committed: matches!(leader, DecidedLeader::Commit(_)),
+            });
+            if slot == last_decided {
+                break;
+            }
+        }
+        while recent_leaders.len() > MAX_RECENT_LEADERS {
+            recent_leaders.pop_front();
+        }
+    }
+
+    /// Returns the current health of the authority.
+    pub fn report(&self) -> ConsensusHealth {
+        let (last_proposed_round, highest_accepted_round, last_commit_index) = {
+            let dag_state = self.dag_state.read();
+            (
+                dag_state
+                    .get_last_block_for_authority(self.context.own_index)
+                    .round(),
+                dag_state.highest_accepted_round(),
+                dag_state.last_commit_index(),
+            )
+        };
+        ConsensusHealth {
+            epoch: self.context.committee.epoch(),
+            own_index: self.context.own_index,
+            authorities: self
+                .context
+                .committee
+                .authorities()
+                .map(|(_, authority)| authority.hostname.clone())
+                .collect(),
+            last_proposed_round,
+            highest_accepted_round,
+            last_commit_index,
+            quorum_commit_index: self.commit_vote_monitor.quorum_commit_index(),
+            leader_timeout_ms: self.context.parameters.leader_timeout.as_millis() as u64,
+            min_round_delay_ms: self.context.parameters.min_round_delay.as_millis() as u64,
+            round_propagation: self.round_propagation.lock().clone(),
+            recent_leaders: self.recent_leaders.lock().iter().cloned().collect(),
+        }
+    }
+}
+
+/// Snapshot of the participation of an authority in consensus.
+#[derive(Clone, Debug, Serialize)]
+pub struct ConsensusHealth {
+    pub epoch: Epoch,
+    pub own_index: AuthorityIndex,
+    /// Hostnames of the authorities in the committee, by index.
+    pub authorities: Vec<String>,
+    pub last_proposed_round: Round,
+    pub highest_accepted_round: Round,
+    pub last_commit_index: CommitIndex,
+    /// Highest commit index voted by a quorum, from the votes in blocks received so far.
+    pub quorum_commit_index: CommitIndex,
+    pub leader_timeout_ms: u64,
+    pub min_round_delay_ms: u64,
+    /// Result of the last round probe, if the round prober is enabled and has run.
+    pub round_propagation: Option<RoundPropagation>,
+    /// Most recently decided leaders, oldest first.
+    pub recent_leaders: Vec<LeaderOutcome>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct RoundPropagation {
+    pub probed_at_ms: BlockTimestampMs,
+    /// Highest round received by each authority (row) from each authority (column). The row of
+    /// a peer that failed to respond is all zeros.
+    pub highest_received_rounds: Vec<Vec<Round>>,
+    /// Quorum round range [low, high] of the blocks from each authority.
+    pub quorum_rounds: Vec<QuorumRound>,
+    /// Number of rounds own blocks are behind the low quorum round of this authority.
+    pub propagation_delay: Round,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LeaderOutcome {
+    pub round: Round,
+    pub authority: AuthorityIndex,
+    /// Whether the leader is committed, or skipped.
+    pub committed: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        block::{TestBlock, VerifiedBlock},
+        storage::mem_store::MemStore,
+    };
+
+    #[tokio::test]
+    async fn report_recent_leaders_and_round_probe() {
+        let (context, _) = Context::new_for_test(4);
+        let context = Arc::new(context);
+        let dag_state = Arc::new(RwLock::new(DagState::new(
+            context.clone(),
+            Arc::new(MemStore::new()),
+        )));
+        let monitor = HealthMonitor::new_for_test(context.clone(), dag_state);
+
+        let report = monitor.report();
+        assert_eq!(report.authorities.len(), 4);
+        assert!(report.round_propagation.is_none());
+        assert!(report.recent_leaders.is_empty());
+
+        // Leaders after the last decided one are not recorded.
+        let decided_leaders = vec![
+            DecidedLeader::Skip(Slot::new_for_test(1, 1)),
+            DecidedLeader::Commit(VerifiedBlock::new_for_test(TestBlock::new(2, 2).build())),
+            DecidedLeader::Skip(Slot::new_for_test(3, 3)),
+        ];
+        monitor.record_decided_leaders(&decided_leaders, Slot::new_for_test(2, 2));
+        monitor.record_round_probe(vec![vec![5; 4]; 4], &[(4, 5); 4], 1);
+
+        let report = monitor.report();
+        assert_eq!(
+            report.recent_leaders,
+            vec![
+                LeaderOutcome {
+                    round: 1,
+                    authority: AuthorityIndex::new_for_test(1),
+                    committed: false,
+                },
+                LeaderOutcome {
+                    round: 2,
+                    authority: AuthorityIndex::new_for_test(2),
+                    committed: true,
+                },
+            ]
+        );
+        let round_propagation = report.round_propagation.unwrap();
+        assert_eq!(
+            round_propagation.highest_received_rounds,
+            vec![vec![5; 4]; 4]
+        );
+        assert_eq!(round_propagation.quorum_rounds, vec![(4, 5); 4]);
+        assert_eq!(round_propagation.propagation_delay, 1);
+
+        // Only the most recent leaders are kept.
+        let decided_leaders: Vec<_> = (1..=2 * MAX_RECENT_LEADERS as Round)
+            .map(|round| DecidedLeader::Skip(Slot::new_for_test(round, 0)))
+            .collect();
+        let last_decided = decided_leaders.last().unwrap().slot();
+        monitor.record_decided_leaders(&decided_leaders, last_decided);
+        let report = monitor.report();
+        assert_eq!(report.recent_leaders.len(), MAX_RECENT_LEADERS);
+        assert_eq!(
+            report.recent_leaders.last().unwrap().round,
+            last_decided.round
+        );
+    }
+}
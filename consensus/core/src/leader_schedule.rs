@@ -15,7 +15,7 @@ use crate::{
     commit::CommitRange,
     context::Context,
     dag_state::DagState,
-    leader_scoring::{ReputationScoreCalculator, ReputationScores},
+    leader_scoring::{scoring_strategy, ReputationScoreCalculator, ReputationScores},
     CommitIndex, Round,
 };
 
@@ -46,7 +46,6 @@ impl LeaderSchedule {
         }
     }
 
-    #[cfg(test)]
     pub(crate) fn with_num_commits_per_schedule(mut self, num_commits_per_schedule: u64) -> Self {
         self.num_commits_per_schedule = num_commits_per_schedule;
         self
@@ -136,9 +135,11 @@ impl LeaderSchedule {
             .with_label_values(&["LeaderSchedule::update_leader_schedule"])
             .start_timer();
 
+        let scoring_strategy = scoring_strategy(&self.context);
         let (reputation_scores, last_commit_index) = {
             let dag_state = dag_state.read();
-            let reputation_scores = dag_state.calculate_scoring_subdag_scores();
+            let reputation_scores =
+                dag_state.calculate_scoring_subdag_scores(scoring_strategy.as_ref());
 
             let last_commit_index = dag_state.scoring_subdag_commit_range();
 
@@ -514,6 +515,7 @@ mod tests {
     use crate::{
         block::{BlockAPI as _, BlockDigest, BlockRef, BlockTimestampMs, TestBlock, VerifiedBlock},
         commit::{CommitDigest, CommitInfo, CommitRef, CommittedSubDag, TrustedCommit},
+        leader_scoring::VoteScoringStrategy,
         storage::{mem_store::MemStore, Store, WriteBatch},
         test_dag_builder::DagBuilder,
     };
@@ -747,7 +749,9 @@ mod tests {
             dag_state.read().last_committed_rounds()
         );
         assert_eq!(1, dag_state.read().scoring_subdags_count());
-        let recovered_scores = dag_state.read().calculate_scoring_subdag_scores();
+        let recovered_scores = dag_state
+            .read()
+            .calculate_scoring_subdag_scores(&VoteScoringStrategy);
         let expected_scores = ReputationScores::new((11..=11).into(), vec![0, 0, 0, 0]);
         assert_eq!(recovered_scores, expected_scores);
 
@@ -863,7 +867,9 @@ mod tests {
             expected_scored_subdags.len(),
             dag_state.read().scoring_subdags_count()
         );
-        let recovered_scores = dag_state.read().calculate_scoring_subdag_scores();
+        let recovered_scores = dag_state
+            .read()
+            .calculate_scoring_subdag_scores(&VoteScoringStrategy);
         let expected_scores = ReputationScores::new((1..=2).into(), vec![0, 0, 0, 0]);
         assert_eq!(recovered_scores, expected_scores);
 
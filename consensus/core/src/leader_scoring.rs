@@ -10,6 +10,7 @@ use std::{
 
 use consensus_config::AuthorityIndex;
 use serde::{Deserialize, Serialize};
+use sui_protocol_config::ConsensusLeaderScoringStrategy;
 
 use crate::{
     block::{BlockAPI, BlockDigest, BlockRef, Slot},
@@ -181,15 +182,24 @@ pub(crate) struct ScoringSubdag {
     // Note: Inlcuding stake aggregator so that we can quickly check if it exceeds
     // quourum threshold and only include those scores for certain scoring strategies.
     pub(crate) votes: BTreeMap<BlockRef, StakeAggregator<QuorumThreshold>>,
+    // Per authority, the number of times its blocks are included as ancestors by
+    // the committed blocks of other authorities. Vec index is the `AuthorityIndex`.
+    pub(crate) inclusions: Vec<u64>,
+    // Per authority, the total number of rounds by which those inclusions came
+    // after the round following the included block, i.e. through weak links.
+    pub(crate) inclusion_delays: Vec<u64>,
 }
 
 impl ScoringSubdag {
     pub(crate) fn new(context: Arc<Context>) -> Self {
+        let num_authorities = context.committee.size();
         Self {
             context,
             commit_range: None,
             leaders: HashSet::new(),
             votes: BTreeMap::new(),
+            inclusions: vec![0; num_authorities],
+            inclusion_delays: vec![0; num_authorities],
         }
     }
 
@@ -221,6 +231,12 @@ impl ScoringSubdag {
             // oldest blocks first
             for block in subdag.blocks {
                 for ancestor in block.ancestors() {
+                    if ancestor.author != block.author() {
+                        let delay = block.round().saturating_sub(ancestor.round + 1);
+                        self.inclusions[ancestor.author] += 1;
+                        self.inclusion_delays[ancestor.author] += delay as u64;
+                    }
+
                     // Weak links may point to blocks with lower round numbers
                     // than strong links.
                     if ancestor.round != block.round().saturating_sub(1) {
@@ -258,8 +274,8 @@ impl ScoringSubdag {
     }
 
     // Iterate through votes and calculate scores for each authority based on
-    // scoring strategy that is used. (Vote or CertifiedVote)
-    pub(crate) fn calculate_scores(&self) -> ReputationScores {
+    // the scoring strategy that is used.
+    pub(crate) fn calculate_scores(&self, strategy: &dyn ScoringStrategy) -> ReputationScores {
         let _s = self
             .context
             .metrics
@@ -268,7 +284,7 @@ impl ScoringSubdag {
             .with_label_values(&["ScoringSubdag::calculate_scores"])
             .start_timer();
 
-        let scores_per_authority = self.score_distributed_votes();
+        let scores_per_authority = strategy.calculate_scores_per_authority(self);
 
         // TODO: Normalize scores
         ReputationScores::new(
@@ -279,65 +295,115 @@ impl ScoringSubdag {
         )
     }
 
-    /// This scoring strategy aims to give scores based on overall vote distribution.
-    /// Instead of only giving one point for each vote that is included in 2f+1
-    /// blocks. We give a score equal to the amount of stake of all blocks that
-    /// included the vote.
-    fn score_distributed_votes(&self) -> Vec<u64> {
-        let num_authorities = self.context.committee.size();
+    pub(crate) fn scored_subdags_count(&self) -> usize {
+        if let Some(commit_range) = &self.commit_range {
+            commit_range.size()
+        } else {
+            0
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.leaders.is_empty() && self.votes.is_empty() && self.commit_range.is_none()
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.leaders.clear();
+        self.votes.clear();
+        self.inclusions.fill(0);
+        self.inclusion_delays.fill(0);
+        self.commit_range = None;
+    }
+}
+
+/// A rule that turns the votes collected in a `ScoringSubdag` into a score per
+/// authority. The strategy in use is selected by the protocol config.
+pub(crate) trait ScoringStrategy: Send + Sync {
+    /// Returns the score per authority. Vec index is the `AuthorityIndex`.
+    fn calculate_scores_per_authority(&self, scoring_subdag: &ScoringSubdag) -> Vec<u64>;
+}
+
+/// Creates the scoring strategy configured for the epoch. Strategies only read
+/// committed data, so that all authorities derive the same leader schedule.
+pub(crate) fn scoring_strategy(context: &Context) -> Box<dyn ScoringStrategy> {
+    match context.protocol_config.consensus_leader_scoring_strategy() {
+        ConsensusLeaderScoringStrategy::Vote => Box::new(VoteScoringStrategy),
+        ConsensusLeaderScoringStrategy::CertifiedVote => Box::new(CertifiedVoteScoringStrategy),
+        ConsensusLeaderScoringStrategy::LatencyWeightedVote => {
+            Box::new(LatencyWeightedVoteScoringStrategy)
+        }
+    }
+}
+
+/// This scoring strategy aims to give scores based on overall vote distribution.
+/// Instead of only giving one point for each vote that is included in 2f+1
+/// blocks. We give a score equal to the amount of stake of all blocks that
+/// included the vote.
+pub(crate) struct VoteScoringStrategy;
+
+impl ScoringStrategy for VoteScoringStrategy {
+    fn calculate_scores_per_authority(&self, scoring_subdag: &ScoringSubdag) -> Vec<u64> {
+        let num_authorities = scoring_subdag.context.committee.size();
         let mut scores_per_authority = vec![0_u64; num_authorities];
 
-        for (vote, stake_agg) in self.votes.iter() {
+        for (vote, stake_agg) in scoring_subdag.votes.iter() {
             let authority = vote.author;
             let stake = stake_agg.stake();
             tracing::trace!(
                 "[{}] scores +{stake} reputation for {authority}!",
-                self.context.own_index,
+                scoring_subdag.context.own_index,
             );
             scores_per_authority[authority.value()] += stake;
         }
         scores_per_authority
     }
+}
 
-    /// This scoring strategy gives points equal to the amount of stake in blocks
-    /// that include the authority's vote, if that amount of total_stake > 2f+1.
-    /// We consider this a certified vote.
-    // TODO: This will be used for ancestor selection
-    #[allow(unused)]
-    fn score_certified_votes(&self) -> Vec<u64> {
-        let num_authorities = self.context.committee.size();
-        let mut scores_per_authority = vec![0_u64; num_authorities];
+/// This scoring strategy gives points equal to the amount of stake in blocks
+/// that include the authority's vote, if that amount of total_stake > 2f+1.
+/// We consider this a certified vote.
+pub(crate) struct CertifiedVoteScoringStrategy;
+
+impl ScoringStrategy for CertifiedVoteScoringStrategy {
+    fn calculate_scores_per_authority(&self, scoring_subdag: &ScoringSubdag) -> Vec<u64> {
+        let context = &scoring_subdag.context;
+        let mut scores_per_authority = vec![0_u64; context.committee.size()];
 
-        for (vote, stake_agg) in self.votes.iter() {
+        for (vote, stake_agg) in scoring_subdag.votes.iter() {
             let authority = vote.author;
-            if stake_agg.reached_threshold(&self.context.committee) {
+            if stake_agg.reached_threshold(&context.committee) {
                 let stake = stake_agg.stake();
                 tracing::trace!(
                     "[{}] scores +{stake} reputation for {authority}!",
-                    self.context.own_index,
+                    context.own_index,
                 );
                 scores_per_authority[authority.value()] += stake;
             }
         }
         scores_per_authority
     }
+}
 
-    pub(crate) fn scored_subdags_count(&self) -> usize {
-        if let Some(commit_range) = &self.commit_range {
-            commit_range.size()
-        } else {
-            0
+/// This scoring strategy starts from the distributed vote scores, and divides
+/// the score of each authority by one plus the average number of rounds its
+/// blocks are late when included by the committed blocks of other authorities.
+/// Authorities whose blocks are slow to propagate, and are only reached through
+/// weak links, lose reputation even while their votes are still included.
+pub(crate) struct LatencyWeightedVoteScoringStrategy;
+
+impl ScoringStrategy for LatencyWeightedVoteScoringStrategy {
+    fn calculate_scores_per_authority(&self, scoring_subdag: &ScoringSubdag) -> Vec<u64> {
+        let mut scores_per_authority =
+            VoteScoringStrategy.calculate_scores_per_authority(scoring_subdag);
+        for (authority, score) in scores_per_authority.iter_mut().enumerate() {
+            let inclusions = scoring_subdag.inclusions[authority];
+            if inclusions == 0 {
+                continue;
+            }
+            let average_delay = scoring_subdag.inclusion_delays[authority] / inclusions;
+            *score /= average_delay + 1;
         }
-    }
-
-    pub(crate) fn is_empty(&self) -> bool {
-        self.leaders.is_empty() && self.votes.is_empty() && self.commit_range.is_none()
-    }
-
-    pub(crate) fn clear(&mut self) {
-        self.leaders.clear();
-        self.votes.clear();
-        self.commit_range = None;
+        scores_per_authority
     }
 }
 
@@ -573,7 +639,7 @@ mod tests {
             scoring_subdag.add_subdags(vec![subdag]);
         }
 
-        let scores = scoring_subdag.calculate_scores();
+        let scores = scoring_subdag.calculate_scores(&VoteScoringStrategy);
         assert_eq!(scores.scores_per_authority, vec![5, 5, 5, 5]);
         assert_eq!(scores.commit_range, (1..=4).into());
     }
@@ -619,11 +685,170 @@ mod tests {
             scoring_subdag.add_subdags(vec![subdag]);
         }
 
-        let scores_per_authority = scoring_subdag.score_certified_votes();
+        let scores_per_authority =
+            CertifiedVoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag);
         assert_eq!(scores_per_authority, vec![4, 4, 4, 4]);
         assert_eq!(scoring_subdag.commit_range.unwrap(), (1..=4).into());
     }
 
+    #[tokio::test]
+    async fn test_scoring_strategies_with_crashed_authority() {
+        telemetry_subscribers::init_for_testing();
+        let context = Arc::new(Context::new_for_test(4).0);
+        let crashed = AuthorityIndex::new_for_test(3);
+
+        // Fully connected rounds 1 ~ 4, then authority 3 stops proposing.
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=4).build();
+        dag_builder
+            .layers(5..=12)
+            .authorities(vec![crashed])
+            .skip_block()
+            .build();
+
+        let leaders = dag_builder
+            .leader_blocks(1..=12)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let scoring_subdag = score_committed_leaders(&context, &dag_builder, leaders);
+
+        let vote = VoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag);
+        let certified_vote =
+            CertifiedVoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag);
+        let latency_weighted_vote =
+            LatencyWeightedVoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag);
+
+        // Every strategy ranks the crashed authority last, as it stopped voting.
+        for scores in [&vote, &certified_vote, &latency_weighted_vote] {
+            for authority in 0..3 {
+                assert!(scores[crashed] < scores[authority], "{scores:?}");
+            }
+        }
+        // Its votes before the crash are still certified.
+        assert!(certified_vote[crashed] > 0);
+        // Its blocks were included on time before the crash, so the latency
+        // weighted strategy does not discount them further.
+        assert_eq!(latency_weighted_vote, vote);
+    }
+
+    #[tokio::test]
+    async fn test_scoring_strategies_with_slow_authority() {
+        telemetry_subscribers::init_for_testing();
+        let context = Arc::new(Context::new_for_test(4).0);
+        let slow = AuthorityIndex::new_for_test(3);
+
+        // The blocks of the slow authority reach the others two rounds late, so
+        // they are only ever included through weak links.
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layer(1).build();
+        for round in 2..=12 {
+            let previous_round = dag_builder.blocks(round - 1..=round - 1);
+            let late_blocks = if round > 2 {
+                dag_builder.blocks(round - 2..=round - 2)
+            } else {
+                vec![]
+            };
+            let connections = context
+                .committee
+                .authorities()
+                .map(|(authority, _)| {
+                    let ancestors: Vec<BlockRef> = if authority == slow {
+                        previous_round.iter().map(|b| b.reference()).collect()
+                    } else {
+                        previous_round
+                            .iter()
+                            .chain(late_blocks.iter())
+                            .filter(|b| (b.round() == round - 1) != (b.author() == slow))
+                            .map(|b| b.reference())
+                            .collect()
+                    };
+                    (authority, ancestors)
+                })
+                .collect();
+            dag_builder.layer_with_connections(connections, round);
+        }
+
+        // Leaders of the slow authority do not gather votes and are skipped.
+        let leaders = dag_builder
+            .leader_blocks(1..=12)
+            .into_iter()
+            .flatten()
+            .filter(|leader| leader.author() != slow)
+            .collect::<Vec<_>>();
+        let scoring_subdag = score_committed_leaders(&context, &dag_builder, leaders);
+
+        let vote = VoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag);
+        let certified_vote =
+            CertifiedVoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag);
+        let latency_weighted_vote =
+            LatencyWeightedVoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag);
+
+        for scores in [&vote, &certified_vote, &latency_weighted_vote] {
+            for authority in 0..3 {
+                assert!(scores[slow] < scores[authority], "{scores:?}");
+            }
+        }
+        // The votes of the slow authority are only included by its own blocks:
+        // they still count as votes, but never get certified.
+        assert!(vote[slow] > 0);
+        assert_eq!(certified_vote[slow], 0);
+        // Its blocks are included one round late, which halves its score.
+        assert!(scoring_subdag.inclusion_delays[slow] > 0);
+        assert_eq!(
+            scoring_subdag.inclusion_delays[slow],
+            scoring_subdag.inclusions[slow]
+        );
+        assert_eq!(latency_weighted_vote[slow], vote[slow] / 2);
+        assert_eq!(latency_weighted_vote[..3], vote[..3]);
+    }
+
+    #[tokio::test]
+    async fn test_latency_weighted_vote_scoring_without_late_blocks() {
+        telemetry_subscribers::init_for_testing();
+        let context = Arc::new(Context::new_for_test(4).0);
+
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=4).build();
+        let leaders = dag_builder
+            .leader_blocks(1..=4)
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        let scoring_subdag = score_committed_leaders(&context, &dag_builder, leaders);
+
+        // When every block is included in the following round, the strategy
+        // scores like distributed votes.
+        assert_eq!(scoring_subdag.inclusion_delays, vec![0; 4]);
+        assert_eq!(
+            LatencyWeightedVoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag),
+            VoteScoringStrategy.calculate_scores_per_authority(&scoring_subdag)
+        );
+    }
+
+    fn score_committed_leaders(
+        context: &Arc<Context>,
+        dag_builder: &DagBuilder,
+        leaders: Vec<VerifiedBlock>,
+    ) -> ScoringSubdag {
+        let mut scoring_subdag = ScoringSubdag::new(context.clone());
+        let mut last_committed_rounds = vec![0; context.committee.size()];
+        for (idx, leader) in leaders.into_iter().enumerate() {
+            let commit_index = idx as u32 + 1;
+            let (subdag, _commit) = dag_builder.get_sub_dag_and_commit(
+                leader,
+                last_committed_rounds.clone(),
+                commit_index,
+            );
+            for block in subdag.blocks.iter() {
+                last_committed_rounds[block.author().value()] =
+                    max(block.round(), last_committed_rounds[block.author().value()]);
+            }
+            scoring_subdag.add_subdags(vec![subdag]);
+        }
+        scoring_subdag
+    }
+
     // TODO: Remove all tests below this when DistributedVoteScoring is enabled.
     #[tokio::test]
     async fn test_reputation_score_calculator() {
@@ -5,9 +5,12 @@ mod authority_node;
 mod authority_service;
 mod base_committer;
 mod block;
+mod block_compression;
 mod block_manager;
 mod block_verifier;
 mod broadcaster;
+#[cfg(test)]
+mod byzantine_core;
 mod commit;
 mod commit_consumer;
 mod commit_observer;
@@ -16,14 +19,18 @@ mod commit_vote_monitor;
 mod context;
 mod core;
 mod core_thread;
+mod dag_inspector;
 mod dag_state;
 mod error;
+mod health_monitor;
 mod leader_schedule;
 mod leader_scoring;
 mod leader_timeout;
 mod linearizer;
 mod metrics;
+mod misbehavior_tracker;
 mod network;
+mod replay;
 mod stake_aggregator;
 mod storage;
 mod subscriber;
@@ -32,6 +39,9 @@ mod threshold_clock;
 mod transaction;
 mod universal_committer;
 
+#[cfg(test)]
+#[path = "tests/byzantine_tests.rs"]
+mod byzantine_tests;
 #[cfg(test)]
 #[path = "tests/randomized_tests.rs"]
 mod randomized_tests;
@@ -45,10 +55,17 @@ mod test_dag_parser;
 
 /// Exported consensus API.
 pub use authority_node::ConsensusAuthority;
-pub use block::{BlockAPI, Round, TransactionIndex};
+pub use block::{BlockAPI, BlockRef, MisbehaviorProof, MisbehaviorReport, Round, TransactionIndex};
 pub use commit::{CommitDigest, CommitIndex, CommitRef, CommittedSubDag};
 pub use commit_consumer::{CommitConsumer, CommitConsumerMonitor};
-pub use transaction::{ClientError, TransactionClient, TransactionVerifier, ValidationError};
+pub use dag_inspector::{
+    AuthorityView, BlockRefView, BlockView, DagInspector, DagView, LeaderDecision, LeaderView,
+};
+pub use health_monitor::{ConsensusHealth, HealthMonitor, LeaderOutcome, RoundPropagation};
+pub use replay::{CommitSummary, ConsensusReplayer, Latency, ReplayReport};
+pub use transaction::{
+    ClientError, TransactionClient, TransactionLane, TransactionVerifier, ValidationError,
+};
 
 /// Exported API for testing.
 pub use block::{TestBlock, Transaction, VerifiedBlock};
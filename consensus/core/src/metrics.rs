@@ -108,6 +108,8 @@ pub(crate) struct NodeMetrics {
     pub(crate) block_proposal_leader_wait_ms: IntCounterVec,
     pub(crate) block_proposal_leader_wait_count: IntCounterVec,
     pub(crate) block_timestamp_drift_wait_ms: IntCounterVec,
+    pub(crate) block_compression_ratio: HistogramVec,
+    pub(crate) block_compression_latency: HistogramVec,
     pub(crate) blocks_per_commit_count: Histogram,
     pub(crate) broadcaster_rtt_estimate_ms: IntGaugeVec,
     pub(crate) core_add_blocks_batch_size: Histogram,
@@ -121,12 +123,17 @@ pub(crate) struct NodeMetrics {
     pub(crate) dag_state_recent_refs: IntGauge,
     pub(crate) dag_state_store_read_count: IntCounterVec,
     pub(crate) dag_state_store_write_count: IntCounter,
+    pub(crate) store_pruned_round: IntGauge,
+    pub(crate) store_pruned_commit_index: IntGauge,
     pub(crate) fetch_blocks_scheduler_inflight: IntGauge,
     pub(crate) fetch_blocks_scheduler_skipped: IntCounterVec,
     pub(crate) synchronizer_fetched_blocks_by_peer: IntCounterVec,
     pub(crate) synchronizer_missing_blocks_by_authority: IntCounterVec,
     pub(crate) synchronizer_fetched_blocks_by_authority: IntCounterVec,
     pub(crate) invalid_blocks: IntCounterVec,
+    pub(crate) misbehavior_detected: IntCounterVec,
+    pub(crate) misbehavior_reports_received: IntCounterVec,
+    pub(crate) misbehaving_authorities: IntGaugeVec,
     pub(crate) rejected_blocks: IntCounterVec,
     pub(crate) rejected_future_blocks: IntCounterVec,
     pub(crate) subscribed_blocks: IntCounterVec,
@@ -251,6 +258,20 @@ impl NodeMetrics {
                 &["authority", "source"],
                 registry,
             ).unwrap(),
+            block_compression_ratio: register_histogram_vec_with_registry!(
+                "block_compression_ratio",
+                "Ratio of the serialized size of blocks to their zstd compressed size, by where the compressed block is sent (network or storage).",
+                &["path"],
+                exponential_buckets(1.0, 1.25, 16).unwrap(),
+                registry,
+            ).unwrap(),
+            block_compression_latency: register_histogram_vec_with_registry!(
+                "block_compression_latency",
+                "Time spent compressing or decompressing a block, by path (network or storage) and operation.",
+                &["path", "operation"],
+                FINE_GRAINED_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            ).unwrap(),
             blocks_per_commit_count: register_histogram_with_registry!(
                 "blocks_per_commit_count",
                 "The number of blocks per commit.",
@@ -318,6 +339,16 @@ impl NodeMetrics {
                 &["type"],
                 registry,
             ).unwrap(),
+            store_pruned_round: register_int_gauge_with_registry!(
+                "store_pruned_round",
+                "Blocks of rounds lower than this have been deleted from the store",
+                registry,
+            ).unwrap(),
+            store_pruned_commit_index: register_int_gauge_with_registry!(
+                "store_pruned_commit_index",
+                "Commits of indices lower than this have been deleted from the store",
+                registry,
+            ).unwrap(),
             dag_state_store_write_count: register_int_counter_with_registry!(
                 "dag_state_store_write_count",
                 "Number of times DagState needs to write to store",
@@ -369,6 +400,24 @@ impl NodeMetrics {
                 &["authority", "source", "error"],
                 registry,
             ).unwrap(),
+            misbehavior_detected: register_int_counter_vec_with_registry!(
+                "misbehavior_detected",
+                "Number of misbehaviors detected locally per authority, by kind of misbehavior",
+                &["authority", "kind"],
+                registry,
+            ).unwrap(),
+            misbehavior_reports_received: register_int_counter_vec_with_registry!(
+                "misbehavior_reports_received",
+                "Number of misbehavior reports against an authority included in accepted blocks, by kind of misbehavior",
+                &["authority", "kind"],
+                registry,
+            ).unwrap(),
+            misbehaving_authorities: register_int_gauge_vec_with_registry!(
+                "misbehaving_authorities",
+                "Set to 1 when misbehavior reports against an authority reach the validity threshold",
+                &["authority"],
+                registry,
+            ).unwrap(),
             rejected_blocks: register_int_counter_vec_with_registry!(
                 "rejected_blocks",
                 "Number of blocks rejected before verifications",
@@ -0,0 +1,253 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::{BTreeSet, VecDeque},
+    sync::Arc,
+};
+
+#[cfg(test)]
+use consensus_config::AuthorityIndex;
+use tracing::warn;
+
+use crate::{
+    block::{BlockAPI as _, BlockRef, MisbehaviorProof, MisbehaviorReport, VerifiedBlock},
+    context::Context,
+    stake_aggregator::{StakeAggregator, ValidityThreshold},
+};
+
+/// Maximum number of distinct proofs reported against the same authority. One proof is enough to
+/// identify a misbehaving authority, so this only bounds the memory and block space a Byzantine
+/// authority can make others spend on it.
+const MAX_REPORTED_PROOFS_PER_AUTHORITY: usize = 3;
+
+/// Keeps track of misbehavior by authorities in the committee.
+///
+/// Misbehavior detected locally is queued to be attached to the next own block proposals, so it
+/// reaches the rest of the committee. Reports attached to accepted blocks are aggregated per
+/// reported authority, which is considered misbehaving once its reporters reach the validity
+/// threshold, i.e. at least one honest authority has observed the misbehavior.
+///
+/// The state is kept in memory only and is rebuilt from newly accepted blocks after a restart.
+pub(crate) struct MisbehaviorTracker {
+    context: Arc<Context>,
+    // Proofs of misbehavior detected locally, per authority.
+    detected_proofs: Vec<BTreeSet<MisbehaviorProof>>,
+    // Reports of locally detected misbehavior, pending to be included in new blocks.
+    pending_reports: VecDeque<MisbehaviorReport>,
+    // Authorities that have reported misbehavior, per reported authority.
+    reporters: Vec<StakeAggregator<ValidityThreshold>>,
+}
+
+impl MisbehaviorTracker {
+    pub(crate) fn new(context: Arc<Context>) -> Self {
+        let num_authorities = context.committee.size();
+        Self {
+            context,
+            detected_proofs: vec![BTreeSet::new(); num_authorities],
+            pending_reports: VecDeque::new(),
+            reporters: (0..num_authorities)
+                .map(|_| StakeAggregator::new())
+                .collect(),
+        }
+    }
+
+    /// Observes a block being accepted into the DAG. `slot_blocks` are the references of the
+    /// blocks already accepted in the same slot, which the block equivocates with.
+    pub(crate) fn observe_block(
+        &mut self,
+        block: &VerifiedBlock,
+        slot_blocks: impl IntoIterator<Item = BlockRef>,
+    ) {
+        let block_ref = block.reference();
+        for other in slot_blocks {
+            if other != block_ref {
+                self.report(MisbehaviorProof::equivocation(other, block_ref));
+            }
+        }
+
+        // Reports are only validated by the block verifier when the feature is enabled.
+        if !self.context.protocol_config.consensus_misbehavior_reports() {
+            return;
+        }
+        let committee = &self.context.committee;
+        for report in block.misbehavior_reports() {
+            let hostname = &committee.authority(report.target).hostname;
+            self.context
+                .metrics
+                .node_metrics
+                .misbehavior_reports_received
+                .with_label_values(&[hostname, report.proof.kind()])
+                .inc();
+
+            let reporters = &mut self.reporters[report.target];
+            if reporters.reached_threshold(committee) {
+                continue;
+            }
+            if reporters.add(block.author(), committee) {
+                warn!(
+                    "Authority {} has been reported as misbehaving by validity stake",
+                    hostname
+                );
+                self.context
+                    .metrics
+                    .node_metrics
+                    .misbehaving_authorities
+                    .with_label_values(&[hostname])
+                    .set(1);
+            }
+        }
+    }
+
+    /// Reports a block that is signed by its author but has failed verification.
+    pub(crate) fn report_invalid_block(&mut self, block_ref: BlockRef) {
+        self.report(MisbehaviorProof::InvalidBlock(block_ref));
+    }
+
+    /// Takes up to `limit` reports of locally detected misbehavior, to be included in a new block.
+    pub(crate) fn take_pending_reports(&mut self, limit: usize) -> Vec<MisbehaviorReport> {
+        let count = limit.min(self.pending_reports.len());
+        self.pending_reports.drain(..count).collect()
+    }
+
+    /// Returns the authorities that are reported as misbehaving by at least validity stake.
+    #[cfg(test)]
+    pub(crate) fn misbehaving_authorities(&self) -> Vec<AuthorityIndex> {
+        self.context
+            .committee
+            .authorities()
+            .filter(|(index, _)| self.reporters[*index].reached_threshold(&self.context.committee))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn report(&mut self, proof: MisbehaviorProof) {
+        let target = proof.author();
+        if target == self.context.own_index {
+            return;
+        }
+        let detected_proofs = &mut self.detected_proofs[target];
+        if detected_proofs.len() >= MAX_REPORTED_PROOFS_PER_AUTHORITY
+            || !detected_proofs.insert(proof.clone())
+        {
+            return;
+        }
+
+        let hostname = &self.context.committee.authority(target).hostname;
+        warn!("Detected misbehavior of authority {hostname}: {proof:?}");
+        self.context
+            .metrics
+            .node_metrics
+            .misbehavior_detected
+            .with_label_values(&[hostname, proof.kind()])
+            .inc();
+
+        if self.context.protocol_config.consensus_misbehavior_reports() {
+            self.pending_reports
+                .push_back(MisbehaviorReport::new(proof));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use consensus_config::AuthorityIndex;
+
+    use super::*;
+    use crate::{
+        block::{BlockDigest, TestBlock},
+        context::Context,
+    };
+
+    #[tokio::test]
+    async fn test_detect_and_report_misbehavior() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut tracker = MisbehaviorTracker::new(context.clone());
+
+        // Two blocks from authority 1 in the same slot.
+        let block = VerifiedBlock::new_for_test(TestBlock::new(5, 1).set_timestamp_ms(1).build());
+        let equivocating_block =
+            VerifiedBlock::new_for_test(TestBlock::new(5, 1).set_timestamp_ms(2).build());
+        tracker.observe_block(&block, vec![]);
+        tracker.observe_block(&equivocating_block, vec![block.reference()]);
+        // Observing the same pair again does not report it twice.
+        tracker.observe_block(&equivocating_block, vec![block.reference()]);
+
+        // An invalid block from authority 2.
+        let invalid_block_ref = BlockRef::new(5, AuthorityIndex::new_for_test(2), BlockDigest::MIN);
+        tracker.report_invalid_block(invalid_block_ref);
+
+        // Misbehavior of the own authority is never reported.
+        tracker.report_invalid_block(BlockRef::new(5, context.own_index, BlockDigest::MIN));
+
+        let reports = tracker.take_pending_reports(1);
+        assert_eq!(
+            reports,
+            vec![MisbehaviorReport::new(MisbehaviorProof::equivocation(
+                block.reference(),
+                equivocating_block.reference(),
+            ))]
+        );
+        assert_eq!(reports[0].target, AuthorityIndex::new_for_test(1));
+        let reports = tracker.take_pending_reports(10);
+        assert_eq!(
+            reports,
+            vec![MisbehaviorReport::new(MisbehaviorProof::InvalidBlock(
+                invalid_block_ref
+            ))]
+        );
+        assert!(tracker.take_pending_reports(10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_limit_reported_proofs_per_authority() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut tracker = MisbehaviorTracker::new(context);
+
+        for round in 1..=10 {
+            tracker.report_invalid_block(BlockRef::new(
+                round,
+                AuthorityIndex::new_for_test(3),
+                BlockDigest::MIN,
+            ));
+        }
+
+        assert_eq!(
+            tracker.take_pending_reports(10).len(),
+            MAX_REPORTED_PROOFS_PER_AUTHORITY
+        );
+    }
+
+    #[tokio::test]
+    async fn test_aggregate_misbehavior_reports() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut tracker = MisbehaviorTracker::new(context);
+
+        let report = MisbehaviorReport::new(MisbehaviorProof::InvalidBlock(BlockRef::new(
+            5,
+            AuthorityIndex::new_for_test(3),
+            BlockDigest::MIN,
+        )));
+        let reporting_block = |round, author| {
+            VerifiedBlock::new_for_test(
+                TestBlock::new(round, author)
+                    .set_misbehavior_reports(vec![report.clone()])
+                    .build(),
+            )
+        };
+
+        // Reports from the same authority are only counted once.
+        tracker.observe_block(&reporting_block(6, 1), vec![]);
+        tracker.observe_block(&reporting_block(7, 1), vec![]);
+        assert!(tracker.misbehaving_authorities().is_empty());
+
+        // Validity threshold of a committee of 4 with equal stake is 2.
+        tracker.observe_block(&reporting_block(7, 2), vec![]);
+        assert_eq!(
+            tracker.misbehaving_authorities(),
+            vec![AuthorityIndex::new_for_test(3)]
+        );
+    }
+}
@@ -39,12 +39,18 @@ use super::{
 };
 use crate::{
     block::{BlockRef, VerifiedBlock},
+    block_compression::{compress_block_for_peer, decompress_block_from_peer},
     commit::CommitRange,
     context::Context,
     error::{ConsensusError, ConsensusResult},
     CommitIndex, Round,
 };
 
+/// Request header set by clients able to handle zstd compressed blocks in responses. Request and
+/// response bodies are BCS encoded, so unlike Tonic messages they cannot carry new fields without
+/// breaking peers running older versions.
+const ACCEPT_COMPRESSED_BLOCKS_HEADER_KEY: &str = "accept-compressed-blocks";
+
 /// Implements Anemo RPC client for Consensus.
 pub(crate) struct AnemoClient {
     context: Arc<Context>,
@@ -65,6 +71,23 @@ impl AnemoClient {
         self.network.store(Some(Arc::new(network)));
     }
 
+    /// Builds a request for blocks, advertising support for compressed blocks when enabled.
+    fn blocks_request<T>(&self, body: T, timeout: Duration) -> anemo::Request<T> {
+        let request = anemo::Request::new(body).with_timeout(timeout);
+        if self.context.parameters.block_compression.network {
+            request.with_header(ACCEPT_COMPRESSED_BLOCKS_HEADER_KEY, "true")
+        } else {
+            request
+        }
+    }
+
+    fn decompress_blocks(&self, blocks: Vec<Bytes>) -> ConsensusResult<Vec<Bytes>> {
+        blocks
+            .into_iter()
+            .map(|block| decompress_block_from_peer(&self.context, block))
+            .collect()
+    }
+
     async fn get_client(
         &self,
         peer: AuthorityIndex,
@@ -178,7 +201,7 @@ impl NetworkClient for AnemoClient {
             highest_accepted_rounds,
         };
         let response = client
-            .fetch_blocks(anemo::Request::new(request).with_timeout(timeout))
+            .fetch_blocks(self.blocks_request(request, timeout))
             .await
             .map_err(|e: Status| {
                 if e.status() == StatusCode::RequestTimeout {
@@ -188,7 +211,7 @@ impl NetworkClient for AnemoClient {
                 }
             })?;
         let body = response.into_body();
-        Ok(body.blocks)
+        self.decompress_blocks(body.blocks)
     }
 
     async fn fetch_commits(
@@ -203,11 +226,12 @@ impl NetworkClient for AnemoClient {
             end: commit_range.end(),
         };
         let response = client
-            .fetch_commits(anemo::Request::new(request).with_timeout(timeout))
+            .fetch_commits(self.blocks_request(request, timeout))
             .await
             .map_err(|e| ConsensusError::NetworkRequest(format!("fetch_blocks failed: {e:?}")))?;
         let response = response.into_body();
-        Ok((response.commits, response.certifier_blocks))
+        let certifier_blocks = self.decompress_blocks(response.certifier_blocks)?;
+        Ok((response.commits, certifier_blocks))
     }
 
     async fn fetch_latest_blocks(
@@ -219,7 +243,7 @@ impl NetworkClient for AnemoClient {
         let mut client = self.get_client(peer, timeout).await?;
         let request = FetchLatestBlocksRequest { authorities };
         let response = client
-            .fetch_latest_blocks(anemo::Request::new(request).with_timeout(timeout))
+            .fetch_latest_blocks(self.blocks_request(request, timeout))
             .await
             .map_err(|e: Status| {
                 if e.status() == StatusCode::RequestTimeout {
@@ -231,7 +255,7 @@ impl NetworkClient for AnemoClient {
                 }
             })?;
         let body = response.into_body();
-        Ok(body.blocks)
+        self.decompress_blocks(body.blocks)
     }
 
     async fn get_latest_rounds(
@@ -258,8 +282,15 @@ impl NetworkClient for AnemoClient {
     }
 }
 
+fn accepts_compressed_blocks<T>(request: &anemo::Request<T>) -> bool {
+    request
+        .headers()
+        .contains_key(ACCEPT_COMPRESSED_BLOCKS_HEADER_KEY)
+}
+
 /// Proxies Anemo requests to NetworkService with actual handler implementation.
 struct AnemoServiceProxy<S: NetworkService> {
+    context: Arc<Context>,
     peer_map: BTreeMap<PeerId, AuthorityIndex>,
     service: Arc<S>,
 }
@@ -274,7 +305,28 @@ impl<S: NetworkService> AnemoServiceProxy<S> {
                 (peer_id, index)
             })
             .collect();
-        Self { peer_map, service }
+        Self {
+            context,
+            peer_map,
+            service,
+        }
+    }
+
+    fn compress_blocks(
+        &self,
+        accept_compressed_blocks: bool,
+        blocks: Vec<Bytes>,
+    ) -> Result<Vec<Bytes>, anemo::rpc::Status> {
+        blocks
+            .into_iter()
+            .map(|block| compress_block_for_peer(&self.context, accept_compressed_blocks, block))
+            .collect::<ConsensusResult<_>>()
+            .map_err(|e| {
+                anemo::rpc::Status::new_with_message(
+                    anemo::types::response::StatusCode::InternalServerError,
+                    format!("{e}"),
+                )
+            })
     }
 }
 
@@ -325,6 +377,7 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                 "peer not found",
             )
         })?;
+        let accept_compressed_blocks = accepts_compressed_blocks(&request);
         let body = request.into_body();
         let block_refs = body
             .block_refs
@@ -350,6 +403,7 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                     format!("{e}"),
                 )
             })?;
+        let blocks = self.compress_blocks(accept_compressed_blocks, blocks)?;
         Ok(Response::new(FetchBlocksResponse { blocks }))
     }
 
@@ -369,6 +423,7 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                 "peer not found",
             )
         })?;
+        let accept_compressed_blocks = accepts_compressed_blocks(&request);
         let request = request.into_body();
         let (commits, certifier_blocks) = self
             .service
@@ -384,10 +439,13 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
             .into_iter()
             .map(|c| c.serialized().clone())
             .collect();
-        let certifier_blocks = certifier_blocks
-            .into_iter()
-            .map(|b| b.serialized().clone())
-            .collect();
+        let certifier_blocks = self.compress_blocks(
+            accept_compressed_blocks,
+            certifier_blocks
+                .into_iter()
+                .map(|b| b.serialized().clone())
+                .collect(),
+        )?;
         Ok(Response::new(FetchCommitsResponse {
             commits,
             certifier_blocks,
@@ -410,6 +468,7 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                 "peer not found",
             )
         })?;
+        let accept_compressed_blocks = accepts_compressed_blocks(&request);
         let body = request.into_body();
         let blocks = self
             .service
@@ -421,6 +480,7 @@ impl<S: NetworkService> ConsensusRpc for AnemoServiceProxy<S> {
                     format!("{e}"),
                 )
             })?;
+        let blocks = self.compress_blocks(accept_compressed_blocks, blocks)?;
         Ok(Response::new(FetchLatestBlocksResponse { blocks }))
     }
 
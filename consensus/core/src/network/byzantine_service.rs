@@ -0,0 +1,218 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Injects Byzantine behavior into an authority for tests, by wrapping the `NetworkService` that
+//! serves its blocks to peers. Core of the authority keeps running honestly, and the blocks it
+//! proposes are altered and re-signed with the authority's protocol key on their way out, so
+//! peers receive validly signed misbehavior. Misbehavior of Core itself, such as equivocating for
+//! every peer, is injected with `ByzantineCoreBehavior` instead.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use consensus_config::{AuthorityIndex, ProtocolKeyPair};
+use futures::{stream, StreamExt as _};
+
+use crate::{
+    block::{Block, BlockAPI as _, BlockDigest, BlockRef, SignedBlock, TestBlock, VerifiedBlock},
+    commit::{CommitRange, TrustedCommit},
+    context::Context,
+    error::{ConsensusError, ConsensusResult},
+    network::{BlockStream, NetworkService},
+    Round,
+};
+
+/// Maximum number of blocks being delayed at once in a subscription stream.
+const MAX_DELAYED_BLOCKS: usize = 1000;
+
+/// Misbehavior of a Byzantine authority towards its peers.
+#[derive(Clone, Debug)]
+pub(crate) enum ByzantineBehavior {
+    /// Serves the blocks actually proposed, when the authority only misbehaves in its Core.
+    Honest,
+    /// Serves the listed peers a conflicting version of each own block, with the same round but a
+    /// different timestamp. Other peers get the blocks actually proposed.
+    Equivocate { targets: Vec<AuthorityIndex> },
+    /// Serves no blocks at all to the listed peers, which have to get them from others.
+    WithholdBlocks { targets: Vec<AuthorityIndex> },
+    /// Serves own blocks with an ancestor from the block's own round, which fails verification.
+    InvalidAncestors,
+    /// Streams own blocks, and so the votes they carry for earlier blocks and commits, late.
+    DelayVotes { delay: Duration },
+}
+
+/// Wraps the `NetworkService` of an authority and applies a `ByzantineBehavior` to what it serves.
+pub(crate) struct ByzantineService<S: NetworkService> {
+    inner: Arc<S>,
+    tamperer: Arc<BlockTamperer>,
+}
+
+impl<S: NetworkService> ByzantineService<S> {
+    pub(crate) fn new(
+        context: Arc<Context>,
+        inner: Arc<S>,
+        protocol_keypair: ProtocolKeyPair,
+        behavior: ByzantineBehavior,
+    ) -> Self {
+        Self {
+            inner,
+            tamperer: Arc::new(BlockTamperer {
+                context,
+                protocol_keypair,
+                behavior,
+            }),
+        }
+    }
+}
+
+/// Alters the own blocks of the authority according to its behavior, before serving them.
+struct BlockTamperer {
+    context: Arc<Context>,
+    protocol_keypair: ProtocolKeyPair,
+    behavior: ByzantineBehavior,
+}
+
+impl BlockTamperer {
+    fn withholds_from(&self, peer: AuthorityIndex) -> bool {
+        matches!(
+            &self.behavior,
+            ByzantineBehavior::WithholdBlocks { targets } if targets.contains(&peer)
+        )
+    }
+
+    fn delay(&self) -> Duration {
+        match &self.behavior {
+            ByzantineBehavior::DelayVotes { delay } => *delay,
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Returns the serialized block to serve to `peer` in place of `serialized`.
+    fn tamper(&self, peer: AuthorityIndex, serialized: Bytes) -> ConsensusResult<Bytes> {
+        let block: SignedBlock =
+            bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
+        if block.author() != self.context.own_index {
+            return Ok(serialized);
+        }
+        let tampered = match &self.behavior {
+            ByzantineBehavior::Equivocate { targets } if targets.contains(&peer) => {
+                copy_block(&block).set_timestamp_ms(block.timestamp_ms() + 1)
+            }
+            ByzantineBehavior::InvalidAncestors => {
+                let mut ancestors = block.ancestors().to_vec();
+                ancestors.push(BlockRef::new(
+                    block.round(),
+                    self.context.own_index,
+                    BlockDigest::default(),
+                ));
+                copy_block(&block).set_ancestors(ancestors)
+            }
+            _ => return Ok(serialized),
+        };
+        SignedBlock::new(tampered.build(), &self.protocol_keypair)?
+            .serialize()
+            .map_err(ConsensusError::SerializationFailure)
+    }
+
+    fn tamper_all(&self, peer: AuthorityIndex, blocks: Vec<Bytes>) -> ConsensusResult<Vec<Bytes>> {
+        blocks
+            .into_iter()
+            .map(|block| self.tamper(peer, block))
+            .collect()
+    }
+}
+
+/// Starts building a block with the same content as `block`.
+pub(crate) fn copy_block(block: &Block) -> TestBlock {
+    TestBlock::new(block.round(), block.author().value() as u32)
+        .set_epoch(block.epoch())
+        .set_timestamp_ms(block.timestamp_ms())
+        .set_ancestors(block.ancestors().to_vec())
+        .set_transactions(block.transactions().to_vec())
+        .set_commit_votes(block.commit_votes().to_vec())
+        .set_misbehavior_reports(block.misbehavior_reports().to_vec())
+}
+
+#[async_trait]
+impl<S: NetworkService> NetworkService for ByzantineService<S> {
+    async fn handle_send_block(&self, peer: AuthorityIndex, block: Bytes) -> ConsensusResult<()> {
+        self.inner.handle_send_block(peer, block).await
+    }
+
+    async fn handle_subscribe_blocks(
+        &self,
+        peer: AuthorityIndex,
+        last_received: Round,
+    ) -> ConsensusResult<BlockStream> {
+        if self.tamperer.withholds_from(peer) {
+            return Ok(Box::pin(stream::pending()));
+        }
+        let blocks = self
+            .inner
+            .handle_subscribe_blocks(peer, last_received)
+            .await?;
+        let tamperer = self.tamperer.clone();
+        let delay = tamperer.delay();
+        // Blocks are delayed concurrently, so the delay does not accumulate over the stream.
+        Ok(Box::pin(
+            blocks
+                .map(move |block| {
+                    let block = tamperer
+                        .tamper(peer, block)
+                        .unwrap_or_else(|e| panic!("Failed to tamper with block: {e:?}"));
+                    async move {
+                        tokio::time::sleep(delay).await;
+                        block
+                    }
+                })
+                .buffered(MAX_DELAYED_BLOCKS),
+        ))
+    }
+
+    async fn handle_fetch_blocks(
+        &self,
+        peer: AuthorityIndex,
+        block_refs: Vec<BlockRef>,
+        highest_accepted_rounds: Vec<Round>,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        if self.tamperer.withholds_from(peer) {
+            return Ok(vec![]);
+        }
+        let blocks = self
+            .inner
+            .handle_fetch_blocks(peer, block_refs, highest_accepted_rounds)
+            .await?;
+        self.tamperer.tamper_all(peer, blocks)
+    }
+
+    async fn handle_fetch_commits(
+        &self,
+        peer: AuthorityIndex,
+        commit_range: CommitRange,
+    ) -> ConsensusResult<(Vec<TrustedCommit>, Vec<VerifiedBlock>)> {
+        if self.tamperer.withholds_from(peer) {
+            return Ok((vec![], vec![]));
+        }
+        self.inner.handle_fetch_commits(peer, commit_range).await
+    }
+
+    async fn handle_fetch_latest_blocks(
+        &self,
+        peer: AuthorityIndex,
+        authorities: Vec<AuthorityIndex>,
+    ) -> ConsensusResult<Vec<Bytes>> {
+        if self.tamperer.withholds_from(peer) {
+            return Ok(vec![]);
+        }
+        let blocks = self
+            .inner
+            .handle_fetch_latest_blocks(peer, authorities)
+            .await?;
+        self.tamperer.tamper_all(peer, blocks)
+    }
+
+    async fn handle_get_latest_rounds(&self, peer: AuthorityIndex) -> ConsensusResult<Vec<Round>> {
+        self.inner.handle_get_latest_rounds(peer).await
+    }
+}
@@ -42,6 +42,8 @@ mod tonic_gen {
 }
 
 pub(crate) mod anemo_network;
+#[cfg(test)]
+pub(crate) mod byzantine_service;
 pub(crate) mod connection_monitor;
 pub(crate) mod epoch_filter;
 pub(crate) mod metrics;
@@ -229,3 +229,57 @@ async fn subscribe_and_receive_blocks(
         .unwrap();
     assert!(receive_stream_1.next().await.is_none());
 }
+
+#[rstest]
+#[tokio::test]
+async fn subscribe_and_receive_compressed_blocks(
+    #[values(TonicManagerBuilder {})] manager_builder: impl ManagerBuilder,
+    #[values(true, false)] peer_compression: bool,
+) {
+    let (mut context, keys) = Context::new_for_test(4);
+    context.parameters.block_compression.network = true;
+
+    let context_0 = Arc::new(
+        context
+            .clone()
+            .with_authority_index(context.committee.to_authority_index(0).unwrap()),
+    );
+    let mut manager_0 = manager_builder.build(context_0.clone(), keys[0].0.clone());
+    let client_0 = manager_0.client();
+    manager_0.install_service(service_with_own_blocks()).await;
+
+    // The peer only compresses blocks when it has compression enabled too, and the blocks
+    // are received the same either way.
+    context.parameters.block_compression.network = peer_compression;
+    let context_1 = Arc::new(
+        context
+            .clone()
+            .with_authority_index(context.committee.to_authority_index(1).unwrap()),
+    );
+    let mut manager_1 = manager_builder.build(context_1.clone(), keys[1].0.clone());
+    manager_1.install_service(service_with_own_blocks()).await;
+
+    let client_0_round = 90;
+    let received = client_0
+        .subscribe_blocks(
+            context_0.committee.to_authority_index(1).unwrap(),
+            client_0_round,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+    let expected = (client_0_round + 1..=100)
+        .map(block_for_round)
+        .collect::<Vec<_>>();
+    assert_eq!(received, expected);
+
+    let compressed = context_1
+        .metrics
+        .node_metrics
+        .block_compression_ratio
+        .with_label_values(&["network"])
+        .get_sample_count();
+    assert_eq!(compressed > 0, peer_compression);
+}
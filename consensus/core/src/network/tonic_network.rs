@@ -49,6 +49,7 @@ use super::{
 };
 use crate::{
     block::{BlockRef, VerifiedBlock},
+    block_compression::{compress_block_for_peer, decompress_block_from_peer},
     commit::CommitRange,
     context::Context,
     error::{ConsensusError, ConsensusResult},
@@ -137,23 +138,33 @@ impl NetworkClient for TonicClient {
     ) -> ConsensusResult<BlockStream> {
         let mut client = self.get_client(peer, timeout).await?;
         // TODO: add sampled block acknowledgments for latency measurements.
+        let accept_compressed_blocks = self.context.parameters.block_compression.network;
         let request = Request::new(stream::once(async move {
             SubscribeBlocksRequest {
                 last_received_round: last_received,
+                accept_compressed_blocks,
             }
         }));
         let response = client.subscribe_blocks(request).await.map_err(|e| {
             ConsensusError::NetworkRequest(format!("subscribe_blocks failed: {e:?}"))
         })?;
+        let context = self.context.clone();
         let stream = response
             .into_inner()
             .take_while(|b| futures::future::ready(b.is_ok()))
-            .filter_map(move |b| async move {
-                match b {
-                    Ok(response) => Some(response.block),
-                    Err(e) => {
-                        debug!("Network error received from {}: {e:?}", peer);
-                        None
+            .filter_map(move |b| {
+                let context = context.clone();
+                async move {
+                    match b.map(|response| decompress_block_from_peer(&context, response.block)) {
+                        Ok(Ok(block)) => Some(block),
+                        Ok(Err(e)) => {
+                            debug!("Failed to decompress block from {}: {e:?}", peer);
+                            None
+                        }
+                        Err(e) => {
+                            debug!("Network error received from {}: {e:?}", peer);
+                            None
+                        }
                     }
                 }
             });
@@ -183,6 +194,7 @@ impl NetworkClient for TonicClient {
                 })
                 .collect(),
             highest_accepted_rounds,
+            accept_compressed_blocks: self.context.parameters.block_compression.network,
         });
         request.set_timeout(timeout);
         let mut stream = client
@@ -233,7 +245,10 @@ impl NetworkClient for TonicClient {
                 }
             }
         }
-        Ok(blocks)
+        blocks
+            .into_iter()
+            .map(|block| decompress_block_from_peer(&self.context, block))
+            .collect()
     }
 
     async fn fetch_commits(
@@ -246,6 +261,7 @@ impl NetworkClient for TonicClient {
         let mut request = Request::new(FetchCommitsRequest {
             start: commit_range.start(),
             end: commit_range.end(),
+            accept_compressed_blocks: self.context.parameters.block_compression.network,
         });
         request.set_timeout(timeout);
         let response = client
@@ -253,7 +269,12 @@ impl NetworkClient for TonicClient {
             .await
             .map_err(|e| ConsensusError::NetworkRequest(format!("fetch_commits failed: {e:?}")))?;
         let response = response.into_inner();
-        Ok((response.commits, response.certifier_blocks))
+        let certifier_blocks = response
+            .certifier_blocks
+            .into_iter()
+            .map(|block| decompress_block_from_peer(&self.context, block))
+            .collect::<ConsensusResult<_>>()?;
+        Ok((response.commits, certifier_blocks))
     }
 
     async fn fetch_latest_blocks(
@@ -268,6 +289,7 @@ impl NetworkClient for TonicClient {
                 .iter()
                 .map(|authority| authority.value() as u32)
                 .collect(),
+            accept_compressed_blocks: self.context.parameters.block_compression.network,
         });
         request.set_timeout(timeout);
         let mut stream = client
@@ -318,7 +340,10 @@ impl NetworkClient for TonicClient {
                 }
             }
         }
-        Ok(blocks)
+        blocks
+            .into_iter()
+            .map(|block| decompress_block_from_peer(&self.context, block))
+            .collect()
     }
 
     async fn get_latest_rounds(
@@ -450,6 +475,18 @@ impl<S: NetworkService> TonicServiceProxy<S> {
     fn new(context: Arc<Context>, service: Arc<S>) -> Self {
         Self { context, service }
     }
+
+    fn compress_blocks(
+        &self,
+        accept_compressed_blocks: bool,
+        blocks: Vec<Bytes>,
+    ) -> Result<Vec<Bytes>, tonic::Status> {
+        blocks
+            .into_iter()
+            .map(|block| compress_block_for_peer(&self.context, accept_compressed_blocks, block))
+            .collect::<ConsensusResult<_>>()
+            .map_err(|e| tonic::Status::internal(format!("{e:?}")))
+    }
 }
 
 #[async_trait]
@@ -501,12 +538,18 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
                 return Err(tonic::Status::invalid_argument("Missing request"));
             }
         };
+        let context = self.context.clone();
+        let accept_compressed_blocks = first_request.accept_compressed_blocks;
         let stream = self
             .service
             .handle_subscribe_blocks(peer_index, first_request.last_received_round)
             .await
             .map_err(|e| tonic::Status::internal(format!("{e:?}")))?
-            .map(|block| Ok(SubscribeBlocksResponse { block }));
+            .map(move |block| {
+                compress_block_for_peer(&context, accept_compressed_blocks, block)
+                    .map(|block| SubscribeBlocksResponse { block })
+                    .map_err(|e| tonic::Status::internal(format!("{e:?}")))
+            });
         let rate_limited_stream =
             tokio_stream::StreamExt::throttle(stream, self.context.parameters.min_round_delay / 2)
                 .boxed();
@@ -544,6 +587,7 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
             .handle_fetch_blocks(peer_index, block_refs, highest_accepted_rounds)
             .await
             .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+        let blocks = self.compress_blocks(inner.accept_compressed_blocks, blocks)?;
         let responses: std::vec::IntoIter<Result<FetchBlocksResponse, tonic::Status>> =
             chunk_blocks(blocks, MAX_FETCH_RESPONSE_BYTES)
                 .into_iter()
@@ -575,10 +619,13 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
             .into_iter()
             .map(|c| c.serialized().clone())
             .collect();
-        let certifier_blocks = certifier_blocks
-            .into_iter()
-            .map(|b| b.serialized().clone())
-            .collect();
+        let certifier_blocks = self.compress_blocks(
+            request.accept_compressed_blocks,
+            certifier_blocks
+                .into_iter()
+                .map(|b| b.serialized().clone())
+                .collect(),
+        )?;
         Ok(Response::new(FetchCommitsResponse {
             commits,
             certifier_blocks,
@@ -621,6 +668,7 @@ impl<S: NetworkService> ConsensusService for TonicServiceProxy<S> {
             .handle_fetch_latest_blocks(peer_index, authorities)
             .await
             .map_err(|e| tonic::Status::internal(format!("{e:?}")))?;
+        let blocks = self.compress_blocks(inner.accept_compressed_blocks, blocks)?;
         let responses: std::vec::IntoIter<Result<FetchLatestBlocksResponse, tonic::Status>> =
             chunk_blocks(blocks, MAX_FETCH_RESPONSE_BYTES)
                 .into_iter()
@@ -1124,10 +1172,14 @@ pub(crate) struct SendBlockResponse {}
 pub(crate) struct SubscribeBlocksRequest {
     #[prost(uint32, tag = "1")]
     last_received_round: Round,
+    // Whether the subscriber can handle zstd compressed blocks.
+    #[prost(bool, tag = "2")]
+    accept_compressed_blocks: bool,
 }
 
 #[derive(Clone, prost::Message)]
 pub(crate) struct SubscribeBlocksResponse {
+    // Serialized SignedBlock, zstd compressed if accepted by the subscriber.
     #[prost(bytes = "bytes", tag = "1")]
     block: Bytes,
 }
@@ -1140,11 +1192,15 @@ pub(crate) struct FetchBlocksRequest {
     // and its length should be the same as the committee size.
     #[prost(uint32, repeated, tag = "2")]
     highest_accepted_rounds: Vec<Round>,
+    // Whether the requester can handle zstd compressed blocks.
+    #[prost(bool, tag = "3")]
+    accept_compressed_blocks: bool,
 }
 
 #[derive(Clone, prost::Message)]
 pub(crate) struct FetchBlocksResponse {
-    // The response of the requested blocks as Serialized SignedBlock.
+    // The response of the requested blocks as Serialized SignedBlock, zstd compressed if
+    // accepted by the requester.
     #[prost(bytes = "bytes", repeated, tag = "1")]
     blocks: Vec<Bytes>,
 }
@@ -1155,6 +1211,9 @@ pub(crate) struct FetchCommitsRequest {
     start: CommitIndex,
     #[prost(uint32, tag = "2")]
     end: CommitIndex,
+    // Whether the requester can handle zstd compressed certifier blocks.
+    #[prost(bool, tag = "3")]
+    accept_compressed_blocks: bool,
 }
 
 #[derive(Clone, prost::Message)]
@@ -1162,7 +1221,8 @@ pub(crate) struct FetchCommitsResponse {
     // Serialized consecutive Commit.
     #[prost(bytes = "bytes", repeated, tag = "1")]
     commits: Vec<Bytes>,
-    // Serialized SignedBlock that certify the last commit from above.
+    // Serialized SignedBlock that certify the last commit from above, zstd compressed if accepted
+    // by the requester.
     #[prost(bytes = "bytes", repeated, tag = "2")]
     certifier_blocks: Vec<Bytes>,
 }
@@ -1171,11 +1231,15 @@ pub(crate) struct FetchCommitsResponse {
 pub(crate) struct FetchLatestBlocksRequest {
     #[prost(uint32, repeated, tag = "1")]
     authorities: Vec<u32>,
+    // Whether the requester can handle zstd compressed blocks.
+    #[prost(bool, tag = "2")]
+    accept_compressed_blocks: bool,
 }
 
 #[derive(Clone, prost::Message)]
 pub(crate) struct FetchLatestBlocksResponse {
-    // The response of the requested blocks as Serialized SignedBlock.
+    // The response of the requested blocks as Serialized SignedBlock, zstd compressed if
+    // accepted by the requester.
     #[prost(bytes = "bytes", repeated, tag = "1")]
     blocks: Vec<Bytes>,
 }
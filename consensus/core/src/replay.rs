@@ -0,0 +1,501 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::BTreeSet, fmt, path::Path, sync::Arc};
+
+use consensus_config::{AuthorityIndex, Committee, Parameters};
+use parking_lot::RwLock;
+use prometheus::Registry;
+use sui_protocol_config::ProtocolConfig;
+
+use crate::{
+    block::{BlockAPI as _, BlockRef, BlockTimestampMs, Round, Slot, VerifiedBlock},
+    commit::{CommitAPI as _, CommitRange, DecidedLeader, TrustedCommit},
+    context::{Clock, Context},
+    dag_state::DagState,
+    error::ConsensusResult,
+    leader_schedule::{LeaderSchedule, LeaderSwapTable},
+    linearizer::Linearizer,
+    metrics::initialise_metrics,
+    storage::{mem_store::MemStore, rocksdb_store::RocksDBStore, Store},
+    universal_committer::{
+        universal_committer_builder::UniversalCommitterBuilder, UniversalCommitter,
+    },
+    CommitIndex,
+};
+
+/// Replays the blocks persisted by an authority through the commit rule and the linearizer
+/// offline, to evaluate how a different configuration would have changed the commit sequence.
+///
+/// Blocks are accepted round by round, and in timestamp order within a round as an approximation
+/// of their arrival order. The commit rule runs after every accepted block, so each commit is
+/// attributed to the block that made its leader decidable.
+///
+/// All blocks of the epoch are kept in memory during the replay.
+pub struct ConsensusReplayer {
+    context: Arc<Context>,
+    num_commits_per_schedule: Option<u64>,
+}
+
+impl ConsensusReplayer {
+    /// Creates a replayer from the point of view of `own_index`, which should be the authority
+    /// that persisted the replayed blocks.
+    pub fn new(
+        own_index: AuthorityIndex,
+        committee: Committee,
+        parameters: Parameters,
+        protocol_config: ProtocolConfig,
+    ) -> Self {
+        let context = Context::new(
+            own_index,
+            committee,
+            parameters,
+            protocol_config,
+            initialise_metrics(Registry::new()),
+            Arc::new(Clock::new()),
+        );
+        Self {
+            context: Arc::new(context),
+            num_commits_per_schedule: None,
+        }
+    }
+
+    /// Overrides the number of commits after which the leader schedule is updated.
+    pub fn with_num_commits_per_schedule(mut self, num_commits_per_schedule: u64) -> Self {
+        self.num_commits_per_schedule = Some(num_commits_per_schedule);
+        self
+    }
+
+    /// Replays the blocks in the consensus store at `db_path` and compares the outcome with the
+    /// commits recorded in the same store.
+    pub fn replay_store(&self, db_path: &Path) -> ConsensusResult<ReplayReport> {
+        let store = RocksDBStore::new(&db_path.to_string_lossy());
+
+        let mut blocks = Vec::new();
+        for (authority, _) in self.context.committee.authorities() {
+            blocks.extend(store.scan_blocks_by_author(authority, 1)?);
+        }
+        let recorded = self.recorded_commits(&store)?;
+        let replayed = self.replay(blocks);
+
+        Ok(ReplayReport { recorded, replayed })
+    }
+
+    /// Replays the given blocks and returns the resulting commits.
+    pub(crate) fn replay(&self, mut blocks: Vec<VerifiedBlock>) -> Vec<CommitSummary> {
+        blocks.sort_by_key(|block| (block.round(), block.timestamp_ms(), block.reference()));
+
+        let mut state = ReplayState::new(self.context.clone(), self.num_commits_per_schedule);
+        let mut commits = Vec::new();
+        for block in blocks {
+            state.dag_state.write().accept_block(block.clone());
+            commits.extend(state.try_commit(&block));
+        }
+        commits
+    }
+
+    /// Reads the commits recorded in the store. Their latency is approximated from the first own
+    /// block voting for the commit, which is proposed right after the commit is made.
+    fn recorded_commits(&self, store: &RocksDBStore) -> ConsensusResult<Vec<CommitSummary>> {
+        let Some(last_commit) = store.read_last_commit()? else {
+            return Ok(vec![]);
+        };
+        let commits = store.scan_commits(CommitRange::new(1..=last_commit.index()))?;
+
+        let mut summaries = Vec::with_capacity(commits.len());
+        for commit in commits {
+            let own_vote = store
+                .read_commit_votes(commit.index())?
+                .into_iter()
+                .filter(|vote| vote.author == self.context.own_index)
+                .min();
+            let blocks = store.read_blocks(&[commit.leader()])?;
+            let latency = match (own_vote, &blocks[0]) {
+                (Some(vote), Some(leader)) => store.read_blocks(&[vote])?[0]
+                    .as_ref()
+                    .map(|vote_block| Latency::between(leader, vote.round - 1, vote_block)),
+                _ => None,
+            };
+            summaries.push(CommitSummary::new(&commit, latency));
+        }
+        Ok(summaries)
+    }
+}
+
+/// Summary of a commit, either recorded by the authority or produced by a replay.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommitSummary {
+    pub index: CommitIndex,
+    pub leader: BlockRef,
+    pub num_blocks: usize,
+    /// Time from the leader proposal to the commit decision, if known.
+    pub latency: Option<Latency>,
+}
+
+impl CommitSummary {
+    fn new(commit: &TrustedCommit, latency: Option<Latency>) -> Self {
+        Self {
+            index: commit.index(),
+            leader: commit.leader(),
+            num_blocks: commit.blocks().len(),
+            latency,
+        }
+    }
+}
+
+/// Delay between a leader proposal and the decision to commit it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Latency {
+    pub rounds: Round,
+    pub ms: BlockTimestampMs,
+}
+
+impl Latency {
+    fn between(
+        leader: &VerifiedBlock,
+        decision_round: Round,
+        decision_block: &VerifiedBlock,
+    ) -> Self {
+        Self {
+            rounds: decision_round.saturating_sub(leader.round()),
+            ms: decision_block
+                .timestamp_ms()
+                .saturating_sub(leader.timestamp_ms()),
+        }
+    }
+}
+
+/// Comparison between the recorded and the replayed commit sequences.
+#[derive(Clone, Debug)]
+pub struct ReplayReport {
+    pub recorded: Vec<CommitSummary>,
+    pub replayed: Vec<CommitSummary>,
+}
+
+impl ReplayReport {
+    /// Returns the index of the first commit whose leader differs between the two sequences, or
+    /// that exists in only one of them.
+    pub fn first_divergence(&self) -> Option<CommitIndex> {
+        let common = self.recorded.len().min(self.replayed.len());
+        self.recorded
+            .iter()
+            .zip(self.replayed.iter())
+            .find(|(recorded, replayed)| recorded.leader != replayed.leader)
+            .map(|(recorded, _)| recorded.index)
+            .or_else(|| {
+                (self.recorded.len() != self.replayed.len()).then_some(common as CommitIndex + 1)
+            })
+    }
+
+    /// Returns the leaders committed in the recorded sequence but not in the replayed one.
+    pub fn leaders_only_recorded(&self) -> Vec<BlockRef> {
+        leaders_difference(&self.recorded, &self.replayed)
+    }
+
+    /// Returns the leaders committed in the replayed sequence but not in the recorded one.
+    pub fn leaders_only_replayed(&self) -> Vec<BlockRef> {
+        leaders_difference(&self.replayed, &self.recorded)
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<24}{:>16}{:>16}", "", "recorded", "replayed")?;
+        writeln!(
+            f,
+            "{:<24}{:>16}{:>16}",
+            "commits",
+            self.recorded.len(),
+            self.replayed.len()
+        )?;
+        writeln!(
+            f,
+            "{:<24}{:>16}{:>16}",
+            "last committed round",
+            last_leader_round(&self.recorded),
+            last_leader_round(&self.replayed)
+        )?;
+        writeln!(
+            f,
+            "{:<24}{:>16.2}{:>16.2}",
+            "mean latency (rounds)",
+            mean_latency(&self.recorded, |latency| latency.rounds as u64),
+            mean_latency(&self.replayed, |latency| latency.rounds as u64)
+        )?;
+        writeln!(
+            f,
+            "{:<24}{:>16.2}{:>16.2}",
+            "mean latency (ms)",
+            mean_latency(&self.recorded, |latency| latency.ms),
+            mean_latency(&self.replayed, |latency| latency.ms)
+        )?;
+        writeln!(
+            f,
+            "{:<24}{:>16}{:>16}",
+            "exclusive leaders",
+            self.leaders_only_recorded().len(),
+            self.leaders_only_replayed().len()
+        )?;
+        match self.first_divergence() {
+            Some(index) => writeln!(f, "Commit sequences diverge at commit {index}"),
+            None => writeln!(f, "Commit sequences are identical"),
+        }
+    }
+}
+
+fn leaders_difference(commits: &[CommitSummary], other: &[CommitSummary]) -> Vec<BlockRef> {
+    let other: BTreeSet<_> = other.iter().map(|commit| commit.leader).collect();
+    commits
+        .iter()
+        .map(|commit| commit.leader)
+        .filter(|leader| !other.contains(leader))
+        .collect()
+}
+
+fn last_leader_round(commits: &[CommitSummary]) -> Round {
+    commits.last().map_or(0, |commit| commit.leader.round)
+}
+
+fn mean_latency(commits: &[CommitSummary], value: impl Fn(&Latency) -> u64) -> f64 {
+    let values: Vec<_> = commits
+        .iter()
+        .filter_map(|commit| commit.latency.as_ref().map(&value))
+        .collect();
+    if values.is_empty() {
+        return 0.0;
+    }
+    values.iter().sum::<u64>() as f64 / values.len() as f64
+}
+
+/// The components of an authority that take part in committing, backed by an in-memory store.
+struct ReplayState {
+    context: Arc<Context>,
+    dag_state: Arc<RwLock<DagState>>,
+    leader_schedule: Arc<LeaderSchedule>,
+    committer: UniversalCommitter,
+    linearizer: Linearizer,
+    last_decided_leader: Slot,
+}
+
+impl ReplayState {
+    fn new(context: Arc<Context>, num_commits_per_schedule: Option<u64>) -> Self {
+        let dag_state = Arc::new(RwLock::new(DagState::new(
+            context.clone(),
+            Arc::new(MemStore::new()),
+        )));
+        let mut leader_schedule = LeaderSchedule::new(context.clone(), LeaderSwapTable::default());
+        if let Some(num_commits_per_schedule) = num_commits_per_schedule {
+            leader_schedule =
+                leader_schedule.with_num_commits_per_schedule(num_commits_per_schedule);
+        }
+        let leader_schedule = Arc::new(leader_schedule);
+        let number_of_leaders = context
+            .protocol_config
+            .mysticeti_num_leaders_per_round()
+            .unwrap_or(1);
+        let committer = UniversalCommitterBuilder::new(
+            context.clone(),
+            leader_schedule.clone(),
+            dag_state.clone(),
+        )
+        .with_number_of_leaders(number_of_leaders)
+        .with_pipeline(true)
+        .build();
+        let linearizer = Linearizer::new(dag_state.clone(), leader_schedule.clone());
+        let last_decided_leader = dag_state.read().last_commit_leader();
+
+        Self {
+            context,
+            dag_state,
+            leader_schedule,
+            committer,
+            linearizer,
+            last_decided_leader,
+        }
+    }
+
+    /// Commits the leaders that became decidable after accepting `trigger`, following the same
+    /// steps as `Core::try_commit()`.
+    fn try_commit(&mut self, trigger: &VerifiedBlock) -> Vec<CommitSummary> {
+        if !self
+            .context
+            .protocol_config
+            .mysticeti_leader_scoring_and_schedule()
+        {
+            let decided_leaders = self.committer.try_decide(self.last_decided_leader);
+            if let Some(last) = decided_leaders.last() {
+                self.last_decided_leader = last.slot();
+            }
+            return self.commit(decided_leaders, trigger);
+        }
+
+        let mut commits = Vec::new();
+        loop {
+            let mut commits_until_update = self
+                .leader_schedule
+                .commits_until_leader_schedule_update(self.dag_state.clone());
+            if commits_until_update == 0 {
+                if self
+                    .context
+                    .protocol_config
+                    .consensus_distributed_vote_scoring_strategy()
+                {
+                    self.leader_schedule
+                        .update_leader_schedule_v2(&self.dag_state);
+                } else {
+                    self.leader_schedule
+                        .update_leader_schedule_v1(&self.dag_state);
+                }
+                commits_until_update = self
+                    .leader_schedule
+                    .commits_until_leader_schedule_update(self.dag_state.clone());
+            }
+
+            let mut decided_leaders = self.committer.try_decide(self.last_decided_leader);
+            let Some(last_decided) = decided_leaders.last() else {
+                break;
+            };
+            self.last_decided_leader = last_decided.slot();
+
+            // Truncate the decided leaders so the schedule can be updated after the last one.
+            let mut num_committed = 0;
+            if let Some(position) = decided_leaders.iter().position(|leader| {
+                if matches!(leader, DecidedLeader::Commit(_)) {
+                    num_committed += 1;
+                }
+                num_committed == commits_until_update
+            }) {
+                decided_leaders.truncate(position + 1);
+                self.last_decided_leader = decided_leaders[position].slot();
+            }
+
+            let new_commits = self.commit(decided_leaders, trigger);
+            if new_commits.is_empty() {
+                break;
+            }
+            commits.extend(new_commits);
+        }
+        commits
+    }
+
+    fn commit(
+        &mut self,
+        decided_leaders: Vec<DecidedLeader>,
+        trigger: &VerifiedBlock,
+    ) -> Vec<CommitSummary> {
+        let sequenced_leaders: Vec<_> = decided_leaders
+            .into_iter()
+            .filter_map(|leader| leader.into_committed_block())
+            .collect();
+        let subdags = self.linearizer.handle_commit(sequenced_leaders.clone());
+
+        let scoring = self
+            .context
+            .protocol_config
+            .mysticeti_leader_scoring_and_schedule();
+        if scoring
+            && self
+                .context
+                .protocol_config
+                .consensus_distributed_vote_scoring_strategy()
+        {
+            self.dag_state.write().add_scoring_subdags(subdags.clone());
+        } else if scoring {
+            self.dag_state
+                .write()
+                .add_unscored_committed_subdags(subdags.clone());
+        }
+
+        sequenced_leaders
+            .iter()
+            .zip(subdags.iter())
+            .map(|(leader, subdag)| CommitSummary {
+                index: subdag.commit_ref.index,
+                leader: subdag.leader,
+                num_blocks: subdag.blocks.len(),
+                latency: Some(Latency::between(leader, trigger.round(), trigger)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{context::Context, test_dag_builder::DagBuilder};
+
+    fn replayer(context: &Context) -> ConsensusReplayer {
+        ConsensusReplayer::new(
+            context.own_index,
+            context.committee.clone(),
+            context.parameters.clone(),
+            context.protocol_config.clone(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_replay_fully_connected_dag() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=10).build();
+
+        let replayed = replayer(&context).replay(dag_builder.blocks.values().cloned().collect());
+
+        // Leaders of rounds 1 ~ 8 are directly committed once round 10 is accepted.
+        assert_eq!(replayed.len(), 8);
+        for (i, (commit, leader)) in replayed
+            .iter()
+            .zip(dag_builder.leader_blocks(1..=8))
+            .enumerate()
+        {
+            let leader = leader.unwrap();
+            assert_eq!(commit.index, i as CommitIndex + 1);
+            assert_eq!(commit.leader, leader.reference());
+            assert_eq!(commit.latency.unwrap().rounds, 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_with_missing_leader() {
+        let context = Arc::new(Context::new_for_test(4).0);
+        let mut dag_builder = DagBuilder::new(context.clone());
+        dag_builder.layers(1..=2).build();
+        dag_builder.layer(3).no_leader_block(vec![]).build();
+        dag_builder.layers(4..=10).build();
+
+        let replayed = replayer(&context).replay(dag_builder.blocks.values().cloned().collect());
+
+        assert_eq!(replayed.len(), 7);
+        assert!(replayed.iter().all(|commit| commit.leader.round != 3));
+    }
+
+    #[test]
+    fn test_report_divergence() {
+        let commit = |index: CommitIndex, round: Round| CommitSummary {
+            index,
+            leader: BlockRef::new(round, AuthorityIndex::new_for_test(0), Default::default()),
+            num_blocks: 1,
+            latency: None,
+        };
+
+        let report = ReplayReport {
+            recorded: vec![commit(1, 1), commit(2, 2), commit(3, 3)],
+            replayed: vec![commit(1, 1), commit(2, 2), commit(3, 3)],
+        };
+        assert_eq!(report.first_divergence(), None);
+
+        let report = ReplayReport {
+            recorded: vec![commit(1, 1), commit(2, 2), commit(3, 3)],
+            replayed: vec![commit(1, 1), commit(2, 3)],
+        };
+        assert_eq!(report.first_divergence(), Some(2));
+        assert_eq!(report.leaders_only_recorded(), vec![commit(2, 2).leader]);
+        assert!(report.leaders_only_replayed().is_empty());
+
+        let report = ReplayReport {
+            recorded: vec![commit(1, 1)],
+            replayed: vec![commit(1, 1), commit(2, 2)],
+        };
+        assert_eq!(report.first_divergence(), Some(2));
+    }
+}
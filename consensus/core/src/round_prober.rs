@@ -25,7 +25,7 @@ use tokio::{task::JoinHandle, time::MissedTickBehavior};
 
 use crate::{
     context::Context, core_thread::CoreThreadDispatcher, dag_state::DagState,
-    network::NetworkClient, BlockAPI as _, Round,
+    health_monitor::HealthMonitor, network::NetworkClient, BlockAPI as _, Round,
 };
 
 /// A [`QuorumRound`] is a round range [low, high]. It is computed from
@@ -66,6 +66,7 @@ pub(crate) struct RoundProber<C: NetworkClient> {
     context: Arc<Context>,
     core_thread_dispatcher: Arc<dyn CoreThreadDispatcher>,
     dag_state: Arc<RwLock<DagState>>,
+    health_monitor: Arc<HealthMonitor>,
     network_client: Arc<C>,
     shutdown_notify: Arc<NotifyOnce>,
 }
@@ -75,12 +76,14 @@ impl<C: NetworkClient> RoundProber<C> {
         context: Arc<Context>,
         core_thread_dispatcher: Arc<dyn CoreThreadDispatcher>,
         dag_state: Arc<RwLock<DagState>>,
+        health_monitor: Arc<HealthMonitor>,
         network_client: Arc<C>,
     ) -> Self {
         Self {
             context,
             core_thread_dispatcher,
             dag_state,
+            health_monitor,
             network_client,
             shutdown_notify: Arc::new(NotifyOnce::new()),
         }
@@ -233,6 +236,11 @@ impl<C: NetworkClient> RoundProber<C> {
         node_metrics
             .round_prober_last_propagation_delay
             .set(propagation_delay as i64);
+        self.health_monitor.record_round_probe(
+            highest_received_rounds,
+            &quorum_rounds,
+            propagation_delay,
+        );
         if let Err(e) = self
             .core_thread_dispatcher
             .set_propagation_delay_and_quorum_rounds(propagation_delay, quorum_rounds.clone())
@@ -477,6 +485,7 @@ mod test {
             context.clone(),
             core_thread_dispatcher.clone(),
             dag_state.clone(),
+            HealthMonitor::new_for_test(context.clone(), dag_state.clone()),
             network_client.clone(),
         );
 
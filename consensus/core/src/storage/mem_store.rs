@@ -3,7 +3,7 @@
 
 use std::{
     collections::{BTreeMap, BTreeSet, VecDeque},
-    ops::Bound::Included,
+    ops::{Bound::Included, RangeInclusive},
 };
 
 use consensus_config::AuthorityIndex;
@@ -129,6 +129,31 @@ impl Store for MemStore {
         Ok(blocks)
     }
 
+    fn scan_blocks_by_author_in_rounds(
+        &self,
+        author: AuthorityIndex,
+        rounds: RangeInclusive<Round>,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let inner = self.inner.read();
+        let mut refs = vec![];
+        for &(author, round, digest) in inner.digests_by_authorities.range((
+            Included((author, *rounds.start(), BlockDigest::MIN)),
+            Included((author, *rounds.end(), BlockDigest::MAX)),
+        )) {
+            refs.push(BlockRef::new(round, author, digest));
+        }
+        let results = self.read_blocks(refs.as_slice())?;
+        let mut blocks = vec![];
+        for (r, block) in refs.into_iter().zip(results.into_iter()) {
+            if let Some(block) = block {
+                blocks.push(block);
+            } else {
+                panic!("Block {:?} not found!", r);
+            }
+        }
+        Ok(blocks)
+    }
+
     fn contains_block_at_slot(&self, slot: Slot) -> ConsensusResult<bool> {
         let inner = self.inner.read();
         let found = inner
@@ -213,4 +238,39 @@ impl Store for MemStore {
             .last_key_value()
             .map(|(k, v)| (CommitRef::new(k.0, k.1), v.clone())))
     }
+
+    fn scan_commit_info(
+        &self,
+        range: CommitRange,
+    ) -> ConsensusResult<Vec<(CommitRef, CommitInfo)>> {
+        let inner = self.inner.read();
+        Ok(inner
+            .commit_info
+            .range((
+                Included((range.start(), CommitDigest::MIN)),
+                Included((range.end(), CommitDigest::MAX)),
+            ))
+            .map(|(k, v)| (CommitRef::new(k.0, k.1), v.clone()))
+            .collect())
+    }
+
+    fn prune(&self, before_round: Round, before_commit_index: CommitIndex) -> ConsensusResult<()> {
+        let mut inner = self.inner.write();
+        inner
+            .blocks
+            .retain(|(round, _, _), _| *round >= before_round);
+        inner
+            .digests_by_authorities
+            .retain(|(_, round, _)| *round >= before_round);
+        inner
+            .commits
+            .retain(|(index, _), _| *index >= before_commit_index);
+        inner
+            .commit_votes
+            .retain(|(index, _, _)| *index >= before_commit_index);
+        inner
+            .commit_info
+            .retain(|(index, _), _| *index >= before_commit_index);
+        Ok(())
+    }
 }
@@ -7,6 +7,8 @@ pub(crate) mod rocksdb_store;
 #[cfg(test)]
 mod store_tests;
 
+use std::ops::RangeInclusive;
+
 use consensus_config::AuthorityIndex;
 
 use crate::{
@@ -38,6 +40,13 @@ pub(crate) trait Store: Send + Sync {
         start_round: Round,
     ) -> ConsensusResult<Vec<VerifiedBlock>>;
 
+    /// Reads blocks for an authority, within `rounds`.
+    fn scan_blocks_by_author_in_rounds(
+        &self,
+        authority: AuthorityIndex,
+        rounds: RangeInclusive<Round>,
+    ) -> ConsensusResult<Vec<VerifiedBlock>>;
+
     // The method returns the last `num_of_rounds` rounds blocks by author in round ascending order.
     // When a `before_round` is defined then the blocks of round `<=before_round` are returned. If not
     // then the max value for round will be used as cut off.
@@ -59,6 +68,14 @@ pub(crate) trait Store: Send + Sync {
 
     /// Reads the last commit info, written atomically with the last commit.
     fn read_last_commit_info(&self) -> ConsensusResult<Option<(CommitRef, CommitInfo)>>;
+
+    /// Reads all commit info from start (inclusive) until end (inclusive).
+    fn scan_commit_info(&self, range: CommitRange)
+        -> ConsensusResult<Vec<(CommitRef, CommitInfo)>>;
+
+    /// Deletes blocks of rounds lower than `before_round`, and commits with their votes and info
+    /// of indices lower than `before_commit_index`.
+    fn prune(&self, before_round: Round, before_commit_index: CommitIndex) -> ConsensusResult<()>;
 }
 
 /// Represents data to be written to the store together atomically.
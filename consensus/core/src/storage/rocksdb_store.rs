@@ -1,10 +1,19 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::VecDeque, ops::Bound::Included, time::Duration};
+use std::{
+    collections::VecDeque,
+    ops::{
+        Bound::{Excluded, Included, Unbounded},
+        RangeInclusive,
+    },
+    sync::Arc,
+    time::Duration,
+};
 
 use bytes::Bytes;
 use consensus_config::AuthorityIndex;
+use serde::Serialize;
 use sui_macros::fail_point;
 use typed_store::{
     metrics::SamplingInterval,
@@ -16,13 +25,15 @@ use typed_store::{
 use super::{CommitInfo, Store, WriteBatch};
 use crate::{
     block::{BlockAPI as _, BlockDigest, BlockRef, Round, SignedBlock, Slot, VerifiedBlock},
+    block_compression::{compress_block, decompress_block, CompressionPath},
     commit::{CommitAPI as _, CommitDigest, CommitIndex, CommitRange, CommitRef, TrustedCommit},
+    context::Context,
     error::{ConsensusError, ConsensusResult},
 };
 
 /// Persistent storage with RocksDB.
 pub(crate) struct RocksDBStore {
-    /// Stores SignedBlock by refs.
+    /// Stores SignedBlock by refs, zstd compressed when block compression is enabled for storage.
     blocks: DBMap<(Round, AuthorityIndex, BlockDigest), Bytes>,
     /// A secondary index that orders refs first by authors.
     digests_by_authorities: DBMap<(AuthorityIndex, Round, BlockDigest), ()>,
@@ -33,6 +44,9 @@ pub(crate) struct RocksDBStore {
     commit_votes: DBMap<(CommitIndex, CommitDigest, BlockRef), ()>,
     /// Stores info related to Commit that helps recovery.
     commit_info: DBMap<(CommitIndex, CommitDigest), CommitInfo>,
+    /// Set when the store belongs to a running authority, to compress written blocks according to
+    /// its parameters and to report compression metrics.
+    context: Option<Arc<Context>>,
 }
 
 impl RocksDBStore {
@@ -42,6 +56,9 @@ impl RocksDBStore {
     const COMMIT_VOTES_CF: &'static str = "commit_votes";
     const COMMIT_INFO_CF: &'static str = "commit_info";
 
+    /// Maximum number of keys deleted by each write batch when pruning.
+    const PRUNE_BATCH_SIZE: usize = 1000;
+
     /// Creates a new instance of RocksDB storage.
     pub(crate) fn new(path: &str) -> Self {
         // Consensus data has high write throughput (all transactions) and is rarely read
@@ -87,7 +104,63 @@ impl RocksDBStore {
             commits,
             commit_votes,
             commit_info,
+            context: None,
+        }
+    }
+
+    /// Compresses blocks written to the store if enabled in the parameters of the authority.
+    /// Blocks are readable regardless of whether they were written compressed.
+    pub(crate) fn with_context(mut self, context: Arc<Context>) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    fn block_value(&self, block: &VerifiedBlock) -> ConsensusResult<Bytes> {
+        match &self.context {
+            Some(context) if context.parameters.block_compression.storage => compress_block(
+                &context.metrics.node_metrics,
+                CompressionPath::Storage,
+                context.parameters.block_compression.level,
+                block.serialized(),
+            ),
+            _ => Ok(block.serialized().clone()),
+        }
+    }
+
+    /// Deletes the blocks of `keys`, from the secondary index too, and clears them.
+    fn delete_blocks(
+        &self,
+        keys: &mut Vec<(AuthorityIndex, Round, BlockDigest)>,
+    ) -> ConsensusResult<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        let mut batch = self.blocks.batch();
+        batch
+            .delete_batch(
+                &self.blocks,
+                keys.iter()
+                    .map(|(author, round, digest)| (*round, *author, *digest)),
+            )
+            .map_err(ConsensusError::RocksDBFailure)?;
+        batch
+            .delete_batch(&self.digests_by_authorities, keys.drain(..))
+            .map_err(ConsensusError::RocksDBFailure)?;
+        batch.write()?;
+        Ok(())
+    }
+
+    /// Deletes `keys` from `table`, and clears them.
+    fn delete_keys<K: Serialize, V>(table: &DBMap<K, V>, keys: &mut Vec<K>) -> ConsensusResult<()> {
+        if keys.is_empty() {
+            return Ok(());
         }
+        let mut batch = table.batch();
+        batch
+            .delete_batch(table, keys.drain(..))
+            .map_err(ConsensusError::RocksDBFailure)?;
+        batch.write()?;
+        Ok(())
     }
 }
 
@@ -103,7 +176,7 @@ impl Store for RocksDBStore {
                     &self.blocks,
                     [(
                         (block_ref.round, block_ref.author, block_ref.digest),
-                        block.serialized(),
+                        self.block_value(&block)?,
                     )],
                 )
                 .map_err(ConsensusError::RocksDBFailure)?;
@@ -155,6 +228,11 @@ impl Store for RocksDBStore {
         let mut blocks = vec![];
         for (key, serialized) in refs.iter().zip(serialized) {
             if let Some(serialized) = serialized {
+                let serialized = decompress_block(
+                    self.context.as_ref().map(|c| &c.metrics.node_metrics),
+                    CompressionPath::Storage,
+                    serialized,
+                )?;
                 let signed_block: SignedBlock =
                     bcs::from_bytes(&serialized).map_err(ConsensusError::MalformedBlock)?;
                 // Only accepted blocks should have been written to storage.
@@ -213,6 +291,29 @@ impl Store for RocksDBStore {
         Ok(blocks)
     }
 
+    fn scan_blocks_by_author_in_rounds(
+        &self,
+        author: AuthorityIndex,
+        rounds: RangeInclusive<Round>,
+    ) -> ConsensusResult<Vec<VerifiedBlock>> {
+        let mut refs = vec![];
+        for kv in self.digests_by_authorities.safe_range_iter((
+            Included((author, *rounds.start(), BlockDigest::MIN)),
+            Included((author, *rounds.end(), BlockDigest::MAX)),
+        )) {
+            let ((author, round, digest), _) = kv?;
+            refs.push(BlockRef::new(round, author, digest));
+        }
+        let results = self.read_blocks(refs.as_slice())?;
+        let mut blocks = Vec::with_capacity(refs.len());
+        for (r, block) in refs.into_iter().zip(results.into_iter()) {
+            blocks.push(
+                block.unwrap_or_else(|| panic!("Storage inconsistency: block {:?} not found!", r)),
+            );
+        }
+        Ok(blocks)
+    }
+
     // The method returns the last `num_of_rounds` rounds blocks by author in round ascending order.
     // When a `before_round` is defined then the blocks of round `<=before_round` are returned. If not
     // then the max value for round will be used as cut off.
@@ -296,4 +397,90 @@ impl Store for RocksDBStore {
         let (key, commit_info) = result.map_err(ConsensusError::RocksDBFailure)?;
         Ok(Some((CommitRef::new(key.0, key.1), commit_info)))
     }
+
+    fn scan_commit_info(
+        &self,
+        range: CommitRange,
+    ) -> ConsensusResult<Vec<(CommitRef, CommitInfo)>> {
+        let mut commit_info = vec![];
+        for result in self.commit_info.safe_range_iter((
+            Included((range.start(), CommitDigest::MIN)),
+            Included((range.end(), CommitDigest::MAX)),
+        )) {
+            let ((index, digest), info) = result?;
+            commit_info.push((CommitRef::new(index, digest), info));
+        }
+        Ok(commit_info)
+    }
+
+    fn prune(&self, before_round: Round, before_commit_index: CommitIndex) -> ConsensusResult<()> {
+        // Keys are deleted individually rather than with range tombstones, which reads ignore
+        // until compaction, in write batches of bounded size. The secondary index is scanned
+        // instead of the blocks, to avoid reading block values from the blob files, one author at
+        // a time so that retained blocks are skipped.
+        let mut block_keys = Vec::with_capacity(Self::PRUNE_BATCH_SIZE);
+        let mut next_author = Included((AuthorityIndex::MIN, Round::MIN, BlockDigest::MIN));
+        loop {
+            let Some(kv) = self
+                .digests_by_authorities
+                .safe_range_iter((next_author, Unbounded))
+                .next()
+            else {
+                break;
+            };
+            let ((author, _, _), _) = kv?;
+            for kv in self.digests_by_authorities.safe_range_iter((
+                Included((author, Round::MIN, BlockDigest::MIN)),
+                Excluded((author, before_round, BlockDigest::MIN)),
+            )) {
+                let (key, _) = kv?;
+                block_keys.push(key);
+                if block_keys.len() == Self::PRUNE_BATCH_SIZE {
+                    self.delete_blocks(&mut block_keys)?;
+                }
+            }
+            next_author = Excluded((author, Round::MAX, BlockDigest::MAX));
+        }
+        self.delete_blocks(&mut block_keys)?;
+
+        let mut commit_keys = Vec::with_capacity(Self::PRUNE_BATCH_SIZE);
+        for kv in self.commits.safe_range_iter((
+            Included((CommitIndex::MIN, CommitDigest::MIN)),
+            Excluded((before_commit_index, CommitDigest::MIN)),
+        )) {
+            let (key, _) = kv?;
+            commit_keys.push(key);
+            if commit_keys.len() == Self::PRUNE_BATCH_SIZE {
+                Self::delete_keys(&self.commits, &mut commit_keys)?;
+            }
+        }
+        Self::delete_keys(&self.commits, &mut commit_keys)?;
+
+        let mut vote_keys = Vec::with_capacity(Self::PRUNE_BATCH_SIZE);
+        for kv in self.commit_votes.safe_range_iter((
+            Included((CommitIndex::MIN, CommitDigest::MIN, BlockRef::MIN)),
+            Excluded((before_commit_index, CommitDigest::MIN, BlockRef::MIN)),
+        )) {
+            let (key, _) = kv?;
+            vote_keys.push(key);
+            if vote_keys.len() == Self::PRUNE_BATCH_SIZE {
+                Self::delete_keys(&self.commit_votes, &mut vote_keys)?;
+            }
+        }
+        Self::delete_keys(&self.commit_votes, &mut vote_keys)?;
+
+        let mut commit_info_keys = Vec::with_capacity(Self::PRUNE_BATCH_SIZE);
+        for kv in self.commit_info.safe_range_iter((
+            Included((CommitIndex::MIN, CommitDigest::MIN)),
+            Excluded((before_commit_index, CommitDigest::MIN)),
+        )) {
+            let (key, _) = kv?;
+            commit_info_keys.push(key);
+            if commit_info_keys.len() == Self::PRUNE_BATCH_SIZE {
+                Self::delete_keys(&self.commit_info, &mut commit_info_keys)?;
+            }
+        }
+        Self::delete_keys(&self.commit_info, &mut commit_info_keys)?;
+        Ok(())
+    }
 }
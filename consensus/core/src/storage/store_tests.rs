@@ -1,14 +1,18 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::sync::Arc;
+
 use consensus_config::AuthorityIndex;
 use rstest::rstest;
 use tempfile::TempDir;
 
 use super::{mem_store::MemStore, rocksdb_store::RocksDBStore, Store, WriteBatch};
 use crate::{
-    block::{BlockAPI, BlockDigest, BlockRef, Slot, TestBlock, VerifiedBlock},
-    commit::{CommitDigest, TrustedCommit},
+    block::{BlockAPI, BlockDigest, BlockRef, Slot, TestBlock, Transaction, VerifiedBlock},
+    commit::{CommitDigest, CommitInfo, CommitRef, TrustedCommit},
+    context::Context,
+    leader_scoring::ReputationScores,
 };
 
 /// Test fixture for store tests. Wraps around various store implementations.
@@ -117,6 +121,44 @@ async fn read_and_contain_blocks(
     }
 }
 
+#[tokio::test]
+async fn read_blocks_written_compressed() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().to_str().unwrap();
+    let (mut context, _) = Context::new_for_test(4);
+    context.parameters.block_compression.storage = true;
+    let context = Arc::new(context);
+
+    let written_blocks: Vec<VerifiedBlock> = (0..4)
+        .map(|author| {
+            VerifiedBlock::new_for_test(
+                TestBlock::new(1, author)
+                    .set_transactions(vec![Transaction::new(vec![author as u8; 1024]); 4])
+                    .build(),
+            )
+        })
+        .collect();
+    let refs: Vec<BlockRef> = written_blocks.iter().map(|b| b.reference()).collect();
+
+    {
+        let store = RocksDBStore::new(path).with_context(context.clone());
+        store
+            .write(WriteBatch::default().blocks(written_blocks.clone()))
+            .unwrap();
+        let read_blocks = store.read_blocks(&refs).unwrap();
+        for (read, written) in read_blocks.iter().zip(written_blocks.iter()) {
+            assert_eq!(read.as_ref().unwrap(), written);
+        }
+    }
+
+    // Blocks written compressed stay readable once compression is disabled.
+    let store = RocksDBStore::new(path);
+    let read_blocks = store.read_blocks(&refs).unwrap();
+    for (read, written) in read_blocks.iter().zip(written_blocks.iter()) {
+        assert_eq!(read.as_ref().unwrap(), written);
+    }
+}
+
 #[rstest]
 #[tokio::test]
 async fn scan_blocks(
@@ -183,6 +225,20 @@ async fn scan_blocks(
         );
     }
 
+    {
+        let scanned_blocks = store
+            .scan_blocks_by_author_in_rounds(AuthorityIndex::new_for_test(1), 11..=13)
+            .expect("Scan blocks should not fail");
+        assert_eq!(
+            scanned_blocks,
+            vec![
+                written_blocks[3].clone(),
+                written_blocks[5].clone(),
+                written_blocks[7].clone(),
+            ]
+        );
+    }
+
     {
         let scanned_blocks = store
             .scan_last_blocks_by_author(AuthorityIndex::new_for_test(1), 2, None)
@@ -297,3 +353,191 @@ async fn read_and_scan_commits(
         assert_eq!(scanned_commits, written_commits,);
     }
 }
+
+#[rstest]
+#[tokio::test]
+async fn read_and_scan_commit_info(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    {
+        let last_commit_info = store
+            .read_last_commit_info()
+            .expect("Read last commit info should not fail");
+        assert!(last_commit_info.is_none(), "{:?}", last_commit_info);
+    }
+
+    let written_commit_info: Vec<_> = [100, 200, 300]
+        .into_iter()
+        .map(|index| {
+            (
+                CommitRef::new(index, CommitDigest::MIN),
+                CommitInfo::new(
+                    vec![index; 4],
+                    ReputationScores::new((index - 99..=index).into(), vec![index as u64; 4]),
+                ),
+            )
+        })
+        .collect();
+    store
+        .write(WriteBatch::default().commit_info(written_commit_info.clone()))
+        .unwrap();
+
+    {
+        let (last_commit_ref, _) = store
+            .read_last_commit_info()
+            .expect("Read last commit info should not fail")
+            .unwrap();
+        assert_eq!(last_commit_ref, written_commit_info[2].0);
+    }
+
+    {
+        let scanned_commit_info = store
+            .scan_commit_info((1..=250).into())
+            .expect("Scan commit info should not fail");
+        assert_eq!(
+            scanned_commit_info
+                .iter()
+                .map(|(commit_ref, info)| (*commit_ref, info.committed_rounds.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (written_commit_info[0].0, vec![100; 4]),
+                (written_commit_info[1].0, vec![200; 4]),
+            ]
+        );
+    }
+}
+
+#[rstest]
+#[tokio::test]
+async fn prune_blocks_and_commits(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    // Blocks of rounds 1 ~ 4 from 4 authorities, where blocks of round r vote for commit r - 1.
+    let written_blocks: Vec<VerifiedBlock> = (1..=4)
+        .flat_map(|round| {
+            (0..4).map(move |author| {
+                VerifiedBlock::new_for_test(
+                    TestBlock::new(round, author)
+                        .set_commit_votes(vec![CommitRef::new(round - 1, CommitDigest::MIN)])
+                        .build(),
+                )
+            })
+        })
+        .collect();
+    let written_commits: Vec<TrustedCommit> = (1..=4)
+        .map(|index| {
+            TrustedCommit::new_for_test(
+                index,
+                CommitDigest::MIN,
+                index as u64,
+                BlockRef::new(
+                    index,
+                    AuthorityIndex::new_for_test(0),
+                    BlockDigest::default(),
+                ),
+                vec![],
+            )
+        })
+        .collect();
+    let written_commit_info: Vec<_> = [2, 4]
+        .into_iter()
+        .map(|index| {
+            (
+                CommitRef::new(index, CommitDigest::MIN),
+                CommitInfo::new(vec![index; 4], ReputationScores::default()),
+            )
+        })
+        .collect();
+    store
+        .write(WriteBatch::new(
+            written_blocks.clone(),
+            written_commits.clone(),
+            written_commit_info.clone(),
+        ))
+        .unwrap();
+
+    store.prune(3, 3).expect("Prune should not fail");
+
+    let refs: Vec<BlockRef> = written_blocks.iter().map(|b| b.reference()).collect();
+    let contain_blocks = store.contains_blocks(&refs).unwrap();
+    for (block, contained) in written_blocks.iter().zip(contain_blocks) {
+        assert_eq!(contained, block.round() >= 3, "{}", block.reference());
+    }
+    let scanned_blocks = store
+        .scan_blocks_by_author(AuthorityIndex::new_for_test(1), 0)
+        .unwrap();
+    assert_eq!(
+        scanned_blocks.iter().map(|b| b.round()).collect::<Vec<_>>(),
+        vec![3, 4]
+    );
+
+    assert_eq!(
+        store.scan_commits((0..=4).into()).unwrap(),
+        written_commits[2..].to_vec()
+    );
+    assert!(store.read_commit_votes(1).unwrap().is_empty());
+    assert_eq!(store.read_commit_votes(3).unwrap().len(), 4);
+    assert_eq!(
+        store
+            .scan_commit_info((0..=4).into())
+            .unwrap()
+            .into_iter()
+            .map(|(commit_ref, _)| commit_ref)
+            .collect::<Vec<_>>(),
+        vec![written_commit_info[1].0]
+    );
+    assert_eq!(
+        store.read_last_commit().unwrap().as_ref(),
+        written_commits.last()
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn prune_more_blocks_than_a_write_batch(
+    #[values(new_rocksdb_teststore(), new_mem_teststore())] test_store: TestStore,
+) {
+    let store = test_store.store();
+
+    // Authorities 1 and 3 propose in every round, authority 2 stops after round 100, and
+    // authority 0 never proposes.
+    let written_blocks: Vec<VerifiedBlock> = (1..=600)
+        .flat_map(|round| {
+            [1, 2, 3]
+                .into_iter()
+                .filter(move |author| *author != 2 || round <= 100)
+                .map(move |author| {
+                    VerifiedBlock::new_for_test(TestBlock::new(round, author).build())
+                })
+        })
+        .collect();
+    store
+        .write(WriteBatch::default().blocks(written_blocks.clone()))
+        .unwrap();
+
+    store.prune(500, 1).expect("Prune should not fail");
+
+    for author in 0..4 {
+        let scanned_rounds: Vec<_> = store
+            .scan_blocks_by_author(AuthorityIndex::new_for_test(author), 0)
+            .unwrap()
+            .iter()
+            .map(|b| b.round())
+            .collect();
+        let expected_rounds: Vec<_> = if author == 1 || author == 3 {
+            (500..=600).collect()
+        } else {
+            vec![]
+        };
+        assert_eq!(scanned_rounds, expected_rounds, "author {author}");
+    }
+    let refs: Vec<BlockRef> = written_blocks.iter().map(|b| b.reference()).collect();
+    let contain_blocks = store.contains_blocks(&refs).unwrap();
+    for (block, contained) in written_blocks.iter().zip(contain_blocks) {
+        assert_eq!(contained, block.round() >= 500, "{}", block.reference());
+    }
+}
@@ -0,0 +1,218 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{sync::Arc, time::Duration};
+
+use consensus_config::{
+    local_committee_and_keys, AuthorityIndex, NetworkKeyPair, Parameters, ProtocolKeyPair,
+};
+use mysten_metrics::monitored_mpsc::{unbounded_channel, UnboundedReceiver};
+use prometheus::Registry;
+use rstest::rstest;
+use sui_protocol_config::ProtocolConfig;
+use tempfile::TempDir;
+use tokio::time::timeout;
+use typed_store::DBMetrics;
+
+use crate::{
+    authority_node::AuthorityNode,
+    authority_service::AuthorityService,
+    byzantine_core::ByzantineCoreBehavior,
+    commit::{CommitRef, CommittedSubDag},
+    core_thread::ChannelCoreThreadDispatcher,
+    network::{
+        byzantine_service::{ByzantineBehavior, ByzantineService},
+        tonic_network::TonicManager,
+    },
+    transaction::NoopTransactionVerifier,
+    BlockRef, CommitConsumer,
+};
+
+const NUM_OF_AUTHORITIES: usize = 4;
+const NUM_OF_COMMITS: usize = 20;
+
+type ByzantineNode =
+    AuthorityNode<TonicManager, ByzantineService<AuthorityService<ChannelCoreThreadDispatcher>>>;
+
+/// Runs a committee where the last authority misbehaves, and checks that the honest authorities
+/// keep committing the same sequence. Tonic is used because it serves own blocks through the
+/// subscription stream, where all behaviors apply.
+#[rstest]
+#[case::equivocate(
+    ByzantineBehavior::Equivocate { targets: vec![AuthorityIndex::new_for_test(0)] }.into(),
+    Some("misbehavior_detected")
+)]
+#[case::core_equivocate(
+    ByzantineCoreBehavior::Equivocate { conflicting_blocks: 2 }.into(),
+    Some("misbehavior_detected")
+)]
+#[case::withhold_blocks(
+    ByzantineBehavior::WithholdBlocks {
+        targets: vec![AuthorityIndex::new_for_test(0), AuthorityIndex::new_for_test(1)],
+    }
+    .into(),
+    None
+)]
+#[case::invalid_ancestors(ByzantineBehavior::InvalidAncestors.into(), Some("invalid_blocks"))]
+#[case::delay_votes(
+    ByzantineBehavior::DelayVotes { delay: Duration::from_millis(500) }.into(),
+    None
+)]
+#[tokio::test(flavor = "current_thread")]
+async fn honest_authorities_commit_with_byzantine_authority(
+    #[case] behavior: Misbehavior,
+    #[case] detected_metric: Option<&str>,
+) {
+    telemetry_subscribers::init_for_testing();
+    let db_registry = Registry::new();
+    DBMetrics::init(&db_registry);
+
+    let (committee, keypairs) = local_committee_and_keys(0, [1; NUM_OF_AUTHORITIES].to_vec());
+    let temp_dirs = (0..NUM_OF_AUTHORITIES)
+        .map(|_| TempDir::new().unwrap())
+        .collect::<Vec<_>>();
+    let byzantine_index = AuthorityIndex::new_for_test(NUM_OF_AUTHORITIES as u32 - 1);
+
+    let mut honest_authorities = Vec::new();
+    for (index, _) in committee.authorities() {
+        if index == byzantine_index {
+            continue;
+        }
+        let registry = Registry::new();
+        let (commit_consumer, receiver) = make_commit_consumer();
+        let (protocol_keypair, network_keypair) = authority_keys(&keypairs, index);
+        let authority = AuthorityNode::<TonicManager>::start(
+            index,
+            committee.clone(),
+            make_parameters(&temp_dirs[index.value()]),
+            ProtocolConfig::get_for_max_version_UNSAFE(),
+            protocol_keypair,
+            network_keypair,
+            Arc::new(NoopTransactionVerifier {}),
+            commit_consumer,
+            registry.clone(),
+            0,
+        )
+        .await;
+        honest_authorities.push((authority, receiver, registry));
+    }
+
+    let (commit_consumer, _byzantine_receiver) = make_commit_consumer();
+    let (protocol_keypair, network_keypair) = authority_keys(&keypairs, byzantine_index);
+    let byzantine_keypair = protocol_keypair.clone();
+    let (service_behavior, core_behavior) = match behavior {
+        Misbehavior::Service(behavior) => (behavior, None),
+        Misbehavior::Core(behavior) => (ByzantineBehavior::Honest, Some(behavior)),
+    };
+    let byzantine_authority = ByzantineNode::start_with_service(
+        byzantine_index,
+        committee.clone(),
+        make_parameters(&temp_dirs[byzantine_index.value()]),
+        ProtocolConfig::get_for_max_version_UNSAFE(),
+        protocol_keypair,
+        network_keypair,
+        Arc::new(NoopTransactionVerifier {}),
+        commit_consumer,
+        Registry::new(),
+        0,
+        |core| {
+            if let Some(behavior) = core_behavior {
+                core.set_byzantine_behavior(behavior);
+            }
+        },
+        |context, service| {
+            Arc::new(ByzantineService::new(
+                context,
+                service,
+                byzantine_keypair,
+                service_behavior,
+            ))
+        },
+    )
+    .await;
+
+    // Honest authorities must keep committing, and agree on what they commit.
+    let mut commit_sequences: Vec<Vec<(CommitRef, BlockRef)>> = Vec::new();
+    for (_, receiver, _) in honest_authorities.iter_mut() {
+        commit_sequences.push(receive_commits(receiver).await);
+    }
+    for sequence in &commit_sequences[1..] {
+        assert_eq!(sequence, &commit_sequences[0]);
+    }
+
+    if let Some(metric) = detected_metric {
+        let detected: u64 = honest_authorities
+            .iter()
+            .map(|(_, _, registry)| metric_total(registry, metric))
+            .sum();
+        assert!(detected > 0, "No honest authority reported {metric}");
+    }
+
+    byzantine_authority.stop().await;
+    for (authority, _, _) in honest_authorities {
+        authority.stop().await;
+    }
+}
+
+/// Where the Byzantine authority misbehaves.
+enum Misbehavior {
+    Service(ByzantineBehavior),
+    Core(ByzantineCoreBehavior),
+}
+
+impl From<ByzantineBehavior> for Misbehavior {
+    fn from(behavior: ByzantineBehavior) -> Self {
+        Misbehavior::Service(behavior)
+    }
+}
+
+impl From<ByzantineCoreBehavior> for Misbehavior {
+    fn from(behavior: ByzantineCoreBehavior) -> Self {
+        Misbehavior::Core(behavior)
+    }
+}
+
+fn make_parameters(db_dir: &TempDir) -> Parameters {
+    Parameters {
+        db_path: db_dir.path().to_path_buf(),
+        ..Default::default()
+    }
+}
+
+fn make_commit_consumer() -> (CommitConsumer, UnboundedReceiver<CommittedSubDag>) {
+    let (sender, receiver) = unbounded_channel("consensus_output");
+    (CommitConsumer::new(sender, 0), receiver)
+}
+
+fn authority_keys(
+    keypairs: &[(NetworkKeyPair, ProtocolKeyPair)],
+    index: AuthorityIndex,
+) -> (ProtocolKeyPair, NetworkKeyPair) {
+    let (network_keypair, protocol_keypair) = &keypairs[index.value()];
+    (protocol_keypair.clone(), network_keypair.clone())
+}
+
+async fn receive_commits(
+    receiver: &mut UnboundedReceiver<CommittedSubDag>,
+) -> Vec<(CommitRef, BlockRef)> {
+    let mut commits = Vec::with_capacity(NUM_OF_COMMITS);
+    while commits.len() < NUM_OF_COMMITS {
+        let subdag = timeout(Duration::from_secs(60), receiver.recv())
+            .await
+            .expect("Timed out waiting for commits")
+            .expect("Commit channel closed");
+        commits.push((subdag.commit_ref, subdag.leader));
+    }
+    commits
+}
+
+/// Sums all the samples of a counter registered in `registry`.
+fn metric_total(registry: &Registry, name: &str) -> u64 {
+    registry
+        .gather()
+        .iter()
+        .filter(|family| family.get_name() == name)
+        .flat_map(|family| family.get_metric())
+        .map(|metric| metric.get_counter().get_value() as u64)
+        .sum()
+}
@@ -14,7 +14,7 @@ use crate::{
     context::Context,
 };
 
-/// The maximum number of transactions pending to the queue to be pulled for block proposal
+/// The maximum number of transactions pending to the queue of a lane to be pulled for block proposal
 const MAX_PENDING_TRANSACTIONS: usize = 2_000;
 
 /// The guard acts as an acknowledgment mechanism for the inclusion of the transactions to a block.
@@ -29,40 +29,147 @@ pub(crate) struct TransactionsGuard {
     included_in_block_ack: oneshot::Sender<BlockRef>,
 }
 
+/// The lane a batch of transactions is submitted to. Each lane has its own queue and a quota of
+/// every proposed block, so that bursts in one lane cannot starve the transactions of another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TransactionLane {
+    /// Transactions needed for the system to make progress, such as checkpoint signatures,
+    /// end of epoch messages and randomness DKG messages.
+    System,
+    /// User transactions.
+    User,
+}
+
+impl TransactionLane {
+    /// All lanes, in the order they are served when filling a block.
+    pub const ALL: [TransactionLane; 2] = [TransactionLane::System, TransactionLane::User];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
 /// The TransactionConsumer is responsible for fetching the next transactions to be included for the block proposals.
-/// The transactions are submitted to a channel which is shared between the TransactionConsumer and the TransactionClient
-/// and are pulled every time the `next` method is called.
+/// The transactions are submitted to one channel per lane, which are shared between the TransactionConsumer and the
+/// TransactionClient, and are pulled every time the `next` method is called.
 pub(crate) struct TransactionConsumer {
-    tx_receiver: Receiver<TransactionsGuard>,
+    lanes: Vec<LaneConsumer>,
     max_consumed_bytes_per_request: u64,
     max_consumed_transactions_per_request: u64,
-    pending_transactions: Option<TransactionsGuard>,
 }
 
 impl TransactionConsumer {
-    pub(crate) fn new(tx_receiver: Receiver<TransactionsGuard>, context: Arc<Context>) -> Self {
+    /// `tx_receivers` holds one receiver per lane, in the order of `TransactionLane::ALL`.
+    pub(crate) fn new(
+        tx_receivers: Vec<Receiver<TransactionsGuard>>,
+        context: Arc<Context>,
+    ) -> Self {
+        assert_eq!(tx_receivers.len(), TransactionLane::ALL.len());
+        let max_consumed_bytes_per_request =
+            context.protocol_config.max_transactions_in_block_bytes();
+        let max_consumed_transactions_per_request =
+            context.protocol_config.max_num_transactions_in_block();
+
+        let parameters = &context.parameters;
+        assert!(
+            parameters.system_lane_block_quota_percent as u64
+                + parameters.user_lane_block_quota_percent as u64
+                <= 100,
+            "Lane quotas should not exceed the block limits"
+        );
+        let lanes = TransactionLane::ALL
+            .iter()
+            .zip(tx_receivers)
+            .map(|(lane, tx_receiver)| {
+                let quota_percent = match lane {
+                    TransactionLane::System => parameters.system_lane_block_quota_percent,
+                    TransactionLane::User => parameters.user_lane_block_quota_percent,
+                } as u64;
+                LaneConsumer {
+                    tx_receiver,
+                    max_quota_bytes: max_consumed_bytes_per_request * quota_percent / 100,
+                    max_quota_transactions: max_consumed_transactions_per_request * quota_percent
+                        / 100,
+                    pending_transactions: None,
+                }
+            })
+            .collect();
+
         Self {
-            tx_receiver,
-            max_consumed_bytes_per_request: context
-                .protocol_config
-                .max_transactions_in_block_bytes(),
-            max_consumed_transactions_per_request: context
-                .protocol_config
-                .max_num_transactions_in_block(),
-            pending_transactions: None,
+            lanes,
+            max_consumed_bytes_per_request,
+            max_consumed_transactions_per_request,
         }
     }
 
-    // Attempts to fetch the next transactions that have been submitted for sequence. Also a `max_consumed_bytes_per_request` parameter
-    // is given in order to ensure up to `max_consumed_bytes_per_request` bytes of transactions are retrieved.
+    // Attempts to fetch the next transactions that have been submitted for sequence. Up to `max_consumed_bytes_per_request`
+    // bytes and `max_consumed_transactions_per_request` transactions are retrieved. Every lane is first served up to its quota,
+    // then the remaining capacity goes to the lanes in priority order.
     // This returns one or more transactions to be included in the block and a callback to acknowledge the inclusion of those transactions.
     // Note that a TransactionsGuard may be partially consumed and the rest saved for the next pull, in which case its `included_in_block_ack`
     // will not be signalled in the callback.
     pub(crate) fn next(&mut self) -> (Vec<Transaction>, Box<dyn FnOnce(BlockRef)>) {
-        let mut transactions = Vec::new();
-        let mut acks = Vec::new();
-        let mut total_size: usize = 0;
+        let mut consumed = ConsumedTransactions::default();
+
+        for lane in &mut self.lanes {
+            let max_bytes = (consumed.total_size as u64 + lane.max_quota_bytes)
+                .min(self.max_consumed_bytes_per_request);
+            let max_transactions = (consumed.transactions.len() as u64
+                + lane.max_quota_transactions)
+                .min(self.max_consumed_transactions_per_request);
+            lane.consume(&mut consumed, max_bytes, max_transactions);
+        }
+        for lane in &mut self.lanes {
+            lane.consume(
+                &mut consumed,
+                self.max_consumed_bytes_per_request,
+                self.max_consumed_transactions_per_request,
+            );
+        }
 
+        let ConsumedTransactions {
+            transactions, acks, ..
+        } = consumed;
+        (
+            transactions,
+            Box::new(move |block_ref: BlockRef| {
+                for ack in acks {
+                    let _ = ack.send(block_ref);
+                }
+            }),
+        )
+    }
+
+    #[cfg(test)]
+    fn is_empty(&mut self) -> bool {
+        self.lanes.iter_mut().all(|lane| lane.is_empty())
+    }
+}
+
+#[derive(Default)]
+struct ConsumedTransactions {
+    transactions: Vec<Transaction>,
+    acks: Vec<oneshot::Sender<BlockRef>>,
+    total_size: usize,
+}
+
+// The queue of a single lane, with its quota of each block.
+struct LaneConsumer {
+    tx_receiver: Receiver<TransactionsGuard>,
+    max_quota_bytes: u64,
+    max_quota_transactions: u64,
+    pending_transactions: Option<TransactionsGuard>,
+}
+
+impl LaneConsumer {
+    // Moves transactions of this lane into `consumed`, in submission order, for as long as the
+    // consumed transactions stay within `max_bytes` and `max_transactions`.
+    fn consume(
+        &mut self,
+        consumed: &mut ConsumedTransactions,
+        max_bytes: u64,
+        max_transactions: u64,
+    ) {
         // Handle one batch of incoming transactions from TransactionGuard.
         // Returns the remaining txs as a new TransactionGuard, if the batch breaks any limit.
         let mut handle_txs = |t: TransactionsGuard| -> Option<TransactionsGuard> {
@@ -70,14 +177,14 @@ impl TransactionConsumer {
                 .transactions
                 .into_iter()
                 .filter_map(|tx| {
-                    if (total_size + tx.data().len()) as u64 > self.max_consumed_bytes_per_request
-                        || transactions.len() as u64 >= self.max_consumed_transactions_per_request
+                    if (consumed.total_size + tx.data().len()) as u64 > max_bytes
+                        || consumed.transactions.len() as u64 >= max_transactions
                     {
                         // Adding this tx would exceed the size limit or the number of txs limit, cache it for the next pull.
                         Some(tx)
                     } else {
-                        total_size += tx.data().len();
-                        transactions.push(tx);
+                        consumed.total_size += tx.data().len();
+                        consumed.transactions.push(tx);
                         None
                     }
                 })
@@ -86,7 +193,7 @@ impl TransactionConsumer {
             if remaining_txs.is_empty() {
                 // The batch has been fully consumed, register its ack.
                 // In case a batch gets split, ack shall only be sent when the last transaction is included in the block.
-                acks.push(t.included_in_block_ack);
+                consumed.acks.push(t.included_in_block_ack);
                 None
             } else {
                 // If we went over the any limit while processing the batch, return the remainings.
@@ -111,15 +218,6 @@ impl TransactionConsumer {
                 break;
             }
         }
-
-        (
-            transactions,
-            Box::new(move |block_ref: BlockRef| {
-                for ack in acks {
-                    let _ = ack.send(block_ref);
-                }
-            }),
-        )
     }
 
     #[cfg(test)]
@@ -137,7 +235,8 @@ impl TransactionConsumer {
 
 #[derive(Clone)]
 pub struct TransactionClient {
-    sender: Sender<TransactionsGuard>,
+    // One sender per lane, in the order of `TransactionLane::ALL`.
+    senders: Vec<Sender<TransactionsGuard>>,
     max_transaction_size: u64,
 }
 
@@ -151,30 +250,43 @@ pub enum ClientError {
 }
 
 impl TransactionClient {
-    pub(crate) fn new(context: Arc<Context>) -> (Self, Receiver<TransactionsGuard>) {
-        let (sender, receiver) = channel("consensus_input", MAX_PENDING_TRANSACTIONS);
+    pub(crate) fn new(context: Arc<Context>) -> (Self, Vec<Receiver<TransactionsGuard>>) {
+        let (senders, receivers): (Vec<_>, Vec<_>) = TransactionLane::ALL
+            .iter()
+            .map(|lane| {
+                let name = match lane {
+                    TransactionLane::System => "consensus_system_input",
+                    TransactionLane::User => "consensus_input",
+                };
+                channel(name, MAX_PENDING_TRANSACTIONS)
+            })
+            .unzip();
 
         (
             Self {
-                sender,
+                senders,
                 max_transaction_size: context.protocol_config.max_transaction_size_bytes(),
             },
-            receiver,
+            receivers,
         )
     }
 
-    /// Submits a list of transactions to be sequenced. The method returns when all the transactions have been successfully included
-    /// to next proposed blocks.
-    pub async fn submit(&self, transactions: Vec<Vec<u8>>) -> Result<BlockRef, ClientError> {
+    /// Submits a list of transactions to be sequenced in the given lane. The method returns when all the transactions have been
+    /// successfully included to next proposed blocks.
+    pub async fn submit(
+        &self,
+        lane: TransactionLane,
+        transactions: Vec<Vec<u8>>,
+    ) -> Result<BlockRef, ClientError> {
         // TODO: Support returning the block refs for transactions that span multiple blocks
-        let included_in_block = self.submit_no_wait(transactions).await?;
+        let included_in_block = self.submit_no_wait(lane, transactions).await?;
         included_in_block
             .await
             .tap_err(|e| warn!("Transaction acknowledge failed with {:?}", e))
             .map_err(|e| ClientError::ConsensusShuttingDown(e.to_string()))
     }
 
-    /// Submits a list of transactions to be sequenced.
+    /// Submits a list of transactions to be sequenced in the given lane.
     /// If any transaction's length exceeds `max_transaction_size`, no transaction will be submitted.
     /// That shouldn't be the common case as sizes should be aligned between consensus and client. The method returns
     /// a receiver to wait on until the transactions has been included in the next block to get proposed. The consumer should
@@ -183,6 +295,7 @@ impl TransactionClient {
     /// If multiple transactions are submitted, the receiver will be signalled when the last transaction is included in the block.
     pub(crate) async fn submit_no_wait(
         &self,
+        lane: TransactionLane,
         transactions: Vec<Vec<u8>>,
     ) -> Result<oneshot::Receiver<BlockRef>, ClientError> {
         let (included_in_block_ack_send, included_in_block_ack_receive) = oneshot::channel();
@@ -199,7 +312,7 @@ impl TransactionClient {
             transactions: transactions.into_iter().map(Transaction::new).collect(),
             included_in_block_ack: included_in_block_ack_send,
         };
-        self.sender
+        self.senders[lane.index()]
             .send(t)
             .await
             .tap_err(|e| error!("Submit transactions failed with {:?}", e))
@@ -253,7 +366,7 @@ mod tests {
     use crate::{
         block::BlockRef,
         context::Context,
-        transaction::{TransactionClient, TransactionConsumer},
+        transaction::{TransactionClient, TransactionConsumer, TransactionLane},
     };
 
     #[tokio::test(flavor = "current_thread", start_paused = true)]
@@ -274,7 +387,7 @@ mod tests {
             let transaction =
                 bcs::to_bytes(&format!("transaction {i}")).expect("Serialization should not fail.");
             let w = client
-                .submit_no_wait(vec![transaction])
+                .submit_no_wait(TransactionLane::User, vec![transaction])
                 .await
                 .expect("Shouldn't submit successfully transaction");
             included_in_block_waiters.push(w);
@@ -325,7 +438,7 @@ mod tests {
             let transaction =
                 bcs::to_bytes(&format!("transaction {i}")).expect("Serialization should not fail.");
             let _w = client
-                .submit_no_wait(vec![transaction])
+                .submit_no_wait(TransactionLane::User, vec![transaction])
                 .await
                 .expect("Shouldn't submit successfully transaction");
         }
@@ -366,6 +479,79 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn lanes_are_served_up_to_their_quotas() {
+        let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
+            config.set_consensus_max_transaction_size_bytes_for_testing(100);
+            config.set_consensus_max_transactions_in_block_bytes_for_testing(100);
+            config
+        });
+
+        // The system lane gets 20 bytes of each block, the user lane 80 bytes.
+        let context = Arc::new(Context::new_for_test(4).0);
+        let (client, tx_receivers) = TransactionClient::new(context.clone());
+        let mut consumer = TransactionConsumer::new(tx_receivers, context.clone());
+
+        // Each user transaction takes 8 bytes, each system transaction 10 bytes.
+        let user_transaction = |i: usize| {
+            bcs::to_bytes(&format!("user {i:02}")).expect("Serialization should not fail.")
+        };
+        let system_transaction = |i: usize| {
+            bcs::to_bytes(&format!("system {i:02}")).expect("Serialization should not fail.")
+        };
+
+        // A burst of user transactions submitted before the system ones does not delay them.
+        for i in 0..20 {
+            client
+                .submit_no_wait(TransactionLane::User, vec![user_transaction(i)])
+                .await
+                .unwrap();
+        }
+        for i in 0..2 {
+            client
+                .submit_no_wait(TransactionLane::System, vec![system_transaction(i)])
+                .await
+                .unwrap();
+        }
+        let (transactions, _ack_transactions) = consumer.next();
+        let expected: Vec<_> = (0..2)
+            .map(system_transaction)
+            .chain((0..10).map(user_transaction))
+            .collect();
+        assert_eq!(
+            transactions.iter().map(|t| t.data()).collect::<Vec<_>>(),
+            expected
+        );
+        while !consumer.is_empty() {
+            consumer.next();
+        }
+
+        // A burst of system transactions does not starve the user lane either, but takes
+        // the capacity it leaves unused.
+        for i in 0..20 {
+            client
+                .submit_no_wait(TransactionLane::System, vec![system_transaction(i)])
+                .await
+                .unwrap();
+        }
+        for i in 0..5 {
+            client
+                .submit_no_wait(TransactionLane::User, vec![user_transaction(i)])
+                .await
+                .unwrap();
+        }
+        let (transactions, _ack_transactions) = consumer.next();
+        let expected: Vec<_> = (0..2)
+            .map(system_transaction)
+            .chain((0..5).map(user_transaction))
+            .chain((2..6).map(system_transaction))
+            .collect();
+        assert_eq!(
+            transactions.iter().map(|t| t.data()).collect::<Vec<_>>(),
+            expected
+        );
+    }
+
     #[tokio::test]
     async fn submit_large_batch_and_ack() {
         let _guard = ProtocolConfig::apply_overrides_for_testing(|_, mut config| {
@@ -383,7 +569,7 @@ mod tests {
             let transaction =
                 bcs::to_bytes(&format!("transaction {i}")).expect("Serialization should not fail.");
             let w = client
-                .submit_no_wait(vec![transaction])
+                .submit_no_wait(TransactionLane::User, vec![transaction])
                 .await
                 .expect("Shouldn't submit successfully transaction");
             all_receivers.push(w);
@@ -398,7 +584,7 @@ mod tests {
                 })
                 .collect();
             let w = client
-                .submit_no_wait(transactions)
+                .submit_no_wait(TransactionLane::User, transactions)
                 .await
                 .expect("Shouldn't submit successfully transaction");
             all_receivers.push(w);
@@ -410,7 +596,7 @@ mod tests {
             let transaction =
                 bcs::to_bytes(&format!("transaction {i}")).expect("Serialization should not fail.");
             let w = client
-                .submit_no_wait(vec![transaction])
+                .submit_no_wait(TransactionLane::User, vec![transaction])
                 .await
                 .expect("Shouldn't submit successfully transaction");
             all_receivers.push(w);
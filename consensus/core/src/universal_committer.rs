@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::VecDeque, sync::Arc};
+use std::{collections::VecDeque, ops::RangeInclusive, sync::Arc};
 
 use consensus_config::AuthorityIndex;
 use parking_lot::RwLock;
@@ -9,7 +9,7 @@ use parking_lot::RwLock;
 use crate::{
     base_committer::BaseCommitter,
     block::{Round, Slot, GENESIS_ROUND},
-    commit::{DecidedLeader, Decision},
+    commit::{DecidedLeader, Decision, LeaderStatus},
     context::Context,
     dag_state::DagState,
 };
@@ -109,6 +109,35 @@ impl UniversalCommitter {
         decided_leaders
     }
 
+    /// Returns the status of every leader elected in `rounds`, along with the rule that decided it.
+    /// Unlike `try_decide()`, leaders following an undecided leader are still evaluated, which
+    /// helps inspecting a stalled DAG. Undecided leaders are reported with `Decision::Indirect`.
+    pub(crate) fn leader_statuses(
+        &self,
+        rounds: RangeInclusive<Round>,
+    ) -> Vec<(LeaderStatus, Decision)> {
+        let mut leaders = VecDeque::new();
+        for round in rounds.rev() {
+            for committer in self.committers.iter().rev() {
+                let Some(slot) = committer.elect_leader(round) else {
+                    continue;
+                };
+                if slot.round == GENESIS_ROUND {
+                    continue;
+                }
+                let status = committer.try_direct_decide(slot);
+                if status.is_decided() {
+                    leaders.push_front((status, Decision::Direct));
+                } else {
+                    let status =
+                        committer.try_indirect_decide(slot, leaders.iter().map(|(x, _)| x));
+                    leaders.push_front((status, Decision::Indirect));
+                }
+            }
+        }
+        leaders.into()
+    }
+
     /// Return list of leaders for the round.
     /// Can return empty vec if round does not have a designated leader.
     pub(crate) fn get_leaders(&self, round: Round) -> Vec<AuthorityIndex> {
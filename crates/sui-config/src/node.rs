@@ -204,6 +204,11 @@ pub struct NodeConfig {
     /// By default, write stall is enabled on validators but not on fullnodes.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enable_db_write_stall: Option<bool>,
+
+    /// JSON-RPC url of a full node with full history. Dry runs at past checkpoints read the
+    /// historical objects and effects that this node has already pruned from it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub historical_data_fallback_rpc_url: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -214,6 +219,11 @@ pub enum ExecutionCacheConfig {
         /// Maximum number of entries in each cache. (There are several different caches).
         /// If None, the default of 10000 is used.
         max_cache_size: Option<usize>,
+
+        /// If set, the ids of the objects held in the cache are written to this file on
+        /// shutdown, and read back at startup in order to prefetch them before serving traffic.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        hot_objects_path: Option<PathBuf>,
     },
 }
 
@@ -221,6 +231,18 @@ impl Default for ExecutionCacheConfig {
     fn default() -> Self {
         ExecutionCacheConfig::WritebackCache {
             max_cache_size: None,
+            hot_objects_path: None,
+        }
+    }
+}
+
+impl ExecutionCacheConfig {
+    pub fn hot_objects_path(&self) -> Option<&Path> {
+        match self {
+            ExecutionCacheConfig::PassthroughCache => None,
+            ExecutionCacheConfig::WritebackCache {
+                hot_objects_path, ..
+            } => hot_objects_path.as_deref(),
         }
     }
 }
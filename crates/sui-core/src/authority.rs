@@ -6,7 +6,9 @@ use crate::execution_cache::ExecutionCacheTraitPointers;
 use crate::execution_cache::TransactionCacheRead;
 use crate::rest_index::RestIndexStore;
 use crate::transaction_outputs::TransactionOutputs;
-use crate::verify_indexes::verify_indexes;
+use crate::verify_indexes::{
+    compute_index_repairs, find_index_repair_candidates, verify_indexes, IndexRepairSummary,
+};
 use anyhow::anyhow;
 use arc_swap::{ArcSwap, Guard};
 use async_trait::async_trait;
@@ -91,7 +93,7 @@ use sui_types::effects::{
     TransactionEvents, VerifiedCertifiedTransactionEffects, VerifiedSignedTransactionEffects,
 };
 use sui_types::error::{ExecutionError, UserInputError};
-use sui_types::event::{Event, EventID};
+use sui_types::event::{Event, EventID, SystemEpochInfoEvent};
 use sui_types::executable_transaction::VerifiedExecutableTransaction;
 use sui_types::gas::{GasCostSummary, SuiGasStatus};
 use sui_types::inner_temporary_store::{
@@ -138,12 +140,13 @@ use crate::authority::authority_store_pruner::{
 };
 use crate::authority::epoch_start_configuration::EpochStartConfigTrait;
 use crate::authority::epoch_start_configuration::EpochStartConfiguration;
+use crate::authority::historical_object_store::{HistoricalDataFetcher, HistoricalObjectStore};
 use crate::checkpoints::CheckpointStore;
 use crate::consensus_adapter::ConsensusAdapter;
 use crate::epoch::committee_store::CommitteeStore;
 use crate::execution_cache::{
-    CheckpointCache, ExecutionCacheCommit, ExecutionCacheReconfigAPI, ExecutionCacheWrite,
-    ObjectCacheRead, StateSyncAPI,
+    CheckpointCache, ExecutionCacheCommit, ExecutionCacheReconfigAPI, ExecutionCacheStatsAPI,
+    ExecutionCacheWrite, ObjectCacheRead, StateSyncAPI,
 };
 use crate::execution_driver::execution_process;
 use crate::metrics::LatencyObserver;
@@ -206,6 +209,7 @@ pub mod authority_store_pruner;
 pub mod authority_store_tables;
 pub mod authority_store_types;
 pub mod epoch_start_configuration;
+pub mod historical_object_store;
 pub mod shared_object_congestion_tracker;
 pub mod shared_object_version_manager;
 #[cfg(any(test, feature = "test-utils"))]
@@ -278,11 +282,18 @@ pub struct AuthorityMetrics {
     post_processing_total_tx_had_event_processed: IntCounter,
     post_processing_total_failures: IntCounter,
 
+    /// Secondary index repair metrics
+    index_repair_objects_scanned: IntCounter,
+    index_repair_inconsistent_entries: IntCounterVec,
+    index_repair_repaired_entries: IntCounterVec,
+    index_repair_in_progress: IntGauge,
+
     /// Consensus handler metrics
     pub consensus_handler_processed: IntCounterVec,
     pub consensus_handler_transaction_sizes: HistogramVec,
     pub consensus_handler_num_low_scoring_authorities: IntGauge,
     pub consensus_handler_scores: IntGaugeVec,
+    pub consensus_handler_misbehaving_authorities: IntGaugeVec,
     pub consensus_handler_deferred_transactions: IntCounter,
     pub consensus_handler_congested_transactions: IntCounter,
     pub consensus_handler_cancelled_transactions: IntCounter,
@@ -666,6 +677,32 @@ impl AuthorityMetrics {
                 registry,
             )
             .unwrap(),
+            index_repair_objects_scanned: register_int_counter_with_registry!(
+                "index_repair_objects_scanned",
+                "Number of live objects scanned by secondary index repair",
+                registry,
+            )
+            .unwrap(),
+            index_repair_inconsistent_entries: register_int_counter_vec_with_registry!(
+                "index_repair_inconsistent_entries",
+                "Number of inconsistent secondary index entries found, by table and kind",
+                &["table", "kind"],
+                registry,
+            )
+            .unwrap(),
+            index_repair_repaired_entries: register_int_counter_vec_with_registry!(
+                "index_repair_repaired_entries",
+                "Number of secondary index entries rewritten or deleted by index repair",
+                &["table"],
+                registry,
+            )
+            .unwrap(),
+            index_repair_in_progress: register_int_gauge_with_registry!(
+                "index_repair_in_progress",
+                "Whether a secondary index repair is currently running",
+                registry,
+            )
+            .unwrap(),
             consensus_handler_processed: register_int_counter_vec_with_registry!(
                 "consensus_handler_processed",
                 "Number of transactions processed by consensus handler",
@@ -690,6 +727,12 @@ impl AuthorityMetrics {
                 &["authority"],
                 registry,
             ).unwrap(),
+            consensus_handler_misbehaving_authorities: register_int_gauge_vec_with_registry!(
+                "consensus_handler_misbehaving_authorities",
+                "Set to 1 for authorities reported as misbehaving in consensus by validity stake",
+                &["authority"],
+                registry,
+            ).unwrap(),
             consensus_handler_deferred_transactions: register_int_counter_with_registry!(
                 "consensus_handler_deferred_transactions",
                 "Number of transactions deferred by consensus handler",
@@ -774,6 +817,15 @@ impl AuthorityMetrics {
 ///
 pub type StableSyncAuthoritySigner = Pin<Arc<dyn Signer<AuthoritySignature> + Send + Sync>>;
 
+/// Everything needed to dry-run a transaction as of a past checkpoint.
+struct HistoricalExecutionContext {
+    store: HistoricalObjectStore,
+    protocol_config: ProtocolConfig,
+    epoch: EpochId,
+    epoch_start_timestamp: CheckpointTimestamp,
+    reference_gas_price: u64,
+}
+
 pub struct AuthorityState {
     // Fixed size, static, identity of the authority
     /// The name of this authority.
@@ -793,6 +845,12 @@ pub struct AuthorityState {
     /// from previous epoch that are executed but did not make into checkpoint.
     execution_lock: RwLock<EpochId>,
 
+    /// Held while a secondary index repair is running, so that only one runs at a time.
+    index_repair_lock: tokio::sync::Mutex<()>,
+
+    /// Serves historical data that has been pruned locally to dry runs at past checkpoints.
+    historical_data_fetcher: OnceCell<Arc<dyn HistoricalDataFetcher>>,
+
     pub indexes: Option<Arc<IndexStore>>,
     pub rest_index: Option<Arc<RestIndexStore>>,
 
@@ -846,6 +904,14 @@ impl AuthorityState {
         self.committee_store.clone()
     }
 
+    /// Configures where dry runs at past checkpoints read data that has been pruned locally. Can
+    /// only be set once.
+    pub fn set_historical_data_fetcher(&self, fetcher: Arc<dyn HistoricalDataFetcher>) {
+        if self.historical_data_fetcher.set(fetcher).is_err() {
+            warn!("historical data fetcher is already set");
+        }
+    }
+
     pub fn overload_config(&self) -> &AuthorityOverloadConfig {
         &self.config.authority_overload_config
     }
@@ -1768,14 +1834,18 @@ impl AuthorityState {
             });
         }
 
-        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest)
+        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest, None)
             .await
     }
 
-    pub async fn dry_exec_transaction_for_benchmark(
+    /// Like `dry_exec_transaction`, but executes against the object versions as of the end of
+    /// `checkpoint` instead of the latest state. See `HistoricalObjectStore` for the limits of
+    /// what can be reconstructed from the local store.
+    pub async fn dry_exec_transaction_at_checkpoint(
         &self,
         transaction: TransactionData,
         transaction_digest: TransactionDigest,
+        checkpoint: CheckpointSequenceNumber,
     ) -> SuiResult<(
         DryRunTransactionBlockResponse,
         BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
@@ -1783,13 +1853,59 @@ impl AuthorityState {
         Option<ObjectID>,
     )> {
         let epoch_store = self.load_epoch_store_one_call_per_task();
-        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest)
-            .await
+        if !self.is_fullnode(&epoch_store) {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "dry-exec is only supported on fullnodes".to_string(),
+            });
+        }
+
+        if transaction.kind().is_system_tx() {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "dry-exec does not support system transactions".to_string(),
+            });
+        }
+
+        self.dry_exec_transaction_impl(
+            &epoch_store,
+            transaction,
+            transaction_digest,
+            Some(checkpoint),
+        )
+        .await
     }
 
-    async fn dry_exec_transaction_impl(
+    /// Like `dry_exec_transaction_at_checkpoint`, but returns the effects and events produced by
+    /// the execution as they are, rather than a JSON-RPC response.
+    pub fn simulate_transaction_at_checkpoint(
+        &self,
+        transaction: TransactionData,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<(TransactionEffects, TransactionEvents)> {
+        let epoch_store = self.load_epoch_store_one_call_per_task();
+        if !self.is_fullnode(&epoch_store) {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "simulation is only supported on fullnodes".to_string(),
+            });
+        }
+
+        if transaction.kind().is_system_tx() {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "simulation does not support system transactions".to_string(),
+            });
+        }
+
+        let transaction_digest = TransactionDigest::new(default_hash(&transaction));
+        let (inner_temp_store, effects, _mock_gas) = self.dry_exec_transaction_to_effects(
+            &epoch_store,
+            &transaction,
+            transaction_digest,
+            Some(checkpoint),
+        )?;
+        Ok((effects, inner_temp_store.events))
+    }
+
+    pub async fn dry_exec_transaction_for_benchmark(
         &self,
-        epoch_store: &AuthorityPerEpochStore,
         transaction: TransactionData,
         transaction_digest: TransactionDigest,
     ) -> SuiResult<(
@@ -1798,14 +1914,180 @@ impl AuthorityState {
         TransactionEffects,
         Option<ObjectID>,
     )> {
+        let epoch_store = self.load_epoch_store_one_call_per_task();
+        self.dry_exec_transaction_impl(&epoch_store, transaction, transaction_digest, None)
+            .await
+    }
+
+    /// A read-only view of the object store as of the end of `checkpoint`: the store that dry runs
+    /// at that checkpoint execute against.
+    pub fn get_historical_object_store(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> HistoricalObjectStore {
+        HistoricalObjectStore::new(
+            self.get_object_cache_reader().clone(),
+            self.get_transaction_cache_reader().clone(),
+            self.get_checkpoint_cache().clone(),
+            self.get_backing_store().clone(),
+            self.historical_data_fetcher.get().cloned(),
+            checkpoint,
+        )
+    }
+
+    /// Resolves the execution environment (protocol config and epoch) that was in effect at
+    /// `checkpoint`, along with a store that serves object reads as of that checkpoint.
+    fn historical_execution_context(
+        &self,
+        epoch_store: &AuthorityPerEpochStore,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<HistoricalExecutionContext> {
+        let summary = self
+            .checkpoint_store
+            .get_checkpoint_by_sequence_number(checkpoint)?
+            .ok_or(UserInputError::VerifiedCheckpointNotFound(checkpoint))?;
+        let epoch = summary.epoch;
+
+        let (protocol_config, epoch_start_timestamp, reference_gas_price) =
+            if epoch == epoch_store.epoch() {
+                (
+                    epoch_store.protocol_config().clone(),
+                    epoch_store
+                        .epoch_start_config()
+                        .epoch_data()
+                        .epoch_start_timestamp(),
+                    epoch_store.reference_gas_price(),
+                )
+            } else {
+                // The protocol version of a past epoch is recorded in the last checkpoint of the
+                // epoch before it, and the epoch started (approximately) when that checkpoint did.
+                let unknown_epoch = || SuiError::UnsupportedFeatureError {
+                    error: format!("cannot determine the execution environment of epoch {epoch}"),
+                };
+                let prev_epoch_last_checkpoint = epoch
+                    .checked_sub(1)
+                    .map(|prev_epoch| self.checkpoint_store.get_epoch_last_checkpoint(prev_epoch))
+                    .transpose()?
+                    .flatten()
+                    .ok_or_else(unknown_epoch)?;
+                let protocol_version = prev_epoch_last_checkpoint
+                    .end_of_epoch_data
+                    .as_ref()
+                    .ok_or_else(unknown_epoch)?
+                    .next_epoch_protocol_version;
+                let reference_gas_price = self
+                    .reference_gas_price_from_epoch_change(epoch, &prev_epoch_last_checkpoint)?
+                    .ok_or_else(unknown_epoch)?;
+                (
+                    ProtocolConfig::get_for_version(
+                        protocol_version,
+                        epoch_store.get_chain_identifier().chain(),
+                    ),
+                    prev_epoch_last_checkpoint.timestamp_ms,
+                    reference_gas_price,
+                )
+            };
+
+        Ok(HistoricalExecutionContext {
+            store: self.get_historical_object_store(checkpoint),
+            protocol_config,
+            epoch,
+            epoch_start_timestamp,
+            reference_gas_price,
+        })
+    }
+
+    /// The reference gas price of `epoch`, as announced by the `SystemEpochInfoEvent` emitted by
+    /// the change epoch transaction at the end of the previous epoch. Returns `None` if that
+    /// transaction or its events are no longer available.
+    fn reference_gas_price_from_epoch_change(
+        &self,
+        epoch: EpochId,
+        prev_epoch_last_checkpoint: &VerifiedCheckpoint,
+    ) -> SuiResult<Option<u64>> {
+        let Some(contents) = self
+            .checkpoint_store
+            .get_checkpoint_contents(&prev_epoch_last_checkpoint.content_digest)?
+        else {
+            return Ok(None);
+        };
+
+        // The change epoch transaction is always the last transaction of the epoch.
+        let Some(change_epoch) = contents.iter().last() else {
+            return Ok(None);
+        };
+        let Some(effects) = self
+            .get_transaction_cache_reader()
+            .get_executed_effects(&change_epoch.transaction)?
+        else {
+            return Ok(None);
+        };
+        let Some(events_digest) = effects.events_digest() else {
+            return Ok(None);
+        };
+        let Some(events) = self
+            .get_transaction_cache_reader()
+            .get_events(events_digest)?
+        else {
+            return Ok(None);
+        };
+
+        for event in events.data {
+            if !event.is_system_epoch_info_event() {
+                continue;
+            }
+            let info: SystemEpochInfoEvent = bcs::from_bytes(&event.contents).map_err(|e| {
+                SuiError::ObjectDeserializationError {
+                    error: e.to_string(),
+                }
+            })?;
+            if info.epoch == epoch {
+                return Ok(Some(info.reference_gas_price));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Executes `transaction` without committing its effects, as of `checkpoint` if it is set or
+    /// against the latest state otherwise. Also returns the id of the mock gas coin, if one had
+    /// to be made up.
+    fn dry_exec_transaction_to_effects(
+        &self,
+        epoch_store: &AuthorityPerEpochStore,
+        transaction: &TransactionData,
+        transaction_digest: TransactionDigest,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> SuiResult<(InnerTemporaryStore, TransactionEffects, Option<ObjectID>)> {
+        let historical = checkpoint
+            .map(|checkpoint| self.historical_execution_context(epoch_store, checkpoint))
+            .transpose()?;
+        let (protocol_config, epoch_id, epoch_start_timestamp, reference_gas_price) =
+            match &historical {
+                Some(historical) => (
+                    &historical.protocol_config,
+                    historical.epoch,
+                    historical.epoch_start_timestamp,
+                    historical.reference_gas_price,
+                ),
+                None => (
+                    epoch_store.protocol_config(),
+                    epoch_store.epoch_start_config().epoch_data().epoch_id(),
+                    epoch_store
+                        .epoch_start_config()
+                        .epoch_data()
+                        .epoch_start_timestamp(),
+                    epoch_store.reference_gas_price(),
+                ),
+            };
+
         // Cheap validity checks for a transaction, including input size limits.
-        transaction.validity_check_no_gas_check(epoch_store.protocol_config())?;
+        transaction.validity_check_no_gas_check(protocol_config)?;
 
         let input_object_kinds = transaction.input_objects()?;
         let receiving_object_refs = transaction.receiving_objects();
 
         sui_transaction_checks::deny::check_transaction_for_signing(
-            &transaction,
+            transaction,
             &[],
             &input_object_kinds,
             &receiving_object_refs,
@@ -1813,13 +2095,18 @@ impl AuthorityState {
             self.get_backing_package_store().as_ref(),
         )?;
 
-        let (input_objects, receiving_objects) = self.input_loader.read_objects_for_signing(
-            // We don't want to cache this transaction since it's a dry run.
-            None,
-            &input_object_kinds,
-            &receiving_object_refs,
-            epoch_store.epoch(),
-        )?;
+        let (input_objects, receiving_objects) = match &historical {
+            Some(historical) => historical
+                .store
+                .read_objects_for_dry_run(&input_object_kinds, &receiving_object_refs)?,
+            None => self.input_loader.read_objects_for_signing(
+                // We don't want to cache this transaction since it's a dry run.
+                None,
+                &input_object_kinds,
+                &receiving_object_refs,
+                epoch_store.epoch(),
+            )?,
+        };
 
         // make a gas object if one was not provided
         let mut gas_object_refs = transaction.gas().to_vec();
@@ -1839,9 +2126,9 @@ impl AuthorityState {
             gas_object_refs = vec![gas_object_ref];
             (
                 sui_transaction_checks::check_transaction_input_with_given_gas(
-                    epoch_store.protocol_config(),
-                    epoch_store.reference_gas_price(),
-                    &transaction,
+                    protocol_config,
+                    reference_gas_price,
+                    transaction,
                     input_objects,
                     receiving_objects,
                     gas_object,
@@ -1853,9 +2140,9 @@ impl AuthorityState {
         } else {
             (
                 sui_transaction_checks::check_transaction_input(
-                    epoch_store.protocol_config(),
-                    epoch_store.reference_gas_price(),
-                    &transaction,
+                    protocol_config,
+                    reference_gas_price,
+                    transaction,
                     input_objects,
                     &receiving_objects,
                     &self.metrics.bytecode_verifier_metrics,
@@ -1865,26 +2152,27 @@ impl AuthorityState {
             )
         };
 
-        let protocol_config = epoch_store.protocol_config();
         let (kind, signer, _) = transaction.execution_parts();
 
         let silent = true;
         let executor = sui_execution::executor(protocol_config, silent, None)
             .expect("Creating an executor should not fail here");
 
+        let backing_store: &dyn BackingStore = match &historical {
+            Some(historical) => &historical.store,
+            None => self.get_backing_store().as_ref(),
+        };
+
         let expensive_checks = false;
         let (inner_temp_store, _, effects, _execution_error) = executor
             .execute_transaction_to_effects(
-                self.get_backing_store().as_ref(),
+                backing_store,
                 protocol_config,
                 self.metrics.limits_metrics.clone(),
                 expensive_checks,
                 self.config.certificate_deny_config.certificate_deny_set(),
-                &epoch_store.epoch_start_config().epoch_data().epoch_id(),
-                epoch_store
-                    .epoch_start_config()
-                    .epoch_data()
-                    .epoch_start_timestamp(),
+                &epoch_id,
+                epoch_start_timestamp,
                 checked_input_objects,
                 gas_object_refs,
                 gas_status,
@@ -1892,6 +2180,27 @@ impl AuthorityState {
                 signer,
                 transaction_digest,
             );
+        Ok((inner_temp_store, effects, mock_gas))
+    }
+
+    async fn dry_exec_transaction_impl(
+        &self,
+        epoch_store: &AuthorityPerEpochStore,
+        transaction: TransactionData,
+        transaction_digest: TransactionDigest,
+        checkpoint: Option<CheckpointSequenceNumber>,
+    ) -> SuiResult<(
+        DryRunTransactionBlockResponse,
+        BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
+        TransactionEffects,
+        Option<ObjectID>,
+    )> {
+        let (inner_temp_store, effects, mock_gas) = self.dry_exec_transaction_to_effects(
+            epoch_store,
+            &transaction,
+            transaction_digest,
+            checkpoint,
+        )?;
         let tx_digest = *effects.transaction_digest();
 
         let module_cache =
@@ -2474,6 +2783,152 @@ impl AuthorityState {
         }))
     }
 
+    /// Like `try_create_dynamic_field_info`, but for an object that is read back from the store
+    /// instead of being written by the transaction under indexing. The child of a dynamic object
+    /// field may have moved past the wrapper's version, in which case its latest version is used.
+    fn dynamic_field_info_for_repair(
+        &self,
+        o: &Object,
+        resolver: &mut dyn LayoutResolver,
+    ) -> Option<DynamicFieldInfo> {
+        let result = match self.try_create_dynamic_field_info(o, &BTreeMap::new(), resolver) {
+            Err(SuiError::UserInputError {
+                error: UserInputError::ObjectNotFound { object_id, .. },
+            }) => self
+                .get_object_store()
+                .get_object(&object_id)
+                .map_err(SuiError::from)
+                .and_then(|child| {
+                    let written = child.map(|child| (object_id, child)).into_iter().collect();
+                    self.try_create_dynamic_field_info(o, &written, resolver)
+                }),
+            result => result,
+        };
+        result.unwrap_or_else(|e| {
+            error!(object_id = ?o.id(), "Failed to compute dynamic field info: {e}");
+            None
+        })
+    }
+
+    pub fn is_index_repair_in_progress(&self) -> bool {
+        self.index_repair_lock.try_lock().is_err()
+    }
+
+    /// Compares the owner, coin and dynamic field indexes with the live object set and, unless
+    /// `dry_run` is set, rewrites the entries that are missing, stale or no longer belong to a live
+    /// object. The scan runs concurrently with execution and takes no locks. Repairs are applied in
+    /// small batches, and execution is only paused while a single batch is recomputed from the
+    /// latest object state and written, so that it cannot race with transactions being indexed.
+    pub async fn repair_indexes(self: &Arc<Self>, dry_run: bool) -> SuiResult<IndexRepairSummary> {
+        const REPAIR_BATCH_SIZE: usize = 100;
+
+        let Some(indexes) = self.indexes.clone() else {
+            return Err(SuiError::IndexStoreNotAvailable);
+        };
+        let Ok(_repair_guard) = self.index_repair_lock.try_lock() else {
+            return Err(SuiError::GenericAuthorityError {
+                error: "An index repair is already in progress".to_string(),
+            });
+        };
+        self.metrics.index_repair_in_progress.set(1);
+        let _in_progress = scopeguard::guard(self.metrics.clone(), |metrics| {
+            metrics.index_repair_in_progress.set(0);
+        });
+
+        info!(dry_run, "Starting secondary index repair");
+        let state = self.clone();
+        let scan_indexes = indexes.clone();
+        let (mut summary, candidates) = tokio::task::spawn_blocking(move || {
+            let epoch_store = state.load_epoch_store_one_call_per_task();
+            let mut resolver = epoch_store
+                .executor()
+                .type_layout_resolver(Box::new(state.get_backing_package_store().as_ref()));
+            find_index_repair_candidates(
+                state.get_accumulator_store().iter_live_object_set(false),
+                &scan_indexes,
+                |id| Ok(state.get_object_store().get_object(id)?),
+                |o| state.dynamic_field_info_for_repair(o, resolver.as_mut()),
+                || state.metrics.index_repair_objects_scanned.inc(),
+            )
+        })
+        .await
+        .map_err(|e| SuiError::GenericAuthorityError {
+            error: format!("index repair scan failed: {e}"),
+        })??;
+
+        for (table, table_summary) in [
+            ("owner_index", &summary.owner_index),
+            ("coin_index", &summary.coin_index),
+            ("dynamic_field_index", &summary.dynamic_field_index),
+        ] {
+            for (kind, count) in [
+                ("missing", table_summary.missing),
+                ("mismatched", table_summary.mismatched),
+                ("extra", table_summary.extra),
+            ] {
+                self.metrics
+                    .index_repair_inconsistent_entries
+                    .with_label_values(&[table, kind])
+                    .inc_by(count);
+            }
+        }
+
+        if dry_run {
+            info!(?summary, "Finished secondary index repair dry run");
+            return Ok(summary);
+        }
+
+        for chunk in candidates.object_ids().chunks(REPAIR_BATCH_SIZE) {
+            // Let execution make progress between batches.
+            tokio::task::yield_now().await;
+            let _execution_guard = self.execution_lock.write().await;
+            let before = summary.clone();
+            let (changes, deleted_coins, new_coins) = {
+                let epoch_store = self.load_epoch_store_one_call_per_task();
+                let mut resolver = epoch_store
+                    .executor()
+                    .type_layout_resolver(Box::new(self.get_backing_package_store().as_ref()));
+                compute_index_repairs(
+                    chunk,
+                    &candidates,
+                    &indexes,
+                    |id| Ok(self.get_object_store().get_object(id)?),
+                    |o| self.dynamic_field_info_for_repair(o, resolver.as_mut()),
+                    &mut summary,
+                )?
+            };
+            indexes
+                .repair_object_indexes(changes, deleted_coins, new_coins)
+                .await?;
+
+            for (table, repaired, repaired_before) in [
+                (
+                    "owner_index",
+                    summary.owner_index.repaired,
+                    before.owner_index.repaired,
+                ),
+                (
+                    "coin_index",
+                    summary.coin_index.repaired,
+                    before.coin_index.repaired,
+                ),
+                (
+                    "dynamic_field_index",
+                    summary.dynamic_field_index.repaired,
+                    before.dynamic_field_index.repaired,
+                ),
+            ] {
+                self.metrics
+                    .index_repair_repaired_entries
+                    .with_label_values(&[table])
+                    .inc_by(repaired - repaired_before);
+            }
+        }
+
+        info!(?summary, "Finished secondary index repair");
+        Ok(summary)
+    }
+
     #[instrument(level = "trace", skip_all, err)]
     async fn post_process_one_tx(
         &self,
@@ -2790,6 +3245,8 @@ impl AuthorityState {
             name,
             secret,
             execution_lock: RwLock::new(epoch),
+            index_repair_lock: tokio::sync::Mutex::new(()),
+            historical_data_fetcher: OnceCell::new(),
             epoch_store: ArcSwap::new(epoch_store.clone()),
             input_loader,
             execution_cache_trait_pointers,
@@ -2854,6 +3311,10 @@ impl AuthorityState {
         &self.execution_cache_trait_pointers.reconfig_api
     }
 
+    pub fn get_cache_stats_api(&self) -> &Arc<dyn ExecutionCacheStatsAPI> {
+        &self.execution_cache_trait_pointers.cache_stats_api
+    }
+
     pub fn get_accumulator_store(&self) -> &Arc<dyn AccumulatorStore> {
         &self.execution_cache_trait_pointers.accumulator_store
     }
@@ -787,7 +787,7 @@ impl AuthorityStore {
     }
 
     pub fn get_epoch_start_configuration(&self) -> SuiResult<Option<EpochStartConfiguration>> {
-        Ok(self.perpetual_tables.epoch_start_configuration.get(&())?)
+        self.perpetual_tables.get_epoch_start_configuration()
     }
 
     /// Acquires read locks for affected indirect objects
@@ -319,6 +319,10 @@ impl AuthorityPerpetualTables {
         Ok(None)
     }
 
+    pub fn get_epoch_start_configuration(&self) -> SuiResult<Option<EpochStartConfiguration>> {
+        Ok(self.epoch_start_configuration.get(&())?)
+    }
+
     pub fn get_recovery_epoch_at_restart(&self) -> SuiResult<EpochId> {
         Ok(self
             .epoch_start_configuration
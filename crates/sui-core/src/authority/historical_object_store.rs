@@ -0,0 +1,319 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A read-only view of the object store as of the end of a given checkpoint.
+//!
+//! Objects are not indexed by checkpoint, so the version of an object at a checkpoint is found by
+//! starting from its latest version and walking back through the effects of the transactions that
+//! modified it, until we reach a version that was written at or before the checkpoint. This only
+//! works for as long as the intermediate versions have not been pruned (unless a
+//! `HistoricalDataFetcher` is configured to serve them), and it cannot see objects that have since
+//! been deleted or wrapped (tombstones do not record the deleting transaction). The walk is capped
+//! at `MAX_VERSIONS_TO_WALK` steps, so objects that are modified constantly (like the clock) can
+//! only be read at recent checkpoints.
+//!
+//! Child objects are read through the underlying store with the usual version upper bound, which
+//! is derived from the (historical) versions of the transaction's inputs.
+
+use std::sync::Arc;
+
+use sui_types::base_types::{EpochId, ObjectID, ObjectRef, SequenceNumber, TransactionDigest};
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::error::{SuiError, SuiResult, UserInputError};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use sui_types::storage::{
+    BackingPackageStore, BackingStore, ChildObjectResolver, ObjectStore, PackageObject, ParentSync,
+};
+use sui_types::transaction::{
+    InputObjectKind, InputObjects, ObjectReadResult, ObjectReadResultKind,
+    ReceivingObjectReadResult, ReceivingObjects,
+};
+use tracing::trace;
+
+use crate::execution_cache::{CheckpointCache, ObjectCacheRead, TransactionCacheRead};
+
+/// Upper bound on the number of versions of a single object that are visited while looking for
+/// its version at a checkpoint.
+pub const MAX_VERSIONS_TO_WALK: usize = 1_000;
+
+/// Source of the data `HistoricalObjectStore` needs when it has been pruned locally, typically a
+/// remote full node that keeps all history (the same source the replay tool reads from).
+///
+/// Lookups happen while the transaction executes, so implementations are called synchronously.
+pub trait HistoricalDataFetcher: Send + Sync {
+    /// The checkpoint that `digest` was included in, if any.
+    fn get_transaction_checkpoint(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>>;
+
+    fn get_executed_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionEffects>>;
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>>;
+}
+
+pub struct HistoricalObjectStore {
+    object_cache_reader: Arc<dyn ObjectCacheRead>,
+    transaction_cache_reader: Arc<dyn TransactionCacheRead>,
+    checkpoint_cache: Arc<dyn CheckpointCache>,
+    backing_store: Arc<dyn BackingStore + Send + Sync>,
+    fallback: Option<Arc<dyn HistoricalDataFetcher>>,
+    checkpoint: CheckpointSequenceNumber,
+    max_versions_to_walk: usize,
+}
+
+impl HistoricalObjectStore {
+    pub fn new(
+        object_cache_reader: Arc<dyn ObjectCacheRead>,
+        transaction_cache_reader: Arc<dyn TransactionCacheRead>,
+        checkpoint_cache: Arc<dyn CheckpointCache>,
+        backing_store: Arc<dyn BackingStore + Send + Sync>,
+        fallback: Option<Arc<dyn HistoricalDataFetcher>>,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> Self {
+        Self {
+            object_cache_reader,
+            transaction_cache_reader,
+            checkpoint_cache,
+            backing_store,
+            fallback,
+            checkpoint,
+            max_versions_to_walk: MAX_VERSIONS_TO_WALK,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_max_versions_to_walk(mut self, max_versions_to_walk: usize) -> Self {
+        self.max_versions_to_walk = max_versions_to_walk;
+        self
+    }
+
+    /// Returns the object as it was at the end of `self.checkpoint`, or None if it did not exist
+    /// (or was wrapped) at that point.
+    pub fn get_object_at_checkpoint(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        let Some(mut object) = self.object_cache_reader.get_object(object_id)? else {
+            // The object is currently deleted or wrapped. We can't tell which transaction removed
+            // it, so we can't tell whether it existed at the checkpoint either.
+            return Err(UserInputError::ObjectNotFound {
+                object_id: *object_id,
+                version: None,
+            }
+            .into());
+        };
+
+        for _ in 0..self.max_versions_to_walk {
+            let tx_digest = object.previous_transaction;
+            let tx_checkpoint = self.get_transaction_checkpoint(&tx_digest)?;
+
+            // Transactions that are executed but not yet checkpointed are necessarily later than
+            // any checkpoint we could have been asked about.
+            if matches!(tx_checkpoint, Some(checkpoint) if checkpoint <= self.checkpoint) {
+                return Ok(Some(object));
+            }
+
+            let effects = self
+                .get_executed_effects(&tx_digest)?
+                .ok_or(SuiError::TransactionNotFound { digest: tx_digest })?;
+
+            let Some((_, prev_version)) = effects
+                .modified_at_versions()
+                .into_iter()
+                .find(|(id, _)| id == object_id)
+            else {
+                // The object was created or unwrapped after the checkpoint.
+                trace!(?object_id, ?tx_digest, "object did not exist at checkpoint");
+                return Ok(None);
+            };
+
+            object = self.get_object_version(object_id, prev_version)?.ok_or(
+                UserInputError::ObjectNotFound {
+                    object_id: *object_id,
+                    version: Some(prev_version),
+                },
+            )?;
+        }
+
+        Err(SuiError::UnsupportedFeatureError {
+            error: format!(
+                "object {object_id} was modified more than {} times after checkpoint {}",
+                self.max_versions_to_walk, self.checkpoint
+            ),
+        })
+    }
+
+    fn get_transaction_checkpoint(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        if let Some((_epoch, checkpoint)) = self
+            .checkpoint_cache
+            .deprecated_get_transaction_checkpoint(digest)?
+        {
+            return Ok(Some(checkpoint));
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.get_transaction_checkpoint(digest),
+            None => Ok(None),
+        }
+    }
+
+    fn get_executed_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionEffects>> {
+        if let Some(effects) = self.transaction_cache_reader.get_executed_effects(digest)? {
+            return Ok(Some(effects));
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.get_executed_effects(digest),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the given version of the object, reading it from the fallback if it has been pruned
+    /// locally.
+    pub fn get_object_version(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        if let Some(object) = self
+            .object_cache_reader
+            .get_object_by_key(object_id, version)?
+        {
+            return Ok(Some(object));
+        }
+        match &self.fallback {
+            Some(fallback) => fallback.get_object_by_key(object_id, version),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the latest version of the object that is at most `version`. Only local versions are
+    /// considered, as the fallback can only serve exact versions.
+    pub fn find_object_lt_or_eq_version(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        self.object_cache_reader
+            .find_object_lt_or_eq_version(*object_id, version)
+    }
+
+    /// Equivalent of `TransactionInputLoader::read_objects_for_signing`, except that packages,
+    /// shared objects and receiving objects are read as of the checkpoint. Owned objects are
+    /// already pinned to a version by the transaction, so they are read by key.
+    pub fn read_objects_for_dry_run(
+        &self,
+        input_object_kinds: &[InputObjectKind],
+        receiving_objects: &[ObjectRef],
+    ) -> SuiResult<(InputObjects, ReceivingObjects)> {
+        let mut input_results = Vec::with_capacity(input_object_kinds.len());
+        for kind in input_object_kinds {
+            let object = match kind {
+                InputObjectKind::MovePackage(id) | InputObjectKind::SharedMoveObject { id, .. } => {
+                    self.get_object_at_checkpoint(id)?
+                }
+                InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => {
+                    self.get_object_version(id, *version)?
+                }
+            };
+            let Some(object) = object else {
+                return Err(kind.object_not_found_error().into());
+            };
+            input_results.push(ObjectReadResult {
+                input_object_kind: *kind,
+                object: ObjectReadResultKind::Object(object),
+            });
+        }
+
+        let mut receiving_results = Vec::with_capacity(receiving_objects.len());
+        for objref in receiving_objects {
+            // Note: the version and digest are checked later in check_transaction_input
+            let Some(object) = self.get_object_at_checkpoint(&objref.0)? else {
+                return Err(UserInputError::ObjectNotFound {
+                    object_id: objref.0,
+                    version: Some(objref.1),
+                }
+                .into());
+            };
+            receiving_results.push(ReceivingObjectReadResult::new(*objref, object.into()));
+        }
+
+        Ok((input_results.into(), receiving_results.into()))
+    }
+}
+
+impl ObjectStore for HistoricalObjectStore {
+    fn get_object(
+        &self,
+        object_id: &ObjectID,
+    ) -> sui_types::storage::error::Result<Option<Object>> {
+        self.get_object_at_checkpoint(object_id)
+            .map_err(sui_types::storage::error::Error::custom)
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> sui_types::storage::error::Result<Option<Object>> {
+        self.backing_store.get_object_by_key(object_id, version)
+    }
+}
+
+impl BackingPackageStore for HistoricalObjectStore {
+    fn get_package_object(&self, package_id: &ObjectID) -> SuiResult<Option<PackageObject>> {
+        // System packages are upgraded in place, and packages published after the checkpoint
+        // must not be visible, so packages are resolved like any other object.
+        Ok(self
+            .get_object_at_checkpoint(package_id)?
+            .filter(|object| object.is_package())
+            .map(PackageObject::new))
+    }
+}
+
+impl ChildObjectResolver for HistoricalObjectStore {
+    fn read_child_object(
+        &self,
+        parent: &ObjectID,
+        child: &ObjectID,
+        child_version_upper_bound: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        self.backing_store
+            .read_child_object(parent, child, child_version_upper_bound)
+    }
+
+    fn get_object_received_at_version(
+        &self,
+        owner: &ObjectID,
+        receiving_object_id: &ObjectID,
+        receive_object_at_version: SequenceNumber,
+        epoch_id: EpochId,
+    ) -> SuiResult<Option<Object>> {
+        self.backing_store.get_object_received_at_version(
+            owner,
+            receiving_object_id,
+            receive_object_at_version,
+            epoch_id,
+        )
+    }
+}
+
+impl ParentSync for HistoricalObjectStore {
+    fn get_latest_parent_entry_ref_deprecated(
+        &self,
+        object_id: ObjectID,
+    ) -> SuiResult<Option<ObjectRef>> {
+        self.backing_store
+            .get_latest_parent_entry_ref_deprecated(object_id)
+    }
+}
@@ -40,7 +40,7 @@ use crate::{
     consensus_throughput_calculator::ConsensusThroughputCalculator,
     consensus_types::{consensus_output_api::ConsensusOutputAPI, AuthorityIndex},
     execution_cache::ObjectCacheRead,
-    scoring_decision::update_low_scoring_authorities,
+    scoring_decision::{update_low_scoring_authorities, MisbehaviorTally},
     transaction_manager::TransactionManager,
 };
 
@@ -117,6 +117,8 @@ pub struct ConsensusHandler<C> {
     low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
     /// The consensus committee used to do stake computations for deciding set of low scoring authorities
     committee: ConsensusCommittee,
+    /// Misbehavior reports from consensus, used to rank misbehaving authorities as low scoring
+    misbehavior_tally: MisbehaviorTally,
     // TODO: ConsensusHandler doesn't really share metrics with AuthorityState. We could define
     // a new metrics type here if we want to.
     metrics: Arc<AuthorityMetrics>,
@@ -157,6 +159,7 @@ impl<C> ConsensusHandler<C> {
             cache_reader,
             low_scoring_authorities,
             committee,
+            misbehavior_tally: MisbehaviorTally::default(),
             metrics,
             processed_cache: LruCache::new(NonZeroUsize::new(PROCESSED_CACHE_CAP).unwrap()),
             transaction_scheduler,
@@ -265,11 +268,24 @@ impl<C: CheckpointServiceNotify + Send + Sync> ConsensusHandler<C> {
             ));
         }
 
+        if self
+            .epoch_store
+            .protocol_config()
+            .consensus_misbehavior_reports()
+        {
+            self.misbehavior_tally.add_reports(
+                &self.committee,
+                consensus_output.misbehavior_reports(),
+                &self.metrics,
+            );
+        }
+
         update_low_scoring_authorities(
             self.low_scoring_authorities.clone(),
             self.epoch_store.committee(),
             &self.committee,
             consensus_output.reputation_score_sorted_desc(),
+            self.misbehavior_tally.misbehaving_authorities(),
             &self.metrics,
             self.epoch_store
                 .protocol_config()
@@ -8,6 +8,7 @@ use crate::consensus_validator::SuiTxValidator;
 use crate::mysticeti_adapter::LazyMysticetiClient;
 use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
+use consensus_core::ConsensusHealth;
 use enum_dispatch::enum_dispatch;
 use fastcrypto::traits::KeyPair as _;
 use mysten_metrics::RegistryService;
@@ -114,6 +115,13 @@ impl ConsensusManager {
     pub fn get_storage_base_path(&self) -> PathBuf {
         self.consensus_config.db_path().to_path_buf()
     }
+
+    /// Returns the health of consensus, if it is running.
+    pub fn consensus_health(&self) -> Option<ConsensusHealth> {
+        match &self.mysticeti_manager {
+            ProtocolManager::Mysticeti(manager) => manager.consensus_health(),
+        }
+    }
 }
 
 #[async_trait]
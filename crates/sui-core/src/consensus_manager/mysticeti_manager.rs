@@ -5,7 +5,9 @@ use std::{path::PathBuf, sync::Arc, time::Duration};
 use arc_swap::ArcSwapOption;
 use async_trait::async_trait;
 use consensus_config::{Committee, NetworkKeyPair, Parameters, ProtocolKeyPair};
-use consensus_core::{CommitConsumer, CommitIndex, ConsensusAuthority};
+use consensus_core::{
+    CommitConsumer, CommitIndex, ConsensusAuthority, ConsensusHealth, HealthMonitor,
+};
 use fastcrypto::ed25519;
 use mysten_metrics::{monitored_mpsc::unbounded_channel, RegistryID, RegistryService};
 use prometheus::Registry;
@@ -40,6 +42,9 @@ pub struct MysticetiManager {
     metrics: Arc<ConsensusManagerMetrics>,
     registry_service: RegistryService,
     authority: ArcSwapOption<(ConsensusAuthority, RegistryID)>,
+    // Kept apart from the authority, so reading it never holds a reference to the authority
+    // while it is being shut down.
+    health_monitor: ArcSwapOption<HealthMonitor>,
     boot_counter: Mutex<u64>,
     // Use a shared lazy mysticeti client so we can update the internal mysticeti
     // client that gets created for every new epoch.
@@ -67,6 +72,7 @@ impl MysticetiManager {
             metrics,
             registry_service,
             authority: ArcSwapOption::empty(),
+            health_monitor: ArcSwapOption::empty(),
             client,
             consensus_handler: Mutex::new(None),
             boot_counter: Mutex::new(0),
@@ -79,6 +85,14 @@ impl MysticetiManager {
         store_path
     }
 
+    /// Returns the health of the running consensus authority, if any.
+    pub fn consensus_health(&self) -> Option<ConsensusHealth> {
+        self.health_monitor
+            .load()
+            .as_ref()
+            .map(|monitor| monitor.report())
+    }
+
     fn pick_network(&self, epoch_store: &AuthorityPerEpochStore) -> ConsensusNetwork {
         if let Ok(type_str) = std::env::var("CONSENSUS_NETWORK") {
             match type_str.to_lowercase().as_str() {
@@ -171,6 +185,7 @@ impl ConsensusManagerTrait for MysticetiManager {
         )
         .await;
         let client = authority.transaction_client();
+        self.health_monitor.store(Some(authority.health_monitor()));
 
         // Now increment the boot counter
         let mut boot_counter = self.boot_counter.lock().await;
@@ -201,6 +216,7 @@ impl ConsensusManagerTrait for MysticetiManager {
 
         // Stop consensus submissions.
         self.client.clear();
+        self.health_monitor.store(None);
 
         // swap with empty to ensure there is no other reference to authority and we can safely do Arc unwrap
         let r = self.authority.swap(None).unwrap();
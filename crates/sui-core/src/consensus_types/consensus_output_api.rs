@@ -27,6 +27,10 @@ pub(crate) trait ConsensusOutputAPI: Display {
     /// Returns all transactions in the commit.
     fn transactions(&self) -> ConsensusOutputTransactions;
 
+    /// Returns the misbehavior reports in the commit, as tuples of
+    /// (reporting authority index, reported authority index).
+    fn misbehavior_reports(&self) -> Vec<(AuthorityIndex, AuthorityIndex)>;
+
     /// Returns the digest of consensus output.
     fn consensus_digest(&self, protocol_config: &ProtocolConfig) -> ConsensusCommitDigest;
 }
@@ -90,6 +94,19 @@ impl ConsensusOutputAPI for consensus_core::CommittedSubDag {
             .collect()
     }
 
+    fn misbehavior_reports(&self) -> Vec<(AuthorityIndex, AuthorityIndex)> {
+        self.blocks
+            .iter()
+            .flat_map(|block| {
+                let reporter = block.author().value() as AuthorityIndex;
+                block
+                    .misbehavior_reports()
+                    .iter()
+                    .map(move |report| (reporter, report.target.value() as AuthorityIndex))
+            })
+            .collect()
+    }
+
     fn consensus_digest(&self, protocol_config: &ProtocolConfig) -> ConsensusCommitDigest {
         if protocol_config.mysticeti_use_committed_subdag_digest() {
             // We port CommitDigest, a consensus space object, into ConsensusCommitDigest, a sui-core space object.
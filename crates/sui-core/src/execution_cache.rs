@@ -35,7 +35,7 @@ use sui_types::{
     object::Owner,
     storage::InputKey,
 };
-use tracing::instrument;
+use tracing::{info, instrument};
 
 pub(crate) mod cache_types;
 pub mod metrics;
@@ -67,6 +67,7 @@ pub struct ExecutionCacheTraitPointers {
     pub checkpoint_cache: Arc<dyn CheckpointCache>,
     pub state_sync_store: Arc<dyn StateSyncAPI>,
     pub cache_commit: Arc<dyn ExecutionCacheCommit>,
+    pub cache_stats_api: Arc<dyn ExecutionCacheStatsAPI>,
     pub testing_api: Arc<dyn TestingAPI>,
 }
 
@@ -84,6 +85,7 @@ impl ExecutionCacheTraitPointers {
             + CheckpointCache
             + StateSyncAPI
             + ExecutionCacheCommit
+            + ExecutionCacheStatsAPI
             + TestingAPI
             + 'static,
     {
@@ -99,6 +101,7 @@ impl ExecutionCacheTraitPointers {
             checkpoint_cache: cache.clone(),
             state_sync_store: cache.clone(),
             cache_commit: cache.clone(),
+            cache_stats_api: cache.clone(),
             testing_api: cache.clone(),
         }
     }
@@ -715,6 +718,92 @@ pub trait StateSyncAPI: Send + Sync {
     ) -> SuiResult;
 }
 
+/// Point-in-time statistics for a single cache (objects, packages or markers).
+#[derive(Clone, Debug, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Number of entries in the dirty (uncommitted) set. Always 0 for caches that do not
+    /// hold uncommitted data.
+    pub dirty_entries: u64,
+    /// Approximate number of entries in the committed cache.
+    pub cached_entries: u64,
+    pub evictions: u64,
+}
+
+impl CacheStats {
+    pub fn hit_ratio(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            0.0
+        } else {
+            self.hits as f64 / total as f64
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionCacheStats {
+    pub objects: CacheStats,
+    pub packages: CacheStats,
+    pub markers: CacheStats,
+}
+
+pub trait ExecutionCacheStatsAPI: Send + Sync {
+    fn cache_stats(&self) -> ExecutionCacheStats;
+
+    /// Ids of objects (including packages) currently held in the committed cache.
+    fn hot_object_ids(&self) -> Vec<ObjectID>;
+
+    /// Read the given objects from the db into the cache, so that the first reads after a
+    /// restart do not all go to disk. Returns the number of objects that were found.
+    fn warm_up_objects(&self, object_ids: &[ObjectID]) -> SuiResult<usize>;
+}
+
+/// Write the ids of the objects currently held by the cache to `path`, so that they can be
+/// prefetched with [`warm_up_execution_cache`] on the next startup.
+pub fn persist_hot_objects(
+    cache: &dyn ExecutionCacheStatsAPI,
+    path: &Path,
+) -> anyhow::Result<usize> {
+    let object_ids = cache.hot_object_ids();
+    let bytes = bcs::to_bytes(&object_ids)?;
+    // Write to a temporary file first so that a crash does not leave a truncated file behind.
+    let tmp_path = path.with_extension("tmp");
+    std::fs::write(&tmp_path, bytes)?;
+    std::fs::rename(&tmp_path, path)?;
+    info!(
+        "persisted {} hot object ids to {}",
+        object_ids.len(),
+        path.display()
+    );
+    Ok(object_ids.len())
+}
+
+/// Prefetch the objects recorded by [`persist_hot_objects`]. A missing file is not an error,
+/// since there is nothing to warm up on the first start of a node.
+pub fn warm_up_execution_cache(
+    cache: &dyn ExecutionCacheStatsAPI,
+    path: &Path,
+) -> anyhow::Result<usize> {
+    if !path.exists() {
+        info!(
+            "no hot object file at {}, skipping cache warmup",
+            path.display()
+        );
+        return Ok(0);
+    }
+    let object_ids: Vec<ObjectID> = bcs::from_bytes(&std::fs::read(path)?)?;
+    let loaded = cache.warm_up_objects(&object_ids)?;
+    info!(
+        "warmed up execution cache with {}/{} objects from {}",
+        loaded,
+        object_ids.len(),
+        path.display()
+    );
+    Ok(loaded)
+}
+
 pub trait TestingAPI: Send + Sync {
     fn database_for_testing(&self) -> Arc<AuthorityStore>;
 }
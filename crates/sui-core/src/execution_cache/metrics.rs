@@ -4,8 +4,8 @@
 use tracing::trace;
 
 use prometheus::{
-    register_int_counter_vec_with_registry, register_int_gauge_with_registry, IntCounterVec,
-    IntGauge, Registry,
+    register_int_counter_vec_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, IntCounter, IntCounterVec, IntGauge, Registry,
 };
 
 pub struct ExecutionCacheMetrics {
@@ -15,6 +15,9 @@ pub struct ExecutionCacheMetrics {
     pub(crate) cache_negative_hits: IntCounterVec,
     pub(crate) cache_misses: IntCounterVec,
     pub(crate) cache_writes: IntCounterVec,
+    pub(crate) cache_lookups: IntCounterVec,
+    pub(crate) cache_evictions: IntCounterVec,
+    pub(crate) cache_warmup_objects: IntCounter,
 }
 
 impl ExecutionCacheMetrics {
@@ -65,6 +68,30 @@ impl ExecutionCacheMetrics {
                 registry,
             )
             .unwrap(),
+
+            // `cache` is "objects", "packages" or "markers", `outcome` is "hit" or "miss".
+            // Unlike `cache_hits`, a lookup is only counted once regardless of how many
+            // levels of the cache were consulted.
+            cache_lookups: register_int_counter_vec_with_registry!(
+                "execution_cache_lookups",
+                "Execution cache lookups by outcome",
+                &["cache", "outcome"],
+                registry,
+            )
+            .unwrap(),
+            cache_evictions: register_int_counter_vec_with_registry!(
+                "execution_cache_evictions",
+                "Entries evicted from the execution cache due to capacity",
+                &["cache"],
+                registry,
+            )
+            .unwrap(),
+            cache_warmup_objects: register_int_counter_with_registry!(
+                "execution_cache_warmup_objects",
+                "Objects prefetched into the execution cache at startup",
+                registry,
+            )
+            .unwrap(),
         }
     }
 
@@ -121,4 +148,27 @@ impl ExecutionCacheMetrics {
     pub(crate) fn record_cache_write(&self, collection: &'static str) {
         self.cache_writes.with_label_values(&[collection]).inc();
     }
+
+    pub(crate) fn record_cache_lookup(&self, cache: &'static str, hit: bool) {
+        let outcome = if hit { "hit" } else { "miss" };
+        self.cache_lookups
+            .with_label_values(&[cache, outcome])
+            .inc();
+    }
+
+    pub(crate) fn record_cache_eviction(&self, cache: &'static str) {
+        self.cache_evictions.with_label_values(&[cache]).inc();
+    }
+
+    /// Returns the number of (hits, misses) recorded for `cache` since startup.
+    pub(crate) fn cache_lookup_counts(&self, cache: &'static str) -> (u64, u64) {
+        (
+            self.cache_lookups.with_label_values(&[cache, "hit"]).get(),
+            self.cache_lookups.with_label_values(&[cache, "miss"]).get(),
+        )
+    }
+
+    pub(crate) fn cache_eviction_count(&self, cache: &'static str) -> u64 {
+        self.cache_evictions.with_label_values(&[cache]).get()
+    }
 }
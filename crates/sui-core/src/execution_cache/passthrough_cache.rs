@@ -34,8 +34,8 @@ use typed_store::Map;
 
 use super::{
     implement_passthrough_traits, CheckpointCache, ExecutionCacheCommit, ExecutionCacheMetrics,
-    ExecutionCacheReconfigAPI, ExecutionCacheWrite, ObjectCacheRead, StateSyncAPI, TestingAPI,
-    TransactionCacheRead,
+    ExecutionCacheReconfigAPI, ExecutionCacheStats, ExecutionCacheStatsAPI, ExecutionCacheWrite,
+    ObjectCacheRead, StateSyncAPI, TestingAPI, TransactionCacheRead,
 };
 
 pub struct PassthroughCache {
@@ -346,4 +346,19 @@ impl ExecutionCacheCommit for PassthroughCache {
     }
 }
 
+impl ExecutionCacheStatsAPI for PassthroughCache {
+    fn cache_stats(&self) -> ExecutionCacheStats {
+        // All reads go directly to the store, there is nothing to report.
+        ExecutionCacheStats::default()
+    }
+
+    fn hot_object_ids(&self) -> Vec<ObjectID> {
+        vec![]
+    }
+
+    fn warm_up_objects(&self, _object_ids: &[ObjectID]) -> SuiResult<usize> {
+        Ok(0)
+    }
+}
+
 implement_passthrough_traits!(PassthroughCache);
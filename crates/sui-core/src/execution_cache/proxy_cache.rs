@@ -30,8 +30,9 @@ use sui_types::transaction::{VerifiedSignedTransaction, VerifiedTransaction};
 
 use super::{
     CheckpointCache, ExecutionCacheCommit, ExecutionCacheConfigType, ExecutionCacheMetrics,
-    ExecutionCacheReconfigAPI, ExecutionCacheWrite, ObjectCacheRead, PassthroughCache,
-    StateSyncAPI, TestingAPI, TransactionCacheRead, WritebackCache,
+    ExecutionCacheReconfigAPI, ExecutionCacheStats, ExecutionCacheStatsAPI, ExecutionCacheWrite,
+    ObjectCacheRead, PassthroughCache, StateSyncAPI, TestingAPI, TransactionCacheRead,
+    WritebackCache,
 };
 
 macro_rules! delegate_method {
@@ -419,6 +420,20 @@ impl StateSyncAPI for ProxyCache {
     }
 }
 
+impl ExecutionCacheStatsAPI for ProxyCache {
+    fn cache_stats(&self) -> ExecutionCacheStats {
+        delegate_method!(self.cache_stats())
+    }
+
+    fn hot_object_ids(&self) -> Vec<ObjectID> {
+        delegate_method!(self.hot_object_ids())
+    }
+
+    fn warm_up_objects(&self, object_ids: &[ObjectID]) -> SuiResult<usize> {
+        delegate_method!(self.warm_up_objects(object_ids))
+    }
+}
+
 impl TestingAPI for ProxyCache {
     fn database_for_testing(&self) -> Arc<AuthorityStore> {
         delegate_method!(self.database_for_testing())
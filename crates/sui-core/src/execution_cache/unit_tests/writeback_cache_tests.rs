@@ -978,6 +978,47 @@ async fn test_invalidate_package_cache_on_revert() {
     .await;
 }
 
+#[tokio::test]
+async fn test_cache_stats_dirty_entries() {
+    telemetry_subscribers::init_for_testing();
+    Scenario::iterate(|mut s| async move {
+        s.with_created(&[1, 2]);
+        let tx = s.do_tx().await;
+
+        assert_eq!(s.cache.cache_stats().objects.dirty_entries, 2);
+        s.commit(tx).await.unwrap();
+        assert_eq!(s.cache.cache_stats().objects.dirty_entries, 0);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_warm_up_hot_objects() {
+    telemetry_subscribers::init_for_testing();
+    let mut s = Scenario::new(None, Arc::new(AtomicU32::new(0))).await;
+    s.with_created(&[1, 2]);
+    s.with_packages(&[3]);
+    let tx = s.do_tx().await;
+    s.commit(tx).await.unwrap();
+
+    let hot_objects = s.cache.hot_object_ids();
+    for short_id in [1, 2, 3] {
+        assert!(hot_objects.contains(&s.obj_id(short_id)));
+    }
+
+    s.reset_cache();
+    assert!(s.cache.hot_object_ids().is_empty());
+
+    let ids = [s.obj_id(1), s.obj_id(2), s.obj_id(3), ObjectID::random()];
+    assert_eq!(s.cache.warm_up_objects(&ids).unwrap(), 3);
+
+    let hot_objects = s.cache.hot_object_ids();
+    for short_id in [1, 2, 3] {
+        assert!(hot_objects.contains(&s.obj_id(short_id)));
+    }
+    assert!(s.cache.packages.contains_key(&s.obj_id(3)));
+}
+
 #[sim_test]
 async fn test_concurrent_readers() {
     telemetry_subscribers::init_for_testing();
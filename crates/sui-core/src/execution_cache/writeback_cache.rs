@@ -79,8 +79,9 @@ use tracing::{debug, info, instrument, trace, warn};
 use super::ExecutionCacheAPI;
 use super::{
     cache_types::CachedVersionMap, implement_passthrough_traits, object_locks::ObjectLocks,
-    CheckpointCache, ExecutionCacheCommit, ExecutionCacheMetrics, ExecutionCacheReconfigAPI,
-    ExecutionCacheWrite, ObjectCacheRead, StateSyncAPI, TestingAPI, TransactionCacheRead,
+    CacheStats, CheckpointCache, ExecutionCacheCommit, ExecutionCacheMetrics,
+    ExecutionCacheReconfigAPI, ExecutionCacheStats, ExecutionCacheStatsAPI, ExecutionCacheWrite,
+    ObjectCacheRead, StateSyncAPI, TestingAPI, TransactionCacheRead,
 };
 
 #[cfg(test)]
@@ -290,10 +291,11 @@ struct CachedCommittedData {
 }
 
 impl CachedCommittedData {
-    fn new() -> Self {
+    fn new(metrics: &Arc<ExecutionCacheMetrics>) -> Self {
         let object_cache = MokaCache::builder()
             .max_capacity(MAX_CACHE_SIZE)
             .max_capacity(MAX_CACHE_SIZE)
+            .eviction_listener(eviction_recorder(metrics, "objects"))
             .build();
         let object_by_id_cache = MokaCache::builder()
             .max_capacity(MAX_CACHE_SIZE)
@@ -302,6 +304,7 @@ impl CachedCommittedData {
         let marker_cache = MokaCache::builder()
             .max_capacity(MAX_CACHE_SIZE)
             .max_capacity(MAX_CACHE_SIZE)
+            .eviction_listener(eviction_recorder(metrics, "markers"))
             .build();
         let transactions = MokaCache::builder()
             .max_capacity(MAX_CACHE_SIZE)
@@ -357,6 +360,20 @@ impl CachedCommittedData {
     }
 }
 
+// Returns a moka eviction listener that counts capacity evictions from `cache`. Explicit
+// invalidations (e.g. on revert or at the end of the epoch) are not counted.
+fn eviction_recorder<K, V>(
+    metrics: &Arc<ExecutionCacheMetrics>,
+    cache: &'static str,
+) -> impl Fn(Arc<K>, V, moka::notification::RemovalCause) + Send + Sync + 'static {
+    let metrics = metrics.clone();
+    move |_, _, cause| {
+        if cause.was_evicted() {
+            metrics.record_cache_eviction(cache);
+        }
+    }
+}
+
 fn assert_empty<K, V>(cache: &MokaCache<K, V>)
 where
     K: std::hash::Hash + std::cmp::Eq + std::cmp::PartialEq + Send + Sync + 'static,
@@ -432,10 +449,11 @@ impl WritebackCache {
         let packages = MokaCache::builder()
             .max_capacity(MAX_CACHE_SIZE)
             .max_capacity(MAX_CACHE_SIZE)
+            .eviction_listener(eviction_recorder(&metrics, "packages"))
             .build();
         Self {
             dirty: UncommittedData::new(),
-            cached: CachedCommittedData::new(),
+            cached: CachedCommittedData::new(&metrics),
             packages,
             object_locks: ObjectLocks::new(),
             executed_effects_digests_notify_read: NotifyRead::new(),
@@ -528,7 +546,7 @@ impl WritebackCache {
         object_id: &ObjectID,
         version: SequenceNumber,
     ) -> CacheResult<ObjectEntry> {
-        Self::with_locked_cache_entries(
+        let result = Self::with_locked_cache_entries(
             &self.dirty.objects,
             &self.cached.object_cache,
             object_id,
@@ -549,7 +567,9 @@ impl WritebackCache {
                 );
                 CacheResult::Miss
             },
-        )
+        );
+        self.record_lookup("objects", &result);
+        result
     }
 
     fn get_object_by_key_cache_only(
@@ -571,6 +591,16 @@ impl WritebackCache {
         &self,
         request_type: &'static str,
         object_id: &ObjectID,
+    ) -> CacheResult<(SequenceNumber, ObjectEntry)> {
+        let result = self.get_object_entry_by_id_cache_only_impl(request_type, object_id);
+        self.record_lookup("objects", &result);
+        result
+    }
+
+    fn get_object_entry_by_id_cache_only_impl(
+        &self,
+        request_type: &'static str,
+        object_id: &ObjectID,
     ) -> CacheResult<(SequenceNumber, ObjectEntry)> {
         self.metrics
             .record_cache_request(request_type, "object_by_id");
@@ -668,7 +698,7 @@ impl WritebackCache {
         version: SequenceNumber,
         epoch_id: EpochId,
     ) -> CacheResult<MarkerValue> {
-        Self::with_locked_cache_entries(
+        let result = Self::with_locked_cache_entries(
             &self.dirty.markers,
             &self.cached.marker_cache,
             &(epoch_id, *object_id),
@@ -689,7 +719,9 @@ impl WritebackCache {
                 );
                 CacheResult::Miss
             },
-        )
+        );
+        self.record_lookup("markers", &result);
+        result
     }
 
     fn get_latest_marker_value_cache_only(
@@ -697,7 +729,7 @@ impl WritebackCache {
         object_id: &ObjectID,
         epoch_id: EpochId,
     ) -> CacheResult<(SequenceNumber, MarkerValue)> {
-        Self::with_locked_cache_entries(
+        let result = Self::with_locked_cache_entries(
             &self.dirty.markers,
             &self.cached.marker_cache,
             &(epoch_id, *object_id),
@@ -706,7 +738,14 @@ impl WritebackCache {
                 check_cache_entry_by_latest!(self, "marker_latest", "committed", cached_entry);
                 CacheResult::Miss
             },
-        )
+        );
+        self.record_lookup("markers", &result);
+        result
+    }
+
+    fn record_lookup<T>(&self, cache: &'static str, result: &CacheResult<T>) {
+        self.metrics
+            .record_cache_lookup(cache, !matches!(result, CacheResult::Miss));
     }
 
     fn get_object_impl(
@@ -1184,6 +1223,67 @@ impl WritebackCache {
 
 impl ExecutionCacheAPI for WritebackCache {}
 
+impl ExecutionCacheStatsAPI for WritebackCache {
+    fn cache_stats(&self) -> ExecutionCacheStats {
+        let stats = |cache: &'static str, dirty_entries: usize, cached_entries: u64| {
+            let (hits, misses) = self.metrics.cache_lookup_counts(cache);
+            CacheStats {
+                hits,
+                misses,
+                dirty_entries: dirty_entries as u64,
+                cached_entries,
+                evictions: self.metrics.cache_eviction_count(cache),
+            }
+        };
+        ExecutionCacheStats {
+            objects: stats(
+                "objects",
+                self.dirty.objects.len(),
+                self.cached.object_cache.entry_count(),
+            ),
+            // Packages are never held in the dirty set separately from objects.
+            packages: stats("packages", 0, self.packages.entry_count()),
+            markers: stats(
+                "markers",
+                self.dirty.markers.len(),
+                self.cached.marker_cache.entry_count(),
+            ),
+        }
+    }
+
+    fn hot_object_ids(&self) -> Vec<ObjectID> {
+        let objects = self
+            .cached
+            .object_by_id_cache
+            .iter()
+            .filter(|(_, entry)| matches!(&*entry.lock(), LatestObjectCacheEntry::Object(..)))
+            .map(|(id, _)| *id);
+        let packages = self.packages.iter().map(|(id, _)| *id);
+        objects
+            .chain(packages)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    fn warm_up_objects(&self, object_ids: &[ObjectID]) -> SuiResult<usize> {
+        let mut loaded = 0;
+        // Reading through the cache populates object_by_id_cache (and the package cache)
+        // exactly as a regular read would, so no coherence invariants can be violated.
+        for object_id in object_ids.iter().take(MAX_CACHE_SIZE as usize) {
+            let Some(object) = self.get_object_impl("warmup", object_id)? else {
+                continue;
+            };
+            if object.is_package() {
+                self.get_package_object(object_id)?;
+            }
+            loaded += 1;
+        }
+        self.metrics.cache_warmup_objects.inc_by(loaded as u64);
+        Ok(loaded)
+    }
+}
+
 impl ExecutionCacheCommit for WritebackCache {
     fn commit_transaction_outputs<'a>(
         &'a self,
@@ -1217,9 +1317,11 @@ impl ObjectCacheRead for WritebackCache {
                 }
             }
             self.metrics.record_cache_hit("package", "package_cache");
+            self.metrics.record_cache_lookup("packages", true);
             return Ok(Some(p));
         } else {
             self.metrics.record_cache_miss("package", "package_cache");
+            self.metrics.record_cache_lookup("packages", false);
         }
 
         // We try the dirty objects cache as well before going to the database. This is necessary
@@ -4,7 +4,7 @@
 use std::{sync::Arc, time::Duration};
 
 use arc_swap::{ArcSwapOption, Guard};
-use consensus_core::{ClientError, TransactionClient};
+use consensus_core::{ClientError, TransactionClient, TransactionLane};
 use sui_types::{
     error::{SuiError, SuiResult},
     messages_consensus::{ConsensusTransaction, ConsensusTransactionKind},
@@ -73,6 +73,21 @@ impl LazyMysticetiClient {
     }
 }
 
+/// Checkpoint signatures, end of publish, capabilities, JWKs and randomness messages are needed
+/// for the network to make progress, so they are not queued behind user traffic. A batch is
+/// submitted as a whole, so one that mixes both kinds goes on the user lane: it must not be able
+/// to use the system lane's quota to get user transactions ahead of the queue.
+fn transaction_lane(transactions: &[ConsensusTransaction]) -> TransactionLane {
+    if transactions
+        .iter()
+        .any(|t| t.is_certified_transaction() || t.is_user_transaction())
+    {
+        TransactionLane::User
+    } else {
+        TransactionLane::System
+    }
+}
+
 #[async_trait::async_trait]
 impl SubmitToConsensus for LazyMysticetiClient {
     async fn submit_to_consensus(
@@ -88,10 +103,11 @@ impl SubmitToConsensus for LazyMysticetiClient {
             .iter()
             .map(|t| bcs::to_bytes(t).expect("Serializing consensus transaction cannot fail"))
             .collect::<Vec<_>>();
+        let lane = transaction_lane(transactions);
         let block_ref = client
             .as_ref()
             .expect("Client should always be returned")
-            .submit(transactions_bytes)
+            .submit(lane, transactions_bytes)
             .await
             .tap_err(|err| {
                 // Will be logged by caller as well.
@@ -129,3 +145,36 @@ impl SubmitToConsensus for LazyMysticetiClient {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sui_types::base_types::{random_object_ref, SuiAddress};
+    use sui_types::crypto::{get_key_pair, AccountKeyPair, AuthorityPublicKeyBytes};
+
+    use super::*;
+    use crate::test_utils::make_transfer_sui_transaction;
+
+    #[test]
+    fn test_transaction_lane() {
+        let authority = AuthorityPublicKeyBytes::ZERO;
+        let (sender, keypair): (_, AccountKeyPair) = get_key_pair();
+        let transaction = make_transfer_sui_transaction(
+            random_object_ref(),
+            SuiAddress::ZERO,
+            None,
+            sender,
+            &keypair,
+            1000,
+        );
+        let user = ConsensusTransaction::new_user_transaction_message(&authority, transaction);
+        let system = ConsensusTransaction::new_end_of_publish(authority);
+
+        assert_eq!(transaction_lane(&[system.clone()]), TransactionLane::System);
+        assert_eq!(transaction_lane(&[user.clone()]), TransactionLane::User);
+        assert_eq!(
+            transaction_lane(&[system.clone(), user.clone()]),
+            TransactionLane::User
+        );
+        assert_eq!(transaction_lane(&[user, system]), TransactionLane::User);
+    }
+}
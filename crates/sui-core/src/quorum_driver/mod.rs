@@ -7,6 +7,7 @@ pub use metrics::*;
 pub mod reconfig_observer;
 
 use arc_swap::ArcSwap;
+use dashmap::DashMap;
 use std::collections::{BTreeMap, BTreeSet};
 use std::fmt::{Debug, Formatter};
 use std::net::SocketAddr;
@@ -72,6 +73,15 @@ impl Debug for QuorumDriverTask {
     }
 }
 
+/// Progress of a transaction between its submission and the notification of its result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InFlightTransaction {
+    /// Whether a certificate has been formed for the transaction.
+    pub certified: bool,
+    /// Number of times the transaction has been enqueued, including retries.
+    pub attempts: u32,
+}
+
 pub struct QuorumDriver<A: Clone> {
     validators: ArcSwap<AuthorityAggregator<A>>,
     task_sender: Sender<QuorumDriverTask>,
@@ -79,6 +89,7 @@ pub struct QuorumDriver<A: Clone> {
     notifier: Arc<NotifyRead<TransactionDigest, QuorumDriverResult>>,
     metrics: Arc<QuorumDriverMetrics>,
     max_retry_times: u32,
+    in_flight_transactions: DashMap<TransactionDigest, InFlightTransaction>,
 }
 
 impl<A: Clone> QuorumDriver<A> {
@@ -97,6 +108,7 @@ impl<A: Clone> QuorumDriver<A> {
             notifier,
             metrics,
             max_retry_times,
+            in_flight_transactions: DashMap::new(),
         }
     }
 
@@ -112,11 +124,41 @@ impl<A: Clone> QuorumDriver<A> {
         self.validators.load().committee.epoch
     }
 
+    /// Returns the progress of a transaction that has been submitted but whose result has not
+    /// been notified yet.
+    pub fn get_in_flight_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Option<InFlightTransaction> {
+        self.in_flight_transactions
+            .get(tx_digest)
+            .map(|entry| *entry.value())
+    }
+
+    fn mark_certified(&self, tx_digest: &TransactionDigest) {
+        if let Some(mut entry) = self.in_flight_transactions.get_mut(tx_digest) {
+            entry.certified = true;
+        }
+    }
+
     async fn enqueue_task(&self, task: QuorumDriverTask) -> SuiResult<()> {
+        // Track the transaction before it is handed over, because the task can be processed and
+        // its result notified (which stops tracking it) before `send` returns.
+        let tx_digest = *task.request.transaction.digest();
+        self.in_flight_transactions.insert(
+            tx_digest,
+            InFlightTransaction {
+                certified: task.tx_cert.is_some(),
+                attempts: task.retry_times + 1,
+            },
+        );
         self.task_sender
             .send(task.clone())
             .await
-            .tap_err(|e| debug!(?task, "Failed to enqueue task: {:?}", e))
+            .tap_err(|e| {
+                debug!(?task, "Failed to enqueue task: {:?}", e);
+                self.in_flight_transactions.remove(&tx_digest);
+            })
             .tap_ok(|_| {
                 debug!(?task, "Enqueued task.");
                 self.metrics.current_requests_in_flight.inc();
@@ -230,6 +272,7 @@ impl<A: Clone> QuorumDriver<A> {
             warn!(?tx_digest, "No subscriber found for effects: {}", err);
         }
         debug!(?tx_digest, "notify QuorumDriver task result");
+        self.in_flight_transactions.remove(tx_digest);
         self.notifier.notify(tx_digest, response);
     }
 }
@@ -703,6 +746,7 @@ where
             notifier: Arc::new(NotifyRead::new()),
             metrics: self.quorum_driver_metrics.clone(),
             max_retry_times: self.quorum_driver.max_retry_times,
+            in_flight_transactions: DashMap::new(),
         });
         let metrics = self.quorum_driver_metrics.clone();
         let processor_handle = {
@@ -751,6 +795,13 @@ where
         self.quorum_driver.current_epoch()
     }
 
+    pub fn get_in_flight_transaction(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> Option<InFlightTransaction> {
+        self.quorum_driver.get_in_flight_transaction(tx_digest)
+    }
+
     /// Process a QuorumDriverTask.
     /// The function has no return value - the corresponding actions of task result
     /// are performed in this call.
@@ -779,6 +830,7 @@ where
                     newly_formed,
                 }) => {
                     debug!(?tx_digest, "Transaction processing succeeded");
+                    quorum_driver.mark_certified(&tx_digest);
                     (certificate, newly_formed)
                 }
                 Ok(ProcessTransactionResult::Executed(effects_cert, events)) => {
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::quorum_driver::reconfig_observer::DummyReconfigObserver;
-use crate::quorum_driver::{AuthorityAggregator, QuorumDriverHandlerBuilder};
+use crate::quorum_driver::{AuthorityAggregator, InFlightTransaction, QuorumDriverHandlerBuilder};
 use crate::test_authority_clients::LocalAuthorityClient;
 use crate::test_authority_clients::LocalAuthorityClientFaultConfig;
 use crate::test_utils::make_transfer_sui_transaction;
@@ -65,9 +65,22 @@ async fn test_quorum_driver_submit_transaction() {
     let (aggregator, tx) = setup().await;
     let digest = *tx.digest();
 
+    // Hold the transaction in the quorum driver by having every validator ask for a retry, so
+    // that its progress can be observed before it is executed.
+    let mut overloaded_aggregator = aggregator.clone();
+    let mut clients = overloaded_aggregator.clone_inner_clients_test_only();
+    for client in clients.values_mut() {
+        client.authority_client_mut().fault_config = LocalAuthorityClientFaultConfig {
+            overload_retry_after_handle_transaction: Some(Duration::from_secs(5)),
+            ..Default::default()
+        };
+    }
+    let clients = clients.into_iter().map(|(k, v)| (k, Arc::new(v))).collect();
+    overloaded_aggregator.authority_clients = Arc::new(clients);
+
     let quorum_driver_handler = Arc::new(
         QuorumDriverHandlerBuilder::new(
-            Arc::new(aggregator),
+            Arc::new(overloaded_aggregator),
             Arc::new(QuorumDriverMetrics::new_for_tests()),
         )
         .with_reconfig_observer(Arc::new(DummyReconfigObserver {}))
@@ -89,7 +102,24 @@ async fn test_quorum_driver_submit_transaction() {
         .submit_transaction(ExecuteTransactionRequestV3::new_v2(tx))
         .await
         .unwrap();
+    assert_eq!(
+        quorum_driver_handler.get_in_flight_transaction(&digest),
+        Some(InFlightTransaction {
+            certified: false,
+            attempts: 1
+        })
+    );
+
+    // Let the retry go through.
+    quorum_driver_handler
+        .clone_quorum_driver()
+        .update_validators(Arc::new(aggregator))
+        .await;
     verify_ticket_response(ticket, &digest).await;
+    assert_eq!(
+        quorum_driver_handler.get_in_flight_transaction(&digest),
+        None
+    );
 
     handle.await.unwrap();
 }
@@ -1,11 +1,14 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::Arc,
+};
 
 use arc_swap::ArcSwap;
 use consensus_config::Committee as ConsensusCommittee;
 use sui_types::{base_types::AuthorityName, committee::Committee};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::{authority::AuthorityMetrics, consensus_types::AuthorityIndex};
 
@@ -16,11 +19,13 @@ use crate::{authority::AuthorityMetrics, consensus_types::AuthorityIndex};
 /// submission side with the consensus leader election schedule. Practically we don't want to submit
 /// transactions for sequencing to validators that have low scores and are not part of the leader
 /// schedule since the chances of getting them sequenced are lower.
+/// Authorities in `misbehaving_authorities` are ranked below all others regardless of their score.
 pub(crate) fn update_low_scoring_authorities(
     low_scoring_authorities: Arc<ArcSwap<HashMap<AuthorityName, u64>>>,
     sui_committee: &Committee,
     consensus_committee: &ConsensusCommittee,
     reputation_score_sorted_desc: Option<Vec<(AuthorityIndex, u64)>>,
+    misbehaving_authorities: &BTreeSet<AuthorityIndex>,
     metrics: &Arc<AuthorityMetrics>,
     consensus_bad_nodes_stake_threshold: u64,
 ) {
@@ -32,10 +37,13 @@ pub(crate) fn update_low_scoring_authorities(
 
     // We order the authorities by score ascending order in the exact same way as the reputation
     // scores do - so we keep complete alignment between implementations
-    let scores_per_authority_order_asc: Vec<_> = reputation_scores
+    let mut scores_per_authority_order_asc: Vec<_> = reputation_scores
         .into_iter()
         .rev() // we reverse so we get them in asc order
         .collect();
+    // Stable sort, so the score order is kept among misbehaving and among other authorities.
+    scores_per_authority_order_asc
+        .sort_by_key(|(index, _)| !misbehaving_authorities.contains(index));
 
     let mut final_low_scoring_map = HashMap::new();
     let mut total_stake = 0;
@@ -77,17 +85,82 @@ pub(crate) fn update_low_scoring_authorities(
     low_scoring_authorities.swap(Arc::new(final_low_scoring_map));
 }
 
+/// Tallies the misbehavior reports sequenced by consensus. An authority is deemed misbehaving once
+/// the authorities reporting it reach the validity threshold, so at least one honest authority has
+/// observed the misbehavior. The tally covers the commits processed since the handler started.
+#[derive(Default)]
+pub(crate) struct MisbehaviorTally {
+    reporters: HashMap<AuthorityIndex, HashSet<AuthorityIndex>>,
+    misbehaving_authorities: BTreeSet<AuthorityIndex>,
+}
+
+impl MisbehaviorTally {
+    /// Adds misbehavior reports, as tuples of (reporting authority index, reported authority index).
+    pub(crate) fn add_reports(
+        &mut self,
+        consensus_committee: &ConsensusCommittee,
+        reports: Vec<(AuthorityIndex, AuthorityIndex)>,
+        metrics: &Arc<AuthorityMetrics>,
+    ) {
+        for (reporter, target) in reports {
+            if self.misbehaving_authorities.contains(&target) {
+                continue;
+            }
+            let reporters = self.reporters.entry(target).or_default();
+            if !reporters.insert(reporter) {
+                continue;
+            }
+            let stake = reporters
+                .iter()
+                .map(|index| {
+                    let authority_index = consensus_committee
+                        .to_authority_index(*index as usize)
+                        .unwrap();
+                    consensus_committee.stake(authority_index)
+                })
+                .sum();
+            if !consensus_committee.reached_validity(stake) {
+                continue;
+            }
+
+            self.misbehaving_authorities.insert(target);
+            let hostname = &consensus_committee
+                .authority(
+                    consensus_committee
+                        .to_authority_index(target as usize)
+                        .unwrap(),
+                )
+                .hostname;
+            warn!("Authority {hostname} has been reported as misbehaving in consensus");
+            metrics
+                .consensus_handler_misbehaving_authorities
+                .with_label_values(&[hostname])
+                .set(1);
+        }
+    }
+
+    pub(crate) fn misbehaving_authorities(&self) -> &BTreeSet<AuthorityIndex> {
+        &self.misbehaving_authorities
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::mutable_key_type)]
-    use std::{collections::HashMap, sync::Arc};
+    use std::{
+        collections::{BTreeSet, HashMap},
+        sync::Arc,
+    };
 
     use arc_swap::ArcSwap;
     use consensus_config::{local_committee_and_keys, Committee as ConsensusCommittee};
     use prometheus::Registry;
     use sui_types::{committee::Committee, crypto::AuthorityPublicKeyBytes};
 
-    use crate::{authority::AuthorityMetrics, scoring_decision::update_low_scoring_authorities};
+    use crate::{
+        authority::AuthorityMetrics,
+        scoring_decision::{update_low_scoring_authorities, MisbehaviorTally},
+    };
 
     #[test]
     #[cfg_attr(msim, ignore)]
@@ -119,6 +192,7 @@ mod tests {
             &sui_committee,
             &consensus_committee,
             Some(authorities_by_score_desc.clone()),
+            &BTreeSet::new(),
             &metrics,
             consensus_bad_nodes_stake_threshold,
         );
@@ -149,6 +223,7 @@ mod tests {
             &sui_committee,
             &consensus_committee,
             Some(authorities_by_score_desc.clone()),
+            &BTreeSet::new(),
             &metrics,
             consensus_bad_nodes_stake_threshold,
         );
@@ -164,6 +239,66 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg_attr(msim, ignore)]
+    pub fn test_misbehaving_authorities_are_low_scoring() {
+        // GIVEN
+        // Total stake is 8 for this committee and every authority has equal stake = 1
+        let (sui_committee, consensus_committee) = generate_committees(8);
+
+        let low_scoring = Arc::new(ArcSwap::from_pointee(HashMap::new()));
+        let metrics = Arc::new(AuthorityMetrics::new(&Registry::new()));
+        let mut tally = MisbehaviorTally::default();
+
+        // Validity threshold is 3, so only authority 6 is reported by enough stake.
+        tally.add_reports(
+            &consensus_committee,
+            vec![(0, 6), (1, 6), (0, 6), (1, 5), (2, 5), (3, 6)],
+            &metrics,
+        );
+        assert_eq!(*tally.misbehaving_authorities(), BTreeSet::from([6]));
+
+        let authorities_by_score_desc = vec![
+            (1, 390_u64),
+            (0, 350_u64),
+            (6, 340_u64),
+            (7, 310_u64),
+            (5, 300_u64),
+            (3, 50_u64),
+            (2, 50_u64),
+            (4, 0_u64), // down node
+        ];
+
+        // WHEN
+        let consensus_bad_nodes_stake_threshold = 33; // 33 * 8 / 100 = 2 low scoring validator
+        update_low_scoring_authorities(
+            low_scoring.clone(),
+            &sui_committee,
+            &consensus_committee,
+            Some(authorities_by_score_desc),
+            tally.misbehaving_authorities(),
+            &metrics,
+            consensus_bad_nodes_stake_threshold,
+        );
+
+        // THEN the misbehaving authority is flagged before the lowest scoring one
+        assert_eq!(low_scoring.load().len(), 2);
+        assert_eq!(
+            *low_scoring
+                .load()
+                .get(sui_committee.authority_by_index(6).unwrap())
+                .unwrap(),
+            340
+        );
+        assert_eq!(
+            *low_scoring
+                .load()
+                .get(sui_committee.authority_by_index(4).unwrap())
+                .unwrap(),
+            0
+        );
+    }
+
     /// Generate a pair of Sui and consensus committees for the given size.
     fn generate_committees(committee_size: usize) -> (Committee, ConsensusCommittee) {
         let (consensus_committee, _) = local_committee_and_keys(0, vec![1; committee_size]);
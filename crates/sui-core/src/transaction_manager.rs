@@ -775,6 +775,13 @@ impl TransactionManager {
             .map(|cert| cert.waiting_input_objects.clone().into_iter().collect())
     }
 
+    /// Returns true if all inputs of the given transaction are available and it is being executed.
+    pub(crate) fn is_executing(&self, digest: &TransactionDigest) -> bool {
+        let reconfig_lock = self.inner.read();
+        let inner = reconfig_lock.read();
+        inner.executing_certificates.contains(digest)
+    }
+
     // Returns the number of transactions waiting on each object ID, as well as the age of the oldest transaction in the queue.
     pub(crate) fn objects_queue_len_and_age(
         &self,
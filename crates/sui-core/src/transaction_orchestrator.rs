@@ -31,14 +31,16 @@ use std::sync::Arc;
 use std::time::Duration;
 use sui_storage::write_path_pending_tx_log::WritePathPendingTransactionLog;
 use sui_types::base_types::TransactionDigest;
+use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::quorum_driver_types::{
     ExecuteTransactionRequestType, ExecuteTransactionRequestV3, ExecuteTransactionResponseV3,
     FinalizedEffects, IsTransactionExecutedLocally, QuorumDriverEffectsQueueResult,
-    QuorumDriverError, QuorumDriverResponse, QuorumDriverResult,
+    QuorumDriverError, QuorumDriverResponse, QuorumDriverResult, TransactionLifecycleStatus,
 };
 use sui_types::sui_system_state::SuiSystemState;
-use sui_types::transaction::VerifiedTransaction;
+use sui_types::transaction::{TransactionData, VerifiedTransaction};
 use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::broadcast::Receiver;
 use tokio::task::JoinHandle;
@@ -531,6 +533,64 @@ where
     pub fn load_all_pending_transactions(&self) -> Vec<VerifiedTransaction> {
         self.pending_tx_log.load_all_pending_transactions()
     }
+
+    /// Returns the furthest lifecycle stage of `tx_digest` known to this node, checking local
+    /// execution state first and falling back to the quorum driver and the pending transaction
+    /// log. Returns `None` if the node has no record of the transaction, e.g. because it was
+    /// never submitted here and has not been executed locally.
+    pub fn get_transaction_status(
+        &self,
+        tx_digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionLifecycleStatus>> {
+        let state = &self.validator_state;
+        let effects_digest = state
+            .get_transaction_cache_reader()
+            .multi_get_executed_effects_digests(&[*tx_digest])?
+            .pop()
+            .expect("multi-get must return correct number of items");
+        if let Some(effects_digest) = effects_digest {
+            let checkpoint = state
+                .get_checkpoint_cache()
+                .deprecated_get_transaction_checkpoint(tx_digest)?;
+            return Ok(Some(match checkpoint {
+                Some((epoch, checkpoint)) => TransactionLifecycleStatus::Finalized {
+                    effects_digest,
+                    epoch,
+                    checkpoint,
+                },
+                None => TransactionLifecycleStatus::Executed { effects_digest },
+            }));
+        }
+
+        let transaction_manager = state.transaction_manager();
+        if let Some(missing_inputs) = transaction_manager.get_missing_input(tx_digest) {
+            return Ok(Some(TransactionLifecycleStatus::WaitingOnInputs {
+                missing_inputs,
+            }));
+        }
+        if transaction_manager.is_executing(tx_digest) {
+            return Ok(Some(TransactionLifecycleStatus::Executing));
+        }
+
+        if let Some(in_flight) = self
+            .quorum_driver_handler
+            .get_in_flight_transaction(tx_digest)
+        {
+            let attempts = in_flight.attempts;
+            return Ok(Some(if in_flight.certified {
+                TransactionLifecycleStatus::Certified { attempts }
+            } else {
+                TransactionLifecycleStatus::Submitted { attempts }
+            }));
+        }
+
+        // The transaction may be between retries or waiting to be re-enqueued after a restart.
+        if self.pending_tx_log.is_pending(tx_digest)? {
+            return Ok(Some(TransactionLifecycleStatus::Submitted { attempts: 0 }));
+        }
+
+        Ok(None)
+    }
 }
 
 /// Prometheus metrics which can be displayed in Grafana, queried and alerted on
@@ -710,4 +770,20 @@ where
     ) -> Result<ExecuteTransactionResponseV3, QuorumDriverError> {
         self.execute_transaction_v3(request, client_addr).await
     }
+
+    fn transaction_status(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionLifecycleStatus>> {
+        self.get_transaction_status(digest)
+    }
+
+    fn simulate_transaction_at_checkpoint(
+        &self,
+        transaction: TransactionData,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<(TransactionEffects, TransactionEvents)> {
+        self.validator_state
+            .simulate_transaction_at_checkpoint(transaction, checkpoint)
+    }
 }
@@ -31,6 +31,7 @@ use sui_json_rpc_types::{
 };
 use sui_macros::sim_test;
 use sui_protocol_config::{Chain, PerObjectCongestionControlMode, ProtocolConfig, ProtocolVersion};
+use sui_swarm_config::test_utils::CommitteeFixture;
 use sui_types::digests::Digest;
 use sui_types::dynamic_field::DynamicFieldType;
 use sui_types::effects::TransactionEffects;
@@ -59,6 +60,7 @@ use sui_types::{
     MOVE_STDLIB_PACKAGE_ID, SUI_AUTHENTICATOR_STATE_OBJECT_ID, SUI_CLOCK_OBJECT_ID,
     SUI_FRAMEWORK_PACKAGE_ID, SUI_RANDOMNESS_STATE_OBJECT_ID, SUI_SYSTEM_STATE_OBJECT_ID,
 };
+use typed_store::Map;
 
 use crate::authority::authority_store_tables::AuthorityPerpetualTables;
 use crate::authority::move_integration_tests::build_and_publish_test_package_with_upgrade_cap;
@@ -269,6 +271,144 @@ async fn test_dry_run_transaction_block() {
     assert_eq!(gas_usage, gas_usage_no_gas);
 }
 
+#[tokio::test]
+async fn test_dry_run_transaction_block_at_checkpoint() {
+    let (_validator, fullnode, transaction, _, shared_object_id) =
+        construct_shared_object_transaction_with_sequence_number(None).await;
+    let txn_data = transaction.data().intent_message().value.clone();
+    let initial_shared_version = fullnode
+        .get_object(&shared_object_id)
+        .await
+        .unwrap()
+        .unwrap()
+        .version();
+
+    // Pretend that everything that wrote the transaction's inputs was included in checkpoint 0.
+    let checkpoint = CommitteeFixture::generate(rand::rngs::OsRng, 0, 4)
+        .make_empty_checkpoints(1, None)
+        .0
+        .remove(0);
+    fullnode
+        .get_checkpoint_store()
+        .insert_verified_checkpoint(&checkpoint)
+        .unwrap();
+    let mut written_by = vec![];
+    let input_ids = txn_data
+        .input_objects()
+        .unwrap()
+        .into_iter()
+        .map(|kind| kind.object_id());
+    for id in input_ids.chain([MOVE_STDLIB_PACKAGE_ID, SUI_FRAMEWORK_PACKAGE_ID]) {
+        let object = fullnode.get_object(&id).await.unwrap().unwrap();
+        written_by.push(object.previous_transaction);
+    }
+    fullnode
+        .get_checkpoint_cache()
+        .deprecated_insert_finalized_transactions(&written_by, 0, 0)
+        .unwrap();
+
+    let (response, _, _, _) = fullnode
+        .dry_exec_transaction_at_checkpoint(txn_data, *transaction.digest(), 0)
+        .await
+        .unwrap();
+    assert_eq!(*response.effects.status(), SuiExecutionStatus::Success);
+    assert!(response
+        .effects
+        .modified_at_versions()
+        .contains(&(shared_object_id, initial_shared_version)));
+}
+
+#[tokio::test]
+async fn test_dry_run_transaction_block_at_unknown_checkpoint() {
+    let (_validator, fullnode, transaction, _, _) =
+        construct_shared_object_transaction_with_sequence_number(None).await;
+
+    let err = fullnode
+        .dry_exec_transaction_at_checkpoint(
+            transaction.data().intent_message().value.clone(),
+            *transaction.digest(),
+            1000,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err,
+        SuiError::UserInputError {
+            error: UserInputError::VerifiedCheckpointNotFound(1000)
+        }
+    );
+}
+
+#[tokio::test]
+async fn test_historical_object_store() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority, package) =
+        init_state_with_ids_and_object_basics(vec![(sender, gas_object_id)]).await;
+
+    let call = |function: &'static str, args: Vec<TestCallArg>| {
+        call_move(
+            &authority,
+            &gas_object_id,
+            &sender,
+            &sender_key,
+            &package.0,
+            "object_basics",
+            function,
+            vec![],
+            args,
+        )
+    };
+    let pure = |value: u64| TestCallArg::Pure(bcs::to_bytes(&value).unwrap());
+    let recipient = || TestCallArg::Pure(bcs::to_bytes(&sender).unwrap());
+
+    // An object created in checkpoint 0...
+    let effects = call("create", vec![pure(0), recipient()]).await.unwrap();
+    let (object_id, checkpointed_version, _) = effects.created()[0].0;
+    authority
+        .get_checkpoint_cache()
+        .deprecated_insert_finalized_transactions(&[*effects.transaction_digest()], 0, 0)
+        .unwrap();
+
+    // ...and modified after it.
+    for value in 1..=3 {
+        call(
+            "set_value",
+            vec![TestCallArg::Object(object_id), pure(value)],
+        )
+        .await
+        .unwrap();
+    }
+
+    // An object created after checkpoint 0.
+    let effects = call("create", vec![pure(0), recipient()]).await.unwrap();
+    let (created_later, _, _) = effects.created()[0].0;
+
+    let store = authority.get_historical_object_store(0);
+    let object = store.get_object_at_checkpoint(&object_id).unwrap().unwrap();
+    assert_eq!(object.version(), checkpointed_version);
+    assert!(store
+        .get_object_at_checkpoint(&created_later)
+        .unwrap()
+        .is_none());
+
+    // Reaching the checkpointed version takes one step per version of the object after it.
+    let walked = authority
+        .get_historical_object_store(0)
+        .with_max_versions_to_walk(4)
+        .get_object_at_checkpoint(&object_id)
+        .unwrap()
+        .unwrap();
+    assert_eq!(walked.version(), checkpointed_version);
+
+    let err = authority
+        .get_historical_object_store(0)
+        .with_max_versions_to_walk(3)
+        .get_object_at_checkpoint(&object_id)
+        .unwrap_err();
+    assert!(matches!(err, SuiError::UnsupportedFeatureError { .. }));
+}
+
 #[tokio::test]
 async fn test_dry_run_no_gas_big_transfer() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -6270,3 +6410,56 @@ async fn test_single_authority_reconfigure() {
     state.reconfigure_for_testing().await;
     assert_eq!(state.epoch_store_for_testing().epoch(), 1);
 }
+
+#[tokio::test]
+async fn test_repair_indexes() {
+    let (sender, _): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let state = init_state_with_ids(vec![(sender, gas_object_id)]).await;
+    let indexes = state.indexes.clone().unwrap();
+    let owner_index = indexes.tables().owner_index();
+
+    // Objects inserted directly into the store are not indexed, so start from a repaired state.
+    state.repair_indexes(false).await.unwrap();
+    let gas_object_info = owner_index
+        .get(&(sender, gas_object_id))
+        .unwrap()
+        .expect("gas object should have been indexed by the repair");
+
+    // Drop the entry of a live object and add one for an object that does not exist.
+    let stale_object = Object::with_id_owner_for_testing(ObjectID::random(), sender);
+    owner_index.remove(&(sender, gas_object_id)).unwrap();
+    owner_index
+        .insert(
+            &(sender, stale_object.id()),
+            &ObjectInfo::new(&stale_object.compute_object_reference(), &stale_object),
+        )
+        .unwrap();
+
+    let summary = state.repair_indexes(true).await.unwrap();
+    assert_eq!(summary.owner_index.missing, 1);
+    assert_eq!(summary.owner_index.extra, 1);
+    assert_eq!(summary.owner_index.mismatched, 0);
+    assert_eq!(summary.owner_index.repaired, 0);
+    assert!(owner_index.get(&(sender, gas_object_id)).unwrap().is_none());
+
+    let summary = state.repair_indexes(false).await.unwrap();
+    assert_eq!(summary.owner_index.repaired, 2);
+    assert_eq!(
+        owner_index.get(&(sender, gas_object_id)).unwrap(),
+        Some(gas_object_info)
+    );
+    assert!(owner_index
+        .get(&(sender, stale_object.id()))
+        .unwrap()
+        .is_none());
+
+    let summary = state.repair_indexes(true).await.unwrap();
+    for table in [
+        summary.owner_index,
+        summary.coin_index,
+        summary.dynamic_field_index,
+    ] {
+        assert_eq!(table.missing + table.mismatched + table.extra, 0);
+    }
+}
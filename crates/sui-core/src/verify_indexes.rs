@@ -1,13 +1,25 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 use anyhow::{anyhow, bail, Result};
-use sui_storage::{indexes::CoinInfo, IndexStore};
-use sui_types::{base_types::ObjectInfo, object::Owner};
+use serde::{de::DeserializeOwned, Serialize};
+use sui_storage::{
+    indexes::{CoinInfo, ObjectIndexChanges},
+    IndexStore,
+};
+use sui_types::{
+    base_types::{ObjectID, ObjectInfo, SuiAddress},
+    dynamic_field::DynamicFieldInfo,
+    error::SuiResult,
+    object::{Object, Owner},
+};
 use tracing::info;
-use typed_store::traits::Map;
+use typed_store::{rocks::DBMap, traits::Map};
 
 use crate::{authority::authority_store_tables::LiveObject, state_accumulator::AccumulatorStore};
 
@@ -88,3 +100,341 @@ pub fn verify_indexes(store: &dyn AccumulatorStore, indexes: Arc<IndexStore>) ->
 
     Ok(())
 }
+
+type OwnerIndexKey = (SuiAddress, ObjectID);
+type CoinIndexKey = (SuiAddress, String, ObjectID);
+type DynamicFieldKey = (ObjectID, ObjectID);
+
+#[derive(Clone, Debug, Default)]
+pub struct TableRepairSummary {
+    /// Entries that should exist according to the live object set but were not found.
+    pub missing: u64,
+    /// Entries whose value differs from the one computed from the live object.
+    pub mismatched: u64,
+    /// Entries without a matching live object.
+    pub extra: u64,
+    /// Entries that were written or deleted. Always 0 in a dry run.
+    pub repaired: u64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct IndexRepairSummary {
+    pub objects_scanned: u64,
+    pub owner_index: TableRepairSummary,
+    pub coin_index: TableRepairSummary,
+    pub dynamic_field_index: TableRepairSummary,
+}
+
+/// The owner, coin and dynamic field index entries that a single object should have.
+#[derive(Default)]
+pub(crate) struct ObjectIndexEntries {
+    owner: Option<(OwnerIndexKey, ObjectInfo)>,
+    coin: Option<(CoinIndexKey, CoinInfo)>,
+    dynamic_field: Option<(DynamicFieldKey, DynamicFieldInfo)>,
+}
+
+impl ObjectIndexEntries {
+    pub(crate) fn new(
+        object: &Object,
+        dynamic_field_info: impl FnOnce(&Object) -> Option<DynamicFieldInfo>,
+    ) -> Self {
+        let mut entries = Self::default();
+        match object.owner {
+            Owner::AddressOwner(owner) => {
+                entries.owner = Some((
+                    (owner, object.id()),
+                    ObjectInfo::new(&object.compute_object_reference(), object),
+                ));
+                if let Some(type_tag) = object.coin_type_maybe() {
+                    let info = CoinInfo::from_object(object)
+                        .expect("already checked that this is a coin type");
+                    entries.coin = Some(((owner, type_tag.to_string(), object.id()), info));
+                }
+            }
+            Owner::ObjectOwner(parent) => {
+                entries.dynamic_field = dynamic_field_info(object)
+                    .map(|info| ((ObjectID::from(parent), object.id()), info));
+            }
+            _ => {}
+        }
+        entries
+    }
+}
+
+/// Index keys found in the tables that do not belong to the current state of their object.
+#[derive(Default)]
+struct StaleIndexKeys {
+    owner: Vec<OwnerIndexKey>,
+    coin: Vec<CoinIndexKey>,
+    dynamic_field: Vec<DynamicFieldKey>,
+}
+
+/// Objects whose index entries were found to be inconsistent with the live object set.
+#[derive(Default)]
+pub(crate) struct IndexRepairCandidates {
+    object_ids: BTreeSet<ObjectID>,
+    stale_keys: BTreeMap<ObjectID, StaleIndexKeys>,
+}
+
+impl IndexRepairCandidates {
+    pub(crate) fn object_ids(&self) -> Vec<ObjectID> {
+        self.object_ids.iter().cloned().collect()
+    }
+}
+
+/// Number of live objects whose index entries are looked up together while scanning.
+const SCAN_CHUNK_SIZE: usize = 1000;
+
+/// Compares the owner, coin and dynamic field indexes against the index entries computed from
+/// `live_objects`, and returns the objects whose entries have to be recomputed. Nothing is written.
+///
+/// Neither side is loaded into memory: the entries of each chunk of live objects are looked up in
+/// the tables, and then each table is streamed and every entry is checked against the current
+/// state of its object (as returned by `get_object`), to find entries that no longer belong.
+pub(crate) fn find_index_repair_candidates(
+    live_objects: impl Iterator<Item = LiveObject>,
+    indexes: &IndexStore,
+    get_object: impl Fn(&ObjectID) -> SuiResult<Option<Object>>,
+    mut dynamic_field_info: impl FnMut(&Object) -> Option<DynamicFieldInfo>,
+    mut on_object_scanned: impl FnMut(),
+) -> SuiResult<(IndexRepairSummary, IndexRepairCandidates)> {
+    let mut summary = IndexRepairSummary::default();
+    let mut candidates = IndexRepairCandidates::default();
+
+    info!("Checking live objects against the indexes");
+    let mut chunk = Vec::with_capacity(SCAN_CHUNK_SIZE);
+    for object in live_objects {
+        let LiveObject::Normal(object) = object else {
+            continue;
+        };
+        chunk.push(ObjectIndexEntries::new(&object, &mut dynamic_field_info));
+        summary.objects_scanned += 1;
+        on_object_scanned();
+        if chunk.len() == SCAN_CHUNK_SIZE {
+            check_expected_entries(
+                indexes,
+                std::mem::take(&mut chunk),
+                &mut summary,
+                &mut candidates,
+            )?;
+        }
+    }
+    check_expected_entries(indexes, chunk, &mut summary, &mut candidates)?;
+
+    info!("Checking index entries against the live objects");
+    let mut expected_entries = |id: &ObjectID| -> SuiResult<ObjectIndexEntries> {
+        Ok(match get_object(id)? {
+            Some(object) => ObjectIndexEntries::new(&object, &mut dynamic_field_info),
+            None => ObjectIndexEntries::default(),
+        })
+    };
+    find_stale_entries(
+        indexes.tables().owner_index(),
+        |(_, id)| *id,
+        |id| Ok(expected_entries(id)?.owner.map(|(key, _)| key)),
+        &mut summary.owner_index,
+        &mut candidates,
+        |stale| &mut stale.owner,
+    )?;
+    find_stale_entries(
+        indexes.tables().coin_index(),
+        |(_, _, id)| *id,
+        |id| Ok(expected_entries(id)?.coin.map(|(key, _)| key)),
+        &mut summary.coin_index,
+        &mut candidates,
+        |stale| &mut stale.coin,
+    )?;
+    find_stale_entries(
+        indexes.tables().dynamic_field_index(),
+        |(_, id)| *id,
+        |id| Ok(expected_entries(id)?.dynamic_field.map(|(key, _)| key)),
+        &mut summary.dynamic_field_index,
+        &mut candidates,
+        |stale| &mut stale.dynamic_field,
+    )?;
+
+    info!(
+        "Found {} objects with inconsistent index entries",
+        candidates.object_ids.len()
+    );
+    Ok((summary, candidates))
+}
+
+/// Looks up the entries computed from a chunk of live objects, counting the ones that are missing
+/// or hold a different value.
+fn check_expected_entries(
+    indexes: &IndexStore,
+    chunk: Vec<ObjectIndexEntries>,
+    summary: &mut IndexRepairSummary,
+    candidates: &mut IndexRepairCandidates,
+) -> SuiResult {
+    let mut owner = vec![];
+    let mut coin = vec![];
+    let mut dynamic_field = vec![];
+    for entries in chunk {
+        owner.extend(entries.owner);
+        coin.extend(entries.coin);
+        dynamic_field.extend(entries.dynamic_field);
+    }
+
+    let tables = indexes.tables();
+    check_table_entries(
+        tables.owner_index(),
+        owner,
+        |(_, id)| *id,
+        &mut summary.owner_index,
+        candidates,
+    )?;
+    check_table_entries(
+        tables.coin_index(),
+        coin,
+        |(_, _, id)| *id,
+        &mut summary.coin_index,
+        candidates,
+    )?;
+    check_table_entries(
+        tables.dynamic_field_index(),
+        dynamic_field,
+        |(_, id)| *id,
+        &mut summary.dynamic_field_index,
+        candidates,
+    )
+}
+
+fn check_table_entries<K, V>(
+    table: &DBMap<K, V>,
+    expected: Vec<(K, V)>,
+    object_id: impl Fn(&K) -> ObjectID,
+    summary: &mut TableRepairSummary,
+    candidates: &mut IndexRepairCandidates,
+) -> SuiResult
+where
+    K: Serialize + DeserializeOwned,
+    V: PartialEq + Serialize + DeserializeOwned,
+{
+    let found = table.multi_get(expected.iter().map(|(key, _)| key))?;
+    for ((key, value), found) in expected.iter().zip(found) {
+        match found {
+            Some(found) if found == *value => continue,
+            Some(_) => summary.mismatched += 1,
+            None => summary.missing += 1,
+        }
+        candidates.object_ids.insert(object_id(key));
+    }
+    Ok(())
+}
+
+/// Streams `table` and records the entries whose key is not the one their object should currently
+/// be indexed under. Entries under the right key were already compared by value.
+fn find_stale_entries<K, V>(
+    table: &DBMap<K, V>,
+    object_id: impl Fn(&K) -> ObjectID,
+    mut expected_key: impl FnMut(&ObjectID) -> SuiResult<Option<K>>,
+    summary: &mut TableRepairSummary,
+    candidates: &mut IndexRepairCandidates,
+    stale_keys: impl Fn(&mut StaleIndexKeys) -> &mut Vec<K>,
+) -> SuiResult
+where
+    K: PartialEq + Serialize + DeserializeOwned,
+    V: Serialize + DeserializeOwned,
+{
+    for (key, _) in table.unbounded_iter() {
+        let id = object_id(&key);
+        if expected_key(&id)?.as_ref() == Some(&key) {
+            continue;
+        }
+        summary.extra += 1;
+        candidates.object_ids.insert(id);
+        stale_keys(candidates.stale_keys.entry(id).or_default()).push(key);
+    }
+    Ok(())
+}
+
+/// Recomputes the index entries of `object_ids` from their current state and returns the changes
+/// needed to bring the tables in line with it. The caller must make sure that no transaction is
+/// indexed concurrently, or the result may already be stale when it is applied.
+pub(crate) fn compute_index_repairs(
+    object_ids: &[ObjectID],
+    candidates: &IndexRepairCandidates,
+    indexes: &IndexStore,
+    get_object: impl Fn(&ObjectID) -> SuiResult<Option<Object>>,
+    mut dynamic_field_info: impl FnMut(&Object) -> Option<DynamicFieldInfo>,
+    summary: &mut IndexRepairSummary,
+) -> SuiResult<(
+    ObjectIndexChanges,
+    Vec<CoinIndexKey>,
+    Vec<(CoinIndexKey, CoinInfo)>,
+)> {
+    let tables = indexes.tables();
+    let mut changes = ObjectIndexChanges {
+        deleted_owners: vec![],
+        deleted_dynamic_fields: vec![],
+        new_owners: vec![],
+        new_dynamic_fields: vec![],
+    };
+    let mut deleted_coins = vec![];
+    let mut new_coins = vec![];
+    let no_stale_keys = StaleIndexKeys::default();
+
+    for id in object_ids {
+        let entries = match get_object(id)? {
+            Some(object) => ObjectIndexEntries::new(&object, &mut dynamic_field_info),
+            None => ObjectIndexEntries::default(),
+        };
+        let stale = candidates.stale_keys.get(id).unwrap_or(&no_stale_keys);
+
+        repair_entry(
+            tables.owner_index(),
+            entries.owner,
+            &stale.owner,
+            &mut changes.deleted_owners,
+            &mut changes.new_owners,
+            &mut summary.owner_index,
+        )?;
+        repair_entry(
+            tables.coin_index(),
+            entries.coin,
+            &stale.coin,
+            &mut deleted_coins,
+            &mut new_coins,
+            &mut summary.coin_index,
+        )?;
+        repair_entry(
+            tables.dynamic_field_index(),
+            entries.dynamic_field,
+            &stale.dynamic_field,
+            &mut changes.deleted_dynamic_fields,
+            &mut changes.new_dynamic_fields,
+            &mut summary.dynamic_field_index,
+        )?;
+    }
+    Ok((changes, deleted_coins, new_coins))
+}
+
+fn repair_entry<K, V>(
+    table: &DBMap<K, V>,
+    expected: Option<(K, V)>,
+    stale_keys: &[K],
+    deletes: &mut Vec<K>,
+    inserts: &mut Vec<(K, V)>,
+    summary: &mut TableRepairSummary,
+) -> SuiResult
+where
+    K: Clone + PartialEq + Serialize + DeserializeOwned,
+    V: PartialEq + Serialize + DeserializeOwned,
+{
+    for key in stale_keys {
+        let is_expected = matches!(&expected, Some((expected_key, _)) if expected_key == key);
+        if !is_expected && table.contains_key(key)? {
+            deletes.push(key.clone());
+            summary.repaired += 1;
+        }
+    }
+    if let Some((key, value)) = expected {
+        if table.get(&key)?.as_ref() != Some(&value) {
+            inserts.push((key, value));
+            summary.repaired += 1;
+        }
+    }
+    Ok(())
+}
@@ -5,6 +5,7 @@ use sui_macros::sim_test;
 use sui_rest_api::client::BalanceChange;
 use sui_rest_api::Client;
 use sui_rest_api::ExecuteTransactionQueryParameters;
+use sui_rest_api::SimulateTransactionQueryParameters;
 use sui_test_transaction_builder::make_transfer_sui_transaction;
 use sui_types::base_types::SuiAddress;
 use sui_types::effects::TransactionEffectsAPI;
@@ -53,3 +54,52 @@ async fn execute_transaction_transfer() {
 
     assert_eq!(actual, expected);
 }
+
+#[sim_test]
+async fn simulate_transaction_at_checkpoint() {
+    let test_cluster = TestClusterBuilder::new().build().await;
+
+    let client = Client::new(test_cluster.rpc_url());
+    let address = SuiAddress::random_for_testing_only();
+
+    let txn = make_transfer_sui_transaction(&test_cluster.wallet, Some(address), Some(9)).await;
+    let (gas_id, gas_version, _) = txn.transaction_data().gas()[0];
+    let parameters = SimulateTransactionQueryParameters {
+        checkpoint: client
+            .get_latest_checkpoint()
+            .await
+            .unwrap()
+            .sequence_number,
+    };
+
+    let simulated = client
+        .simulate_transaction(&parameters, txn.transaction_data())
+        .await
+        .unwrap();
+    assert!(simulated.effects.status().is_ok());
+
+    // Executing the transaction moves the gas coin on past the checkpoint, but simulating at the
+    // checkpoint still reads the coin as it was then.
+    let request = ExecuteTransactionQueryParameters {
+        events: false,
+        balance_changes: false,
+        input_objects: false,
+        output_objects: false,
+    };
+    let executed = client.execute_transaction(&request, &txn).await.unwrap();
+    assert!(executed.effects.status().is_ok());
+
+    let resimulated = client
+        .simulate_transaction(&parameters, txn.transaction_data())
+        .await
+        .unwrap();
+    assert!(resimulated.effects.status().is_ok());
+    assert!(resimulated
+        .effects
+        .modified_at_versions()
+        .contains(&(gas_id, gas_version)));
+    assert_eq!(
+        resimulated.effects.gas_cost_summary(),
+        executed.effects.gas_cost_summary()
+    );
+}
@@ -0,0 +1,55 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 51 --accounts A --simulator
+
+// Aggregations over transactions, events and coins
+
+//# advance-clock --duration-ns 1
+
+//# programmable --sender A --inputs 1 @A
+//> 0: SplitCoins(Gas, [Input(0)]);
+//> TransferObjects([Result(0)], Input(1))
+
+//# create-checkpoint
+
+//# advance-clock --duration-ns 1
+
+//# create-checkpoint
+
+//# advance-clock --duration-ns 1
+
+//# create-checkpoint
+
+//# run-graphql
+{   # Counting by checkpoint bounds, and with a filter on a side table
+    all: transactionBlockCount
+    c1: transactionBlockCount(filter: { atCheckpoint: 1 })
+    beforeC2: transactionBlockCount(filter: { afterCheckpoint: 0, beforeCheckpoint: 2 })
+    sentByA: transactionBlockCount(filter: { sentAddress: "@{A}" })
+    contradictory: transactionBlockCount(filter: { afterCheckpoint: 2, beforeCheckpoint: 2 })
+    # Checkpoint 4 has not been indexed yet, so there is nothing to count.
+    unavailable: transactionBlockCount(filter: { atCheckpoint: 4 })
+}
+
+//# run-graphql
+{   # A's transaction is the only one to use gas after genesis, and it emits no events.
+    gasUsedBySender(afterCheckpoint: 0, first: 1) {
+        sender { address }
+        gasUsed
+        transactionCount
+    }
+
+    eventCountsByType(afterCheckpoint: 0) {
+        eventType { repr }
+        count
+    }
+}
+
+//# run-graphql
+{   # Thresholds must be strictly increasing
+    coinBalanceDistribution(coinType: "0x2::sui::SUI", thresholds: ["2", "1"]) {
+        minBalance
+        ownerCount
+    }
+}
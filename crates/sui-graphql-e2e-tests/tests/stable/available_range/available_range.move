@@ -118,3 +118,14 @@
     sequenceNumber
   }
 }
+
+//# run-graphql
+{
+  pruned: owner(address: "0x42", atCheckpoint: 2) {
+    address
+  }
+
+  first: owner(address: "0x42", atCheckpoint: 3) {
+    address
+  }
+}
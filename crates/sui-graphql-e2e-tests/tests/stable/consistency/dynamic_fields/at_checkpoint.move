@@ -0,0 +1,116 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+// Viewing an object and its dynamic fields at an earlier checkpoint, after some of those fields
+// have been deleted.
+//
+// checkpoint | parent version | dynamic fields
+// -----------|----------------|-------------------------------
+// 1          | 4              | df1, df2, df3, df4, df5, df6
+// 2          | 6              | df4, df5, df6
+
+//# init --protocol-version 51 --addresses Test=0x0 --accounts A --simulator
+
+//# publish
+module Test::M1 {
+    use sui::dynamic_field as field;
+    use std::string::{String, utf8};
+
+    public struct Parent has key, store {
+        id: UID,
+        count: u64
+    }
+
+    public entry fun parent(recipient: address, ctx: &mut TxContext) {
+        transfer::public_transfer(
+            Parent { id: object::new(ctx), count: 0 },
+            recipient
+        )
+    }
+
+    public entry fun mutate_parent(parent: &mut Parent) {
+        parent.count = parent.count + 42;
+    }
+
+    public entry fun add_df(obj: &mut Parent) {
+        let id = &mut obj.id;
+        field::add<String, String>(id, utf8(b"df1"), utf8(b"df1"));
+        field::add<String, String>(id, utf8(b"df2"), utf8(b"df2"));
+        field::add<String, String>(id, utf8(b"df3"), utf8(b"df3"));
+    }
+
+    public entry fun remove_df(obj: &mut Parent) {
+        let id = &mut obj.id;
+        field::remove<String, String>(id, utf8(b"df1"));
+        field::remove<String, String>(id, utf8(b"df2"));
+        field::remove<String, String>(id, utf8(b"df3"));
+    }
+
+    public entry fun add_more_df(obj: &mut Parent) {
+        let id = &mut obj.id;
+        field::add<String, String>(id, utf8(b"df4"), utf8(b"df4"));
+        field::add<String, String>(id, utf8(b"df5"), utf8(b"df5"));
+        field::add<String, String>(id, utf8(b"df6"), utf8(b"df6"));
+    }
+}
+
+//# run Test::M1::parent --sender A --args @A
+
+//# run Test::M1::add_df --sender A --args object(2,0)
+
+//# run Test::M1::add_more_df --sender A --args object(2,0)
+
+//# create-checkpoint
+
+//# run Test::M1::remove_df --sender A --args object(2,0)
+
+//# run Test::M1::mutate_parent --sender A --args object(2,0)
+
+//# create-checkpoint
+
+//# run-graphql
+fragment DynamicFieldSelect on DynamicField {
+  name {
+    bcs
+  }
+  value {
+    ... on MoveValue {
+      json
+    }
+  }
+}
+
+{
+  latest: owner(address: "@{obj_2_0}") {
+    dynamicFields {
+      nodes {
+        ...DynamicFieldSelect
+      }
+    }
+  }
+
+  owner_before_removal: owner(address: "@{obj_2_0}", atCheckpoint: 1) {
+    dynamicFields {
+      nodes {
+        ...DynamicFieldSelect
+      }
+    }
+    df1: dynamicField(name: {type: "0x0000000000000000000000000000000000000000000000000000000000000001::string::String", bcs: "A2RmMQ=="}) {
+      ...DynamicFieldSelect
+    }
+  }
+
+  object_before_removal: object(address: "@{obj_2_0}", atCheckpoint: 1) {
+    version
+    df1: dynamicField(name: {type: "0x0000000000000000000000000000000000000000000000000000000000000001::string::String", bcs: "A2RmMQ=="}) {
+      ...DynamicFieldSelect
+    }
+  }
+}
+
+//# run-graphql
+{
+  owner(address: "@{obj_2_0}", atCheckpoint: 3) {
+    address
+  }
+}
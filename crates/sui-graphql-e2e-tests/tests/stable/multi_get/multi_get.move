@@ -0,0 +1,130 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//# init --protocol-version 51 --addresses P0=0x0 P1=0x0 --accounts A --simulator
+
+// Fetching objects and transaction blocks by key returns them in the order of the keys, with null
+// in place of keys that don't match anything.
+
+//# publish --upgradeable --sender A
+module P0::m {
+    public fun f(): u64 { 42 }
+}
+
+//# create-checkpoint
+
+//# upgrade --package P0 --upgrade-capability 1,1 --sender A
+module P1::m {
+    public fun f(): u64 { 42 }
+    public fun g(): u64 { 43 }
+}
+
+//# create-checkpoint
+
+//# run-graphql
+{
+    # The upgraded package is stored at a new address, so there is no version 2 of the object
+    # at P0, and nothing exists at 0x42.
+    multiGetObjects(keys: [
+        { objectId: "@{P1}", version: 2 },
+        { objectId: "@{P0}", version: 1 },
+        { objectId: "@{P0}", version: 2 },
+        { objectId: "0x42", version: 1 },
+    ]) {
+        version
+        asMovePackage {
+            module(name: "m") {
+                functions { nodes { name } }
+            }
+        }
+    }
+}
+
+//# run-graphql
+{
+    multiGetTransactionBlocks(digests: [
+        "@{digest_3}",
+        "11111111111111111111111111111111",
+        "@{digest_1}",
+    ]) {
+        effects {
+            status
+            checkpoint { sequenceNumber }
+        }
+    }
+}
+
+//# run-graphql
+{
+    serviceConfig { maxMultiGetSize }
+}
+
+//# run-graphql
+{
+    # One key over the limit
+    multiGetObjects(keys: [
+        { objectId: "0x1", version: 1 }, { objectId: "0x2", version: 1 },
+        { objectId: "0x3", version: 1 }, { objectId: "0x4", version: 1 },
+        { objectId: "0x5", version: 1 }, { objectId: "0x6", version: 1 },
+        { objectId: "0x7", version: 1 }, { objectId: "0x8", version: 1 },
+        { objectId: "0x9", version: 1 }, { objectId: "0xa", version: 1 },
+        { objectId: "0xb", version: 1 }, { objectId: "0xc", version: 1 },
+        { objectId: "0xd", version: 1 }, { objectId: "0xe", version: 1 },
+        { objectId: "0xf", version: 1 }, { objectId: "0x10", version: 1 },
+        { objectId: "0x11", version: 1 }, { objectId: "0x12", version: 1 },
+        { objectId: "0x13", version: 1 }, { objectId: "0x14", version: 1 },
+        { objectId: "0x15", version: 1 }, { objectId: "0x16", version: 1 },
+        { objectId: "0x17", version: 1 }, { objectId: "0x18", version: 1 },
+        { objectId: "0x19", version: 1 }, { objectId: "0x1a", version: 1 },
+        { objectId: "0x1b", version: 1 }, { objectId: "0x1c", version: 1 },
+        { objectId: "0x1d", version: 1 }, { objectId: "0x1e", version: 1 },
+        { objectId: "0x1f", version: 1 }, { objectId: "0x20", version: 1 },
+        { objectId: "0x21", version: 1 }, { objectId: "0x22", version: 1 },
+        { objectId: "0x23", version: 1 }, { objectId: "0x24", version: 1 },
+        { objectId: "0x25", version: 1 }, { objectId: "0x26", version: 1 },
+        { objectId: "0x27", version: 1 }, { objectId: "0x28", version: 1 },
+        { objectId: "0x29", version: 1 }, { objectId: "0x2a", version: 1 },
+        { objectId: "0x2b", version: 1 }, { objectId: "0x2c", version: 1 },
+        { objectId: "0x2d", version: 1 }, { objectId: "0x2e", version: 1 },
+        { objectId: "0x2f", version: 1 }, { objectId: "0x30", version: 1 },
+        { objectId: "0x31", version: 1 }, { objectId: "0x32", version: 1 },
+        { objectId: "0x33", version: 1 }
+    ]) {
+        version
+    }
+}
+
+//# run-graphql
+{
+    # One digest over the limit
+    multiGetTransactionBlocks(digests: [
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111", "11111111111111111111111111111111",
+        "11111111111111111111111111111111"
+    ]) {
+        digest
+    }
+}
@@ -5,3 +5,7 @@ use axum::http::HeaderName;
 
 pub static VERSION_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-version");
 pub static LIMITS_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-show-usage");
+pub static API_KEY_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-api-key");
+pub static REQUEST_COST_HEADER: HeaderName = HeaderName::from_static("x-sui-rpc-request-cost");
+pub static COST_BUDGET_REMAINING_HEADER: HeaderName =
+    HeaderName::from_static("x-sui-rpc-cost-budget-remaining");
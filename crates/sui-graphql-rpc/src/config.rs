@@ -8,7 +8,7 @@ use fastcrypto_zkp::bn254::zk_login_api::ZkLoginEnv;
 use move_core_types::ident_str;
 use move_core_types::identifier::IdentStr;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeSet, fmt::Display, time::Duration};
+use std::{collections::BTreeSet, fmt::Display, path::PathBuf, time::Duration};
 use sui_graphql_config::GraphQLConfig;
 use sui_json_rpc::name_service::NameServiceConfig;
 use sui_types::base_types::{ObjectID, SuiAddress};
@@ -81,6 +81,8 @@ pub struct ServiceConfig {
     pub background_tasks: BackgroundTasksConfig,
     pub zklogin: ZkLoginConfig,
     pub move_registry: MoveRegistryConfig,
+    pub persisted_queries: PersistedQueriesConfig,
+    pub cost_budget: CostBudgetConfig,
 }
 
 #[GraphQLConfig]
@@ -125,6 +127,18 @@ pub struct Limits {
     pub max_transaction_ids: u32,
     /// Maximum number of candidates to scan when gathering a page of results.
     pub max_scan_limit: u32,
+    /// Aggregation queries (counts, sums and distributions) whose EXPLAIN cost is more than this
+    /// are rejected instead of being run. Given in the same units as `max_db_query_cost`.
+    pub max_aggregation_cost: u32,
+    /// Maximum number of keys that can be passed to a multi-get query.
+    pub max_multi_get_size: u32,
+    /// Maximum number of subscriptions that can be active at once, across all connections.
+    pub max_concurrent_subscriptions: u32,
+    /// Maximum number of templates a value can be rendered with in one `display` field.
+    pub max_display_entries: u32,
+    /// Maximum size in bytes of the rendered output of one `display` field, across all of its
+    /// entries.
+    pub max_display_output_size: u32,
 }
 
 #[GraphQLConfig]
@@ -135,6 +149,43 @@ pub struct BackgroundTasksConfig {
     pub watermark_update_ms: u64,
 }
 
+/// Configuration for queries that clients refer to by the SHA-256 hash of their document, following
+/// the automatic persisted queries protocol (the `persistedQuery` request extension).
+#[GraphQLConfig]
+pub struct PersistedQueriesConfig {
+    /// Path to a JSON file mapping hex-encoded SHA-256 hashes to the query documents they are the
+    /// hashes of. The file is read when the service starts.
+    pub(crate) file: Option<PathBuf>,
+    /// Whether to look up query documents that are not in the file in the `persisted_queries`
+    /// table of the database.
+    pub(crate) database: bool,
+    /// Whether to only serve queries registered in the file or the database, rejecting all other
+    /// documents.
+    pub(crate) allowlist: bool,
+    /// Maximum number of query documents kept in memory after being registered by clients or read
+    /// from the database. Clients cannot register documents when `allowlist` is set.
+    pub(crate) max_cached_queries: usize,
+}
+
+/// Configuration for limiting the estimated cost of the requests each client can make over time.
+/// Clients are identified by the API key they send in the `x-sui-rpc-api-key` header, if it is one
+/// of `api_keys`, or by their IP address otherwise.
+#[GraphQLConfig]
+pub struct CostBudgetConfig {
+    /// Total estimated cost of the requests a client can make within a window. The cost of a
+    /// request is the number of output nodes it can produce, plus the EXPLAIN cost of the database
+    /// queries it runs. Clients are not limited if this is not set.
+    pub(crate) budget: Option<u64>,
+    /// Length (in milliseconds) of the sliding window that costs are charged over.
+    pub(crate) window_ms: u64,
+    /// Maximum number of clients whose costs are tracked at once. Past this, the clients that made
+    /// a request least recently are forgotten.
+    pub(crate) max_clients: usize,
+    /// API keys that identify clients. Requests with any other key are charged to their IP
+    /// address.
+    pub(crate) api_keys: BTreeSet<String>,
+}
+
 #[GraphQLConfig]
 #[derive(Clone)]
 pub struct MoveRegistryConfig {
@@ -143,12 +194,18 @@ pub struct MoveRegistryConfig {
     pub(crate) page_limit: u16,
     pub(crate) package_address: SuiAddress,
     pub(crate) registry_id: ObjectID,
+    /// Path to a TOML file mapping names to package addresses, used to resolve names when the
+    /// resolution type is `Local`. The file is read when the service starts.
+    pub(crate) local_registry_file: Option<PathBuf>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub(crate) enum ResolutionType {
     Internal,
     External,
+    /// Resolve names using the registry in `local_registry_file`, for testing on networks where
+    /// the registry is not available.
+    Local,
 }
 
 /// The Version of the service. `year.month` represents the major release.
@@ -209,6 +266,7 @@ pub struct InternalFeatureConfig {
     pub(crate) feature_gate: bool,
     pub(crate) logger: bool,
     pub(crate) query_timeout: bool,
+    pub(crate) persisted_queries: bool,
     pub(crate) metrics: bool,
     pub(crate) tracing: bool,
     pub(crate) apollo_tracing: bool,
@@ -347,6 +405,38 @@ impl ServiceConfig {
     async fn max_scan_limit(&self) -> u32 {
         self.limits.max_scan_limit
     }
+
+    /// Maximum estimated cost of the database query behind an aggregation (such as
+    /// `Query.transactionBlockCount`). Aggregations estimated to cost more are rejected rather
+    /// than run. This is measured in the same units that the database uses in EXPLAIN queries.
+    async fn max_aggregation_cost(&self) -> u32 {
+        self.limits.max_aggregation_cost
+    }
+
+    /// Maximum number of keys that can be passed to a multi-get query, such as
+    /// `Query.multiGetObjects` or `Query.multiGetTransactionBlocks`.
+    async fn max_multi_get_size(&self) -> u32 {
+        self.limits.max_multi_get_size
+    }
+
+    /// Maximum number of subscriptions that this service serves at once. Subscribing while this
+    /// many subscriptions are active fails with an error.
+    async fn max_concurrent_subscriptions(&self) -> u32 {
+        self.limits.max_concurrent_subscriptions
+    }
+
+    /// Maximum number of `Display` templates that a value can be rendered with at once, whether
+    /// they are supplied by the caller or registered on-chain.
+    async fn max_display_entries(&self) -> u32 {
+        self.limits.max_display_entries
+    }
+
+    /// Maximum size in bytes of the strings produced by rendering a value with `Display`
+    /// templates, summed over all the templates. Renders that would produce more fail with an
+    /// error.
+    async fn max_display_output_size(&self) -> u32 {
+        self.limits.max_display_output_size
+    }
 }
 
 impl TxExecFullNodeConfig {
@@ -414,6 +504,7 @@ impl ServiceConfig {
                 package_address: pkg_address.unwrap_or_default(),
                 registry_id: object_id.unwrap_or(ObjectID::random()),
                 page_limit: page_limit.unwrap_or(50),
+                local_registry_file: None,
             },
             ..Self::test_defaults()
         }
@@ -447,6 +538,7 @@ impl MoveRegistryConfig {
         page_limit: u16,
         package_address: SuiAddress,
         registry_id: ObjectID,
+        local_registry_file: Option<PathBuf>,
     ) -> Self {
         Self {
             resolution_type,
@@ -454,6 +546,7 @@ impl MoveRegistryConfig {
             page_limit,
             package_address,
             registry_id,
+            local_registry_file,
         }
     }
 }
@@ -511,6 +604,13 @@ impl Default for Limits {
             // for the `TransactionBlockFilter`.
             max_transaction_ids: 1000,
             max_scan_limit: 100_000_000,
+            max_aggregation_cost: 1_000_000,
+            // Keys are given inline in the query, so this is kept to the number of object keys
+            // that fit in `max_query_payload_size`.
+            max_multi_get_size: 50,
+            max_concurrent_subscriptions: 1000,
+            max_display_entries: 50,
+            max_display_output_size: 1024 * 1024,
             // This value is set to be the size of the max transaction bytes allowed + base64
             // overhead (roughly 1/3 of the original string). This is rounded up.
             //
@@ -528,6 +628,7 @@ impl Default for InternalFeatureConfig {
             feature_gate: true,
             logger: true,
             query_timeout: true,
+            persisted_queries: true,
             metrics: true,
             tracing: false,
             apollo_tracing: false,
@@ -544,6 +645,28 @@ impl Default for BackgroundTasksConfig {
     }
 }
 
+impl Default for PersistedQueriesConfig {
+    fn default() -> Self {
+        Self {
+            file: None,
+            database: false,
+            allowlist: false,
+            max_cached_queries: 1000,
+        }
+    }
+}
+
+impl Default for CostBudgetConfig {
+    fn default() -> Self {
+        Self {
+            budget: None,
+            window_ms: 60_000,
+            max_clients: 100_000,
+            api_keys: BTreeSet::new(),
+        }
+    }
+}
+
 impl Display for Version {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.full)
@@ -560,6 +683,7 @@ impl Default for MoveRegistryConfig {
             DEFAULT_PAGE_LIMIT,
             SuiAddress::from_str(MOVE_REGISTRY_PACKAGE).unwrap(),
             ObjectID::from_str(MOVE_REGISTRY_TABLE_ID).unwrap(),
+            None,
         )
     }
 }
@@ -595,6 +719,11 @@ mod tests {
                 max-move-value-depth = 256
                 max-transaction-ids = 11
                 max-scan-limit = 50
+                max-aggregation-cost = 1000
+                max-multi-get-size = 20
+                max-concurrent-subscriptions = 10
+                max-display-entries = 5
+                max-display-output-size = 4096
             "#,
         )
         .unwrap();
@@ -617,6 +746,11 @@ mod tests {
                 max_move_value_depth: 256,
                 max_transaction_ids: 11,
                 max_scan_limit: 50,
+                max_aggregation_cost: 1000,
+                max_multi_get_size: 20,
+                max_concurrent_subscriptions: 10,
+                max_display_entries: 5,
+                max_display_output_size: 4096,
             },
             ..Default::default()
         };
@@ -683,6 +817,8 @@ mod tests {
                 max-move-value-depth = 256
                 max-transaction-ids = 42
                 max-scan-limit = 420
+                max-aggregation-cost = 4200
+                max-multi-get-size = 42
 
                 [experiments]
                 test-flag = true
@@ -708,6 +844,11 @@ mod tests {
                 max_move_value_depth: 256,
                 max_transaction_ids: 42,
                 max_scan_limit: 420,
+                max_aggregation_cost: 4200,
+                max_multi_get_size: 42,
+                max_concurrent_subscriptions: 1000,
+                max_display_entries: 50,
+                max_display_output_size: 1024 * 1024,
             },
             disabled_features: BTreeSet::from([FunctionalGroup::Analytics]),
             experiments: Experiments { test_flag: true },
@@ -742,4 +883,72 @@ mod tests {
 
         assert_eq!(actual, expect);
     }
+
+    #[test]
+    fn test_read_persisted_queries_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [persisted-queries]
+                file = "/opt/graphql/persisted-queries.json"
+                allowlist = true
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            persisted_queries: PersistedQueriesConfig {
+                file: Some(PathBuf::from("/opt/graphql/persisted-queries.json")),
+                allowlist: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_read_local_move_registry_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [move-registry]
+                resolution-type = "Local"
+                local-registry-file = "/opt/graphql/move-registry.toml"
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            move_registry: MoveRegistryConfig {
+                resolution_type: ResolutionType::Local,
+                local_registry_file: Some(PathBuf::from("/opt/graphql/move-registry.toml")),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
+
+    #[test]
+    fn test_read_cost_budget_in_service_config() {
+        let actual = ServiceConfig::read(
+            r#" [cost-budget]
+                budget = 1000000
+                window-ms = 10000
+                api-keys = ["alice", "bob"]
+            "#,
+        )
+        .unwrap();
+
+        let expect = ServiceConfig {
+            cost_budget: CostBudgetConfig {
+                budget: Some(1_000_000),
+                window_ms: 10_000,
+                api_keys: BTreeSet::from(["alice".to_string(), "bob".to_string()]),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(actual, expect);
+    }
 }
@@ -19,6 +19,7 @@ use diesel::{
 use diesel_async::{methods::LoadQuery, scoped_futures::ScopedBoxFuture};
 
 use crate::error::Error;
+use crate::server::rate_limiter;
 
 /// Database Backend in use -- abstracting a specific implementation.
 pub(crate) type Db = pg::PgExecutor;
@@ -108,6 +109,15 @@ pub(crate) trait DbConnection {
         Q: QueryId + QueryFragment<Self::Backend>,
         U: Send;
 
+    /// Estimate the cost of running a query, in the units used by the database's query planner,
+    /// without running it. Returns `None` if the cost could not be estimated. `query` is a thunk
+    /// that returns a query when called.
+    async fn estimate_cost<T, Q>(&mut self, query: T) -> Option<f64>
+    where
+        T: Fn() -> Q + Send,
+        Q: diesel::query_builder::Query + Send + 'static,
+        Q: QueryId + QueryFragment<Self::Backend>;
+
     /// Helper to limit a query that fetches multiple values to return only its first value. `query`
     /// is a thunk that returns a query when called.
     async fn first<T, Q: LimitDsl, U>(&mut self, query: T) -> QueryResult<U>
@@ -124,6 +134,7 @@ pub(crate) trait DbConnection {
 
 impl DataLoader {
     pub(crate) fn new(db: Db) -> Self {
-        Self(Arc::new(AGDataLoader::new(db, tokio::spawn)))
+        // Batches are spawned so that their queries are charged to the request that started them.
+        Self(Arc::new(AGDataLoader::new(db, rate_limiter::spawn_metered)))
     }
 }
@@ -160,6 +160,15 @@ impl<'c> super::DbConnection for PgConnection<'c> {
         query_cost::log(self.conn, self.max_cost, query()).await;
         query().get_results(self.conn).await
     }
+
+    async fn estimate_cost<T, Q>(&mut self, query: T) -> Option<f64>
+    where
+        T: Fn() -> Q + Send,
+        Q: diesel::query_builder::Query + Send + 'static,
+        Q: QueryId + QueryFragment<Self::Backend>,
+    {
+        query_cost::explain(self.conn, query()).await
+    }
 }
 
 impl fmt::Display for ByteaLiteral<'_> {
@@ -176,6 +185,7 @@ pub(crate) fn bytea_literal(slice: &[u8]) -> ByteaLiteral<'_> {
 mod query_cost {
     use super::*;
 
+    use crate::server::rate_limiter;
     use diesel::{query_builder::AstPass, sql_types::Text, QueryResult};
     use diesel_async::AsyncPgConnection;
     use serde_json::Value;
@@ -199,7 +209,8 @@ mod query_cost {
         }
     }
 
-    /// Run `EXPLAIN` on the `query`, and log the estimated cost.
+    /// Run `EXPLAIN` on the `query`, log the estimated cost, and charge it to the request being
+    /// served.
     pub(crate) async fn log<Q>(conn: &mut AsyncPgConnection, max_db_query_cost: u32, query: Q)
     where
         Q: Query + QueryId + QueryFragment<Pg> + RunQueryDsl<AsyncPgConnection> + Send,
@@ -216,6 +227,8 @@ mod query_cost {
         } else {
             info!(cost, max_db_query_cost, exceeds = false, "Estimated cost");
         }
+
+        rate_limiter::charge(cost.ceil() as u64);
     }
 
     pub(crate) async fn explain<Q>(conn: &mut AsyncPgConnection, query: Q) -> Option<f64>
@@ -8,11 +8,12 @@ use async_graphql::{
     ServerError, ServerResult, Value,
 };
 use async_trait::async_trait;
+use sui_indexer::config::IndexingConfig;
 
 use crate::{
     config::ServiceConfig,
     error::{code, graphql_error},
-    functional_group::functional_group,
+    functional_group::{functional_group, table_group},
 };
 
 pub(crate) struct FeatureGate;
@@ -72,6 +73,27 @@ impl Extension for FeatureGate {
             }
         }
 
+        // Fields served from tables that the indexer has been configured not to populate are
+        // unavailable, rather than returning incomplete results.
+        let indexing_config: Option<&IndexingConfig> = ctx.data_opt();
+        if let (Some(config), Some(group)) = (indexing_config, table_group(parent_type, name)) {
+            if !config.is_enabled(group) {
+                return if *is_for_introspection {
+                    Ok(None)
+                } else {
+                    Err(ServerError::new(
+                        format!(
+                            "Cannot query field \"{name}\" on type \"{parent_type}\". The \
+                             indexer does not populate the {} tables (table group {group} is \
+                             disabled).",
+                            group.tables(),
+                        ),
+                        None,
+                    ))
+                };
+            }
+        }
+
         next.run(ctx, info).await
     }
 }
@@ -80,17 +102,27 @@ impl Extension for FeatureGate {
 mod tests {
     use std::collections::BTreeSet;
 
-    use async_graphql::{EmptySubscription, Schema};
+    use async_graphql::{EmptyMutation, EmptySubscription, Schema};
     use expect_test::expect;
-
-    use crate::{functional_group::FunctionalGroup, mutation::Mutation, types::query::Query};
+    use sui_indexer::config::TableGroup;
+    use sui_types::{
+        base_types::{ObjectID, SuiAddress},
+        object::Object as NativeObject,
+    };
+
+    use crate::{
+        functional_group::FunctionalGroup,
+        mutation::Mutation,
+        subscription::Subscription,
+        types::{object::Object, query::Query},
+    };
 
     use super::*;
 
     #[tokio::test]
     #[should_panic] // because it tries to access the data provider, which isn't there
     async fn test_accessing_an_enabled_field() {
-        Schema::build(Query, Mutation, EmptySubscription)
+        Schema::build(Query, Mutation, Subscription)
             .data(ServiceConfig::default())
             .extension(FeatureGate)
             .finish()
@@ -100,7 +132,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_accessing_a_disabled_field() {
-        let errs: Vec<_> = Schema::build(Query, Mutation, EmptySubscription)
+        let errs: Vec<_> = Schema::build(Query, Mutation, Subscription)
             .data(ServiceConfig {
                 disabled_features: BTreeSet::from_iter([FunctionalGroup::SystemState]),
                 ..Default::default()
@@ -121,4 +153,72 @@ mod tests {
             ]"#]];
         expect.assert_eq(&format!("{errs:#?}"));
     }
+
+    #[tokio::test]
+    async fn test_accessing_a_field_without_indexed_tables() {
+        let errs: Vec<_> = Schema::build(Query, Mutation, Subscription)
+            .data(ServiceConfig::default())
+            .data(IndexingConfig {
+                disabled_table_groups: vec![TableGroup::Events],
+                ..Default::default()
+            })
+            .extension(FeatureGate)
+            .finish()
+            .execute("{ events { nodes { sendingModule { name } } } }")
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+
+        let expect = expect![[r#"
+            [
+                "Cannot query field \"events\" on type \"Query\". The indexer does not populate the events and event_* tables (table group events is disabled).",
+            ]"#]];
+        expect.assert_eq(&format!("{errs:#?}"));
+    }
+
+    /// A root that serves an object without a database, to reach fields nested under it.
+    struct ObjectQuery;
+
+    #[async_graphql::Object]
+    impl ObjectQuery {
+        async fn object(&self) -> Object {
+            let native = NativeObject::with_id_owner_for_testing(ObjectID::ZERO, SuiAddress::ZERO);
+
+            Object::from_native(SuiAddress::from(native.id()), native, 0, None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accessing_a_nested_field_without_indexed_tables() {
+        let errs: Vec<_> = Schema::build(ObjectQuery, EmptyMutation, EmptySubscription)
+            .data(ServiceConfig::default())
+            .data(IndexingConfig {
+                disabled_table_groups: vec![TableGroup::ObjectsHistory],
+                ..Default::default()
+            })
+            .extension(FeatureGate)
+            .finish()
+            .execute(
+                "{ object { asMoveObject { \
+                     dynamicFields { nodes { name { json } } } \
+                     coins { nodes { coinBalance } } \
+                } } }",
+            )
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+
+        let expect = expect![[r#"
+            [
+                "Cannot query field \"dynamicFields\" on type \"MoveObject\". The indexer does not populate the objects_history tables (table group objects-history is disabled).",
+                "Cannot query field \"coins\" on type \"MoveObject\". The indexer does not populate the objects_history tables (table group objects-history is disabled).",
+            ]"#]];
+        expect.assert_eq(&format!("{errs:#?}"));
+    }
 }
@@ -4,5 +4,6 @@
 pub(crate) mod directive_checker;
 pub(crate) mod feature_gate;
 pub(crate) mod logger;
+pub(crate) mod persisted_queries;
 pub(crate) mod query_limits_checker;
 pub(crate) mod timeout;
@@ -0,0 +1,428 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextPrepareRequest},
+    from_value, Request, ServerError, ServerResult,
+};
+use async_trait::async_trait;
+use diesel::{ExpressionMethods, OptionalExtension, QueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use fastcrypto::hash::{HashFunction, Sha256};
+use lru::LruCache;
+use serde::Deserialize;
+use sui_indexer::schema::persisted_queries;
+
+use crate::{
+    config::PersistedQueriesConfig,
+    data::{Db, DbConnection, QueryExecutor},
+    error::{code, graphql_error, Error},
+    metrics::Metrics,
+};
+
+/// Name of the request extension carrying the hash of a persisted query.
+const PERSISTED_QUERY_EXTENSION: &str = "persistedQuery";
+
+/// Label for the metrics of queries registered by clients rather than operators, so that clients
+/// cannot grow the number of metrics.
+const AUTOMATIC_QUERY_LABEL: &str = "automatic";
+
+/// Extension factory for serving queries by the SHA-256 hash of their document, and optionally
+/// restricting the service to a known set of documents (allowlist mode).
+///
+/// Clients refer to a document by its hash in the `persistedQuery` request extension, following
+/// the automatic persisted queries protocol: when the service does not know the hash, it responds
+/// with a `PersistedQueryNotFound` error, and the client retries with both the hash and the
+/// document, which registers the document (unless the service is in allowlist mode).
+pub(crate) struct PersistedQueries {
+    store: Arc<PersistedQueryStore>,
+}
+
+struct PersistedQueriesExt {
+    store: Arc<PersistedQueryStore>,
+}
+
+/// Documents known to the service, by the hex-encoded SHA-256 hash of their text.
+struct PersistedQueryStore {
+    allowlist: bool,
+    /// Documents registered by operators in the persisted queries file.
+    registered: HashMap<String, String>,
+    /// Documents registered by operators in the database, if enabled.
+    db: Option<Db>,
+    /// Documents recently read from the database or registered by clients.
+    cache: Option<Mutex<LruCache<String, PersistedQuery>>>,
+}
+
+#[derive(Clone)]
+struct PersistedQuery {
+    document: String,
+    /// Whether the document was registered by an operator, rather than a client.
+    registered: bool,
+}
+
+/// Contents of the `persistedQuery` request extension.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedQueryExtension {
+    version: u32,
+    sha256_hash: String,
+}
+
+impl PersistedQueries {
+    /// Reads the documents registered in the persisted queries file, if there is one. `db` is
+    /// used to look up documents that are not in the file, if the config enables it.
+    pub(crate) fn new(config: &PersistedQueriesConfig, db: Db) -> Result<Self, Error> {
+        let registered = match &config.file {
+            Some(path) => read_persisted_queries(path)?,
+            None => HashMap::new(),
+        };
+
+        Ok(Self {
+            store: Arc::new(PersistedQueryStore {
+                allowlist: config.allowlist,
+                registered,
+                db: config.database.then_some(db),
+                cache: NonZeroUsize::new(config.max_cached_queries)
+                    .map(|capacity| Mutex::new(LruCache::new(capacity))),
+            }),
+        })
+    }
+}
+
+impl ExtensionFactory for PersistedQueries {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(PersistedQueriesExt {
+            store: self.store.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl Extension for PersistedQueriesExt {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        mut request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        let extension = request
+            .extensions
+            .remove(PERSISTED_QUERY_EXTENSION)
+            .map(from_value::<PersistedQueryExtension>)
+            .transpose()
+            .map_err(|e| bad_user_input(format!("Invalid persisted query extension: {e}")))?;
+
+        let (hash, persisted) = match extension {
+            Some(PersistedQueryExtension {
+                version,
+                sha256_hash,
+            }) => {
+                if version != 1 {
+                    return Err(bad_user_input(format!(
+                        "Unsupported persisted query version: {version}"
+                    )));
+                }
+
+                let hash = sha256_hash.to_lowercase();
+                let persisted = self.store.get(&hash).await?;
+                if request.query.is_empty() {
+                    let Some(persisted) = &persisted else {
+                        return Err(graphql_error(
+                            code::PERSISTED_QUERY_NOT_FOUND,
+                            "PersistedQueryNotFound",
+                        ));
+                    };
+                    request.query = persisted.document.clone();
+                } else if hash_query(&request.query) != hash {
+                    return Err(bad_user_input(
+                        "Persisted query hash does not match the query",
+                    ));
+                }
+
+                (hash, persisted)
+            }
+
+            // Requests with a document and no hash only need to be looked up in allowlist mode.
+            None if self.store.allowlist => {
+                let hash = hash_query(&request.query);
+                let persisted = self.store.get(&hash).await?;
+                (hash, persisted)
+            }
+
+            None => return next.run(ctx, request).await,
+        };
+
+        let label = match persisted {
+            Some(PersistedQuery {
+                registered: true, ..
+            }) => hash.as_str(),
+            Some(_) => AUTOMATIC_QUERY_LABEL,
+            None if self.store.allowlist => {
+                return Err(bad_user_input(
+                    "Query is not in the allowlist of persisted queries",
+                ))
+            }
+            None => {
+                self.store.register(hash, request.query.clone());
+                AUTOMATIC_QUERY_LABEL
+            }
+        };
+
+        ctx.data_unchecked::<Metrics>()
+            .request_metrics
+            .persisted_queries
+            .with_label_values(&[label])
+            .inc();
+
+        next.run(ctx, request).await
+    }
+}
+
+impl PersistedQueryStore {
+    /// Looks up the document with the given hash, in the file, then the cache, then the database.
+    async fn get(&self, hash: &str) -> ServerResult<Option<PersistedQuery>> {
+        if let Some(document) = self.registered.get(hash) {
+            return Ok(Some(PersistedQuery {
+                document: document.clone(),
+                registered: true,
+            }));
+        }
+
+        if let Some(cache) = &self.cache {
+            if let Some(persisted) = cache.lock().unwrap().get(hash) {
+                return Ok(Some(persisted.clone()));
+            }
+        }
+
+        let Some(db) = &self.db else {
+            return Ok(None);
+        };
+
+        // Hashes that are not valid hex can't be in the database.
+        let Ok(query_hash) = hex::decode(hash) else {
+            return Ok(None);
+        };
+
+        use persisted_queries::dsl;
+        let document: Option<String> = db
+            .execute(move |conn| {
+                async move {
+                    conn.first(move || {
+                        dsl::persisted_queries
+                            .select(dsl::query)
+                            .filter(dsl::query_hash.eq(query_hash.clone()))
+                    })
+                    .await
+                    .optional()
+                }
+                .scope_boxed()
+            })
+            .await
+            .map_err(|e| {
+                graphql_error(
+                    code::INTERNAL_SERVER_ERROR,
+                    format!("Failed to fetch persisted query: {e}"),
+                )
+            })?;
+
+        let persisted = document.map(|document| PersistedQuery {
+            document,
+            registered: true,
+        });
+
+        if let (Some(cache), Some(persisted)) = (&self.cache, &persisted) {
+            cache
+                .lock()
+                .unwrap()
+                .put(hash.to_string(), persisted.clone());
+        }
+
+        Ok(persisted)
+    }
+
+    /// Registers a document sent by a client, so that later requests can refer to it by hash.
+    fn register(&self, hash: String, document: String) {
+        if let Some(cache) = &self.cache {
+            cache.lock().unwrap().put(
+                hash,
+                PersistedQuery {
+                    document,
+                    registered: false,
+                },
+            );
+        }
+    }
+}
+
+/// Reads a JSON file mapping hex-encoded SHA-256 hashes to query documents, checking that each
+/// document has the hash it is registered under.
+fn read_persisted_queries(path: &Path) -> Result<HashMap<String, String>, Error> {
+    let contents = std::fs::read_to_string(path).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to read persisted queries from {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    let queries: HashMap<String, String> = serde_json::from_str(&contents).map_err(|e| {
+        Error::Internal(format!(
+            "Failed to parse persisted queries from {}: {e}",
+            path.display()
+        ))
+    })?;
+
+    queries
+        .into_iter()
+        .map(|(hash, document)| {
+            let hash = hash.to_lowercase();
+            if hash_query(&document) != hash {
+                return Err(Error::Internal(format!(
+                    "Persisted query {hash} in {} does not match its hash",
+                    path.display()
+                )));
+            }
+            Ok((hash, document))
+        })
+        .collect()
+}
+
+/// The hex-encoded SHA-256 hash of a query document.
+fn hash_query(query: &str) -> String {
+    hex::encode(Sha256::digest(query.as_bytes()).digest)
+}
+
+fn bad_user_input(message: impl Into<String>) -> ServerError {
+    graphql_error(code::BAD_USER_INPUT, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use async_graphql::{Name, Schema, Value};
+    use prometheus::Registry;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::{mutation::Mutation, subscription::Subscription, types::query::Query};
+
+    const QUERY: &str = "{ __typename }";
+
+    fn persisted_queries(allowlist: bool) -> (PersistedQueries, NamedTempFile) {
+        let mut file = NamedTempFile::new().unwrap();
+        let queries = HashMap::from([(hash_query(QUERY), QUERY.to_string())]);
+        write!(file, "{}", serde_json::to_string(&queries).unwrap()).unwrap();
+
+        let store = PersistedQueryStore {
+            allowlist,
+            registered: read_persisted_queries(file.path()).unwrap(),
+            db: None,
+            cache: Some(Mutex::new(LruCache::new(NonZeroUsize::new(10).unwrap()))),
+        };
+
+        let persisted_queries = PersistedQueries {
+            store: Arc::new(store),
+        };
+
+        (persisted_queries, file)
+    }
+
+    fn request(query: &str, hash: Option<String>) -> Request {
+        let mut request = Request::new(query);
+        if let Some(hash) = hash {
+            let extension = Value::Object(
+                [
+                    (Name::new("version"), Value::from(1)),
+                    (Name::new("sha256Hash"), Value::from(hash)),
+                ]
+                .into_iter()
+                .collect(),
+            );
+            request
+                .extensions
+                .insert(PERSISTED_QUERY_EXTENSION.to_string(), extension);
+        }
+        request
+    }
+
+    async fn errors(
+        schema: &Schema<Query, Mutation, Subscription>,
+        request: Request,
+    ) -> Vec<String> {
+        schema
+            .execute(request)
+            .await
+            .errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_automatic_persisted_queries() {
+        let (persisted_queries, _file) = persisted_queries(false);
+        let schema = Schema::build(Query, Mutation, Subscription)
+            .data(Metrics::new(&Registry::new()))
+            .extension(persisted_queries)
+            .finish();
+
+        let query = "{ __typename alias: __typename }";
+        let hash = hash_query(query);
+
+        // Registered queries and plain queries are served.
+        assert!(errors(&schema, request("", Some(hash_query(QUERY))))
+            .await
+            .is_empty());
+        assert!(errors(&schema, request(query, None)).await.is_empty());
+
+        // Unknown hashes need to be registered by sending the query with its hash.
+        assert_eq!(
+            errors(&schema, request("", Some(hash.clone()))).await,
+            vec!["PersistedQueryNotFound"],
+        );
+        assert_eq!(
+            errors(&schema, request(QUERY, Some(hash.clone()))).await,
+            vec!["Persisted query hash does not match the query"],
+        );
+        assert!(errors(&schema, request(query, Some(hash.clone())))
+            .await
+            .is_empty());
+        assert!(errors(&schema, request("", Some(hash))).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_allowlisted_queries() {
+        let (persisted_queries, _file) = persisted_queries(true);
+        let schema = Schema::build(Query, Mutation, Subscription)
+            .data(Metrics::new(&Registry::new()))
+            .extension(persisted_queries)
+            .finish();
+
+        let query = "{ __typename alias: __typename }";
+        let not_allowed = vec!["Query is not in the allowlist of persisted queries".to_string()];
+
+        // Registered queries are served, whether they are sent by hash or in full.
+        assert!(errors(&schema, request("", Some(hash_query(QUERY))))
+            .await
+            .is_empty());
+        assert!(errors(&schema, request(QUERY, None)).await.is_empty());
+
+        // Other queries are rejected, and clients can't register them.
+        assert_eq!(errors(&schema, request(query, None)).await, not_allowed);
+        assert_eq!(
+            errors(&schema, request(query, Some(hash_query(query)))).await,
+            not_allowed,
+        );
+        assert_eq!(
+            errors(&schema, request("", Some(hash_query(query)))).await,
+            vec!["PersistedQueryNotFound"],
+        );
+    }
+}
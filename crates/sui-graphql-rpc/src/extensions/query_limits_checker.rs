@@ -4,9 +4,12 @@
 use crate::config::{Limits, ServiceConfig};
 use crate::error::{code, graphql_error, graphql_error_at_pos};
 use crate::metrics::Metrics;
+use crate::server::rate_limiter::{self, ClientId, RateLimiter};
 use async_graphql::extensions::NextParseQuery;
 use async_graphql::extensions::NextRequest;
+use async_graphql::extensions::NextSubscribe;
 use async_graphql::extensions::{Extension, ExtensionContext, ExtensionFactory};
+use async_graphql::parser::parse_query;
 use async_graphql::parser::types::{
     DocumentOperations, ExecutableDocument, Field, FragmentDefinition, OperationDefinition,
     Selection,
@@ -16,8 +19,9 @@ use async_graphql_value::Value as GqlValue;
 use async_graphql_value::{ConstValue, Value};
 use async_trait::async_trait;
 use axum::http::HeaderName;
+use futures::stream::{BoxStream, StreamExt};
 use serde::Serialize;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::mem;
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
@@ -28,6 +32,16 @@ use uuid::Uuid;
 
 pub(crate) const CONNECTION_FIELDS: [&str; 2] = ["edges", "nodes"];
 const DRY_RUN_TX_BLOCK: &str = "dryRunTransactionBlock";
+
+/// Limits are relaxed by this factor when explaining a query, so that the explanation can report
+/// by how much the query exceeds them.
+const EXPLAIN_HEADROOM: u32 = 10;
+
+/// Fields that fetch a list of entities, one per key in the argument that they are paired with.
+const MULTI_GET_FIELDS: [(&str, &str); 2] = [
+    ("multiGetObjects", "keys"),
+    ("multiGetTransactionBlocks", "digests"),
+];
 const EXECUTE_TX_BLOCK: &str = "executeTransactionBlock";
 
 /// The size of the query payload in bytes, as it comes from the request header: `Content-Length`.
@@ -67,6 +81,11 @@ struct LimitsTraversal<'a> {
     input_budget: u32,
     output_budget: u32,
     depth_seen: u32,
+
+    /// When explaining a query, the estimated output nodes of each field, by its path in the
+    /// response, and the path to the field currently being traversed.
+    output_by_field: Option<BTreeMap<String, u32>>,
+    path: Vec<String>,
 }
 
 /// Builds error messages and reports them to tracing.
@@ -76,6 +95,27 @@ struct Reporter<'a> {
     session_id: &'a SocketAddr,
 }
 
+/// How a query measures up against the service's limits, found without executing it.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Explanation {
+    usage: Usage,
+    limits_exceeded: Vec<LimitExceeded>,
+    output_nodes_by_field: BTreeMap<String, u32>,
+    /// Why the query could not be fully explained, e.g. if it could not be parsed, or if it
+    /// exceeds its limits by too much.
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LimitExceeded {
+    /// Name of the limit, as it appears in `serviceConfig`.
+    limit: &'static str,
+    max: u32,
+    usage: u32,
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct Usage {
@@ -93,6 +133,14 @@ impl ShowUsage {
     }
 }
 
+impl Explanation {
+    /// The cost charged to a client for explaining a query. The query is not executed, so this is
+    /// the number of input nodes analysed, with a minimum of one so that no request is free.
+    pub(crate) fn cost(&self) -> u64 {
+        self.usage.input_nodes.max(1) as u64
+    }
+}
+
 impl<'a> LimitsTraversal<'a> {
     fn new(
         PayloadSize(payload_size): PayloadSize,
@@ -110,6 +158,8 @@ impl<'a> LimitsTraversal<'a> {
             input_budget: reporter.limits.max_query_nodes,
             output_budget: reporter.limits.max_output_nodes,
             depth_seen: 0,
+            output_by_field: None,
+            path: vec![],
         }
     }
 
@@ -406,6 +456,13 @@ impl<'a> LimitsTraversal<'a> {
                     self.output_budget -= multiplicity;
                 }
 
+                let explaining = self.output_by_field.is_some();
+                if let Some(output_by_field) = &mut self.output_by_field {
+                    self.path.push(f.node.response_key().node.to_string());
+                    let nodes = output_by_field.entry(self.path.join(".")).or_default();
+                    *nodes = nodes.saturating_add(multiplicity);
+                }
+
                 // If the field being traversed is a connection field, increase multiplicity by a
                 // factor of page size. This operation can fail due to overflow, which will be
                 // treated as a limits check failure, even if the resulting value does not get used
@@ -425,10 +482,22 @@ impl<'a> LimitsTraversal<'a> {
                         .ok_or_else(|| self.output_node_error())?
                 };
 
+                // Multi-get fields output their selection once for each key.
+                let multiplicity = match self.multi_get_size(f)? {
+                    Some(size) => multiplicity
+                        .checked_mul(size)
+                        .ok_or_else(|| self.output_node_error())?,
+                    None => multiplicity,
+                };
+
                 let page_size = self.connection_page_size(f)?;
                 for selection in &f.node.selection_set.node.items {
                     self.traverse_selection_for_output(selection, multiplicity, page_size)?;
                 }
+
+                if explaining {
+                    self.path.pop();
+                }
             }
 
             // Just recurse through fragments, because they are inlined into their "call site".
@@ -475,6 +544,22 @@ impl<'a> LimitsTraversal<'a> {
         ))
     }
 
+    /// If the field `f` is a multi-get field, extract the number of keys it fetches, otherwise
+    /// return `None`. If the keys are not known, the maximum number of keys is assumed. Returns an
+    /// error if the number of keys cannot be represented as a `u32`.
+    fn multi_get_size(&mut self, f: &Positioned<Field>) -> ServerResult<Option<u32>> {
+        let name = f.node.name.node.as_str();
+        let Some((_, arg)) = MULTI_GET_FIELDS.iter().find(|(field, _)| *field == name) else {
+            return Ok(None);
+        };
+
+        let size = self
+            .resolve_list_len(f.node.get_argument(arg))
+            .unwrap_or(self.reporter.limits.max_multi_get_size as usize);
+
+        Ok(Some(size.try_into().map_err(|_| self.output_node_error())?))
+    }
+
     /// Checks if the given field corresponds to a connection based on whether it contains a
     /// selection for `edges` or `nodes`. That selection could be immediately in that field's
     /// selection set, or nested within a fragment or inline fragment spread.
@@ -539,6 +624,23 @@ impl<'a> LimitsTraversal<'a> {
         .as_u64()
     }
 
+    /// Find the length of a list value, which could be given inline or as a variable.
+    fn resolve_list_len(&self, value: Option<&Positioned<Value>>) -> Option<usize> {
+        match &value?.node {
+            Value::List(elems) => Some(elems.len()),
+
+            Value::Variable(var) => {
+                if let ConstValue::List(elems) = self.variables.get(var)? {
+                    Some(elems.len())
+                } else {
+                    None
+                }
+            }
+
+            _ => None,
+        }
+    }
+
     /// Error returned if transaction payloads exceed limit. Also sets the transaction payload
     /// budget to zero to indicate it has been spent (This is done to prevent future checks for
     /// smaller arguments from succeeding even though a previous larger argument has already
@@ -572,6 +674,97 @@ impl<'a> LimitsTraversal<'a> {
     }
 }
 
+/// Explain how `query` measures up against `limits`, without executing it: how deep it is, how
+/// many input nodes it has, how many output nodes it is estimated to produce (in total and for
+/// each field), and which limits it exceeds.
+pub(crate) fn explain(
+    limits: &Limits,
+    query: &str,
+    variables: &Variables,
+    PayloadSize(payload_size): PayloadSize,
+    query_id: &Uuid,
+    session_id: &SocketAddr,
+) -> Explanation {
+    // Requests that are too large to parse are not explained.
+    let max_payload_size = limits.max_query_payload_size as u64 + limits.max_tx_payload_size as u64;
+    if payload_size > max_payload_size {
+        return Explanation {
+            error: Some(format!("Overall request too large: {payload_size} bytes")),
+            ..Default::default()
+        };
+    }
+
+    let doc = match parse_query(query) {
+        Ok(doc) => doc,
+        Err(e) => {
+            return Explanation {
+                error: Some(e.to_string()),
+                ..Default::default()
+            }
+        }
+    };
+
+    let relaxed = Limits {
+        max_query_depth: limits.max_query_depth.saturating_mul(EXPLAIN_HEADROOM),
+        max_query_nodes: limits.max_query_nodes.saturating_mul(EXPLAIN_HEADROOM),
+        max_output_nodes: limits.max_output_nodes.saturating_mul(EXPLAIN_HEADROOM),
+        max_query_payload_size: limits
+            .max_query_payload_size
+            .saturating_mul(EXPLAIN_HEADROOM),
+        max_tx_payload_size: limits.max_tx_payload_size.saturating_mul(EXPLAIN_HEADROOM),
+        ..limits.clone()
+    };
+
+    let reporter = Reporter {
+        limits: &relaxed,
+        query_id,
+        session_id,
+    };
+
+    let mut traversal = LimitsTraversal::new(
+        PayloadSize(payload_size),
+        &reporter,
+        &doc.fragments,
+        variables,
+    );
+
+    traversal.output_by_field = Some(BTreeMap::new());
+    let error = traversal.check_document(&doc).err().map(|e| e.message);
+    let tx_payload = relaxed.max_tx_payload_size - traversal.tx_payload_budget;
+    let output_nodes_by_field = traversal.output_by_field.take().unwrap_or_default();
+    let usage = traversal.finish(query.len() as u32);
+
+    let query_payload = payload_size.saturating_sub(tx_payload as u64);
+    let query_payload = u32::try_from(query_payload).unwrap_or(u32::MAX);
+
+    let limits_exceeded = [
+        ("maxQueryDepth", limits.max_query_depth, usage.depth),
+        ("maxQueryNodes", limits.max_query_nodes, usage.input_nodes),
+        (
+            "maxOutputNodes",
+            limits.max_output_nodes,
+            usage.output_nodes,
+        ),
+        ("maxTxPayloadSize", limits.max_tx_payload_size, tx_payload),
+        (
+            "maxQueryPayloadSize",
+            limits.max_query_payload_size,
+            query_payload,
+        ),
+    ]
+    .into_iter()
+    .filter(|(_, max, usage)| usage > max)
+    .map(|(limit, max, usage)| LimitExceeded { limit, max, usage })
+    .collect();
+
+    Explanation {
+        usage,
+        limits_exceeded,
+        output_nodes_by_field,
+        error,
+    }
+}
+
 impl<'a> Reporter<'a> {
     fn new(ctx: &'a ExtensionContext<'a>) -> Self {
         let cfg: &ServiceConfig = ctx.data_unchecked();
@@ -670,6 +863,13 @@ impl Usage {
     }
 }
 
+/// The budget left for the client making the request, if it is limited.
+fn remaining_budget(ctx: &ExtensionContext<'_>) -> Option<u64> {
+    let limiter = ctx.data_opt::<Arc<RateLimiter>>()?;
+    let client = ctx.data_opt::<ClientId>()?;
+    limiter.remaining(client)
+}
+
 impl ExtensionFactory for QueryLimitsChecker {
     fn create(&self) -> Arc<dyn Extension> {
         Arc::new(QueryLimitsCheckerExt {
@@ -690,6 +890,15 @@ impl Extension for QueryLimitsCheckerExt {
         }
     }
 
+    fn subscribe<'s>(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        stream: BoxStream<'s, Response>,
+        next: NextSubscribe<'_>,
+    ) -> BoxStream<'s, Response> {
+        next.run(ctx, rate_limiter::with_response_estimate(stream).boxed())
+    }
+
     /// Validates the query against the limits set in the service config
     /// If the limits are hit, the operation terminates early
     async fn parse_query(
@@ -700,7 +909,12 @@ impl Extension for QueryLimitsCheckerExt {
         next: NextParseQuery<'_>,
     ) -> ServerResult<ExecutableDocument> {
         let metrics: &Metrics = ctx.data_unchecked();
-        let payload_size: &PayloadSize = ctx.data_unchecked();
+        // Requests over a WebSocket (subscriptions) have no `Content-Length`, so the size of their
+        // query is used instead.
+        let payload_size = ctx
+            .data_opt::<PayloadSize>()
+            .copied()
+            .unwrap_or(PayloadSize(query.len() as u64));
         let reporter = Reporter::new(ctx);
 
         let instant = Instant::now();
@@ -731,14 +945,30 @@ impl Extension for QueryLimitsCheckerExt {
         }
 
         let mut traversal =
-            LimitsTraversal::new(*payload_size, &reporter, &doc.fragments, variables);
+            LimitsTraversal::new(payload_size, &reporter, &doc.fragments, variables);
 
         let res = traversal.check_document(&doc);
         let usage = traversal.finish(query.len() as u32);
         metrics.query_validation_latency(instant.elapsed());
         usage.report(metrics);
 
+        // Every output node the query can produce counts towards the cost of the request, so the
+        // request is only executed if its client can afford all of them.
+        let cost = usage.output_nodes as u64;
+        let res = res.and_then(|()| match remaining_budget(ctx) {
+            Some(remaining) if remaining < cost => Err(graphql_error(
+                code::RATE_LIMITED,
+                format!(
+                    "Query could cost up to {cost}, more than the {remaining} left in the cost \
+                     budget, try again later"
+                ),
+            )),
+            _ => Ok(()),
+        });
+
         res.map(|()| {
+            rate_limiter::charge(cost);
+            rate_limiter::set_response_estimate(cost);
             if ctx.data_opt::<ShowUsage>().is_some() {
                 *self.usage.lock().unwrap() = Some(usage);
             }
@@ -747,3 +977,53 @@ impl Extension for QueryLimitsCheckerExt {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_explain_query() {
+        let limits = Limits {
+            max_output_nodes: 10,
+            ..Default::default()
+        };
+
+        let query = "{ checkpoints(first: 5) { nodes { sequenceNumber digest } } }";
+        let explanation = explain(
+            &limits,
+            query,
+            &Variables::default(),
+            PayloadSize(query.len() as u64),
+            &Uuid::new_v4(),
+            &"127.0.0.1:0".parse().unwrap(),
+        );
+
+        // The query is explained in full, even though it is over the limit.
+        assert_eq!(
+            serde_json::to_value(explanation).unwrap(),
+            json!({
+                "usage": {
+                    "inputNodes": 4,
+                    "outputNodes": 12,
+                    "depth": 3,
+                    "variables": 0,
+                    "fragments": 0,
+                    "queryPayload": query.len(),
+                },
+                "limitsExceeded": [
+                    { "limit": "maxOutputNodes", "max": 10, "usage": 12 },
+                ],
+                "outputNodesByField": {
+                    "checkpoints": 1,
+                    "checkpoints.nodes": 1,
+                    "checkpoints.nodes.digest": 5,
+                    "checkpoints.nodes.sequenceNumber": 5,
+                },
+                "error": null,
+            })
+        );
+    }
+}
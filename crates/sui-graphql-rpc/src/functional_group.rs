@@ -7,6 +7,7 @@ use async_graphql::*;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json as json;
+use sui_indexer::config::TableGroup;
 
 /// Groups of features served by the RPC service.  The GraphQL Service can be configured to enable
 /// or disable these features.
@@ -26,7 +27,7 @@ pub enum FunctionalGroup {
     /// SuiNS name and reverse name look-up.
     NameService,
 
-    /// Transaction and Event subscriptions.
+    /// Transaction, Event and object change subscriptions.
     Subscriptions,
 
     /// Aspects that affect the running of the system that are managed by the
@@ -95,14 +96,19 @@ fn functional_groups() -> &'static BTreeMap<(&'static str, &'static str), Functi
             (("Owner", "dynamicObjectField"), G::DynamicFields),
             (("Owner", "dynamicFields"), G::DynamicFields),
             (("Owner", "suinsRegistrations"), G::NameService),
+            (("Query", "coinBalanceDistribution"), G::Analytics),
             (("Query", "coinMetadata"), G::Coins),
+            (("Query", "eventCountsByType"), G::Analytics),
+            (("Query", "gasUsedBySender"), G::Analytics),
             (("Query", "moveCallMetrics"), G::Analytics),
             (("Query", "networkMetrics"), G::Analytics),
             (("Query", "protocolConfig"), G::SystemState),
             (("Query", "resolveSuinsAddress"), G::NameService),
+            (("Query", "transactionBlockCount"), G::Analytics),
             (("Query", "packageByName"), G::MoveRegistry),
             (("Query", "typeByName"), G::MoveRegistry),
             (("Subscription", "events"), G::Subscriptions),
+            (("Subscription", "objectChanges"), G::Subscriptions),
             (("Subscription", "transactions"), G::Subscriptions),
             (("SystemStateSummary", "safeMode"), G::SystemState),
             (("SystemStateSummary", "storageFund"), G::SystemState),
@@ -120,14 +126,89 @@ pub(crate) fn functional_group(type_: &str, field: &str) -> Option<FunctionalGro
     functional_groups().get(&(type_, field)).copied()
 }
 
+/// Types implementing `IOwner`, whose owned objects, coins and balances are served from the
+/// objects tables.
+const OWNER_TYPES: [&str; 9] = [
+    "Address",
+    "Coin",
+    "CoinMetadata",
+    "MoveObject",
+    "MovePackage",
+    "Object",
+    "Owner",
+    "StakedSui",
+    "SuinsRegistration",
+];
+
+/// `IOwner` fields that are served from the objects tables.
+const OWNER_FIELDS: [&str; 5] = ["balance", "balances", "coins", "objects", "stakedSuis"];
+
+/// Types with dynamic fields: those implementing `IMoveObject`, as well as `Object` and `Owner`.
+const DYNAMIC_FIELD_TYPES: [&str; 7] = [
+    "Coin",
+    "CoinMetadata",
+    "MoveObject",
+    "Object",
+    "Owner",
+    "StakedSui",
+    "SuinsRegistration",
+];
+
+const DYNAMIC_FIELD_FIELDS: [&str; 3] = ["dynamicField", "dynamicObjectField", "dynamicFields"];
+
+/// Mapping from type and field name in the schema to the group of tables that the indexer must
+/// populate for the field to be served. Fields whose need for a table group depends on their
+/// arguments (e.g. transaction filters) are checked when they are resolved instead.
+///
+/// Fields are gated on the concrete types that serve them, so fields inherited from an interface
+/// are listed for every type that implements it.
+fn table_groups() -> &'static BTreeMap<(&'static str, &'static str), TableGroup> {
+    use TableGroup as T;
+    static GROUPS: Lazy<BTreeMap<(&str, &str), TableGroup>> = Lazy::new(|| {
+        let owner_fields = OWNER_TYPES
+            .iter()
+            .flat_map(|type_| OWNER_FIELDS.iter().map(move |field| (*type_, *field)));
+
+        let dynamic_fields = DYNAMIC_FIELD_TYPES.iter().flat_map(|type_| {
+            DYNAMIC_FIELD_FIELDS
+                .iter()
+                .map(move |field| (*type_, *field))
+        });
+
+        owner_fields
+            .chain(dynamic_fields)
+            .map(|key| (key, T::ObjectsHistory))
+            .chain([
+                (("Query", "coinBalanceDistribution"), T::ObjectsHistory),
+                (("Query", "coins"), T::ObjectsHistory),
+                (("Query", "eventCountsByType"), T::Events),
+                (("Query", "events"), T::Events),
+                (("Query", "gasUsedBySender"), T::TxIndices),
+                (("Query", "multiGetObjects"), T::FullObjectsHistory),
+                (("Query", "object"), T::FullObjectsHistory),
+                (("Query", "objects"), T::ObjectsHistory),
+                (("Subscription", "events"), T::Events),
+            ])
+            .collect()
+    });
+
+    Lazy::force(&GROUPS)
+}
+
+/// Map a type and field name to the group of tables it is served from, if the indexer can be
+/// configured not to populate them.
+pub(crate) fn table_group(type_: &str, field: &str) -> Option<TableGroup> {
+    table_groups().get(&(type_, field)).copied()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::BTreeSet;
 
-    use async_graphql::registry::Registry;
-    use async_graphql::OutputType;
+    use async_graphql::registry::{MetaType, Registry};
+    use async_graphql::{OutputType, SubscriptionType};
 
-    use crate::types::query::Query;
+    use crate::{subscription::Subscription, types::query::Query};
 
     use super::*;
 
@@ -138,14 +219,13 @@ mod tests {
     fn test_groups_match_schema() {
         let mut registry = Registry::default();
         Query::create_type_info(&mut registry);
+        Subscription::create_type_info(&mut registry);
 
         let unimplemented = BTreeSet::from_iter([
             ("Checkpoint", "addressMetrics"),
             ("Epoch", "protocolConfig"),
             ("Query", "moveCallMetrics"),
             ("Query", "networkMetrics"),
-            ("Subscription", "events"),
-            ("Subscription", "transactions"),
         ]);
 
         for (type_, field) in &unimplemented {
@@ -163,7 +243,7 @@ mod tests {
             );
         }
 
-        for (type_, field) in functional_groups().keys() {
+        for (type_, field) in functional_groups().keys().chain(table_groups().keys()) {
             if unimplemented.contains(&(type_, field)) {
                 continue;
             }
@@ -180,4 +260,38 @@ mod tests {
             };
         }
     }
+
+    #[test]
+    /// Makes sure that fields inherited from `IOwner` and `IMoveObject` are gated on every type
+    /// that implements those interfaces, so that none of them can be used to reach tables that
+    /// are not indexed.
+    fn test_table_groups_cover_interface_implementations() {
+        let mut registry = Registry::default();
+        Query::create_type_info(&mut registry);
+        Subscription::create_type_info(&mut registry);
+
+        for interface in ["IOwner", "IMoveObject"] {
+            let Some(MetaType::Interface {
+                fields,
+                possible_types,
+                ..
+            }) = registry.concrete_type_by_name(interface)
+            else {
+                panic!("Interface '{interface}' does not appear in schema.");
+            };
+
+            for field in fields.keys() {
+                let groups: BTreeSet<_> = possible_types
+                    .iter()
+                    .map(|type_| table_group(type_, field).map(|g| g.to_string()))
+                    .collect();
+
+                assert_eq!(
+                    groups.len(),
+                    1,
+                    "Field '{interface}.{field}' is not gated on all implementations: {groups:?}"
+                );
+            }
+        }
+    }
 }
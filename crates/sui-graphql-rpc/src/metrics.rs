@@ -77,6 +77,8 @@ pub(crate) struct RequestMetrics {
     pub num_queries: IntCounter,
     /// Number of queries by top level path
     pub num_queries_top_level: IntCounterVec,
+    /// Number of requests for persisted queries, by the hash of the query
+    pub persisted_queries: IntCounterVec,
     /// Total inflight requests
     pub inflight_requests: Gauge,
 }
@@ -243,6 +245,13 @@ impl RequestMetrics {
                 registry
             )
             .unwrap(),
+            persisted_queries: register_int_counter_vec_with_registry!(
+                "persisted_queries",
+                "Number of requests for persisted queries, by the hash of the query",
+                &["hash"],
+                registry
+            )
+            .unwrap(),
             inflight_requests: register_gauge_with_registry!(
                 "inflight_requests",
                 "Number of queries that are being resolved at a moment in time",
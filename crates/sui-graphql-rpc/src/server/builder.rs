@@ -2,30 +2,33 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::exchange_rates_task::TriggerExchangeRatesTask;
+use super::rate_limiter::{self, RateLimiter};
 use super::system_package_task::SystemPackageTask;
 use super::watermark_task::{ChainIdentifierLock, Watermark, WatermarkLock, WatermarkTask};
 use crate::config::{
-    ConnectionConfig, ServiceConfig, Version, MAX_CONCURRENT_REQUESTS,
-    RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
+    ConnectionConfig, MoveRegistryConfig, ResolutionType, ServiceConfig, Version,
+    MAX_CONCURRENT_REQUESTS, RPC_TIMEOUT_ERR_SLEEP_RETRY_PERIOD,
 };
 use crate::data::move_registry_data_loader::MoveRegistryDataLoader;
 use crate::data::package_resolver::{DbPackageStore, PackageResolver};
-use crate::data::{DataLoader, Db};
+use crate::data::{DataLoader, Db, DbConnection, QueryExecutor};
 use crate::extensions::directive_checker::DirectiveChecker;
 use crate::metrics::Metrics;
 use crate::mutation::Mutation;
+use crate::subscription::{Subscription, SubscriptionPermits};
 use crate::types::datatype::IMoveDatatype;
 use crate::types::move_object::IMoveObject;
 use crate::types::object::IObject;
 use crate::types::owner::IOwner;
 use crate::{
-    config::ServerConfig,
+    config::{Limits, ServerConfig},
     context_data::db_data_provider::PgManager,
-    error::Error,
+    error::{code, graphql_error, Error},
     extensions::{
         feature_gate::FeatureGate,
         logger::Logger,
-        query_limits_checker::{PayloadSize, QueryLimitsChecker, ShowUsage},
+        persisted_queries::PersistedQueries,
+        query_limits_checker::{explain, PayloadSize, QueryLimitsChecker, ShowUsage},
         timeout::Timeout,
     },
     server::version::set_version_middleware,
@@ -33,21 +36,24 @@ use crate::{
 };
 use async_graphql::extensions::ApolloTracing;
 use async_graphql::extensions::Tracing;
-use async_graphql::EmptySubscription;
+use async_graphql::http::ALL_WEBSOCKET_PROTOCOLS;
 use async_graphql::{extensions::ExtensionFactory, Schema, SchemaBuilder};
-use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
-use axum::body::Body;
-use axum::extract::FromRef;
-use axum::extract::{ConnectInfo, Query as AxumQuery, State};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+use axum::body::{Body, Bytes};
+use axum::extract::{ConnectInfo, Query as AxumQuery, State, WebSocketUpgrade};
+use axum::extract::{DefaultBodyLimit, FromRef};
 use axum::http::{HeaderMap, StatusCode};
 use axum::middleware::{self};
 use axum::response::IntoResponse;
 use axum::routing::{get, post, MethodRouter, Route};
 use axum::Extension;
+use axum::Json;
 use axum::Router;
 use axum_extra::headers::ContentLength;
 use axum_extra::TypedHeader;
 use chrono::Utc;
+use diesel::{OptionalExtension, QueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
 use http::{HeaderValue, Method, Request};
 use mysten_metrics::spawn_monitored_task;
 use mysten_network::callback::{CallbackLayer, MakeCallbackHandler, ResponseHandler};
@@ -56,12 +62,17 @@ use std::net::TcpStream;
 use std::sync::Arc;
 use std::time::Duration;
 use std::{any::Any, net::SocketAddr, time::Instant};
-use sui_graphql_rpc_headers::LIMITS_HEADER;
+use sui_graphql_rpc_headers::{
+    API_KEY_HEADER, COST_BUDGET_REMAINING_HEADER, LIMITS_HEADER, REQUEST_COST_HEADER,
+};
+use sui_indexer::config::IndexingConfig;
 use sui_indexer::db::check_db_migration_consistency;
+use sui_indexer::schema::indexing_config;
 use sui_package_resolver::{PackageStoreWithLruCache, Resolver};
 use sui_sdk::SuiClientBuilder;
+use sui_transaction_builder::move_registry::LocalMoveRegistry;
 use tokio::join;
-use tokio::sync::OnceCell;
+use tokio::sync::{watch, OnceCell};
 use tokio_util::sync::CancellationToken;
 use tower::{Layer, Service};
 use tower_http::cors::{AllowOrigin, CorsLayer};
@@ -145,7 +156,7 @@ impl Server {
 
 pub(crate) struct ServerBuilder {
     state: AppState,
-    schema: SchemaBuilder<Query, Mutation, EmptySubscription>,
+    schema: SchemaBuilder<Query, Mutation, Subscription>,
     router: Option<Router>,
     db_reader: Option<Db>,
     resolver: Option<PackageResolver>,
@@ -184,6 +195,12 @@ impl FromRef<AppState> for ConnectionConfig {
     }
 }
 
+impl FromRef<AppState> for ServiceConfig {
+    fn from_ref(app_state: &AppState) -> ServiceConfig {
+        app_state.service.clone()
+    }
+}
+
 impl FromRef<AppState> for Metrics {
     fn from_ref(app_state: &AppState) -> Metrics {
         app_state.metrics.clone()
@@ -219,7 +236,7 @@ impl ServerBuilder {
     }
 
     #[cfg(test)]
-    fn build_schema(self) -> Schema<Query, Mutation, EmptySubscription> {
+    fn build_schema(self) -> Schema<Query, Mutation, Subscription> {
         self.schema.finish()
     }
 
@@ -229,7 +246,7 @@ impl ServerBuilder {
         self,
     ) -> (
         String,
-        Schema<Query, Mutation, EmptySubscription>,
+        Schema<Query, Mutation, Subscription>,
         Db,
         PackageResolver,
         Router,
@@ -256,6 +273,12 @@ impl ServerBuilder {
             let router: Router = Router::new()
                 .route("/", post(graphql_handler))
                 .route("/graphql", post(graphql_handler))
+                .route("/subscriptions", get(graphql_subscription_handler))
+                .route("/explain", explain_route(&self.state.service.limits))
+                .route(
+                    "/graphql/explain",
+                    explain_route(&self.state.service.limits),
+                )
                 .route("/health", get(health_check))
                 .route("/graphql/health", get(health_check))
                 .with_state(self.state.clone())
@@ -308,7 +331,11 @@ impl ServerBuilder {
             .allow_methods([Method::POST])
             // Allow requests from any origin
             .allow_origin(acl)
-            .allow_headers([hyper::header::CONTENT_TYPE, LIMITS_HEADER.clone()]);
+            .allow_headers([
+                hyper::header::CONTENT_TYPE,
+                LIMITS_HEADER.clone(),
+                API_KEY_HEADER.clone(),
+            ]);
         Ok(cors)
     }
 
@@ -345,6 +372,15 @@ impl ServerBuilder {
             .layer(axum::extract::Extension(schema))
             .layer(axum::extract::Extension(watermark_task.lock()))
             .layer(axum::extract::Extension(watermark_task.chain_id_lock()))
+            .layer(axum::extract::Extension(
+                watermark_task.checkpoint_receiver(),
+            ))
+            .layer(axum::extract::Extension(SubscriptionPermits::new(
+                state.service.limits.max_concurrent_subscriptions,
+            )))
+            .layer(axum::extract::Extension(Arc::new(RateLimiter::new(
+                &state.service.cost_budget,
+            ))))
             .layer(Self::cors()?);
 
         Ok(Server {
@@ -364,6 +400,15 @@ impl ServerBuilder {
         version: &Version,
         cancellation_token: CancellationToken,
     ) -> Result<Self, Error> {
+        // The indexer can also write to SQLite, but the service's queries are only implemented
+        // for Postgres.
+        if config.connection.db_url.starts_with("sqlite:") {
+            return Err(Error::Internal(
+                "GraphQL can only read from a Postgres database, SQLite is not supported"
+                    .to_string(),
+            ));
+        }
+
         // PROMETHEUS
         let prom_addr: SocketAddr = format!(
             "{}:{}",
@@ -441,6 +486,13 @@ impl ServerBuilder {
         builder.db_reader = Some(db.clone());
         builder.resolver = Some(resolver.clone());
 
+        // The tables that the indexer populates are fixed when the service starts, so it needs to
+        // be restarted to pick up changes to the indexer's configuration.
+        let indexing_config = query_indexing_config(&db).await?;
+        if indexing_config != IndexingConfig::default() {
+            info!("Serving data indexed with {indexing_config:?}");
+        }
+
         // SDK for talking to fullnode. Used for executing transactions only
         // TODO: fail fast if no url, once we enable mutations fully
         let sui_sdk_client = if let Some(url) = &config.tx_exec_full_node.node_rpc_url {
@@ -460,7 +512,7 @@ impl ServerBuilder {
         builder = builder
             .context_data(config.service.clone())
             .context_data(loader)
-            .context_data(db)
+            .context_data(db.clone())
             .context_data(pg_conn_pool)
             .context_data(resolver)
             .context_data(sui_sdk_client)
@@ -469,7 +521,12 @@ impl ServerBuilder {
             .context_data(metrics.clone())
             .context_data(config.clone())
             .context_data(move_registry_config.clone())
-            .context_data(MoveRegistryDataLoader::new(move_registry_config));
+            .context_data(MoveRegistryDataLoader::new(move_registry_config.clone()))
+            .context_data(indexing_config);
+
+        if let Some(registry) = local_move_registry(&move_registry_config)? {
+            builder = builder.context_data(registry);
+        }
 
         if config.internal_features.feature_gate {
             builder = builder.extension(FeatureGate);
@@ -479,6 +536,13 @@ impl ServerBuilder {
             builder = builder.extension(Logger::default());
         }
 
+        if config.internal_features.persisted_queries {
+            builder = builder.extension(PersistedQueries::new(
+                &config.service.persisted_queries,
+                db,
+            )?);
+        }
+
         if config.internal_features.query_limits_checker {
             builder = builder.extension(QueryLimitsChecker);
         }
@@ -506,8 +570,52 @@ impl ServerBuilder {
     }
 }
 
-fn schema_builder() -> SchemaBuilder<Query, Mutation, EmptySubscription> {
-    async_graphql::Schema::build(Query, Mutation, EmptySubscription)
+/// The configuration the indexer writer populates the database with, or the default configuration
+/// (everything is indexed) if the writer has not recorded one yet.
+async fn query_indexing_config(db: &Db) -> Result<IndexingConfig, Error> {
+    use indexing_config::dsl;
+
+    let config = db
+        .execute(move |conn| {
+            async {
+                conn.first(move || dsl::indexing_config.select(dsl::config))
+                    .await
+                    .optional()
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to fetch indexing config: {e}")))?;
+
+    let Some(config) = config else {
+        return Ok(IndexingConfig::default());
+    };
+
+    serde_json::from_str(&config)
+        .map_err(|e| Error::Internal(format!("Failed to deserialize indexing config: {e}")))
+}
+
+/// The registry to resolve Move Registry names with locally, read from the configured file, if
+/// there is one. The `Local` resolution type cannot work without it, so that configuration is
+/// rejected, rather than failing every name lookup at query time.
+fn local_move_registry(config: &MoveRegistryConfig) -> Result<Option<LocalMoveRegistry>, Error> {
+    let Some(path) = &config.local_registry_file else {
+        return if config.resolution_type == ResolutionType::Local {
+            Err(Error::Internal(
+                "Move Registry resolution type 'Local' requires a local-registry-file".to_string(),
+            ))
+        } else {
+            Ok(None)
+        };
+    };
+
+    LocalMoveRegistry::from_file(path)
+        .map(Some)
+        .map_err(|e| Error::Internal(format!("{e:#}")))
+}
+
+fn schema_builder() -> SchemaBuilder<Query, Mutation, Subscription> {
+    async_graphql::Schema::build(Query, Mutation, Subscription)
         .register_output_type::<IMoveObject>()
         .register_output_type::<IObject>()
         .register_output_type::<IOwner>()
@@ -527,9 +635,15 @@ async fn graphql_handler(
     schema: Extension<SuiGraphQLSchema>,
     Extension(watermark_lock): Extension<WatermarkLock>,
     Extension(chain_identifier_lock): Extension<ChainIdentifierLock>,
+    Extension(limiter): Extension<Arc<RateLimiter>>,
     headers: HeaderMap,
     req: GraphQLRequest,
-) -> (axum::http::Extensions, GraphQLResponse) {
+) -> (
+    StatusCode,
+    axum::http::Extensions,
+    HeaderMap,
+    GraphQLResponse,
+) {
     let mut req = req.into_inner();
 
     req.data.insert(PayloadSize(content_length));
@@ -544,7 +658,42 @@ async fn graphql_handler(
     req.data.insert(Watermark::new(watermark_lock).await);
     req.data.insert(chain_identifier_lock.read().await);
 
-    let result = schema.execute(req).await;
+    // Clients that have spent their budget are turned away until enough of what they spent falls
+    // out of the window, as are requests that could cost more than their client has left (checked
+    // by `QueryLimitsChecker` before the request is executed). Otherwise, the cost of the request
+    // is charged once it has been served.
+    let client = limiter.client_id(&headers, addr.ip());
+    req.data.insert(limiter.clone());
+    req.data.insert(client.clone());
+
+    let mut response_headers = HeaderMap::new();
+    let result = if limiter.remaining(&client) == Some(0) {
+        response_headers.insert(
+            COST_BUDGET_REMAINING_HEADER.clone(),
+            HeaderValue::from(0u64),
+        );
+        async_graphql::Response::from_errors(vec![graphql_error(
+            code::RATE_LIMITED,
+            "Cost budget exhausted, try again later",
+        )])
+    } else {
+        let (result, cost) = rate_limiter::metered(schema.execute(req)).await;
+        response_headers.insert(REQUEST_COST_HEADER.clone(), HeaderValue::from(cost));
+        if let Some(remaining) = limiter.charge(client, cost) {
+            response_headers.insert(
+                COST_BUDGET_REMAINING_HEADER.clone(),
+                HeaderValue::from(remaining),
+            );
+        }
+        result
+    };
+
+    // Requests turned away for their cost are reported with the same status as by `/explain`.
+    let status = if is_rate_limited(&result.errors) {
+        StatusCode::TOO_MANY_REQUESTS
+    } else {
+        StatusCode::OK
+    };
 
     // If there are errors, insert them as an extension so that the Metrics callback handler can
     // pull it out later.
@@ -552,7 +701,143 @@ async fn graphql_handler(
     if result.is_err() {
         extensions.insert(GraphqlErrors(std::sync::Arc::new(result.errors.clone())));
     };
-    (extensions, result.into())
+    (status, extensions, response_headers, result.into())
+}
+
+/// Whether `errors` report that the request was turned away because of its client's cost budget.
+fn is_rate_limited(errors: &[async_graphql::ServerError]) -> bool {
+    errors.iter().any(|e| {
+        matches!(
+            e.extensions.as_ref().and_then(|ext| ext.get("code")),
+            Some(async_graphql::Value::String(c)) if c == code::RATE_LIMITED
+        )
+    })
+}
+
+/// Route for explaining queries. The handler reads the whole request before explaining it, so its
+/// body is limited to the largest request that `/graphql` would accept, and requests over that
+/// size are rejected with `413 Payload Too Large`.
+fn explain_route(limits: &Limits) -> MethodRouter<AppState> {
+    let max_request_size =
+        limits.max_query_payload_size as usize + limits.max_tx_payload_size as usize;
+    post(explain_handler).layer(DefaultBodyLimit::max(max_request_size))
+}
+
+/// Entry point for explaining a query: reports how it measures up against the service's limits
+/// without executing it, so that clients can tune queries before sending them for real. The size
+/// of the request is measured from its body, so requests do not need a `Content-Length` header.
+///
+/// Explanations are charged to the client's cost budget like other requests, and clients that have
+/// spent their budget are turned away with `429 Too Many Requests`.
+async fn explain_handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    State(service): State<ServiceConfig>,
+    Extension(limiter): Extension<Arc<RateLimiter>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> axum::response::Response {
+    let client = limiter.client_id(&headers, addr.ip());
+    if limiter.remaining(&client) == Some(0) {
+        return rate_limited();
+    }
+
+    let req: async_graphql::Request = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("Invalid GraphQL request: {e}"),
+            )
+                .into_response()
+        }
+    };
+
+    let explanation = explain(
+        &service.limits,
+        &req.query,
+        &req.variables,
+        PayloadSize(body.len() as u64),
+        &Uuid::new_v4(),
+        &addr,
+    );
+
+    let cost = explanation.cost();
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(REQUEST_COST_HEADER.clone(), HeaderValue::from(cost));
+    if let Some(remaining) = limiter.charge(client, cost) {
+        response_headers.insert(
+            COST_BUDGET_REMAINING_HEADER.clone(),
+            HeaderValue::from(remaining),
+        );
+    }
+
+    (response_headers, Json(explanation)).into_response()
+}
+
+/// Response for requests that are turned away before they are served, because their client has
+/// spent its cost budget.
+fn rate_limited() -> axum::response::Response {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        COST_BUDGET_REMAINING_HEADER.clone(),
+        HeaderValue::from(0u64),
+    );
+
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        headers,
+        "Cost budget exhausted, try again later",
+    )
+        .into_response()
+}
+
+/// Entry point for graphql subscriptions, served over a WebSocket. Requests on the connection share
+/// the data it is stamped with: a unique ID, the client's address, the watermark as of connecting,
+/// a receiver of watermark updates that drives subscriptions forward, and the permits that limit
+/// how many subscriptions are active at once.
+///
+/// The values fetched for subscriptions on the connection are charged to the client's cost budget,
+/// and clients that have spent their budget cannot connect.
+async fn graphql_subscription_handler(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    schema: Extension<SuiGraphQLSchema>,
+    Extension(watermark_lock): Extension<WatermarkLock>,
+    Extension(chain_identifier_lock): Extension<ChainIdentifierLock>,
+    Extension(checkpoint_receiver): Extension<watch::Receiver<Option<Watermark>>>,
+    Extension(subscription_permits): Extension<SubscriptionPermits>,
+    Extension(limiter): Extension<Arc<RateLimiter>>,
+    headers: HeaderMap,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> axum::response::Response {
+    let client = limiter.client_id(&headers, addr.ip());
+    if limiter.remaining(&client) == Some(0) {
+        return rate_limited();
+    }
+
+    let mut data = async_graphql::Data::default();
+    data.insert(Uuid::new_v4());
+    if headers.contains_key(ShowUsage::name()) {
+        data.insert(ShowUsage)
+    }
+
+    data.insert(addr);
+    data.insert(Watermark::new(watermark_lock).await);
+    data.insert(chain_identifier_lock.read().await);
+    data.insert(checkpoint_receiver);
+    data.insert(subscription_permits);
+    data.insert(limiter);
+    data.insert(client);
+
+    let schema = schema.0;
+    upgrade
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            GraphQLWebSocket::new(stream, schema, protocol)
+                .with_data(data)
+                .serve()
+        })
+        .into_response()
 }
 
 #[derive(Clone)]
@@ -681,7 +966,7 @@ pub mod tests {
     use crate::test_infra::cluster::{prep_executor_cluster, start_cluster};
     use crate::types::chain_identifier::ChainIdentifier;
     use crate::{
-        config::{ConnectionConfig, Limits, ServiceConfig, Version},
+        config::{ConnectionConfig, CostBudgetConfig, Limits, ServiceConfig, Version},
         context_data::db_data_provider::PgManager,
         extensions::{query_limits_checker::QueryLimitsChecker, timeout::Timeout},
     };
@@ -689,6 +974,7 @@ pub mod tests {
         extensions::{Extension, ExtensionContext, NextExecute},
         Request, Response, Variables,
     };
+    use axum::extract::connect_info::MockConnectInfo;
     use serde_json::json;
     use std::sync::Arc;
     use std::time::Duration;
@@ -696,6 +982,7 @@ pub mod tests {
     use sui_sdk::SuiClient;
     use sui_types::digests::get_mainnet_chain_identifier;
     use sui_types::transaction::TransactionData;
+    use tower::ServiceExt;
     use uuid::Uuid;
 
     /// Prepares a schema for tests dealing with extensions. Returns a `ServerBuilder` that can be
@@ -1138,6 +1425,59 @@ pub mod tests {
         assert_eq!(req_metrics.query_depth.get_sample_sum(), 1. + 3.);
     }
 
+    #[tokio::test]
+    async fn test_query_multi_get_limits() {
+        telemetry_subscribers::init_for_testing();
+        let cluster = prep_executor_cluster().await;
+        let db_url = cluster.graphql_connection_config.db_url.clone();
+        let service_config = ServiceConfig {
+            limits: Limits {
+                max_multi_get_size: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let server_builder = prep_schema(db_url, Some(service_config))
+            .await
+            .context_data(PayloadSize(100));
+        let metrics = server_builder.state.metrics.clone();
+        let schema = server_builder.extension(QueryLimitsChecker).build_schema();
+
+        // Each key outputs its own copy of the selection.
+        let digest = "11111111111111111111111111111111";
+        let resp = schema
+            .execute(format!(
+                r#"{{ multiGetTransactionBlocks(digests: ["{digest}", "{digest}"]) {{ digest }} }}"#
+            ))
+            .await
+            .into_result()
+            .expect("Should complete successfully");
+
+        let data = resp.data.into_json().unwrap();
+        assert_eq!(data["multiGetTransactionBlocks"], json!([null, null]));
+
+        let req_metrics = metrics.request_metrics;
+        assert_eq!(req_metrics.output_nodes.get_sample_sum(), 1. + 2.);
+
+        let err: Vec<_> = schema
+            .execute(format!(
+                r#"{{ multiGetTransactionBlocks(digests: ["{digest}", "{digest}", "{digest}"]) {{
+                    digest
+                }} }}"#
+            ))
+            .await
+            .into_result()
+            .unwrap_err()
+            .into_iter()
+            .map(|e| e.message)
+            .collect();
+        assert_eq!(
+            err,
+            vec!["Number of keys (3) exceeds max limit of '2'".to_string()]
+        );
+    }
+
     #[tokio::test]
     pub async fn test_health_check() {
         let cluster = prep_executor_cluster().await;
@@ -1155,6 +1495,234 @@ pub mod tests {
         assert_eq!(resp.status(), reqwest::StatusCode::GATEWAY_TIMEOUT);
     }
 
+    /// A router serving `/graphql` and `/explain` from a schema that does not need a database.
+    fn test_router(service_config: ServiceConfig) -> Router {
+        let metrics = metrics();
+        let schema = schema_builder()
+            .data(service_config.clone())
+            .data(metrics.clone())
+            .extension(QueryLimitsChecker)
+            .finish();
+
+        let limiter = Arc::new(RateLimiter::new(&service_config.cost_budget));
+        let explain = explain_route(&service_config.limits);
+        let state = AppState::new(
+            ConnectionConfig::default(),
+            service_config,
+            metrics,
+            CancellationToken::new(),
+            Version::for_testing(),
+        );
+
+        Router::new()
+            .route("/graphql", post(graphql_handler))
+            .route("/explain", explain)
+            .with_state(state)
+            .layer(axum::Extension(schema))
+            .layer(axum::Extension(WatermarkLock::default()))
+            .layer(axum::Extension(ChainIdentifierLock::default()))
+            .layer(axum::Extension(limiter))
+            .layer(MockConnectInfo(ip_address()))
+    }
+
+    /// A router like [`test_router`], for clients with a cost budget of `budget`.
+    fn cost_budget_router(budget: u64) -> Router {
+        test_router(ServiceConfig {
+            cost_budget: CostBudgetConfig {
+                budget: Some(budget),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    fn post_query(path: &str, query: &str) -> http::Request<Body> {
+        let body = json!({ "query": query }).to_string();
+        http::Request::builder()
+            .method(Method::POST)
+            .uri(path)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .header(http::header::CONTENT_LENGTH, body.len())
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cost_budget_headers_and_rate_limiting() {
+        let router = cost_budget_router(2);
+        let query = "{ __typename }";
+
+        // The query produces one output node, so it costs one unit of budget.
+        let response = router
+            .clone()
+            .oneshot(post_query("/graphql", query))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[&REQUEST_COST_HEADER], "1");
+        assert_eq!(response.headers()[&COST_BUDGET_REMAINING_HEADER], "1");
+
+        // Explaining the query (one input node) is charged to the same budget.
+        let response = router
+            .clone()
+            .oneshot(post_query("/explain", query))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[&REQUEST_COST_HEADER], "1");
+        assert_eq!(response.headers()[&COST_BUDGET_REMAINING_HEADER], "0");
+
+        // Once the budget is spent, requests are turned away without being executed.
+        let response = router
+            .clone()
+            .oneshot(post_query("/graphql", query))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[&COST_BUDGET_REMAINING_HEADER], "0");
+        assert!(!response.headers().contains_key(&REQUEST_COST_HEADER));
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"], serde_json::Value::Null);
+        assert_eq!(body["errors"][0]["extensions"]["code"], code::RATE_LIMITED);
+
+        let response = router.oneshot(post_query("/explain", query)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[&COST_BUDGET_REMAINING_HEADER], "0");
+    }
+
+    #[tokio::test]
+    async fn test_cost_budget_checked_before_execution() {
+        let router = cost_budget_router(10);
+
+        // The query could produce 12 output nodes, which is more than the client can afford, so
+        // it is turned away without being executed or charged.
+        let query = "{ checkpoints(first: 5) { nodes { sequenceNumber digest } } }";
+        let response = router
+            .clone()
+            .oneshot(post_query("/graphql", query))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[&REQUEST_COST_HEADER], "0");
+        assert_eq!(response.headers()[&COST_BUDGET_REMAINING_HEADER], "10");
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"], serde_json::Value::Null);
+        assert_eq!(body["errors"][0]["extensions"]["code"], code::RATE_LIMITED);
+
+        // Queries the client can afford are still served.
+        let response = router
+            .oneshot(post_query("/graphql", "{ __typename }"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[&COST_BUDGET_REMAINING_HEADER], "9");
+    }
+
+    #[tokio::test]
+    async fn test_explain_over_http() {
+        let router = test_router(ServiceConfig::default());
+        let query = "{ checkpoints(first: 5) { nodes { sequenceNumber digest } } }";
+
+        let response = router
+            .clone()
+            .oneshot(post_query("/explain", query))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["usage"]["inputNodes"], 4);
+        assert_eq!(body["usage"]["outputNodes"], 12);
+        assert_eq!(body["limitsExceeded"], json!([]));
+        assert_eq!(body["error"], serde_json::Value::Null);
+
+        // Requests that are not GraphQL requests are rejected.
+        let request = http::Request::builder()
+            .method(Method::POST)
+            .uri("/explain")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from("{ checkpoints }"))
+            .unwrap();
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_explain_chunked_request() {
+        let router = test_router(ServiceConfig::default());
+
+        // A streamed body is sent with chunked encoding, without a `Content-Length`.
+        let body = json!({ "query": "{ __typename }" }).to_string();
+        let payload_size = body.len();
+        let chunks = futures::stream::iter(
+            body.into_bytes()
+                .chunks(4)
+                .map(|c| Ok::<_, Infallible>(c.to_vec()))
+                .collect::<Vec<_>>(),
+        );
+
+        let request = http::Request::builder()
+            .method(Method::POST)
+            .uri("/explain")
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(Body::from_stream(chunks))
+            .unwrap();
+        assert!(!request.headers().contains_key(http::header::CONTENT_LENGTH));
+
+        let response = router.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["usage"]["inputNodes"], 1);
+        assert_eq!(body["usage"]["queryPayload"], payload_size);
+    }
+
+    #[tokio::test]
+    async fn test_explain_request_too_large() {
+        let router = test_router(ServiceConfig {
+            limits: Limits {
+                max_query_payload_size: 20,
+                max_tx_payload_size: 20,
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        // The request is explained even though its query part is over the limit, as long as the
+        // request as a whole is within the size that `/graphql` accepts...
+        let response = router
+            .clone()
+            .oneshot(post_query("/explain", "{ __typename }"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["limitsExceeded"][0]["limit"], "maxQueryPayloadSize");
+
+        // ...but larger requests are turned away before they are read.
+        let query = "{ chainIdentifier protocolConfig { configs { key value } } }";
+        let response = router.oneshot(post_query("/explain", query)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
     /// Execute a GraphQL request with `limits` in place, expecting an error to be returned.
     /// Returns the list of errors returned.
     async fn execute_for_error(db_url: &str, limits: Limits, request: Request) -> String {
@@ -2026,4 +2594,44 @@ pub mod tests {
              bytes or fewer."
         );
     }
+
+    #[test]
+    fn test_local_move_registry_requires_file() {
+        let config = MoveRegistryConfig {
+            resolution_type: ResolutionType::Local,
+            local_registry_file: None,
+            ..Default::default()
+        };
+
+        let err = local_move_registry(&config).unwrap_err();
+        assert!(err.to_string().contains("local-registry-file"), "{err}");
+
+        let config = MoveRegistryConfig {
+            resolution_type: ResolutionType::Internal,
+            local_registry_file: None,
+            ..Default::default()
+        };
+
+        assert!(local_move_registry(&config).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reject_sqlite_database() {
+        let config = ServerConfig {
+            connection: ConnectionConfig {
+                db_url: "sqlite:///tmp/sui_indexer.db".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let Err(err) =
+            ServerBuilder::from_config(&config, &Version::for_testing(), CancellationToken::new())
+                .await
+        else {
+            panic!("Expected SQLite database to be rejected");
+        };
+
+        assert!(err.to_string().contains("SQLite is not supported"), "{err}");
+    }
 }
@@ -5,6 +5,7 @@ pub mod graphiql_server;
 
 pub mod builder;
 pub(crate) mod exchange_rates_task;
+pub(crate) mod rate_limiter;
 pub(crate) mod system_package_task;
 pub mod version;
 pub(crate) mod watermark_task;
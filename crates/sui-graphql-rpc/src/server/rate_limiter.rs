@@ -0,0 +1,293 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Limits the estimated cost of the requests each client can make over a sliding window.
+//!
+//! The cost of a request accumulates while it is served: `QueryLimitsChecker` charges the output
+//! nodes it can produce, and database connections charge the EXPLAIN cost of the queries they
+//! run. Costs are attributed to a request when they are incurred on the task serving it, or on a
+//! task it spawned with `spawn_metered`. The `DataLoader` spawns its batches this way, so a batch
+//! is charged to the request whose load started it, even if other requests join the batch later.
+//!
+//! Requests are only served if their client can afford them in the worst case: `QueryLimitsChecker`
+//! turns away requests whose output node estimate exceeds the client's remaining budget, and
+//! subscriptions check the estimate for each page of values before fetching it.
+
+use std::{
+    collections::{BTreeSet, VecDeque},
+    future::Future,
+    net::IpAddr,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use axum::http::HeaderMap;
+use futures::{stream, Stream, StreamExt};
+use lru::LruCache;
+use sui_graphql_rpc_headers::API_KEY_HEADER;
+use tokio::task::JoinHandle;
+
+use crate::config::CostBudgetConfig;
+
+tokio::task_local! {
+    /// Running total of the estimated cost of the request served by the current task.
+    static REQUEST_COST: Arc<AtomicU64>;
+
+    /// Estimated cost of one response to the subscription served by the current task.
+    static RESPONSE_ESTIMATE: Arc<AtomicU64>;
+}
+
+/// Adds `cost` to the estimated cost of the request served by the current task, if any.
+pub(crate) fn charge(cost: u64) {
+    let _ = REQUEST_COST.try_with(|total| total.fetch_add(cost, Ordering::Relaxed));
+}
+
+/// Runs `fut`, returning its output along with the cost charged while running it.
+pub(crate) async fn metered<F: Future>(fut: F) -> (F::Output, u64) {
+    let total = Arc::new(AtomicU64::new(0));
+    let output = REQUEST_COST.scope(total.clone(), fut).await;
+    (output, total.load(Ordering::Relaxed))
+}
+
+/// Spawns `fut` on a new task, charging the costs it incurs to the request served by the current
+/// task, if any.
+pub(crate) fn spawn_metered<F>(fut: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match REQUEST_COST.try_with(Arc::clone) {
+        Ok(total) => tokio::spawn(REQUEST_COST.scope(total, fut)),
+        Err(_) => tokio::spawn(fut),
+    }
+}
+
+/// Records `cost` as the estimated cost of one response to the subscription served by the current
+/// task, if any.
+pub(crate) fn set_response_estimate(cost: u64) {
+    let _ = RESPONSE_ESTIMATE.try_with(|estimate| estimate.store(cost, Ordering::Relaxed));
+}
+
+/// The estimated cost of one response to the subscription served by the current task, if it has
+/// been recorded.
+pub(crate) fn response_estimate() -> Option<u64> {
+    RESPONSE_ESTIMATE
+        .try_with(|estimate| estimate.load(Ordering::Relaxed))
+        .ok()
+}
+
+/// Polls `stream` with room to record the estimated cost of each of its items, so that the code
+/// producing them can check it against the client's budget.
+pub(crate) fn with_response_estimate<S: Stream + Unpin>(
+    mut stream: S,
+) -> impl Stream<Item = S::Item> {
+    let estimate = Arc::new(AtomicU64::new(0));
+    stream::poll_fn(move |cx| {
+        RESPONSE_ESTIMATE.sync_scope(estimate.clone(), || stream.poll_next_unpin(cx))
+    })
+}
+
+/// Identifies the client that a request's cost is charged to.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum ClientId {
+    ApiKey(String),
+    Ip(IpAddr),
+}
+
+/// Tracks the costs charged to each client within the window, and how much of their budget is
+/// left. Without a budget, nothing is tracked and every client is allowed through.
+pub(crate) struct RateLimiter {
+    budget: Option<u64>,
+    window: Duration,
+    api_keys: BTreeSet<String>,
+    clients: Mutex<LruCache<ClientId, Spending>>,
+}
+
+/// Costs charged to a client, oldest first, and their sum.
+#[derive(Default)]
+struct Spending {
+    charges: VecDeque<(Instant, u64)>,
+    total: u64,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: &CostBudgetConfig) -> Self {
+        let max_clients = NonZeroUsize::new(config.max_clients).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            budget: config.budget,
+            window: Duration::from_millis(config.window_ms),
+            api_keys: config.api_keys.clone(),
+            clients: Mutex::new(LruCache::new(max_clients)),
+        }
+    }
+
+    /// Identifies a client by the API key in its request headers, if it is one of the configured
+    /// keys, falling back to its IP address otherwise (so that clients cannot get a fresh budget
+    /// by making up keys). If a load balancer is used, it must forward the client's IP address.
+    pub(crate) fn client_id(&self, headers: &HeaderMap, ip: IpAddr) -> ClientId {
+        headers
+            .get(&API_KEY_HEADER)
+            .and_then(|key| key.to_str().ok())
+            .filter(|key| self.api_keys.contains(*key))
+            .map_or(ClientId::Ip(ip), |key| ClientId::ApiKey(key.to_string()))
+    }
+
+    /// The budget `client` has left in the current window, or `None` if clients are not limited.
+    pub(crate) fn remaining(&self, client: &ClientId) -> Option<u64> {
+        self.remaining_at(client, Instant::now())
+    }
+
+    /// Charges `cost` to `client`, returning the budget it has left afterwards, or `None` if
+    /// clients are not limited.
+    pub(crate) fn charge(&self, client: ClientId, cost: u64) -> Option<u64> {
+        self.charge_at(client, cost, Instant::now())
+    }
+
+    fn remaining_at(&self, client: &ClientId, now: Instant) -> Option<u64> {
+        let budget = self.budget?;
+        let mut clients = self.clients.lock().unwrap();
+        let spent = clients.get_mut(client).map_or(0, |spending| {
+            spending.expire(now, self.window);
+            spending.total
+        });
+
+        Some(budget.saturating_sub(spent))
+    }
+
+    fn charge_at(&self, client: ClientId, cost: u64, now: Instant) -> Option<u64> {
+        let budget = self.budget?;
+        let mut clients = self.clients.lock().unwrap();
+        let spending = clients.get_or_insert_mut(client, Spending::default);
+        spending.expire(now, self.window);
+        spending.charges.push_back((now, cost));
+        spending.total = spending.total.saturating_add(cost);
+
+        Some(budget.saturating_sub(spending.total))
+    }
+}
+
+impl Spending {
+    /// Forgets the charges made at least `window` before `now`.
+    fn expire(&mut self, now: Instant, window: Duration) {
+        while let Some(&(at, cost)) = self.charges.front() {
+            if now.saturating_duration_since(at) < window {
+                break;
+            }
+
+            self.charges.pop_front();
+            self.total = self.total.saturating_sub(cost);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metered_collects_charges_from_task() {
+        charge(5);
+        let ((), cost) = metered(async {
+            charge(10);
+            charge(20);
+        })
+        .await;
+
+        assert_eq!(cost, 30);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_metered_charges_spawning_task() {
+        let ((), cost) = metered(async {
+            charge(1);
+            spawn_metered(async { charge(10) }).await.unwrap();
+        })
+        .await;
+
+        assert_eq!(cost, 11);
+
+        // Outside of a request, spawned tasks are not charged to anything.
+        spawn_metered(async { charge(10) }).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_response_estimate_is_scoped_to_stream() {
+        set_response_estimate(5);
+        assert_eq!(response_estimate(), None);
+
+        let estimates: Vec<_> = with_response_estimate(stream::iter(1..=3).map(|cost| {
+            let before = response_estimate();
+            set_response_estimate(cost);
+            (before, response_estimate())
+        }))
+        .collect()
+        .await;
+
+        assert_eq!(
+            estimates,
+            vec![(Some(0), Some(1)), (Some(1), Some(2)), (Some(2), Some(3))]
+        );
+    }
+
+    #[test]
+    fn test_client_id_prefers_known_api_key() {
+        let limiter = RateLimiter::new(&CostBudgetConfig {
+            api_keys: BTreeSet::from(["key".to_string()]),
+            ..Default::default()
+        });
+
+        let ip = IpAddr::V4(Ipv4Addr::LOCALHOST);
+        assert_eq!(limiter.client_id(&HeaderMap::new(), ip), ClientId::Ip(ip));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(API_KEY_HEADER.clone(), "key".parse().unwrap());
+        assert_eq!(
+            limiter.client_id(&headers, ip),
+            ClientId::ApiKey("key".to_string())
+        );
+
+        // Unknown keys are charged to the client's IP address.
+        headers.insert(API_KEY_HEADER.clone(), "made-up".parse().unwrap());
+        assert_eq!(limiter.client_id(&headers, ip), ClientId::Ip(ip));
+    }
+
+    #[test]
+    fn test_budget_recovers_over_sliding_window() {
+        let limiter = RateLimiter::new(&CostBudgetConfig {
+            budget: Some(100),
+            window_ms: 1000,
+            ..Default::default()
+        });
+
+        let alice = ClientId::ApiKey("alice".to_string());
+        let bob = ClientId::ApiKey("bob".to_string());
+        let start = Instant::now();
+
+        assert_eq!(limiter.charge_at(alice.clone(), 60, start), Some(40));
+        let later = start + Duration::from_millis(500);
+        assert_eq!(limiter.charge_at(alice.clone(), 60, later), Some(0));
+        assert_eq!(limiter.remaining_at(&bob, later), Some(100));
+
+        // The first charge leaves the window, but the second is still in it.
+        let after_first = start + Duration::from_millis(1200);
+        assert_eq!(limiter.remaining_at(&alice, after_first), Some(40));
+
+        let after_both = start + Duration::from_millis(1600);
+        assert_eq!(limiter.remaining_at(&alice, after_both), Some(100));
+    }
+
+    #[test]
+    fn test_no_budget_is_unlimited() {
+        let limiter = RateLimiter::new(&CostBudgetConfig::default());
+        let client = ClientId::Ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+
+        assert_eq!(limiter.charge(client.clone(), u64::MAX), None);
+        assert_eq!(limiter.remaining(&client), None);
+    }
+}
@@ -29,6 +29,8 @@ pub(crate) struct WatermarkTask {
     cancel: CancellationToken,
     sender: watch::Sender<u64>,
     receiver: watch::Receiver<u64>,
+    checkpoint_sender: watch::Sender<Option<Watermark>>,
+    checkpoint_receiver: watch::Receiver<Option<Watermark>>,
 }
 
 #[derive(Clone, Default)]
@@ -57,6 +59,7 @@ impl WatermarkTask {
         cancel: CancellationToken,
     ) -> Self {
         let (sender, receiver) = watch::channel(0);
+        let (checkpoint_sender, checkpoint_receiver) = watch::channel(None);
 
         Self {
             watermark: Default::default(),
@@ -67,6 +70,8 @@ impl WatermarkTask {
             cancel,
             sender,
             receiver,
+            checkpoint_sender,
+            checkpoint_receiver,
         }
     }
 
@@ -104,6 +109,14 @@ impl WatermarkTask {
                     if epoch > prev_epoch {
                         self.sender.send(epoch).unwrap();
                     }
+
+                    self.checkpoint_sender.send_if_modified(|w| {
+                        if w.as_ref().is_some_and(|w| w.checkpoint == checkpoint) {
+                            return false;
+                        }
+                        *w = Some(Watermark { checkpoint, checkpoint_timestamp_ms, epoch });
+                        true
+                    });
                 }
             }
         }
@@ -122,6 +135,12 @@ impl WatermarkTask {
         self.receiver.clone()
     }
 
+    /// Receiver for subscribing to new checkpoints, along with the rest of the watermark as of
+    /// that checkpoint. Holds `None` until the watermark has been read from the database.
+    pub(crate) fn checkpoint_receiver(&self) -> watch::Receiver<Option<Watermark>> {
+        self.checkpoint_receiver.clone()
+    }
+
     // Fetch the chain identifier (once) from the database and cache it.
     async fn get_and_cache_chain_identifier(&self, interval: &mut Interval) {
         loop {
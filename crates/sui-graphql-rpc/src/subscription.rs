@@ -0,0 +1,529 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::config::ServiceConfig;
+use crate::data::{Db, DbConnection, QueryExecutor};
+use crate::error::{code, Error};
+use crate::server::rate_limiter::{self, ClientId, RateLimiter};
+use crate::server::watermark_task::Watermark;
+use crate::types::{
+    cursor::Page,
+    event::{self, Event, EventFilter, EventKey},
+    object_change::ObjectChange,
+    sui_address::SuiAddress,
+    transaction_block::{self, TransactionBlock, TransactionBlockFilter},
+    transaction_block_effects::TransactionBlockEffects,
+    uint53::UInt53,
+};
+use async_graphql::connection::CursorType;
+use async_graphql::*;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use futures::{stream, Stream, StreamExt};
+use sui_indexer::schema::checkpoints;
+use tokio::sync::{watch, OwnedSemaphorePermit, Semaphore};
+
+pub struct Subscription;
+
+/// Subscriptions are used to follow the Sui network as it makes progress. Each subscription starts
+/// at the latest checkpoint indexed when it is made, and streams the values in every checkpoint
+/// indexed after that, in order. Values are streamed once their whole checkpoint is indexed, so
+/// they are consistent with queries made at that checkpoint or later.
+///
+/// A subscription ends with an error if values could not be fetched for some checkpoint, so it
+/// never silently skips values.
+///
+/// At most `serviceConfig.maxConcurrentSubscriptions` subscriptions are served at once, across all
+/// connections.
+#[Subscription]
+impl Subscription {
+    /// Events emitted in the network, optionally filtered in the same way as `Query.events`.
+    async fn events<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        filter: Option<EventFilter>,
+    ) -> Result<impl Stream<Item = Result<Event>> + 'ctx> {
+        let filter = filter.unwrap_or_default();
+        filter.check_indexed(ctx).extend()?;
+
+        stream_checkpoints(ctx, move |ctx, from, to, after| {
+            let filter = filter.clone();
+            async move { events_page(&ctx, filter, from, to, after).await }
+        })
+    }
+
+    /// Transaction blocks executed in the network, optionally filtered in the same way as
+    /// `Query.transactionBlocks`. Filters that would require a scan limit in a query are limited
+    /// to the service's maximum scan limit, per page.
+    async fn transactions<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<impl Stream<Item = Result<TransactionBlock>> + 'ctx> {
+        let filter = filter.unwrap_or_default();
+        filter.check_indexed(ctx).extend()?;
+
+        stream_checkpoints(ctx, move |ctx, from, to, after| {
+            let filter = filter.clone();
+            async move { transactions_page(&ctx, filter, from, to, after).await }
+        })
+    }
+
+    /// New versions of objects, as they are created, modified, wrapped or deleted by transaction
+    /// blocks. When `address` is provided, only the changes to that object are streamed.
+    async fn object_changes<'ctx>(
+        &self,
+        ctx: &Context<'ctx>,
+        address: Option<SuiAddress>,
+    ) -> Result<impl Stream<Item = Result<ObjectChange>> + 'ctx> {
+        stream_checkpoints(ctx, move |ctx, from, to, after| async move {
+            object_changes_page(&ctx, address, from, to, after).await
+        })
+    }
+}
+
+/// Limits the number of subscriptions that are active at once. Each subscription holds a permit
+/// until its stream is dropped.
+#[derive(Clone)]
+pub(crate) struct SubscriptionPermits(Arc<Semaphore>);
+
+impl SubscriptionPermits {
+    pub(crate) fn new(max_concurrent_subscriptions: u32) -> Self {
+        Self(Arc::new(Semaphore::new(
+            max_concurrent_subscriptions as usize,
+        )))
+    }
+
+    fn acquire(&self) -> Result<OwnedSemaphorePermit, Error> {
+        self.0.clone().try_acquire_owned().map_err(|_| {
+            Error::Client("Too many active subscriptions, try again later".to_string())
+        })
+    }
+}
+
+/// Streams the values returned by `fetch` for each range of checkpoints `(from, to]` that the
+/// watermark advances over, starting from the watermark at the time of subscribing. The cost of
+/// each page fetched is charged to the subscribing client, and the stream ends with an error once
+/// the client cannot afford the next page: a full page of values, each costing up to the output
+/// node estimate of the subscription's query.
+fn stream_checkpoints<'ctx, T, C, F, Fut>(
+    ctx: &Context<'ctx>,
+    mut fetch: F,
+) -> Result<impl Stream<Item = Result<T>> + 'ctx>
+where
+    T: Send + 'ctx,
+    C: Send + 'ctx,
+    F: FnMut(Context<'ctx>, u64, u64, Option<C>) -> Fut + Send + 'ctx,
+    Fut: Future<Output = Result<(Vec<T>, Option<C>)>> + Send + 'ctx,
+{
+    let permit = ctx.data::<SubscriptionPermits>()?.acquire().extend()?;
+    let receiver = ctx.data::<watch::Receiver<Option<Watermark>>>()?.clone();
+    let limiter = ctx.data::<Arc<RateLimiter>>()?.clone();
+    let client = ctx.data::<ClientId>()?.clone();
+    let page_size = ctx.data::<ServiceConfig>()?.limits.max_page_size as u64;
+    let ctx = ctx.clone();
+
+    Ok(checkpoint_pages(
+        receiver,
+        permit,
+        move |from, to, after| {
+            let page = fetch(ctx.clone(), from, to, after);
+            let limiter = limiter.clone();
+            let client = client.clone();
+            async move {
+                let estimate = rate_limiter::response_estimate()
+                    .unwrap_or(0)
+                    .saturating_mul(page_size);
+                if limiter
+                    .remaining(&client)
+                    .is_some_and(|remaining| remaining < estimate.max(1))
+                {
+                    return Err(async_graphql::Error::new(
+                        "Cost budget exhausted, try again later",
+                    )
+                    .extend_with(|_, e| e.set("code", code::RATE_LIMITED)));
+                }
+
+                let (page, cost) = rate_limiter::metered(page).await;
+                limiter.charge(client, cost);
+                page
+            }
+        },
+    ))
+}
+
+/// State of a stream of checkpoint pages, between pages.
+struct PageState<C, F> {
+    receiver: watch::Receiver<Option<Watermark>>,
+    /// The last checkpoint whose values have all been streamed, or `None` if the watermark was not
+    /// known yet when the stream started.
+    from: Option<u64>,
+    /// The checkpoint range currently being paged through, and the cursor to continue it from.
+    pending: Option<(u64, C)>,
+    fetch: F,
+    _permit: OwnedSemaphorePermit,
+}
+
+/// Streams values from the checkpoints that the watermark in `receiver` advances over, one page at
+/// a time. `fetch(from, to, after)` returns a page of the values in checkpoints `(from, to]`,
+/// following on from cursor `after`, and the cursor to fetch the next page from, if there is one.
+/// The watermark is only waited on once a range has been exhausted, so values are streamed in
+/// order, and at most one page of them is held in memory at once.
+///
+/// The stream starts from the watermark held in `receiver` when it is created. If the watermark is
+/// not known yet, it starts from the first watermark received, without streaming any history.
+fn checkpoint_pages<T, C, F, Fut>(
+    mut receiver: watch::Receiver<Option<Watermark>>,
+    permit: OwnedSemaphorePermit,
+    fetch: F,
+) -> impl Stream<Item = Result<T>>
+where
+    F: FnMut(u64, u64, Option<C>) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<C>)>>,
+{
+    let from = receiver.borrow_and_update().as_ref().map(|w| w.checkpoint);
+    let state = PageState {
+        receiver,
+        from,
+        pending: None,
+        fetch,
+        _permit: permit,
+    };
+
+    let pages = stream::unfold(Some(state), |state| async move {
+        let mut state = state?;
+        let (from, to, after) = match state.pending.take() {
+            Some((to, after)) => (state.from?, to, Some(after)),
+            None => loop {
+                // The stream ends when the watermark task shuts down.
+                state.receiver.changed().await.ok()?;
+                let Some(watermark) = *state.receiver.borrow_and_update() else {
+                    continue;
+                };
+
+                match state.from {
+                    Some(from) if from < watermark.checkpoint => {
+                        break (from, watermark.checkpoint, None)
+                    }
+                    Some(_) => continue,
+                    None => state.from = Some(watermark.checkpoint),
+                }
+            },
+        };
+
+        Some(match (state.fetch)(from, to, after).await {
+            Ok((values, next)) => {
+                match next {
+                    Some(cursor) => state.pending = Some((to, cursor)),
+                    None => state.from = Some(to),
+                }
+                (values.into_iter().map(Ok).collect(), Some(state))
+            }
+            Err(e) => (vec![Err(e)], None),
+        })
+    });
+
+    pages.flat_map(stream::iter)
+}
+
+/// A page of the events emitted by transactions in checkpoints `(from, to]`, viewed at checkpoint
+/// `to`, following on from `after`.
+async fn events_page(
+    ctx: &Context<'_>,
+    filter: EventFilter,
+    from: u64,
+    to: u64,
+    after: Option<event::Cursor>,
+) -> Result<(Vec<Event>, Option<event::Cursor>)> {
+    let config: &ServiceConfig = ctx.data_unchecked();
+    let db: &Db = ctx.data_unchecked();
+
+    // The first page starts after the last event of the last transaction in checkpoint `from`.
+    let after = match after {
+        Some(after) => Some(after),
+        None => {
+            let tx_lo = network_total_transactions(db, from).await.extend()?;
+            tx_lo.checked_sub(1).map(|tx| {
+                event::Cursor::new(EventKey {
+                    tx,
+                    e: i64::MAX as u64,
+                    checkpoint_viewed_at: to,
+                })
+            })
+        }
+    };
+
+    let page = Page::from_params(
+        config,
+        Some(config.limits.max_page_size as u64),
+        after,
+        None,
+        None,
+    )?;
+
+    let connection = Event::paginate(db, page, filter, to).await.extend()?;
+
+    let next = match connection.edges.last() {
+        Some(edge) if connection.has_next_page => Some(decode_cursor(&edge.cursor)?),
+        _ => None,
+    };
+
+    let events = connection.edges.into_iter().map(|edge| edge.node).collect();
+    Ok((events, next))
+}
+
+/// A page of the transaction blocks in checkpoints `(from, to]` that match `filter`, viewed at
+/// checkpoint `to`, following on from `after`.
+async fn transactions_page(
+    ctx: &Context<'_>,
+    filter: TransactionBlockFilter,
+    from: u64,
+    to: u64,
+    after: Option<transaction_block::Cursor>,
+) -> Result<(Vec<TransactionBlock>, Option<transaction_block::Cursor>)> {
+    let config: &ServiceConfig = ctx.data_unchecked();
+
+    let range = TransactionBlockFilter {
+        after_checkpoint: Some(UInt53::from(from)),
+        before_checkpoint: Some(UInt53::from(to + 1)),
+        ..Default::default()
+    };
+
+    let Some(filter) = filter.intersect(range) else {
+        return Ok((vec![], None));
+    };
+
+    let scan_limit = filter
+        .requires_scan_limit()
+        .then_some(config.limits.max_scan_limit as u64);
+
+    let page = Page::from_params(
+        config,
+        Some(config.limits.max_page_size as u64),
+        after,
+        None,
+        None,
+    )?;
+
+    let connection = TransactionBlock::paginate(ctx, page, filter, to, scan_limit)
+        .await
+        .extend()?;
+
+    // With a scan limit, the end cursor can point past the last transaction in the page, so it is
+    // used to continue rather than the cursor of the last edge.
+    let next = match &connection.end_cursor {
+        Some(cursor) if connection.has_next_page => Some(decode_cursor(cursor)?),
+        _ => None,
+    };
+
+    let transactions = connection.edges.into_iter().map(|edge| edge.node).collect();
+    Ok((transactions, next))
+}
+
+/// Changes to objects made by a page of the transaction blocks in checkpoints `(from, to]`, viewed
+/// at checkpoint `to`, limited to the object at `address` if it is provided.
+async fn object_changes_page(
+    ctx: &Context<'_>,
+    address: Option<SuiAddress>,
+    from: u64,
+    to: u64,
+    after: Option<transaction_block::Cursor>,
+) -> Result<(Vec<ObjectChange>, Option<transaction_block::Cursor>)> {
+    let filter = TransactionBlockFilter {
+        changed_object: address,
+        ..Default::default()
+    };
+
+    let (transactions, next) = transactions_page(ctx, filter, from, to, after).await?;
+
+    let mut changes = vec![];
+    for transaction in transactions {
+        let effects = TransactionBlockEffects::try_from(transaction).extend()?;
+        changes.extend(
+            effects
+                .native()
+                .object_changes()
+                .into_iter()
+                .filter(|change| address.map_or(true, |a| a == SuiAddress::from(change.id)))
+                .map(|native| ObjectChange {
+                    native,
+                    checkpoint_viewed_at: to,
+                }),
+        );
+    }
+
+    Ok((changes, next))
+}
+
+/// The total number of transactions in the network as of `checkpoint`.
+async fn network_total_transactions(db: &Db, checkpoint: u64) -> Result<u64, Error> {
+    use checkpoints::dsl;
+
+    let total: i64 = db
+        .execute(move |conn| {
+            async move {
+                conn.first(move || {
+                    dsl::checkpoints
+                        .select(dsl::network_total_transactions)
+                        .filter(dsl::sequence_number.eq(checkpoint as i64))
+                })
+                .await
+            }
+            .scope_boxed()
+        })
+        .await
+        .map_err(|e| Error::Internal(format!("Failed to fetch checkpoint {checkpoint}: {e}")))?;
+
+    Ok(total as u64)
+}
+
+fn decode_cursor<C: CursorType>(cursor: &str) -> Result<C> {
+    C::decode_cursor(cursor)
+        .map_err(|e| Error::Internal(format!("Failed to decode cursor: {e}")))
+        .extend()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::future::{ready, Ready};
+    use futures::FutureExt;
+    use std::sync::Mutex;
+
+    fn watermark(checkpoint: u64) -> Option<Watermark> {
+        Some(Watermark {
+            checkpoint,
+            ..Default::default()
+        })
+    }
+
+    /// Fetches the checkpoint numbers in `(from, to]` as values, two at a time, recording the
+    /// arguments it was called with in `calls`.
+    fn fetch_checkpoints(
+        calls: Arc<Mutex<Vec<(u64, u64, Option<u64>)>>>,
+    ) -> impl FnMut(u64, u64, Option<u64>) -> Ready<Result<(Vec<u64>, Option<u64>)>> {
+        move |from, to, after| {
+            calls.lock().unwrap().push((from, to, after));
+            let lo = after.unwrap_or(from) + 1;
+            let hi = to.min(lo + 1);
+            let next = (hi < to).then_some(hi);
+            ready(Ok(((lo..=hi).collect(), next)))
+        }
+    }
+
+    fn permit() -> OwnedSemaphorePermit {
+        SubscriptionPermits::new(1).acquire().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_pages_through_checkpoints_in_order() {
+        let (sender, receiver) = watch::channel(watermark(0));
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut stream = Box::pin(checkpoint_pages(
+            receiver,
+            permit(),
+            fetch_checkpoints(calls.clone()),
+        ));
+
+        sender.send(watermark(5)).unwrap();
+        for expect in 1..=5 {
+            assert_eq!(stream.next().await.unwrap().unwrap(), expect);
+        }
+
+        // The stream waits for the watermark to advance before fetching more.
+        assert!(stream.next().now_or_never().is_none());
+
+        sender.send(watermark(7)).unwrap();
+        for expect in 6..=7 {
+            assert_eq!(stream.next().await.unwrap().unwrap(), expect);
+        }
+
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![(0, 5, None), (0, 5, Some(2)), (0, 5, Some(4)), (5, 7, None),],
+        );
+
+        // The stream ends when the watermark stops being updated.
+        drop(sender);
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_starts_from_current_watermark() {
+        let (sender, receiver) = watch::channel(watermark(10));
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut stream = Box::pin(checkpoint_pages(
+            receiver,
+            permit(),
+            fetch_checkpoints(calls.clone()),
+        ));
+
+        sender.send(watermark(11)).unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), 11);
+        assert_eq!(*calls.lock().unwrap(), vec![(10, 11, None)]);
+    }
+
+    #[tokio::test]
+    async fn test_skips_history_before_first_watermark() {
+        let (sender, receiver) = watch::channel(None);
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut stream = Box::pin(checkpoint_pages(
+            receiver,
+            permit(),
+            fetch_checkpoints(calls.clone()),
+        ));
+
+        // The first watermark is where the stream starts, so nothing is fetched for it.
+        sender.send(watermark(100)).unwrap();
+        assert!(stream.next().now_or_never().is_none());
+        assert!(calls.lock().unwrap().is_empty());
+
+        sender.send(watermark(101)).unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), 101);
+        assert_eq!(*calls.lock().unwrap(), vec![(100, 101, None)]);
+    }
+
+    #[tokio::test]
+    async fn test_ends_after_error() {
+        let (sender, receiver) = watch::channel(watermark(0));
+        let mut stream = Box::pin(checkpoint_pages(
+            receiver,
+            permit(),
+            |_, to, _: Option<()>| {
+                ready(if to < 2 {
+                    Ok((vec![to], None))
+                } else {
+                    Err(Error::Internal("boom".to_string()).extend())
+                })
+            },
+        ));
+
+        sender.send(watermark(1)).unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), 1);
+
+        sender.send(watermark(2)).unwrap();
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_subscription_permits() {
+        let (sender, receiver) = watch::channel(watermark(0));
+        let permits = SubscriptionPermits::new(1);
+
+        let stream = checkpoint_pages(
+            receiver,
+            permits.acquire().unwrap(),
+            fetch_checkpoints(Default::default()),
+        );
+        assert!(permits.acquire().is_err());
+
+        // Dropping the stream releases its permit.
+        drop(stream);
+        assert!(permits.acquire().is_ok());
+        drop(sender);
+    }
+}
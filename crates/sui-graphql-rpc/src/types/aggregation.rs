@@ -0,0 +1,563 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Aggregations over transactions, events and coins, computed by the database. Each aggregation
+//! is run as a single query, as long as the database estimates its cost to be within the
+//! `max_aggregation_cost` limit, so that clients can summarize large ranges of data without paging
+//! through them, but cannot tie up the database doing so.
+
+use std::str::FromStr;
+
+use async_graphql::*;
+use diesel::{
+    sql_types::{BigInt as SqlBigInt, Bytea, Integer, Text},
+    ExpressionMethods, OptionalExtension, QueryDsl, QueryResult, QueryableByName,
+};
+use diesel_async::scoped_futures::ScopedFutureExt;
+use sui_indexer::{schema::tx_senders, types::OwnerType};
+use sui_types::TypeTag;
+
+use super::{
+    address::Address,
+    available_range::AvailableRange,
+    big_int::BigInt,
+    cursor::Page,
+    move_type::MoveType,
+    sui_address::SuiAddress,
+    transaction_block::{self, subqueries, TransactionBlockFilter, TxBounds},
+    uint53::UInt53,
+};
+use crate::config::ServiceConfig;
+use crate::data::{Conn, Db, DbConnection, DieselBackend, QueryExecutor};
+use crate::error::Error;
+use crate::raw_query::RawQuery;
+use crate::{filter, query};
+
+/// The gas used by the transactions sent by an address.
+#[derive(SimpleObject)]
+pub(crate) struct SenderGasUsage {
+    /// The address that sent the transactions.
+    sender: Address,
+    /// Net gas used by the transactions (computation and storage costs, less storage rebates), in
+    /// MIST.
+    gas_used: BigInt,
+    /// The number of transactions the gas was used by.
+    transaction_count: UInt53,
+}
+
+/// The number of events emitted of a particular type.
+#[derive(SimpleObject)]
+pub(crate) struct EventTypeCount {
+    /// The Move type of the events.
+    event_type: MoveType,
+    /// The number of events emitted of this type.
+    count: UInt53,
+}
+
+/// The addresses whose total balance of a coin falls in the range `[minBalance, maxBalance)`.
+#[derive(SimpleObject)]
+pub(crate) struct CoinBalanceBucket {
+    /// The smallest balance in the bucket (inclusive).
+    min_balance: BigInt,
+    /// The bound on balances in the bucket (exclusive), or null for the last bucket, which has no
+    /// bound.
+    max_balance: Option<BigInt>,
+    /// The number of addresses whose balance is in the bucket.
+    owner_count: UInt53,
+    /// The sum of the balances in the bucket.
+    total_balance: BigInt,
+}
+
+#[derive(QueryableByName)]
+struct StoredCount {
+    #[diesel(sql_type = SqlBigInt)]
+    count: i64,
+}
+
+#[derive(QueryableByName)]
+struct StoredSenderGasUsage {
+    #[diesel(sql_type = Bytea)]
+    sender: Vec<u8>,
+    /// Read as text, because sums can be larger than 2^63 - 1.
+    #[diesel(sql_type = Text)]
+    gas_used: String,
+    #[diesel(sql_type = SqlBigInt)]
+    count: i64,
+}
+
+#[derive(QueryableByName)]
+struct StoredEventTypeCount {
+    #[diesel(sql_type = Text)]
+    event_type: String,
+    #[diesel(sql_type = SqlBigInt)]
+    count: i64,
+}
+
+#[derive(QueryableByName)]
+struct StoredCoinBalanceBucket {
+    #[diesel(sql_type = Integer)]
+    bucket: i32,
+    #[diesel(sql_type = SqlBigInt)]
+    owners: i64,
+    #[diesel(sql_type = Text)]
+    total_balance: String,
+}
+
+/// The outcome of an aggregation: its rows, or its estimated cost if it was not run because it
+/// would cost too much (`None` if its cost could not be estimated).
+type Aggregated<T> = Result<Vec<T>, Option<f64>>;
+
+/// Count the transaction blocks that match `filter`, as of `checkpoint_viewed_at`. Filters that
+/// only bound the checkpoint range are counted without scanning any transactions. Returns `None`
+/// if none of the checkpoints that `filter` bounds to are available to count from.
+pub(crate) async fn transaction_block_count(
+    ctx: &Context<'_>,
+    filter: TransactionBlockFilter,
+    checkpoint_viewed_at: u64,
+) -> Result<Option<UInt53>> {
+    let config: &ServiceConfig = ctx.data_unchecked();
+    let db: &Db = ctx.data_unchecked();
+    let max_cost = config.limits.max_aggregation_cost;
+
+    if let Some(tx_ids) = &filter.transaction_ids {
+        if tx_ids.len() > config.limits.max_transaction_ids as usize {
+            return Err(Error::Client(format!(
+                "Transaction IDs exceed max limit of '{}'",
+                config.limits.max_transaction_ids
+            )))
+            .extend();
+        }
+    }
+
+    filter.check_indexed(ctx).extend()?;
+
+    if filter.is_empty() {
+        return Ok(Some(UInt53::from(0)));
+    }
+
+    let page = Page::<transaction_block::Cursor>::from_params(config, None, None, None, None)?;
+    let counted: Option<Aggregated<StoredCount>> = db
+        .execute_repeatable(move |conn| {
+            async move {
+                let Some(bounds) = TxBounds::query(
+                    conn,
+                    filter.after_checkpoint.map(u64::from),
+                    filter.at_checkpoint.map(u64::from),
+                    filter.before_checkpoint.map(u64::from),
+                    checkpoint_viewed_at,
+                    None,
+                    &page,
+                )
+                .await?
+                else {
+                    return Ok::<_, diesel::result::Error>(None);
+                };
+
+                // Without filters on side tables, every transaction in the bounds matches.
+                let Some(matches) = subqueries(&filter, bounds) else {
+                    let count = bounds.scan_hi() - bounds.scan_lo();
+                    return Ok(Some(Ok(vec![StoredCount {
+                        count: count as i64,
+                    }])));
+                };
+
+                let query = query!("SELECT COUNT(*) AS count FROM ({}) matches", matches);
+                Ok(Some(aggregate(conn, max_cost, query).await?))
+            }
+            .scope_boxed()
+        })
+        .await
+        .extend()?;
+
+    let Some(counted) = counted else {
+        return Ok(None);
+    };
+
+    let counted = counted
+        .map_err(|cost| too_expensive(cost, max_cost))
+        .extend()?;
+    let count = counted.first().map_or(0, |c| c.count);
+    Ok(Some(UInt53::from(count as u64)))
+}
+
+/// The addresses that used the most gas sending transactions in the checkpoint range, most gas
+/// first, and at most `first` of them. Fails if the range includes transactions that were indexed
+/// before gas usage was recorded, rather than report totals that leave them out.
+pub(crate) async fn gas_used_by_sender(
+    ctx: &Context<'_>,
+    after_checkpoint: Option<u64>,
+    before_checkpoint: Option<u64>,
+    first: Option<u64>,
+    checkpoint_viewed_at: u64,
+) -> Result<Vec<SenderGasUsage>> {
+    let config: &ServiceConfig = ctx.data_unchecked();
+    let db: &Db = ctx.data_unchecked();
+    let max_cost = config.limits.max_aggregation_cost;
+
+    let page = Page::<transaction_block::Cursor>::from_params(config, first, None, None, None)?;
+    let limit = page.limit() as i64;
+    let usages: Option<Aggregated<StoredSenderGasUsage>> = db
+        .execute_repeatable(move |conn| {
+            async move {
+                let Some(bounds) = TxBounds::query(
+                    conn,
+                    after_checkpoint,
+                    None,
+                    before_checkpoint,
+                    checkpoint_viewed_at,
+                    None,
+                    &page,
+                )
+                .await?
+                else {
+                    return Ok::<_, diesel::result::Error>(Some(Ok(vec![])));
+                };
+
+                let (lo, hi) = (bounds.scan_lo() as i64, bounds.scan_hi() as i64);
+                let missing: Option<i64> = conn
+                    .first(move || {
+                        use tx_senders::dsl;
+                        dsl::tx_senders
+                            .select(dsl::tx_sequence_number)
+                            .filter(dsl::tx_sequence_number.ge(lo))
+                            .filter(dsl::tx_sequence_number.lt(hi))
+                            .filter(dsl::gas_used.is_null())
+                    })
+                    .await
+                    .optional()?;
+
+                if missing.is_some() {
+                    return Ok(None);
+                }
+
+                let query = filter!(
+                    query!(
+                        "SELECT sender, CAST(SUM(gas_used) AS TEXT) AS gas_used, COUNT(*) AS count \
+                         FROM tx_senders"
+                    ),
+                    format!("{lo} <= tx_sequence_number AND tx_sequence_number < {hi}")
+                )
+                .group_by("sender")
+                .order_by("SUM(gas_used) DESC")
+                .order_by("sender")
+                .limit(limit);
+
+                Ok(Some(aggregate(conn, max_cost, query).await?))
+            }
+            .scope_boxed()
+        })
+        .await
+        .extend()?;
+
+    let Some(usages) = usages else {
+        return Err(Error::Client(
+            "Gas used is not indexed for some transactions in this checkpoint range, so totals \
+             would be incomplete. Try a more recent range."
+                .to_string(),
+        ))
+        .extend();
+    };
+
+    let usages = usages
+        .map_err(|cost| too_expensive(cost, max_cost))
+        .extend()?;
+    usages
+        .into_iter()
+        .map(|stored| {
+            let sender = SuiAddress::from_bytes(&stored.sender)
+                .map_err(|e| Error::Internal(format!("Failed to read sender: {e}")))?;
+            let gas_used = BigInt::from_str(&stored.gas_used)
+                .map_err(|_| Error::Internal("Failed to read gas used.".to_string()))?;
+
+            Ok(SenderGasUsage {
+                sender: Address {
+                    address: sender,
+                    checkpoint_viewed_at,
+                },
+                gas_used,
+                transaction_count: UInt53::from(stored.count as u64),
+            })
+        })
+        .collect::<Result<_, Error>>()
+        .extend()
+}
+
+/// The types of the events emitted in the checkpoint range, most emitted first, and at most
+/// `first` of them.
+pub(crate) async fn event_counts_by_type(
+    ctx: &Context<'_>,
+    after_checkpoint: Option<u64>,
+    before_checkpoint: Option<u64>,
+    first: Option<u64>,
+    checkpoint_viewed_at: u64,
+) -> Result<Vec<EventTypeCount>> {
+    let config: &ServiceConfig = ctx.data_unchecked();
+    let db: &Db = ctx.data_unchecked();
+    let max_cost = config.limits.max_aggregation_cost;
+
+    let page = Page::<transaction_block::Cursor>::from_params(config, first, None, None, None)?;
+    let limit = page.limit() as i64;
+    let counts: Aggregated<StoredEventTypeCount> = db
+        .execute_repeatable(move |conn| {
+            async move {
+                let Some(bounds) = TxBounds::query(
+                    conn,
+                    after_checkpoint,
+                    None,
+                    before_checkpoint,
+                    checkpoint_viewed_at,
+                    None,
+                    &page,
+                )
+                .await?
+                else {
+                    return Ok::<_, diesel::result::Error>(Ok(vec![]));
+                };
+
+                let query = filter!(
+                    query!("SELECT event_type, COUNT(*) AS count FROM events"),
+                    format!(
+                        "{} <= tx_sequence_number AND tx_sequence_number < {}",
+                        bounds.scan_lo(),
+                        bounds.scan_hi()
+                    )
+                )
+                .group_by("event_type")
+                .order_by("count DESC")
+                .order_by("event_type")
+                .limit(limit);
+
+                aggregate(conn, max_cost, query).await
+            }
+            .scope_boxed()
+        })
+        .await
+        .extend()?;
+
+    let counts = counts
+        .map_err(|cost| too_expensive(cost, max_cost))
+        .extend()?;
+    counts
+        .into_iter()
+        .map(|stored| {
+            let event_type = TypeTag::from_str(&stored.event_type)
+                .map_err(|e| Error::Internal(format!("Failed to parse event type: {e}")))?;
+
+            Ok(EventTypeCount {
+                event_type: event_type.into(),
+                count: UInt53::from(stored.count as u64),
+            })
+        })
+        .collect::<Result<_, Error>>()
+        .extend()
+}
+
+/// How the addresses that own coins of `coin_type` are distributed by their total balance of it,
+/// as of `checkpoint_viewed_at`. The `thresholds` split balances into buckets: below the first
+/// threshold, between each pair of consecutive thresholds, and from the last threshold onwards.
+/// Every bucket is returned, even if no address falls into it.
+pub(crate) async fn coin_balance_distribution(
+    ctx: &Context<'_>,
+    coin_type: TypeTag,
+    thresholds: Vec<BigInt>,
+    checkpoint_viewed_at: u64,
+) -> Result<Vec<CoinBalanceBucket>> {
+    let config: &ServiceConfig = ctx.data_unchecked();
+    let db: &Db = ctx.data_unchecked();
+    let max_cost = config.limits.max_aggregation_cost;
+
+    if thresholds.len() > config.limits.max_page_size as usize {
+        return Err(Error::Client(format!(
+            "Number of thresholds exceeds max limit of '{}'",
+            config.limits.max_page_size
+        )))
+        .extend();
+    }
+
+    let bounds = thresholds
+        .iter()
+        .map(|t| t.to_string().parse::<u128>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| Error::Client("Thresholds must be non-negative integers".to_string()))
+        .extend()?;
+
+    if bounds.windows(2).any(|w| w[0] >= w[1]) {
+        return Err(Error::Client(
+            "Thresholds must be in strictly increasing order".to_string(),
+        ))
+        .extend();
+    }
+
+    let buckets: Option<Aggregated<StoredCoinBalanceBucket>> = db
+        .execute_repeatable(move |conn| {
+            async move {
+                let Some(range) = AvailableRange::result(conn, checkpoint_viewed_at).await? else {
+                    return Ok::<_, diesel::result::Error>(None);
+                };
+
+                let query = balance_buckets_query(coin_type, &bounds, range);
+                Ok(Some(aggregate(conn, max_cost, query).await?))
+            }
+            .scope_boxed()
+        })
+        .await
+        .extend()?;
+
+    let Some(buckets) = buckets else {
+        return Err(Error::Client(
+            "Requested data is outside the available range".to_string(),
+        ))
+        .extend();
+    };
+
+    let buckets = buckets
+        .map_err(|cost| too_expensive(cost, max_cost))
+        .extend()?;
+
+    // Start with every bucket empty, and fill in the ones that addresses fall into.
+    let zero = BigInt::from(0u64);
+    let mut distribution: Vec<_> = (0..=thresholds.len())
+        .map(|i| CoinBalanceBucket {
+            min_balance: i
+                .checked_sub(1)
+                .map_or_else(|| zero.clone(), |j| thresholds[j].clone()),
+            max_balance: thresholds.get(i).cloned(),
+            owner_count: UInt53::from(0),
+            total_balance: zero.clone(),
+        })
+        .collect();
+
+    for stored in buckets {
+        let Some(bucket) = distribution.get_mut(stored.bucket as usize) else {
+            return Err(Error::Internal(format!(
+                "Unexpected balance bucket: {}",
+                stored.bucket
+            )))
+            .extend();
+        };
+
+        bucket.owner_count = UInt53::from(stored.owners as u64);
+        bucket.total_balance = BigInt::from_str(&stored.total_balance)
+            .map_err(|_| Error::Internal("Failed to read balance.".to_string()))
+            .extend()?;
+    }
+
+    Ok(distribution)
+}
+
+/// Runs the aggregation `query` if the database estimates that it costs at most `max_cost`.
+async fn aggregate<T>(
+    conn: &mut Conn<'_>,
+    max_cost: u32,
+    query: RawQuery,
+) -> QueryResult<Aggregated<T>>
+where
+    T: QueryableByName<DieselBackend> + Send + 'static,
+{
+    match conn.estimate_cost(|| query.clone().into_boxed()).await {
+        Some(cost) if cost <= max_cost as f64 => {
+            Ok(Ok(conn.results(|| query.clone().into_boxed()).await?))
+        }
+        cost => Ok(Err(cost)),
+    }
+}
+
+fn too_expensive(cost: Option<f64>, max_cost: u32) -> Error {
+    match cost {
+        Some(cost) => Error::Client(format!(
+            "Aggregation is too expensive: its estimated cost of {cost:.0} exceeds the limit of \
+             {max_cost}. Try narrowing its checkpoint range or filters."
+        )),
+        None => Error::Internal("Failed to estimate the cost of the aggregation".to_string()),
+    }
+}
+
+/// Builds the `RawQuery` that buckets the addresses owning coins of `coin_type` by their total
+/// balance, given the bucket `thresholds`. Like the query for an address's balances, this considers
+/// the latest version of each coin from `objects_snapshot` and `objects_history` within the
+/// checkpoint `range`, discarding versions that have been superseded.
+fn balance_buckets_query(
+    coin_type: TypeTag,
+    thresholds: &[u128],
+    range: AvailableRange,
+) -> RawQuery {
+    let coin_type = coin_type
+        .to_canonical_display(/* with_prefix */ true)
+        .to_string();
+    let filter_coins = |query: RawQuery| {
+        filter!(
+            query,
+            format!(
+                "coin_type IS NOT NULL AND coin_type = {{}} AND owner_type = {}",
+                OwnerType::Address as i16
+            ),
+            coin_type.clone()
+        )
+    };
+
+    let in_range = format!(
+        "checkpoint_sequence_number BETWEEN {} AND {}",
+        range.first, range.last
+    );
+
+    let snapshot_coins = filter_coins(query!("SELECT * FROM objects_snapshot"));
+    let history_coins = filter!(
+        filter_coins(query!("SELECT * FROM objects_history")),
+        in_range.clone()
+    );
+
+    // Combine the two queries, and select the most recent version of each coin.
+    let candidates = query!(
+        r#"SELECT DISTINCT ON (object_id) * FROM (({}) UNION ALL ({})) o"#,
+        snapshot_coins,
+        history_coins
+    )
+    .order_by("object_id")
+    .order_by("object_version DESC");
+
+    // Coins may have been transferred to an object, or deleted, in a newer version.
+    let newer = filter!(
+        query!("SELECT object_id, object_version FROM objects_history"),
+        in_range
+    );
+
+    let balances = filter!(
+        query!(
+            r#"SELECT owner_id, SUM(coin_balance) AS balance
+            FROM ({}) candidates
+            LEFT JOIN ({}) newer
+            ON (
+                candidates.object_id = newer.object_id
+                AND candidates.object_version < newer.object_version
+            )"#,
+            candidates,
+            newer
+        ),
+        "newer.object_version IS NULL"
+    )
+    .group_by("owner_id");
+
+    let thresholds = thresholds
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    // The thresholds are interpolated rather than bound, because they are numbers that have
+    // already been validated.
+    let (balances, binds) = balances.finish();
+    let bucketed = RawQuery::new(
+        format!(
+            "SELECT width_bucket(balance, ARRAY[{thresholds}]::NUMERIC[]) AS bucket, balance \
+             FROM ({balances}) balances"
+        ),
+        binds,
+    );
+
+    query!(
+        "SELECT bucket, COUNT(*) AS owners, CAST(SUM(balance) AS TEXT) AS total_balance \
+         FROM ({}) bucketed",
+        bucketed
+    )
+    .group_by("bucket")
+}
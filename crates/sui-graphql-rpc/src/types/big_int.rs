@@ -1,7 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 use async_graphql::*;
 use move_core_types::u256::U256;
@@ -36,6 +36,12 @@ impl Description for BigInt {
     }
 }
 
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl FromStr for BigInt {
     type Err = NotANumber;
 
@@ -10,6 +10,7 @@ use sui_indexer::{models::display::StoredDisplay, schema::display};
 use sui_types::TypeTag;
 
 use crate::{
+    config::Limits,
     data::{Db, DbConnection, QueryExecutor},
     error::Error,
 };
@@ -19,6 +20,17 @@ pub(crate) struct Display {
     pub stored: StoredDisplay,
 }
 
+/// A named template to render a Move value with, in the same format as the templates of an
+/// on-chain `Display` object: `{field}` placeholders, separated by dots to access nested fields,
+/// are substituted with the value's fields.
+#[derive(InputObject)]
+pub(crate) struct DisplayTemplateEntry {
+    /// The identifier for the template.
+    pub key: String,
+    /// The template string.
+    pub value: String,
+}
+
 /// The set of named templates defined on-chain for the type of this object,
 /// to be handled off-chain. The server substitutes data from the object
 /// into these templates to generate a display string per template.
@@ -68,22 +80,28 @@ impl Display {
         Ok(stored.map(|stored| Display { stored }))
     }
 
-    /// Render the fields defined by this `Display` from the contents of `struct_`.
-    pub(crate) fn render(&self, struct_: &MoveStruct) -> Result<Vec<DisplayEntry>, Error> {
+    /// The `(key, template)` pairs defined by this `Display`.
+    pub(crate) fn templates(&self) -> Result<Vec<(String, String)>, Error> {
         let event = self
             .stored
             .to_display_update_event()
             .map_err(|e| Error::Internal(e.to_string()))?;
 
-        let mut rendered = vec![];
-        for entry in event.fields.contents {
-            rendered.push(match parse_template(&entry.value, struct_) {
-                Ok(v) => DisplayEntry::create_value(entry.key, v),
-                Err(e) => DisplayEntry::create_error(entry.key, e.to_string()),
-            });
-        }
+        Ok(event
+            .fields
+            .contents
+            .into_iter()
+            .map(|entry| (entry.key, entry.value))
+            .collect())
+    }
 
-        Ok(rendered)
+    /// Render the fields defined by this `Display` from the contents of `struct_`.
+    pub(crate) fn render(
+        &self,
+        struct_: &MoveStruct,
+        limits: &Limits,
+    ) -> Result<Vec<DisplayEntry>, Error> {
+        render_templates(self.templates()?, struct_, limits)
     }
 }
 
@@ -105,6 +123,44 @@ impl DisplayEntry {
     }
 }
 
+/// Render each `(key, template)` pair from the contents of `struct_`. A template that fails to
+/// render produces an entry with an error, without affecting the other entries, but the render as
+/// a whole fails if there are more templates than `limits` allow, or if together they produce more
+/// output than `limits` allow.
+pub(crate) fn render_templates(
+    templates: Vec<(String, String)>,
+    struct_: &MoveStruct,
+    limits: &Limits,
+) -> Result<Vec<DisplayEntry>, Error> {
+    if templates.len() > limits.max_display_entries as usize {
+        return Err(Error::Client(format!(
+            "Number of Display templates exceeds max limit of '{}'",
+            limits.max_display_entries
+        )));
+    }
+
+    let mut output_size = 0usize;
+    let mut entries = Vec::with_capacity(templates.len());
+    for (key, template) in templates {
+        let entry = match parse_template(&template, struct_) {
+            Ok(v) => DisplayEntry::create_value(key, v),
+            Err(e) => DisplayEntry::create_error(key, e.to_string()),
+        };
+
+        output_size += entry.value.as_ref().map_or(0, String::len);
+        if output_size > limits.max_display_output_size as usize {
+            return Err(Error::Client(format!(
+                "Display output exceeds max size of '{}' bytes",
+                limits.max_display_output_size
+            )));
+        }
+
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
 /// Handles the PART of the grammar, defined as:
 /// PART   ::= '{' CHAIN '}'
 ///          | '\{' | '\}'
@@ -1,12 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::error::Error;
 use crate::types::{
     digest::Digest,
     sui_address::SuiAddress,
     type_filter::{ModuleFilter, TypeFilter},
 };
 use async_graphql::*;
+use sui_indexer::config::IndexingConfig;
+use sui_types::base_types::ObjectID;
 
 #[derive(InputObject, Clone, Default)]
 pub(crate) struct EventFilter {
@@ -42,3 +45,110 @@ pub(crate) struct EventFilter {
     // pub start_time
     // pub end_time
 }
+
+impl EventFilter {
+    /// Fail if the indexer only keeps some transactions, and the filter could select events from
+    /// transactions that it dropped.
+    pub(crate) fn check_indexed(&self, ctx: &Context<'_>) -> Result<(), Error> {
+        let Some(indexing_config) = ctx.data_opt::<IndexingConfig>() else {
+            return Ok(());
+        };
+
+        if indexing_config.is_filtered() && !self.is_fully_indexed(indexing_config) {
+            return Err(Error::Client(
+                "The indexer only keeps events from transaction blocks that touch specific \
+                 packages, addresses or event types, so results for this filter would be \
+                 incomplete. Filter by an indexed `sender`, an `emittingModule` in an indexed \
+                 package, or an indexed `eventType`."
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether every event the filter selects comes from a transaction that is kept by an indexer
+    /// that only keeps the transactions selected by `config`.
+    fn is_fully_indexed(&self, config: &IndexingConfig) -> bool {
+        let indexed_package = |p: &SuiAddress| config.packages.contains(&ObjectID::from(*p));
+
+        let indexed_module = |m: &ModuleFilter| {
+            let (ModuleFilter::ByPackage(p) | ModuleFilter::ByModule(p, _)) = m;
+            indexed_package(p)
+        };
+
+        let indexed_type = |t: &TypeFilter| match t {
+            TypeFilter::ByModule(m) => indexed_module(m),
+            TypeFilter::ByType(tag) => {
+                indexed_package(&tag.address.into())
+                    || config.event_types.iter().any(|indexed| {
+                        (&indexed.address, &indexed.module, &indexed.name)
+                            == (&tag.address, &tag.module, &tag.name)
+                            && (indexed.type_params.is_empty() || indexed == tag)
+                    })
+            }
+        };
+
+        self.sender
+            .is_some_and(|s| config.addresses.contains(&s.into()))
+            || self.emitting_module.as_ref().is_some_and(indexed_module)
+            || self.event_type.as_ref().is_some_and(indexed_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use move_core_types::language_storage::StructTag;
+
+    use super::*;
+
+    #[test]
+    fn test_fully_indexed() {
+        let address = SuiAddress::from_str("0x43").unwrap();
+        let config = IndexingConfig {
+            packages: vec![SuiAddress::from_str("0x42").unwrap().into()],
+            addresses: vec![address.into()],
+            event_types: vec![
+                StructTag::from_str("0x2::coin::CoinEvent").unwrap(),
+                StructTag::from_str("0x3::pool::Swap<0x2::sui::SUI>").unwrap(),
+            ],
+            ..Default::default()
+        };
+
+        let fully_indexed = |filter: EventFilter| filter.is_fully_indexed(&config);
+        let event_type = |t: &str| EventFilter {
+            event_type: Some(TypeFilter::from_str(t).unwrap()),
+            ..Default::default()
+        };
+
+        assert!(fully_indexed(EventFilter {
+            sender: Some(address),
+            ..Default::default()
+        }));
+
+        assert!(fully_indexed(EventFilter {
+            emitting_module: Some(ModuleFilter::from_str("0x42::m").unwrap()),
+            ..Default::default()
+        }));
+
+        assert!(fully_indexed(event_type("0x42")));
+        assert!(fully_indexed(event_type("0x42::m::E<u64>")));
+        assert!(fully_indexed(event_type("0x2::coin::CoinEvent")));
+        assert!(fully_indexed(event_type("0x2::coin::CoinEvent<u64>")));
+        assert!(fully_indexed(event_type("0x3::pool::Swap<0x2::sui::SUI>")));
+
+        // Events of other types, or from other packages, may have been dropped.
+        assert!(!fully_indexed(EventFilter::default()));
+        assert!(!fully_indexed(event_type("0x2::coin")));
+        assert!(!fully_indexed(event_type("0x2::coin::Coin")));
+        assert!(!fully_indexed(event_type("0x3::pool::Swap")));
+        assert!(!fully_indexed(event_type("0x3::pool::Swap<u64>")));
+
+        assert!(!fully_indexed(EventFilter {
+            transaction_digest: Some(Digest::from_str("11111111111111111111111111111111").unwrap()),
+            ..Default::default()
+        }));
+    }
+}
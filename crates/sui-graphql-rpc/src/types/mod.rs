@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub(crate) mod address;
+pub(crate) mod aggregation;
 pub(crate) mod available_range;
 pub(crate) mod balance;
 pub(crate) mod balance_change;
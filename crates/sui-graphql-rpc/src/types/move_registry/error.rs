@@ -16,6 +16,9 @@ pub enum MoveRegistryError {
     #[error("Move Registry: External API url is not available so resolution is not on this RPC.")]
     ExternalApiUrlUnavailable,
 
+    #[error("Move Registry: Local registry is not available so resolution is not on this RPC.")]
+    LocalRegistryUnavailable,
+
     #[error(
         "Move Registry: Internal Error, failed to query external API due to an internal error: {0}"
     )]
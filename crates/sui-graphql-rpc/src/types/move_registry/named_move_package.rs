@@ -4,6 +4,7 @@
 use std::str::FromStr;
 
 use async_graphql::Context;
+use sui_transaction_builder::move_registry::LocalMoveRegistry;
 
 use crate::{
     config::{MoveRegistryConfig, ResolutionType},
@@ -40,6 +41,7 @@ impl NamedMovePackage {
             ResolutionType::External => {
                 query_external(ctx, config, versioned, checkpoint_viewed_at).await
             }
+            ResolutionType::Local => query_local(ctx, name, versioned, checkpoint_viewed_at).await,
         }
     }
 }
@@ -102,6 +104,40 @@ async fn query_external(
     .await
 }
 
+async fn query_local(
+    ctx: &Context<'_>,
+    name: &str,
+    versioned: VersionedName,
+    checkpoint_viewed_at: u64,
+) -> Result<Option<MovePackage>, Error> {
+    let Some(registry) = ctx.data_opt::<LocalMoveRegistry>() else {
+        return Err(MoveRegistryError::LocalRegistryUnavailable.into());
+    };
+
+    let Some((package_address, version)) = registry.resolve(name) else {
+        return Ok(None);
+    };
+
+    // A versioned name with its own entry in the registry refers to that exact package, rather
+    // than a version of the package at its address.
+    if versioned.version.is_some() && version.is_none() {
+        return MovePackage::query(
+            ctx,
+            package_address,
+            MovePackage::by_id_at(checkpoint_viewed_at),
+        )
+        .await;
+    }
+
+    let app_info = AppInfo {
+        package_info_id: None,
+        package_address: Some(package_address),
+        upgrade_cap_id: None,
+    };
+
+    package_from_app_info(ctx, app_info, version, checkpoint_viewed_at).await
+}
+
 async fn package_from_app_info(
     ctx: &Context<'_>,
     app_info: AppInfo,
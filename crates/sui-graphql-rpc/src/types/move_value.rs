@@ -11,10 +11,17 @@ use move_core_types::{
 use serde::{Deserialize, Serialize};
 use sui_types::object::bounded_visitor::BoundedVisitor;
 
+use crate::config::{Limits, ServiceConfig};
 use crate::data::package_resolver::PackageResolver;
 use crate::{error::Error, types::json::Json, types::move_type::unexpected_signer_error};
 
-use super::{base64::Base64, big_int::BigInt, move_type::MoveType, sui_address::SuiAddress};
+use super::{
+    base64::Base64,
+    big_int::BigInt,
+    display::{render_templates, Display, DisplayEntry, DisplayTemplateEntry},
+    move_type::MoveType,
+    sui_address::SuiAddress,
+};
 
 const STD: AccountAddress = AccountAddress::ONE;
 const SUI: AccountAddress = AccountAddress::TWO;
@@ -29,6 +36,10 @@ const TYP_OPTION: &IdentStr = ident_str!("Option");
 const TYP_STRING: &IdentStr = ident_str!("String");
 const TYP_UID: &IdentStr = ident_str!("UID");
 
+/// Maximum number of fields a path can select through, matching the depth allowed for the fields
+/// referenced in `Display` templates.
+const MAX_PATH_DEPTH: usize = 10;
+
 #[derive(SimpleObject)]
 #[graphql(complex)]
 pub(crate) struct MoveValue {
@@ -121,7 +132,12 @@ impl MoveValue {
     ///
     /// This form is offered as a less verbose convenience in cases where the layout of the type is
     /// known by the client.
-    async fn json(&self, ctx: &Context<'_>) -> Result<Json> {
+    ///
+    /// If a `path` is supplied, only the part of the value it selects is returned. The path is a
+    /// sequence of field names (or indices, for vectors) separated by dots, as in
+    /// `balance.value`, or `items.0.name`, and is applied to the JSON representation, so optional
+    /// values are selected through without naming their contents.
+    async fn json(&self, ctx: &Context<'_>, path: Option<String>) -> Result<Json> {
         let resolver: &PackageResolver = ctx
             .data()
             .map_err(|_| Error::Internal("Unable to fetch Package Cache.".to_string()))
@@ -135,7 +151,51 @@ impl MoveValue {
         };
 
         // Factor out into its own non-GraphQL, non-async function for better testability
-        self.json_impl(layout).extend()
+        self.json_impl(layout, path.as_deref()).extend()
+    }
+
+    /// Render this value using `Display` templates: the `template` entries supplied by the caller,
+    /// or if none are supplied, the templates of the `Display` object registered on-chain for the
+    /// value's type.
+    ///
+    /// Returns `null` if the value is not a struct, or if no templates were supplied and its type
+    /// has no `Display` object.
+    async fn display(
+        &self,
+        ctx: &Context<'_>,
+        template: Option<Vec<DisplayTemplateEntry>>,
+    ) -> Result<Option<Vec<DisplayEntry>>> {
+        let templates = match template {
+            Some(template) => template.into_iter().map(|t| (t.key, t.value)).collect(),
+            None => {
+                let Ok(tag @ TypeTag::Struct(_)) = self.type_.native.as_type_tag() else {
+                    return Ok(None);
+                };
+
+                let Some(display) = Display::query(ctx.data_unchecked(), tag).await.extend()?
+                else {
+                    return Ok(None);
+                };
+
+                display.templates().extend()?
+            }
+        };
+
+        let resolver: &PackageResolver = ctx
+            .data()
+            .map_err(|_| Error::Internal("Unable to fetch Package Cache.".to_string()))
+            .extend()?;
+
+        let Some(layout) = self.type_.layout_impl(resolver).await.extend()? else {
+            return Err(Error::Internal(
+                "Move value must have valid layout".to_string(),
+            ))
+            .extend();
+        };
+
+        let config: &ServiceConfig = ctx.data_unchecked();
+        self.display_impl(layout, templates, &config.limits)
+            .extend()
     }
 }
 
@@ -160,8 +220,26 @@ impl MoveValue {
         MoveData::try_from(self.value_impl(layout)?)
     }
 
-    fn json_impl(&self, layout: A::MoveTypeLayout) -> Result<Json, Error> {
-        Ok(try_to_json_value(self.value_impl(layout)?)?.into())
+    fn json_impl(&self, layout: A::MoveTypeLayout, path: Option<&str>) -> Result<Json, Error> {
+        let value = try_to_json_value(self.value_impl(layout)?)?;
+        Ok(match path {
+            Some(path) => select_path(value, path)?,
+            None => value,
+        }
+        .into())
+    }
+
+    fn display_impl(
+        &self,
+        layout: A::MoveTypeLayout,
+        templates: Vec<(String, String)>,
+        limits: &Limits,
+    ) -> Result<Option<Vec<DisplayEntry>>, Error> {
+        let A::MoveValue::Struct(struct_) = self.value_impl(layout)? else {
+            return Ok(None);
+        };
+
+        Ok(Some(render_templates(templates, &struct_, limits)?))
     }
 }
 
@@ -324,6 +402,43 @@ fn try_to_json_value(value: A::MoveValue) -> Result<Value, Error> {
     })
 }
 
+/// Select the part of the JSON `value` that `path` refers to. Each dot-separated part of the path
+/// names a field of an object, or the index of an element in a list.
+fn select_path(mut value: Value, path: &str) -> Result<Value, Error> {
+    let parts: Vec<_> = path.split('.').collect();
+    if parts.len() > MAX_PATH_DEPTH {
+        return Err(Error::Client(format!(
+            "Path '{path}' exceeds maximum depth of {MAX_PATH_DEPTH}"
+        )));
+    }
+
+    for part in parts {
+        value = match value {
+            Value::Object(mut fields) => fields.swap_remove(part).ok_or_else(|| {
+                Error::Client(format!("Field '{part}' not found in path '{path}'"))
+            })?,
+
+            Value::List(mut elems) => match part.parse::<usize>() {
+                Ok(ix) if ix < elems.len() => elems.swap_remove(ix),
+                _ => {
+                    return Err(Error::Client(format!(
+                        "Index '{part}' out of bounds in path '{path}'"
+                    )))
+                }
+            },
+
+            _ => {
+                return Err(Error::Client(format!(
+                    "Cannot select '{part}' from a value that is not a struct or vector in path \
+                     '{path}'"
+                )))
+            }
+        };
+    }
+
+    Ok(value)
+}
+
 fn is_type(tag: &StructTag, address: &AccountAddress, module: &IdentStr, name: &IdentStr) -> bool {
     &tag.address == address
         && tag.module.as_ident_str() == module
@@ -527,10 +642,18 @@ mod tests {
     }
 
     fn json<T: Serialize>(layout: A::MoveTypeLayout, data: T) -> Result<Json, Error> {
+        json_at(layout, data, None)
+    }
+
+    fn json_at<T: Serialize>(
+        layout: A::MoveTypeLayout,
+        data: T,
+        path: Option<&str>,
+    ) -> Result<Json, Error> {
         let tag: TypeTag = (&layout).into();
         let type_ = MoveType::from(tag);
         let bcs = Base64(bcs::to_bytes(&data).unwrap());
-        MoveValue { type_, bcs }.json_impl(layout)
+        MoveValue { type_, bcs }.json_impl(layout, path)
     }
 
     #[test]
@@ -948,6 +1071,190 @@ mod tests {
         expect.assert_eq(&format!("{v}"));
     }
 
+    #[test]
+    fn json_path() {
+        let l = struct_layout!("0x42::foo::Bar" {
+            "balance": struct_layout!("0x2::balance::Balance" { "value": L::U64 }),
+            "items": vector_layout!(struct_layout!("0x42::foo::Item" {
+                "name": struct_layout!("0x1::string::String" {
+                    "bytes": vector_layout!(L::U8),
+                }),
+            })),
+        });
+
+        let data = (42u64, vec!["first", "second"]);
+
+        let v = json_at(l.clone(), data.clone(), Some("balance.value")).unwrap();
+        let expect = expect![[r#""42""#]];
+        expect.assert_eq(&format!("{v}"));
+
+        let v = json_at(l.clone(), data.clone(), Some("items.1.name")).unwrap();
+        let expect = expect![[r#""second""#]];
+        expect.assert_eq(&format!("{v}"));
+
+        let v = json_at(l, data, Some("items.1")).unwrap();
+        let expect = expect![[r#"{name: "second"}"#]];
+        expect.assert_eq(&format!("{v}"));
+    }
+
+    #[test]
+    fn json_path_errors() {
+        let l = struct_layout!("0x42::foo::Bar" {
+            "balance": struct_layout!("0x2::balance::Balance" { "value": L::U64 }),
+            "items": vector_layout!(L::U8),
+        });
+
+        let data = (42u64, vec![1u8]);
+
+        let v = json_at(l.clone(), data.clone(), Some("balance.amount"));
+        let expect = expect![[r#"
+            Err(
+                Client(
+                    "Field 'amount' not found in path 'balance.amount'",
+                ),
+            )"#]];
+        expect.assert_eq(&format!("{v:#?}"));
+
+        let v = json_at(l.clone(), data.clone(), Some("items.1"));
+        let expect = expect![[r#"
+            Err(
+                Client(
+                    "Index '1' out of bounds in path 'items.1'",
+                ),
+            )"#]];
+        expect.assert_eq(&format!("{v:#?}"));
+
+        let v = json_at(l, data, Some("balance.value.inner"));
+        let expect = expect![[r#"
+            Err(
+                Client(
+                    "Cannot select 'inner' from a value that is not a struct or vector in path 'balance.value.inner'",
+                ),
+            )"#]];
+        expect.assert_eq(&format!("{v:#?}"));
+    }
+
+    fn display<T: Serialize>(
+        layout: A::MoveTypeLayout,
+        data: T,
+        templates: &[(&str, &str)],
+        limits: &Limits,
+    ) -> Result<Option<Vec<DisplayEntry>>, Error> {
+        let tag: TypeTag = (&layout).into();
+        let type_ = MoveType::from(tag);
+        let bcs = Base64(bcs::to_bytes(&data).unwrap());
+        let templates = templates
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        MoveValue { type_, bcs }.display_impl(layout, templates, limits)
+    }
+
+    #[test]
+    fn display_templates() {
+        let l = struct_layout!("0x42::foo::Bar" {
+            "name": struct_layout!("0x1::string::String" {
+                "bytes": vector_layout!(L::U8),
+            }),
+            "balance": struct_layout!("0x2::balance::Balance" { "value": L::U64 }),
+        });
+
+        let v = display(
+            l,
+            ("Alice", 42u64),
+            &[
+                ("greeting", "Hello, {name}!"),
+                ("balance", "{balance.value} MIST"),
+                ("missing", "{balance.amount}"),
+            ],
+            &Limits::default(),
+        );
+
+        let expect = expect![[r#"
+            Ok(
+                Some(
+                    [
+                        DisplayEntry {
+                            key: "greeting",
+                            value: Some(
+                                "Hello, Alice!",
+                            ),
+                            error: None,
+                        },
+                        DisplayEntry {
+                            key: "balance",
+                            value: Some(
+                                "42 MIST",
+                            ),
+                            error: None,
+                        },
+                        DisplayEntry {
+                            key: "missing",
+                            value: None,
+                            error: Some(
+                                "Field 'amount' not found",
+                            ),
+                        },
+                    ],
+                ),
+            )"#]];
+        expect.assert_eq(&format!("{v:#?}"));
+    }
+
+    #[test]
+    fn display_non_struct() {
+        let v = display(L::U64, 42u64, &[("value", "{value}")], &Limits::default());
+        let expect = expect!["Ok(None)"];
+        expect.assert_eq(&format!("{v:?}"));
+    }
+
+    #[test]
+    fn display_limits() {
+        let l = struct_layout!("0x42::foo::Bar" { "value": L::U64 });
+        let limits = Limits {
+            max_display_entries: 2,
+            max_display_output_size: 8,
+            ..Limits::default()
+        };
+
+        // Entries that fail to render don't count towards the output size.
+        let v = display(
+            l.clone(),
+            42u64,
+            &[("a", "{value}"), ("b", "{missing}")],
+            &limits,
+        );
+        assert!(v.is_ok(), "{v:?}");
+
+        let v = display(
+            l.clone(),
+            42u64,
+            &[("a", "{value}"), ("b", "{value}"), ("c", "{value}")],
+            &limits,
+        );
+        let expect = expect![[r#"
+            Err(
+                Client(
+                    "Number of Display templates exceeds max limit of '2'",
+                ),
+            )"#]];
+        expect.assert_eq(&format!("{v:#?}"));
+
+        let v = display(
+            l,
+            42u64,
+            &[("a", "{value}"), ("b", "value {value}")],
+            &limits,
+        );
+        let expect = expect![[r#"
+            Err(
+                Client(
+                    "Display output exceeds max size of '8' bytes",
+                ),
+            )"#]];
+        expect.assert_eq(&format!("{v:#?}"));
+    }
+
     #[test]
     fn signer_value() {
         let v = data(L::Signer, address("0x42"));
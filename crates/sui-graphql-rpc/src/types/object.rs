@@ -24,6 +24,7 @@ use super::transaction_block::TransactionBlockFilter;
 use super::type_filter::{ExactTypeFilter, TypeFilter};
 use super::uint53::UInt53;
 use super::{owner::Owner, sui_address::SuiAddress, transaction_block::TransactionBlock};
+use crate::config::ServiceConfig;
 use crate::connection::ScanConnection;
 use crate::consistency::{build_objects_query, Checkpointed, View};
 use crate::data::package_resolver::PackageResolver;
@@ -721,7 +722,8 @@ impl ObjectImpl<'_> {
             return Ok(None);
         };
 
-        Ok(Some(display.render(&move_struct).extend()?))
+        let config: &ServiceConfig = ctx.data_unchecked();
+        Ok(Some(display.render(&move_struct, &config.limits).extend()?))
     }
 }
 
@@ -951,6 +953,29 @@ impl Object {
         }
     }
 
+    /// Look up multiple objects by their IDs and versions, as of `checkpoint_viewed_at`. Returns a
+    /// map from `(id, version)` to the objects that could be found, as the DB does not return them
+    /// in the order of `keys`.
+    pub(crate) async fn multi_query(
+        ctx: &Context<'_>,
+        keys: Vec<ObjectKey>,
+        checkpoint_viewed_at: u64,
+    ) -> Result<HashMap<(SuiAddress, u64), Self>, Error> {
+        let DataLoader(loader) = &ctx.data_unchecked();
+        let result = loader
+            .load_many(keys.into_iter().map(|key| HistoricalKey {
+                id: key.object_id,
+                version: key.version.into(),
+                checkpoint_viewed_at,
+            }))
+            .await?;
+
+        Ok(result
+            .into_iter()
+            .map(|(k, v)| ((k.id, k.version), v))
+            .collect())
+    }
+
     /// Query for a singleton object identified by its type. Note: the object is assumed to be a
     /// singleton (we either find at least one object with this type and then return it, or return
     /// nothing).
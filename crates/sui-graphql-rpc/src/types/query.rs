@@ -21,7 +21,9 @@ use super::suins_registration::NameService;
 use super::uint53::UInt53;
 use super::{
     address::Address,
+    aggregation::{self, CoinBalanceBucket, EventTypeCount, SenderGasUsage},
     available_range::AvailableRange,
+    big_int::BigInt,
     chain_identifier::ChainIdentifier,
     checkpoint::{self, Checkpoint, CheckpointId},
     coin::Coin,
@@ -32,7 +34,7 @@ use super::{
     epoch::Epoch,
     event::{self, Event, EventFilter},
     move_type::MoveType,
-    object::{self, Object, ObjectFilter},
+    object::{self, Object, ObjectFilter, ObjectKey},
     owner::Owner,
     protocol_config::ProtocolConfigs,
     sui_address::SuiAddress,
@@ -47,10 +49,10 @@ use crate::types::base64::Base64 as GraphQLBase64;
 use crate::types::zklogin_verify_signature::verify_zklogin_signature;
 use crate::types::zklogin_verify_signature::ZkLoginIntentScope;
 use crate::types::zklogin_verify_signature::ZkLoginVerifyResult;
-use crate::{config::ServiceConfig, error::Error, mutation::Mutation};
+use crate::{config::ServiceConfig, error::Error, mutation::Mutation, subscription::Subscription};
 
 pub(crate) struct Query;
-pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, EmptySubscription>;
+pub(crate) type SuiGraphQLSchema = async_graphql::Schema<Query, Mutation, Subscription>;
 
 #[Object]
 impl Query {
@@ -205,13 +207,19 @@ impl Query {
     /// If `rootVersion` is omitted, dynamic fields will be from a consistent snapshot of the Sui
     /// state at the latest checkpoint known to the GraphQL RPC. Similarly, `Owner.asObject` will
     /// return the object's version at the latest checkpoint.
+    ///
+    /// `atCheckpoint` views the Owner as of an earlier checkpoint in the available range instead
+    /// of the latest one. Its dynamic fields are then the fields that existed at that checkpoint,
+    /// at their versions as of that checkpoint, including fields that have been deleted since.
+    /// Pages of dynamic fields fetched using their cursors stay at that checkpoint.
     async fn owner(
         &self,
         ctx: &Context<'_>,
         address: SuiAddress,
         root_version: Option<UInt53>,
+        at_checkpoint: Option<UInt53>,
     ) -> Result<Option<Owner>> {
-        let Watermark { checkpoint, .. } = *ctx.data()?;
+        let checkpoint = checkpoint_to_view(ctx, at_checkpoint).await?;
         Ok(Some(Owner {
             address,
             checkpoint_viewed_at: checkpoint,
@@ -221,13 +229,18 @@ impl Query {
 
     /// The object corresponding to the given address at the (optionally) given version.
     /// When no version is given, the latest version is returned.
+    ///
+    /// `atCheckpoint` views the object as of an earlier checkpoint in the available range instead
+    /// of the latest one: without a version, the object's version at that checkpoint is returned,
+    /// and its dynamic fields are the ones it had then (see `Query.owner`).
     async fn object(
         &self,
         ctx: &Context<'_>,
         address: SuiAddress,
         version: Option<UInt53>,
+        at_checkpoint: Option<UInt53>,
     ) -> Result<Option<Object>> {
-        let Watermark { checkpoint, .. } = *ctx.data()?;
+        let checkpoint = checkpoint_to_view(ctx, at_checkpoint).await?;
         let key = match version {
             Some(version) => Object::at_version(version.into(), checkpoint),
             None => Object::latest_at(checkpoint),
@@ -236,6 +249,29 @@ impl Query {
         Object::query(ctx, address, key).await.extend()
     }
 
+    /// The objects corresponding to the given keys (object IDs and versions), in the order of the
+    /// keys, with `null` in place of any object that does not exist at its key's version. At most
+    /// `serviceConfig.maxMultiGetSize` keys can be given, and the objects are fetched together.
+    async fn multi_get_objects(
+        &self,
+        ctx: &Context<'_>,
+        keys: Vec<ObjectKey>,
+    ) -> Result<Vec<Option<Object>>> {
+        let Watermark { checkpoint, .. } = *ctx.data()?;
+        check_multi_get_size(ctx, keys.len())?;
+
+        let lookups: Vec<_> = keys
+            .iter()
+            .map(|key| (key.object_id, u64::from(key.version)))
+            .collect();
+
+        let objects = Object::multi_query(ctx, keys, checkpoint).await.extend()?;
+        Ok(lookups
+            .into_iter()
+            .map(|lookup| objects.get(&lookup).cloned())
+            .collect())
+    }
+
     /// The package corresponding to the given address (at the optionally given version).
     ///
     /// When no version is given, the package is loaded directly from the address given. Otherwise,
@@ -328,6 +364,29 @@ impl Query {
             .extend()
     }
 
+    /// The transaction blocks with the given digests, in the order of the digests, with `null` in
+    /// place of any transaction block that could not be found. At most
+    /// `serviceConfig.maxMultiGetSize` digests can be given, and the transaction blocks are
+    /// fetched together. If the indexer only keeps some transaction blocks, a block that could not
+    /// be found is an error instead, as it may exist without being indexed.
+    async fn multi_get_transaction_blocks(
+        &self,
+        ctx: &Context<'_>,
+        digests: Vec<Digest>,
+    ) -> Result<Vec<Option<TransactionBlock>>> {
+        let Watermark { checkpoint, .. } = *ctx.data()?;
+        check_multi_get_size(ctx, digests.len())?;
+
+        let transactions = TransactionBlock::multi_query(ctx, digests.clone(), checkpoint)
+            .await
+            .extend()?;
+
+        Ok(digests
+            .into_iter()
+            .map(|digest| transactions.get(&digest).cloned())
+            .collect())
+    }
+
     /// The coin objects that exist in the network.
     ///
     /// The type field is a string of the inner type of the coin by which to filter (e.g.
@@ -437,15 +496,85 @@ impl Query {
     ) -> Result<Connection<String, Event>> {
         let Watermark { checkpoint, .. } = *ctx.data()?;
 
+        let filter = filter.unwrap_or_default();
+        filter.check_indexed(ctx).extend()?;
+
         let page = Page::from_params(ctx.data_unchecked(), first, after, last, before)?;
-        Event::paginate(
-            ctx.data_unchecked(),
-            page,
-            filter.unwrap_or_default(),
+        Event::paginate(ctx.data_unchecked(), page, filter, checkpoint)
+            .await
+            .extend()
+    }
+
+    /// The number of transaction blocks that match `filter`. Counting transactions filtered only
+    /// by checkpoint is cheap, but counts that apply other filters are rejected if the database
+    /// estimates they would cost more than `serviceConfig.maxAggregationCost`. Returns null if
+    /// none of the checkpoints that `filter` selects are available.
+    async fn transaction_block_count(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<TransactionBlockFilter>,
+    ) -> Result<Option<UInt53>> {
+        let Watermark { checkpoint, .. } = *ctx.data()?;
+        aggregation::transaction_block_count(ctx, filter.unwrap_or_default(), checkpoint).await
+    }
+
+    /// The addresses that used the most gas (net of storage rebates) sending transactions between
+    /// `afterCheckpoint` and `beforeCheckpoint` (exclusive), ordered by the gas they used, from
+    /// most to least. At most `first` addresses are returned, and by default, the range covers
+    /// all transactions known to GraphQL. Ranges that include transactions indexed before gas
+    /// usage was recorded are rejected.
+    async fn gas_used_by_sender(
+        &self,
+        ctx: &Context<'_>,
+        after_checkpoint: Option<UInt53>,
+        before_checkpoint: Option<UInt53>,
+        first: Option<u64>,
+    ) -> Result<Vec<SenderGasUsage>> {
+        let Watermark { checkpoint, .. } = *ctx.data()?;
+        aggregation::gas_used_by_sender(
+            ctx,
+            after_checkpoint.map(u64::from),
+            before_checkpoint.map(u64::from),
+            first,
             checkpoint,
         )
         .await
-        .extend()
+    }
+
+    /// The types of the events emitted between `afterCheckpoint` and `beforeCheckpoint`
+    /// (exclusive), with the number of events of each type, from most to least emitted. At most
+    /// `first` types are returned, and by default, the range covers all transactions known to
+    /// GraphQL.
+    async fn event_counts_by_type(
+        &self,
+        ctx: &Context<'_>,
+        after_checkpoint: Option<UInt53>,
+        before_checkpoint: Option<UInt53>,
+        first: Option<u64>,
+    ) -> Result<Vec<EventTypeCount>> {
+        let Watermark { checkpoint, .. } = *ctx.data()?;
+        aggregation::event_counts_by_type(
+            ctx,
+            after_checkpoint.map(u64::from),
+            before_checkpoint.map(u64::from),
+            first,
+            checkpoint,
+        )
+        .await
+    }
+
+    /// How the addresses holding coins of `coinType` are distributed by their total balance.
+    /// `thresholds` must be strictly increasing, and split balances into buckets: one below the
+    /// first threshold, one between each consecutive pair of thresholds, and one from the last
+    /// threshold upwards. Every bucket is returned, including empty ones.
+    async fn coin_balance_distribution(
+        &self,
+        ctx: &Context<'_>,
+        coin_type: ExactTypeFilter,
+        thresholds: Vec<BigInt>,
+    ) -> Result<Vec<CoinBalanceBucket>> {
+        let Watermark { checkpoint, .. } = *ctx.data()?;
+        aggregation::coin_balance_distribution(ctx, coin_type.0, thresholds, checkpoint).await
     }
 
     /// The objects that exist in the network.
@@ -601,6 +730,52 @@ impl Query {
     }
 }
 
+/// The checkpoint to view data at: `at_checkpoint` if it is provided, otherwise the latest
+/// checkpoint known to the service. Only checkpoints in the available range can be viewed: data
+/// from before its first checkpoint may have been pruned.
+async fn checkpoint_to_view(ctx: &Context<'_>, at_checkpoint: Option<UInt53>) -> Result<u64> {
+    let Watermark { checkpoint, .. } = *ctx.data()?;
+    let Some(at_checkpoint) = at_checkpoint.map(u64::from) else {
+        return Ok(checkpoint);
+    };
+
+    if at_checkpoint > checkpoint {
+        return Err(Error::Client(format!(
+            "Cannot view data at checkpoint {at_checkpoint}, after the latest checkpoint \
+             {checkpoint}"
+        )))
+        .extend();
+    }
+
+    let AvailableRange { first, .. } = AvailableRange::query(ctx.data_unchecked(), checkpoint)
+        .await
+        .extend()?;
+
+    if at_checkpoint < first {
+        return Err(Error::Client(format!(
+            "Cannot view data at checkpoint {at_checkpoint}, before the first available \
+             checkpoint {first}"
+        )))
+        .extend();
+    }
+
+    Ok(at_checkpoint)
+}
+
+/// Multi-get queries accept at most `max_multi_get_size` keys.
+fn check_multi_get_size(ctx: &Context<'_>, size: usize) -> Result<()> {
+    let config: &ServiceConfig = ctx.data_unchecked();
+    let max = config.limits.max_multi_get_size;
+    if size > max as usize {
+        return Err(Error::Client(format!(
+            "Number of keys ({size}) exceeds max limit of '{max}'"
+        )))
+        .extend();
+    }
+
+    Ok(())
+}
+
 fn deserialize_tx_data<T>(tx_bytes: &str) -> Result<T>
 where
     T: DeserializeOwned,
@@ -2,10 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::TransactionBlockKindInput;
-use crate::types::{digest::Digest, sui_address::SuiAddress, type_filter::FqNameFilter};
+use crate::error::Error;
+use crate::types::{
+    digest::Digest,
+    sui_address::SuiAddress,
+    type_filter::{FqNameFilter, ModuleFilter},
+};
 use crate::types::{intersect, uint53::UInt53};
-use async_graphql::InputObject;
+use async_graphql::{Context, InputObject};
 use std::collections::BTreeSet;
+use sui_indexer::config::{IndexingConfig, TableGroup};
 use sui_types::base_types::SuiAddress as NativeSuiAddress;
 
 #[derive(InputObject, Debug, Default, Clone)]
@@ -134,6 +140,69 @@ impl TransactionBlockFilter {
             > 1
     }
 
+    /// Whether the filter is served from the `tx_*` lookup tables, apart from `tx_digests` (which
+    /// the indexer always populates).
+    pub(crate) fn requires_tx_indices(&self) -> bool {
+        Self {
+            transaction_ids: None,
+            ..self.clone()
+        }
+        .has_filters()
+    }
+
+    /// Fail if the filter needs lookup tables that the indexer does not populate, or if the
+    /// indexer only keeps some transactions and the filter could select ones it dropped.
+    pub(crate) fn check_indexed(&self, ctx: &Context<'_>) -> Result<(), Error> {
+        let Some(indexing_config) = ctx.data_opt::<IndexingConfig>() else {
+            return Ok(());
+        };
+
+        let group = TableGroup::TxIndices;
+        if self.requires_tx_indices() && !indexing_config.is_enabled(group) {
+            return Err(Error::Client(format!(
+                "Only filtering transaction blocks by checkpoint or digest is supported: the \
+                 indexer does not populate the {} tables (table group {group} is disabled).",
+                group.tables(),
+            )));
+        }
+
+        if indexing_config.is_filtered() && !self.is_fully_indexed(indexing_config) {
+            return Err(Error::Client(
+                "The indexer only keeps transaction blocks that touch specific packages, addresses \
+                 or event types, so results for this filter would be incomplete. Filter by a \
+                 `function` in an indexed package, or by an indexed `sentAddress`, `signAddress` \
+                 or `recvAddress`."
+                    .to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Whether every transaction the filter selects is kept by an indexer that only keeps the
+    /// transactions selected by `config`.
+    fn is_fully_indexed(&self, config: &IndexingConfig) -> bool {
+        let indexed_address =
+            |a: &Option<SuiAddress>| a.is_some_and(|a| config.addresses.contains(&a.into()));
+
+        let indexed_function = self.function.as_ref().is_some_and(|f| {
+            let (FqNameFilter::ByModule(ModuleFilter::ByPackage(p))
+            | FqNameFilter::ByModule(ModuleFilter::ByModule(p, _))
+            | FqNameFilter::ByFqName(p, _, _)) = f;
+            config.packages.contains(&(*p).into())
+        });
+
+        let fully_indexed = indexed_function
+            || indexed_address(&self.sent_address)
+            || indexed_address(&self.sign_address)
+            || indexed_address(&self.recv_address);
+
+        #[cfg(feature = "staging")]
+        let fully_indexed = fully_indexed || indexed_address(&self.affected_address);
+
+        fully_indexed
+    }
+
     /// If we don't query a lookup table that has a denormalized sender column, we need to
     /// explicitly specify the sender with a query on `tx_sender`. This function returns the sender
     /// we need to add an explicit query for if one is required, or `None` otherwise.
@@ -202,3 +271,54 @@ impl TransactionBlockFilter {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_fully_indexed() {
+        let package = SuiAddress::from_str("0x42").unwrap();
+        let address = SuiAddress::from_str("0x43").unwrap();
+        let config = IndexingConfig {
+            packages: vec![package.into()],
+            addresses: vec![address.into()],
+            ..Default::default()
+        };
+
+        let fully_indexed = |filter: TransactionBlockFilter| filter.is_fully_indexed(&config);
+
+        assert!(fully_indexed(TransactionBlockFilter {
+            function: Some(FqNameFilter::from_str("0x42::m::f").unwrap()),
+            ..Default::default()
+        }));
+
+        assert!(fully_indexed(TransactionBlockFilter {
+            function: Some(FqNameFilter::from_str("0x42::m").unwrap()),
+            sent_address: Some(SuiAddress::from_str("0x44").unwrap()),
+            ..Default::default()
+        }));
+
+        assert!(fully_indexed(TransactionBlockFilter {
+            recv_address: Some(address),
+            at_checkpoint: Some(UInt53::from(1)),
+            ..Default::default()
+        }));
+
+        // Transactions sent by other addresses, or calling other packages, may have been dropped.
+        assert!(!fully_indexed(TransactionBlockFilter::default()));
+
+        assert!(!fully_indexed(TransactionBlockFilter {
+            at_checkpoint: Some(UInt53::from(1)),
+            ..Default::default()
+        }));
+
+        assert!(!fully_indexed(TransactionBlockFilter {
+            function: Some(FqNameFilter::from_str("0x2").unwrap()),
+            sent_address: Some(package),
+            ..Default::default()
+        }));
+    }
+}
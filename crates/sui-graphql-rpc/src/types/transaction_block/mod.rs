@@ -28,6 +28,7 @@ use fastcrypto::encoding::{Base58, Encoding};
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use sui_indexer::{
+    config::IndexingConfig,
     models::transactions::StoredTransaction,
     schema::{transactions, tx_digests},
 };
@@ -239,12 +240,18 @@ impl TransactionBlock {
         checkpoint_viewed_at: u64,
     ) -> Result<Option<Self>, Error> {
         let DataLoader(loader) = ctx.data_unchecked();
-        loader
+        let transaction = loader
             .load_one(DigestKey {
                 digest,
                 checkpoint_viewed_at,
             })
-            .await
+            .await?;
+
+        if transaction.is_none() {
+            Self::check_not_dropped(ctx, digest)?;
+        }
+
+        Ok(transaction)
     }
 
     /// Look up multiple `TransactionBlock`s by their digests. Returns a map from those digests to
@@ -258,13 +265,33 @@ impl TransactionBlock {
     ) -> Result<BTreeMap<Digest, Self>, Error> {
         let DataLoader(loader) = ctx.data_unchecked();
         let result = loader
-            .load_many(digests.into_iter().map(|digest| DigestKey {
-                digest,
+            .load_many(digests.iter().map(|digest| DigestKey {
+                digest: *digest,
                 checkpoint_viewed_at,
             }))
             .await?;
 
-        Ok(result.into_iter().map(|(k, v)| (k.digest, v)).collect())
+        let result: BTreeMap<_, _> = result.into_iter().map(|(k, v)| (k.digest, v)).collect();
+        if let Some(missing) = digests.into_iter().find(|d| !result.contains_key(d)) {
+            Self::check_not_dropped(ctx, missing)?;
+        }
+
+        Ok(result)
+    }
+
+    /// Fail if the indexer only keeps some transactions, in which case the transaction block with
+    /// `digest`, which could not be found, may have been dropped rather than not exist.
+    fn check_not_dropped(ctx: &Context<'_>, digest: Digest) -> Result<(), Error> {
+        let indexing_config: Option<&IndexingConfig> = ctx.data_opt();
+        if indexing_config.is_some_and(|c| c.is_filtered()) {
+            return Err(Error::Client(format!(
+                "Transaction block {digest} could not be found. The indexer only keeps transaction \
+                 blocks that touch specific packages, addresses or event types, so it may exist \
+                 without being indexed."
+            )));
+        }
+
+        Ok(())
     }
 
     /// Query the database for a `page` of TransactionBlocks. The page uses `tx_sequence_number` and
@@ -315,6 +342,8 @@ impl TransactionBlock {
             }
         }
 
+        filter.check_indexed(ctx)?;
+
         // If page size or scan limit is 0, we want to standardize behavior by returning an empty
         // connection
         if filter.is_empty() || page.limit() == 0 || scan_limit.is_some_and(|v| v == 0) {
@@ -0,0 +1,8 @@
+-- Query documents that GraphQL clients can refer to by the SHA-256 hash of their text. Rows are
+-- managed by operators, and read by the GraphQL service.
+CREATE TABLE persisted_queries
+(
+    query_hash          BYTEA    NOT NULL,
+    query               TEXT     NOT NULL,
+    PRIMARY KEY(query_hash)
+);
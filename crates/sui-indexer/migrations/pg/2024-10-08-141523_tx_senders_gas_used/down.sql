@@ -0,0 +1 @@
+ALTER TABLE tx_senders DROP COLUMN gas_used;
@@ -0,0 +1,3 @@
+-- Net gas used by the transaction (computation and storage costs, less the storage rebate), or
+-- NULL for transactions indexed before the column was added.
+ALTER TABLE tx_senders ADD COLUMN gas_used BIGINT;
@@ -0,0 +1,7 @@
+-- The selective indexing configuration that the writer was started with, serialized as JSON, so
+-- that readers can tell which queries the indexed data can serve. Holds at most one row, with id 0.
+CREATE TABLE indexing_config
+(
+    id                  SMALLINT PRIMARY KEY,
+    config              TEXT     NOT NULL
+);
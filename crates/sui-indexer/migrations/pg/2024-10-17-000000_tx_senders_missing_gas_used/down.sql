@@ -0,0 +1 @@
+DROP INDEX CONCURRENTLY IF EXISTS tx_senders_missing_gas_used;
@@ -0,0 +1,6 @@
+-- Finds transactions indexed before `gas_used` was added, so that gas aggregations can refuse to
+-- report totals over ranges that include them.
+CREATE INDEX CONCURRENTLY IF NOT EXISTS
+    tx_senders_missing_gas_used
+ON  tx_senders (tx_sequence_number)
+WHERE gas_used IS NULL;
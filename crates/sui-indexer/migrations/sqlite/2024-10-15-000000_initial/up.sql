@@ -0,0 +1,386 @@
+-- The same tables as the Postgres schema, for running the indexer against an embedded SQLite
+-- database during local development. Array and JSON columns, which SQLite does not support, are
+-- stored as BCS-serialized blobs and text respectively, and tables are not partitioned.
+
+CREATE TABLE chain_identifier
+(
+    checkpoint_digest               BLOB NOT NULL,
+    PRIMARY KEY (checkpoint_digest)
+);
+
+CREATE TABLE checkpoints
+(
+    sequence_number                 BIGINT NOT NULL,
+    checkpoint_digest               BLOB NOT NULL,
+    epoch                           BIGINT NOT NULL,
+    network_total_transactions      BIGINT NOT NULL,
+    previous_checkpoint_digest      BLOB,
+    end_of_epoch                    BOOLEAN NOT NULL,
+    -- BCS-serialized array of optional byte strings (BYTEA[] in Postgres)
+    tx_digests                      BLOB NOT NULL,
+    timestamp_ms                    BIGINT NOT NULL,
+    total_gas_cost                  BIGINT NOT NULL,
+    computation_cost                BIGINT NOT NULL,
+    storage_cost                    BIGINT NOT NULL,
+    storage_rebate                  BIGINT NOT NULL,
+    non_refundable_storage_fee      BIGINT NOT NULL,
+    checkpoint_commitments          BLOB NOT NULL,
+    validator_signature             BLOB NOT NULL,
+    end_of_epoch_data               BLOB,
+    min_tx_sequence_number          BIGINT,
+    max_tx_sequence_number          BIGINT,
+    PRIMARY KEY (sequence_number)
+);
+
+CREATE INDEX checkpoints_epoch ON checkpoints (epoch, sequence_number);
+
+CREATE TABLE display
+(
+    object_type                     TEXT NOT NULL,
+    id                              BLOB NOT NULL,
+    version                         SMALLINT NOT NULL,
+    bcs                             BLOB NOT NULL,
+    PRIMARY KEY (object_type)
+);
+
+CREATE TABLE epochs
+(
+    epoch                           BIGINT NOT NULL,
+    first_checkpoint_id             BIGINT NOT NULL,
+    epoch_start_timestamp           BIGINT NOT NULL,
+    reference_gas_price             BIGINT NOT NULL,
+    protocol_version                BIGINT NOT NULL,
+    total_stake                     BIGINT NOT NULL,
+    storage_fund_balance            BIGINT NOT NULL,
+    system_state                    BLOB,
+    epoch_total_transactions        BIGINT,
+    last_checkpoint_id              BIGINT,
+    epoch_end_timestamp             BIGINT,
+    storage_fund_reinvestment       BIGINT,
+    storage_charge                  BIGINT,
+    storage_rebate                  BIGINT,
+    stake_subsidy_amount            BIGINT,
+    total_gas_fees                  BIGINT,
+    total_stake_rewards_distributed BIGINT,
+    leftover_storage_fund_inflow    BIGINT,
+    epoch_commitments               BLOB,
+    -- JSON text (JSONB in Postgres)
+    system_state_summary_json       TEXT,
+    PRIMARY KEY (epoch)
+);
+
+CREATE TABLE event_emit_module
+(
+    package                         BLOB NOT NULL,
+    module                          TEXT NOT NULL,
+    tx_sequence_number              BIGINT NOT NULL,
+    event_sequence_number           BIGINT NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, module, tx_sequence_number, event_sequence_number)
+);
+
+CREATE TABLE event_emit_package
+(
+    package                         BLOB NOT NULL,
+    tx_sequence_number              BIGINT NOT NULL,
+    event_sequence_number           BIGINT NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, tx_sequence_number, event_sequence_number)
+);
+
+CREATE TABLE event_senders
+(
+    sender                          BLOB NOT NULL,
+    tx_sequence_number              BIGINT NOT NULL,
+    event_sequence_number           BIGINT NOT NULL,
+    PRIMARY KEY (sender, tx_sequence_number, event_sequence_number)
+);
+
+CREATE TABLE event_struct_instantiation
+(
+    package                         BLOB NOT NULL,
+    module                          TEXT NOT NULL,
+    type_instantiation              TEXT NOT NULL,
+    tx_sequence_number              BIGINT NOT NULL,
+    event_sequence_number           BIGINT NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, module, type_instantiation, tx_sequence_number, event_sequence_number)
+);
+
+CREATE TABLE event_struct_module
+(
+    package                         BLOB NOT NULL,
+    module                          TEXT NOT NULL,
+    tx_sequence_number              BIGINT NOT NULL,
+    event_sequence_number           BIGINT NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, module, tx_sequence_number, event_sequence_number)
+);
+
+CREATE TABLE event_struct_name
+(
+    package                         BLOB NOT NULL,
+    module                          TEXT NOT NULL,
+    type_name                       TEXT NOT NULL,
+    tx_sequence_number              BIGINT NOT NULL,
+    event_sequence_number           BIGINT NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, module, type_name, tx_sequence_number, event_sequence_number)
+);
+
+CREATE TABLE event_struct_package
+(
+    package                         BLOB NOT NULL,
+    tx_sequence_number              BIGINT NOT NULL,
+    event_sequence_number           BIGINT NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, tx_sequence_number, event_sequence_number)
+);
+
+CREATE TABLE events
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    event_sequence_number           BIGINT NOT NULL,
+    transaction_digest              BLOB NOT NULL,
+    -- BCS-serialized array of optional byte strings (BYTEA[] in Postgres)
+    senders                         BLOB NOT NULL,
+    package                         BLOB NOT NULL,
+    module                          TEXT NOT NULL,
+    event_type                      TEXT NOT NULL,
+    timestamp_ms                    BIGINT NOT NULL,
+    bcs                             BLOB NOT NULL,
+    sender                          BLOB,
+    PRIMARY KEY (tx_sequence_number, event_sequence_number)
+);
+
+CREATE TABLE feature_flags
+(
+    protocol_version                BIGINT NOT NULL,
+    flag_name                       TEXT NOT NULL,
+    flag_value                      BOOLEAN NOT NULL,
+    PRIMARY KEY (protocol_version, flag_name)
+);
+
+CREATE TABLE full_objects_history
+(
+    object_id                       BLOB NOT NULL,
+    object_version                  BIGINT NOT NULL,
+    serialized_object               BLOB,
+    PRIMARY KEY (object_id, object_version)
+);
+
+CREATE TABLE objects
+(
+    object_id                       BLOB NOT NULL,
+    object_version                  BIGINT NOT NULL,
+    object_digest                   BLOB NOT NULL,
+    owner_type                      SMALLINT NOT NULL,
+    owner_id                        BLOB,
+    object_type                     TEXT,
+    object_type_package             BLOB,
+    object_type_module              TEXT,
+    object_type_name                TEXT,
+    serialized_object               BLOB NOT NULL,
+    coin_type                       TEXT,
+    coin_balance                    BIGINT,
+    df_kind                         SMALLINT,
+    PRIMARY KEY (object_id)
+);
+
+CREATE TABLE objects_history
+(
+    object_id                       BLOB NOT NULL,
+    object_version                  BIGINT NOT NULL,
+    object_status                   SMALLINT NOT NULL,
+    object_digest                   BLOB,
+    checkpoint_sequence_number      BIGINT NOT NULL,
+    owner_type                      SMALLINT,
+    owner_id                        BLOB,
+    object_type                     TEXT,
+    object_type_package             BLOB,
+    object_type_module              TEXT,
+    object_type_name                TEXT,
+    serialized_object               BLOB,
+    coin_type                       TEXT,
+    coin_balance                    BIGINT,
+    df_kind                         SMALLINT,
+    PRIMARY KEY (checkpoint_sequence_number, object_id, object_version)
+);
+
+CREATE TABLE objects_snapshot
+(
+    object_id                       BLOB NOT NULL,
+    object_version                  BIGINT NOT NULL,
+    object_status                   SMALLINT NOT NULL,
+    object_digest                   BLOB,
+    checkpoint_sequence_number      BIGINT NOT NULL,
+    owner_type                      SMALLINT,
+    owner_id                        BLOB,
+    object_type                     TEXT,
+    object_type_package             BLOB,
+    object_type_module              TEXT,
+    object_type_name                TEXT,
+    serialized_object               BLOB,
+    coin_type                       TEXT,
+    coin_balance                    BIGINT,
+    df_kind                         SMALLINT,
+    PRIMARY KEY (object_id)
+);
+
+CREATE TABLE objects_version
+(
+    object_id                       BLOB NOT NULL,
+    object_version                  BIGINT NOT NULL,
+    cp_sequence_number              BIGINT NOT NULL,
+    PRIMARY KEY (object_id, object_version)
+);
+
+CREATE TABLE packages
+(
+    package_id                      BLOB NOT NULL,
+    original_id                     BLOB NOT NULL,
+    package_version                 BIGINT NOT NULL,
+    move_package                    BLOB NOT NULL,
+    checkpoint_sequence_number      BIGINT NOT NULL,
+    PRIMARY KEY (package_id, original_id, package_version),
+    CONSTRAINT packages_unique_package_id UNIQUE (package_id)
+);
+
+CREATE TABLE persisted_queries
+(
+    query_hash                      BLOB NOT NULL,
+    query                           TEXT NOT NULL,
+    PRIMARY KEY (query_hash)
+);
+
+CREATE TABLE protocol_configs
+(
+    protocol_version                BIGINT NOT NULL,
+    config_name                     TEXT NOT NULL,
+    config_value                    TEXT,
+    PRIMARY KEY (protocol_version, config_name)
+);
+
+CREATE TABLE pruner_cp_watermark
+(
+    checkpoint_sequence_number      BIGINT NOT NULL,
+    min_tx_sequence_number          BIGINT NOT NULL,
+    max_tx_sequence_number          BIGINT NOT NULL,
+    PRIMARY KEY (checkpoint_sequence_number)
+);
+
+CREATE TABLE raw_checkpoints
+(
+    sequence_number                 BIGINT NOT NULL,
+    certified_checkpoint            BLOB NOT NULL,
+    checkpoint_contents             BLOB NOT NULL,
+    PRIMARY KEY (sequence_number)
+);
+
+CREATE TABLE transactions
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    transaction_digest              BLOB NOT NULL,
+    raw_transaction                 BLOB NOT NULL,
+    raw_effects                     BLOB NOT NULL,
+    checkpoint_sequence_number      BIGINT NOT NULL,
+    timestamp_ms                    BIGINT NOT NULL,
+    -- BCS-serialized array of optional byte strings (BYTEA[] in Postgres)
+    object_changes                  BLOB NOT NULL,
+    -- BCS-serialized array of optional byte strings (BYTEA[] in Postgres)
+    balance_changes                 BLOB NOT NULL,
+    -- BCS-serialized array of optional byte strings (BYTEA[] in Postgres)
+    events                          BLOB NOT NULL,
+    transaction_kind                SMALLINT NOT NULL,
+    success_command_count           SMALLINT NOT NULL,
+    PRIMARY KEY (tx_sequence_number)
+);
+
+CREATE TABLE tx_affected_addresses
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    affected                        BLOB NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (affected, tx_sequence_number)
+);
+
+CREATE TABLE tx_affected_objects
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    affected                        BLOB NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (affected, tx_sequence_number)
+);
+
+CREATE TABLE tx_calls_fun
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    package                         BLOB NOT NULL,
+    module                          TEXT NOT NULL,
+    func                            TEXT NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, module, func, tx_sequence_number)
+);
+
+CREATE TABLE tx_calls_mod
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    package                         BLOB NOT NULL,
+    module                          TEXT NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, module, tx_sequence_number)
+);
+
+CREATE TABLE tx_calls_pkg
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    package                         BLOB NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (package, tx_sequence_number)
+);
+
+CREATE TABLE tx_changed_objects
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    object_id                       BLOB NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (object_id, tx_sequence_number)
+);
+
+CREATE TABLE tx_digests
+(
+    tx_digest                       BLOB NOT NULL,
+    tx_sequence_number              BIGINT NOT NULL,
+    PRIMARY KEY (tx_digest)
+);
+
+CREATE TABLE tx_input_objects
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    object_id                       BLOB NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (object_id, tx_sequence_number)
+);
+
+CREATE TABLE tx_kinds
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    tx_kind                         SMALLINT NOT NULL,
+    PRIMARY KEY (tx_kind, tx_sequence_number)
+);
+
+CREATE TABLE tx_recipients
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    recipient                       BLOB NOT NULL,
+    sender                          BLOB NOT NULL,
+    PRIMARY KEY (recipient, tx_sequence_number)
+);
+
+CREATE TABLE tx_senders
+(
+    tx_sequence_number              BIGINT NOT NULL,
+    sender                          BLOB NOT NULL,
+    gas_used                        BIGINT,
+    PRIMARY KEY (sender, tx_sequence_number)
+);
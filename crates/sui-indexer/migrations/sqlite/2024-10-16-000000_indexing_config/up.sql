@@ -0,0 +1,6 @@
+-- See the Postgres migration of the same name.
+CREATE TABLE indexing_config
+(
+    id                  SMALLINT PRIMARY KEY,
+    config              TEXT     NOT NULL
+);
@@ -10,11 +10,12 @@ use jsonrpsee::RpcModule;
 use sui_json_rpc::SuiRpcModule;
 use sui_json_rpc_api::{WriteApiClient, WriteApiServer};
 use sui_json_rpc_types::{
-    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlockResponse,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse,
+    SuiTransactionBlockLifecycleStatus, SuiTransactionBlockResponse,
     SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc::Module;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::sui_serde::BigInt;
 
@@ -77,6 +78,23 @@ impl WriteApiServer for WriteApi {
     ) -> RpcResult<DryRunTransactionBlockResponse> {
         self.fullnode.dry_run_transaction_block(tx_bytes).await
     }
+
+    async fn dry_run_transaction_block_at_checkpoint(
+        &self,
+        tx_bytes: Base64,
+        checkpoint: BigInt<u64>,
+    ) -> RpcResult<DryRunTransactionBlockResponse> {
+        self.fullnode
+            .dry_run_transaction_block_at_checkpoint(tx_bytes, checkpoint)
+            .await
+    }
+
+    async fn get_transaction_block_status(
+        &self,
+        digest: TransactionDigest,
+    ) -> RpcResult<Option<SuiTransactionBlockLifecycleStatus>> {
+        self.fullnode.get_transaction_block_status(digest).await
+    }
 }
 
 impl SuiRpcModule for WriteApi {
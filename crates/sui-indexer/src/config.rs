@@ -3,8 +3,11 @@
 
 use crate::backfill::BackfillTaskKind;
 use crate::db::ConnectionPoolConfig;
-use clap::{Args, Parser, Subcommand};
-use std::{net::SocketAddr, path::PathBuf};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use move_core_types::language_storage::StructTag;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
+use std::{fmt, net::SocketAddr, path::PathBuf};
 use sui_json_rpc::name_service::NameServiceConfig;
 use sui_types::base_types::{ObjectID, SuiAddress};
 use url::Url;
@@ -175,6 +178,8 @@ pub enum Command {
         pruning_options: PruningOptions,
         #[command(flatten)]
         upload_options: UploadOptions,
+        #[command(flatten)]
+        indexing_config: IndexingConfig,
     },
     JsonRpcService(JsonRpcConfig),
     ResetDatabase {
@@ -250,6 +255,102 @@ pub struct UploadOptions {
     pub gcs_cred_path: Option<String>,
 }
 
+/// Groups of tables that the writer can be configured not to populate, to save on storage and
+/// write throughput when the queries they serve are not needed.
+#[derive(
+    ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum TableGroup {
+    /// `events` and the `event_*` index tables, used to query events.
+    Events,
+    /// The `tx_*` index tables (except `tx_digests`), used to filter transactions.
+    TxIndices,
+    /// `objects_history`, used to query objects and dynamic fields at past checkpoints.
+    ObjectsHistory,
+    /// `full_objects_history` and `objects_version`, used to look up objects by version.
+    FullObjectsHistory,
+}
+
+/// Restricts what the writer indexes, so that it only populates the tables and rows that an
+/// application needs. Readers use the copy of this configuration that the writer stores in the
+/// database to report the queries that the indexed data cannot serve.
+#[serde_as]
+#[derive(Args, Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct IndexingConfig {
+    /// Table groups that the writer will not populate.
+    #[arg(long = "disable-table-group", value_enum, value_delimiter = ',')]
+    #[serde(default)]
+    pub disabled_table_groups: Vec<TableGroup>,
+
+    /// Only index transactions that call a Move function in, or emit an event from, one of these
+    /// packages (in addition to those selected by the other filters).
+    #[arg(long = "index-package", value_delimiter = ',')]
+    #[serde(default)]
+    pub packages: Vec<ObjectID>,
+
+    /// Only index transactions sent by, sent to, or paid for by one of these addresses (in
+    /// addition to those selected by the other filters).
+    #[arg(long = "index-address", value_delimiter = ',')]
+    #[serde(default)]
+    pub addresses: Vec<SuiAddress>,
+
+    /// Only index transactions that emit an event of one of these types (in addition to those
+    /// selected by the other filters). Types without type parameters match all instantiations.
+    /// Unlike the other filters, this one is not comma-separated, as types can contain commas.
+    #[arg(long = "index-event-type")]
+    #[serde_as(as = "Vec<DisplayFromStr>")]
+    #[serde(default)]
+    pub event_types: Vec<StructTag>,
+}
+
+impl IndexingConfig {
+    /// Whether the writer populates the tables in `group`.
+    pub fn is_enabled(&self, group: TableGroup) -> bool {
+        !self.disabled_table_groups.contains(&group)
+    }
+
+    /// Whether the writer only indexes a subset of transactions (and their events).
+    pub fn is_filtered(&self) -> bool {
+        !self.packages.is_empty() || !self.addresses.is_empty() || !self.event_types.is_empty()
+    }
+
+    /// Sorts and deduplicates every list in the config, so that configs that index the same data
+    /// compare (and are stored) the same, regardless of the order flags were passed in.
+    pub fn normalized(mut self) -> Self {
+        fn normalize<T: Ord>(items: &mut Vec<T>) {
+            items.sort();
+            items.dedup();
+        }
+
+        normalize(&mut self.disabled_table_groups);
+        normalize(&mut self.packages);
+        normalize(&mut self.addresses);
+        normalize(&mut self.event_types);
+        self
+    }
+}
+
+impl TableGroup {
+    /// The tables in this group, for use in error messages.
+    pub fn tables(&self) -> &'static str {
+        match self {
+            TableGroup::Events => "events and event_*",
+            TableGroup::TxIndices => "tx_*",
+            TableGroup::ObjectsHistory => "objects_history",
+            TableGroup::FullObjectsHistory => "full_objects_history and objects_version",
+        }
+    }
+}
+
+impl fmt::Display for TableGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = self.to_possible_value().expect("No skipped variants");
+        write!(f, "{}", value.get_name())
+    }
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct RestoreConfig {
     #[arg(long, env = "START_EPOCH", required = true)]
@@ -354,4 +455,46 @@ mod test {
         // fullnode rpc url must be present
         parse_args::<JsonRpcConfig>([]).unwrap_err();
     }
+
+    #[test]
+    fn indexing_config() {
+        let config = parse_args::<IndexingConfig>([]).unwrap();
+        assert_eq!(config, IndexingConfig::default());
+        assert!(config.is_enabled(TableGroup::Events));
+        assert!(!config.is_filtered());
+
+        let config = parse_args::<IndexingConfig>([
+            "--disable-table-group=events,tx-indices",
+            "--disable-table-group",
+            "full-objects-history",
+            "--index-package=0x2",
+            "--index-event-type=0x3::validator::StakingRequestEvent",
+        ])
+        .unwrap();
+
+        assert!(!config.is_enabled(TableGroup::Events));
+        assert!(!config.is_enabled(TableGroup::TxIndices));
+        assert!(config.is_enabled(TableGroup::ObjectsHistory));
+        assert!(!config.is_enabled(TableGroup::FullObjectsHistory));
+        assert!(config.is_filtered());
+
+        // The configuration survives the round trip through the database.
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(config, serde_json::from_str(&json).unwrap());
+
+        // Neither the order of the flags nor duplicates change what is indexed.
+        let reordered = parse_args::<IndexingConfig>([
+            "--index-event-type=0x3::validator::StakingRequestEvent",
+            "--index-package=0x2,0x2",
+            "--disable-table-group=full-objects-history,tx-indices",
+            "--disable-table-group=events",
+        ])
+        .unwrap();
+
+        assert_ne!(config, reordered);
+        assert_eq!(config.clone().normalized(), reordered.normalized());
+
+        parse_args::<IndexingConfig>(["--disable-table-group=checkpoints"]).unwrap_err();
+        parse_args::<IndexingConfig>(["--index-event-type=0x2::coin"]).unwrap_err();
+    }
 }
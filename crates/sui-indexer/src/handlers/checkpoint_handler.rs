@@ -25,12 +25,13 @@ use sui_types::object::Owner;
 use sui_types::sui_system_state::{get_sui_system_state, SuiSystemStateTrait};
 use sui_types::transaction::TransactionDataAPI;
 
+use crate::config::IndexingConfig;
 use crate::errors::IndexerError;
 use crate::handlers::committer::start_tx_checkpoint_commit_task;
 use crate::metrics::IndexerMetrics;
 use crate::models::display::StoredDisplay;
 use crate::models::obj_indices::StoredObjectVersion;
-use crate::store::{IndexerStore, PgIndexerStore};
+use crate::store::IndexerStore;
 use crate::types::{
     EventIndex, IndexedCheckpoint, IndexedDeletedObject, IndexedEpochInfo, IndexedEvent,
     IndexedObject, IndexedPackage, IndexedTransaction, IndexerResult, TransactionKind, TxIndex,
@@ -44,12 +45,13 @@ use super::TransactionObjectChangesToCommit;
 
 const CHECKPOINT_QUEUE_SIZE: usize = 100;
 
-pub async fn new_handlers(
-    state: PgIndexerStore,
+pub async fn new_handlers<S: IndexerStore>(
+    state: S,
     metrics: IndexerMetrics,
     next_checkpoint_sequence_number: CheckpointSequenceNumber,
+    indexing_config: IndexingConfig,
     cancel: CancellationToken,
-) -> Result<CheckpointHandler, IndexerError> {
+) -> Result<CheckpointHandler<S>, IndexerError> {
     let checkpoint_queue_size = std::env::var("CHECKPOINT_QUEUE_SIZE")
         .unwrap_or(CHECKPOINT_QUEUE_SIZE.to_string())
         .parse::<usize>()
@@ -70,6 +72,7 @@ pub async fn new_handlers(
         metrics_clone,
         indexed_checkpoint_receiver,
         next_checkpoint_sequence_number,
+        indexing_config,
         cancel.clone()
     ));
     Ok(CheckpointHandler::new(
@@ -79,14 +82,14 @@ pub async fn new_handlers(
     ))
 }
 
-pub struct CheckpointHandler {
-    state: PgIndexerStore,
+pub struct CheckpointHandler<S> {
+    state: S,
     metrics: IndexerMetrics,
     indexed_checkpoint_sender: mysten_metrics::metered_channel::Sender<CheckpointDataToCommit>,
 }
 
 #[async_trait]
-impl Worker for CheckpointHandler {
+impl<S: IndexerStore> Worker for CheckpointHandler<S> {
     async fn process_checkpoint(&self, checkpoint: &CheckpointData) -> anyhow::Result<()> {
         let time_now_ms = chrono::Utc::now().timestamp_millis();
         let cp_download_lag = time_now_ms - checkpoint.checkpoint_summary.timestamp_ms as i64;
@@ -119,9 +122,9 @@ impl Worker for CheckpointHandler {
     }
 }
 
-impl CheckpointHandler {
+impl<S: IndexerStore> CheckpointHandler<S> {
     fn new(
-        state: PgIndexerStore,
+        state: S,
         metrics: IndexerMetrics,
         indexed_checkpoint_sender: mysten_metrics::metered_channel::Sender<CheckpointDataToCommit>,
     ) -> Self {
@@ -133,7 +136,7 @@ impl CheckpointHandler {
     }
 
     async fn index_epoch(
-        state: &PgIndexerStore,
+        state: &S,
         data: &CheckpointData,
     ) -> Result<Option<EpochToCommit>, IndexerError> {
         let checkpoint_object_store = EpochEndIndexingObjectStore::new(data);
@@ -238,7 +241,7 @@ impl CheckpointHandler {
     }
 
     async fn index_checkpoint(
-        state: &PgIndexerStore,
+        state: &S,
         data: &CheckpointData,
         metrics: Arc<IndexerMetrics>,
         packages: Vec<IndexedPackage>,
@@ -487,6 +490,7 @@ impl CheckpointHandler {
                 recipients,
                 move_calls,
                 tx_kind: transaction_kind,
+                gas_used: fx.gas_cost_summary().net_gas_usage(),
             });
         }
         Ok((
@@ -1,18 +1,21 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use tap::tap::TapFallible;
 use tokio_util::sync::CancellationToken;
 use tracing::instrument;
 use tracing::{error, info};
 
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
+use crate::config::{IndexingConfig, TableGroup};
 use crate::metrics::IndexerMetrics;
+use crate::models::tx_indices::StoredTxDigest;
 use crate::store::IndexerStore;
-use crate::types::IndexerResult;
+use crate::types::{IndexedEvent, IndexerResult, TxIndex};
 
 use super::{CheckpointDataToCommit, EpochToCommit};
 
@@ -23,6 +26,7 @@ pub async fn start_tx_checkpoint_commit_task<S>(
     metrics: IndexerMetrics,
     tx_indexing_receiver: mysten_metrics::metered_channel::Receiver<CheckpointDataToCommit>,
     mut next_checkpoint_sequence_number: CheckpointSequenceNumber,
+    indexing_config: IndexingConfig,
     cancel: CancellationToken,
 ) -> IndexerResult<()>
 where
@@ -58,7 +62,7 @@ where
             next_checkpoint_sequence_number += 1;
             let epoch_number_option = epoch.as_ref().map(|epoch| epoch.new_epoch.epoch);
             if batch.len() == checkpoint_commit_batch_size || epoch.is_some() {
-                commit_checkpoints(&state, batch, epoch, &metrics).await;
+                commit_checkpoints(&state, batch, epoch, &indexing_config, &metrics).await;
                 batch = vec![];
             }
             if let Some(epoch_number) = epoch_number_option {
@@ -72,7 +76,7 @@ where
             }
         }
         if !batch.is_empty() && unprocessed.is_empty() {
-            commit_checkpoints(&state, batch, None, &metrics).await;
+            commit_checkpoints(&state, batch, None, &indexing_config, &metrics).await;
             batch = vec![];
         }
     }
@@ -88,6 +92,7 @@ async fn commit_checkpoints<S>(
     state: &S,
     indexed_checkpoint_batch: Vec<CheckpointDataToCommit>,
     epoch: Option<EpochToCommit>,
+    indexing_config: &IndexingConfig,
     metrics: &IndexerMetrics,
 ) where
     S: IndexerStore + Clone + Sync + Send + 'static,
@@ -103,7 +108,11 @@ async fn commit_checkpoints<S>(
     let mut object_versions_batch = vec![];
     let mut packages_batch = vec![];
 
-    for indexed_checkpoint in indexed_checkpoint_batch {
+    for mut indexed_checkpoint in indexed_checkpoint_batch {
+        if indexing_config.is_filtered() {
+            retain_indexed_transactions(indexing_config, &mut indexed_checkpoint);
+        }
+
         let CheckpointDataToCommit {
             checkpoint,
             transactions,
@@ -156,9 +165,6 @@ async fn commit_checkpoints<S>(
         let _step_1_guard = metrics.checkpoint_db_commit_latency_step_1.start_timer();
         let mut persist_tasks = vec![
             state.persist_transactions(tx_batch),
-            state.persist_tx_indices(tx_indices_batch),
-            state.persist_events(events_batch),
-            state.persist_event_indices(event_indices_batch),
             state.persist_displays(display_updates_batch),
             state.persist_packages(packages_batch),
             // TODO: There are a few ways we could make the following more memory efficient.
@@ -166,11 +172,33 @@ async fn commit_checkpoints<S>(
             //    committed object list. We could call it early and share the result.
             // 2. We could avoid clone by using Arc.
             state.persist_objects(object_changes_batch.clone()),
-            state.persist_object_history(object_history_changes_batch.clone()),
-            state.persist_full_objects_history(object_history_changes_batch.clone()),
-            state.persist_object_versions(object_versions_batch.clone()),
             state.persist_raw_checkpoints(raw_checkpoints_batch),
         ];
+        if indexing_config.is_enabled(TableGroup::TxIndices) {
+            persist_tasks.push(state.persist_tx_indices(tx_indices_batch));
+        } else {
+            // Transaction lookups by digest go through tx_digests, so it is always populated.
+            let tx_digests_batch = tx_indices_batch
+                .iter()
+                .map(|index| StoredTxDigest {
+                    tx_digest: index.transaction_digest.into_inner().to_vec(),
+                    tx_sequence_number: index.tx_sequence_number as i64,
+                })
+                .collect();
+            persist_tasks.push(state.persist_tx_digests(tx_digests_batch));
+        }
+        if indexing_config.is_enabled(TableGroup::Events) {
+            persist_tasks.push(state.persist_events(events_batch));
+            persist_tasks.push(state.persist_event_indices(event_indices_batch));
+        }
+        if indexing_config.is_enabled(TableGroup::ObjectsHistory) {
+            persist_tasks.push(state.persist_object_history(object_history_changes_batch.clone()));
+        }
+        if indexing_config.is_enabled(TableGroup::FullObjectsHistory) {
+            persist_tasks
+                .push(state.persist_full_objects_history(object_history_changes_batch.clone()));
+            persist_tasks.push(state.persist_object_versions(object_versions_batch.clone()));
+        }
         if let Some(epoch_data) = epoch.clone() {
             persist_tasks.push(state.persist_epoch(epoch_data));
         }
@@ -245,7 +273,167 @@ async fn commit_checkpoints<S>(
         .observe(tx_count as f64 / (last_checkpoint_seq - first_checkpoint_seq + 1) as f64);
     // 1000.0 is not necessarily the batch size, it's to roughly map average tx commit latency to [0.1, 1] seconds,
     // which is well covered by DB_COMMIT_LATENCY_SEC_BUCKETS.
-    metrics
-        .thousand_transaction_avg_db_commit_latency
-        .observe(elapsed * 1000.0 / tx_count as f64);
+    // With selective indexing, a batch may not include any transactions.
+    if tx_count > 0 {
+        metrics
+            .thousand_transaction_avg_db_commit_latency
+            .observe(elapsed * 1000.0 / tx_count as f64);
+    }
+}
+
+/// Drop the transactions in `checkpoint` that none of the filters in `config` select, along with
+/// their indices and events. Objects, packages and display updates are kept, so that the live
+/// object set stays complete.
+fn retain_indexed_transactions(config: &IndexingConfig, checkpoint: &mut CheckpointDataToCommit) {
+    let mut events_by_tx: HashMap<u64, Vec<&IndexedEvent>> = HashMap::new();
+    for event in &checkpoint.events {
+        events_by_tx
+            .entry(event.tx_sequence_number)
+            .or_default()
+            .push(event);
+    }
+
+    let indexed: HashSet<u64> = checkpoint
+        .tx_indices
+        .iter()
+        .filter(|index| {
+            let events = events_by_tx
+                .get(&index.tx_sequence_number)
+                .map_or(&[][..], Vec::as_slice);
+            is_indexed(config, index, events)
+        })
+        .map(|index| index.tx_sequence_number)
+        .collect();
+
+    checkpoint
+        .transactions
+        .retain(|tx| indexed.contains(&tx.tx_sequence_number));
+    checkpoint
+        .tx_indices
+        .retain(|index| indexed.contains(&index.tx_sequence_number));
+    checkpoint
+        .events
+        .retain(|e| indexed.contains(&e.tx_sequence_number));
+    checkpoint
+        .event_indices
+        .retain(|e| indexed.contains(&e.tx_sequence_number));
+}
+
+/// Whether any of the filters in `config` selects the transaction described by `index`, which
+/// emitted `events`.
+fn is_indexed(config: &IndexingConfig, index: &TxIndex, events: &[&IndexedEvent]) -> bool {
+    let package_matches = |package: &ObjectID| config.packages.contains(package);
+    let address_matches = |address: &SuiAddress| config.addresses.contains(address);
+
+    index
+        .move_calls
+        .iter()
+        .any(|(package, _, _)| package_matches(package))
+        || events
+            .iter()
+            .any(|e| package_matches(&e.package) || package_matches(&e.event_type_package))
+        || address_matches(&index.sender)
+        || index.recipients.iter().any(address_matches)
+        || index.payers.iter().any(address_matches)
+        || events.iter().any(|e| {
+            config.event_types.iter().any(|tag| {
+                ObjectID::from(tag.address) == e.event_type_package
+                    && tag.module.as_str() == e.event_type_module
+                    && tag.name.as_str() == e.event_type_name
+                    && (tag.type_params.is_empty()
+                        || tag.to_canonical_string(/* with_prefix */ true) == e.event_type)
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use sui_types::digests::TransactionDigest;
+
+    use crate::types::TransactionKind;
+
+    use super::*;
+
+    fn tx_index(sender: SuiAddress, move_calls: Vec<(ObjectID, String, String)>) -> TxIndex {
+        TxIndex {
+            tx_sequence_number: 0,
+            tx_kind: TransactionKind::ProgrammableTransaction,
+            transaction_digest: TransactionDigest::ZERO,
+            checkpoint_sequence_number: 0,
+            input_objects: vec![],
+            changed_objects: vec![],
+            affected_objects: vec![],
+            payers: vec![sender],
+            sender,
+            recipients: vec![],
+            move_calls,
+            gas_used: 0,
+        }
+    }
+
+    fn event(package: ObjectID, event_type: &str) -> IndexedEvent {
+        let tag: move_core_types::language_storage::StructTag = event_type.parse().unwrap();
+        IndexedEvent {
+            tx_sequence_number: 0,
+            event_sequence_number: 0,
+            checkpoint_sequence_number: 0,
+            transaction_digest: TransactionDigest::ZERO,
+            sender: SuiAddress::ZERO,
+            package,
+            module: "m".to_string(),
+            event_type: tag.to_canonical_string(/* with_prefix */ true),
+            event_type_package: tag.address.into(),
+            event_type_module: tag.module.to_string(),
+            event_type_name: tag.name.to_string(),
+            bcs: vec![],
+            timestamp_ms: 0,
+        }
+    }
+
+    #[test]
+    fn filter_transactions() {
+        let pkg = ObjectID::from_single_byte(0xa);
+        let other = ObjectID::from_single_byte(0xb);
+        let sender = SuiAddress::random_for_testing_only();
+
+        let call = tx_index(SuiAddress::ZERO, vec![(pkg, "m".into(), "f".into())]);
+        let sent = tx_index(sender, vec![(other, "m".into(), "f".into())]);
+        let coin = event(other, "0x2::coin::CoinEvent<0x2::sui::SUI>");
+
+        let by_package = IndexingConfig {
+            packages: vec![pkg],
+            ..Default::default()
+        };
+        assert!(is_indexed(&by_package, &call, &[]));
+        assert!(!is_indexed(&by_package, &sent, &[]));
+        assert!(is_indexed(&by_package, &sent, &[&event(pkg, "0x2::m::E")]));
+
+        let by_address = IndexingConfig {
+            addresses: vec![sender],
+            ..Default::default()
+        };
+        assert!(!is_indexed(&by_address, &call, &[]));
+        assert!(is_indexed(&by_address, &sent, &[]));
+
+        let by_type = |t: &str| IndexingConfig {
+            event_types: vec![t.parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(is_indexed(
+            &by_type("0x2::coin::CoinEvent"),
+            &call,
+            &[&coin]
+        ));
+        assert!(is_indexed(
+            &by_type("0x2::coin::CoinEvent<0x2::sui::SUI>"),
+            &call,
+            &[&coin]
+        ));
+        assert!(!is_indexed(
+            &by_type("0x2::coin::CoinEvent<0x3::x::X>"),
+            &call,
+            &[&coin]
+        ));
+        assert!(!is_indexed(&by_type("0x2::coin::Other"), &call, &[&coin]));
+    }
 }
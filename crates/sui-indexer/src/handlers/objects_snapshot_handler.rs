@@ -11,7 +11,6 @@ use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 use crate::config::SnapshotLagConfig;
-use crate::store::PgIndexerStore;
 use crate::types::IndexerResult;
 use crate::{metrics::IndexerMetrics, store::IndexerStore};
 
@@ -20,8 +19,8 @@ use super::TransactionObjectChangesToCommit;
 use super::{CommonHandler, Handler};
 
 #[derive(Clone)]
-pub struct ObjectsSnapshotHandler {
-    pub store: PgIndexerStore,
+pub struct ObjectsSnapshotHandler<S> {
+    pub store: S,
     pub sender: Sender<(u64, TransactionObjectChangesToCommit)>,
     snapshot_config: SnapshotLagConfig,
     metrics: IndexerMetrics,
@@ -33,9 +32,10 @@ pub struct CheckpointObjectChanges {
 }
 
 #[async_trait]
-impl Worker for ObjectsSnapshotHandler {
+impl<S: IndexerStore> Worker for ObjectsSnapshotHandler<S> {
     async fn process_checkpoint(&self, checkpoint: &CheckpointData) -> anyhow::Result<()> {
-        let transformed_data = CheckpointHandler::index_objects(checkpoint, &self.metrics).await?;
+        let transformed_data =
+            CheckpointHandler::<S>::index_objects(checkpoint, &self.metrics).await?;
         self.sender
             .send((
                 checkpoint.checkpoint_summary.sequence_number,
@@ -47,7 +47,7 @@ impl Worker for ObjectsSnapshotHandler {
 }
 
 #[async_trait]
-impl Handler<TransactionObjectChangesToCommit> for ObjectsSnapshotHandler {
+impl<S: IndexerStore> Handler<TransactionObjectChangesToCommit> for ObjectsSnapshotHandler<S> {
     fn name(&self) -> String {
         "objects_snapshot_handler".to_string()
     }
@@ -85,12 +85,12 @@ impl Handler<TransactionObjectChangesToCommit> for ObjectsSnapshotHandler {
     }
 }
 
-pub async fn start_objects_snapshot_handler(
-    store: PgIndexerStore,
+pub async fn start_objects_snapshot_handler<S: IndexerStore>(
+    store: S,
     metrics: IndexerMetrics,
     snapshot_config: SnapshotLagConfig,
     cancel: CancellationToken,
-) -> IndexerResult<(ObjectsSnapshotHandler, u64)> {
+) -> IndexerResult<(ObjectsSnapshotHandler<S>, u64)> {
     info!("Starting object snapshot handler...");
 
     let global_metrics = get_metrics().unwrap();
@@ -110,13 +110,13 @@ pub async fn start_objects_snapshot_handler(
     Ok((objects_snapshot_handler, watermark_hi.unwrap_or_default()))
 }
 
-impl ObjectsSnapshotHandler {
+impl<S: IndexerStore> ObjectsSnapshotHandler<S> {
     pub fn new(
-        store: PgIndexerStore,
+        store: S,
         sender: Sender<(u64, TransactionObjectChangesToCommit)>,
         metrics: IndexerMetrics,
         snapshot_config: SnapshotLagConfig,
-    ) -> ObjectsSnapshotHandler {
+    ) -> Self {
         Self {
             store,
             sender,
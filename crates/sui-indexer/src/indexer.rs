@@ -19,7 +19,9 @@ use sui_data_ingestion_core::{
 use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 use crate::build_json_rpc_server;
-use crate::config::{IngestionConfig, JsonRpcConfig, PruningOptions, SnapshotLagConfig};
+use crate::config::{
+    IndexingConfig, IngestionConfig, JsonRpcConfig, PruningOptions, SnapshotLagConfig,
+};
 use crate::database::ConnectionPool;
 use crate::errors::IndexerError;
 use crate::handlers::checkpoint_handler::new_handlers;
@@ -38,6 +40,39 @@ impl Indexer {
         metrics: IndexerMetrics,
         snapshot_config: SnapshotLagConfig,
         pruning_options: PruningOptions,
+        indexing_config: IndexingConfig,
+        cancel: CancellationToken,
+    ) -> Result<(), IndexerError> {
+        if let Some(epochs_to_keep) = pruning_options.epochs_to_keep {
+            info!(
+                "Starting indexer pruner with epochs to keep: {}",
+                epochs_to_keep
+            );
+            assert!(epochs_to_keep > 0, "Epochs to keep must be positive");
+            let pruner = Pruner::new(store.clone(), epochs_to_keep, metrics.clone())?;
+            let cancel_clone = cancel.clone();
+            spawn_monitored_task!(pruner.start(cancel_clone));
+        }
+
+        Self::start_writer_with_store(
+            config,
+            store,
+            metrics,
+            snapshot_config,
+            indexing_config,
+            cancel,
+        )
+        .await
+    }
+
+    /// Run the indexer writer against any [`IndexerStore`]. Unlike [`Indexer::start_writer`],
+    /// this does not prune old epochs, which relies on Postgres table partitions.
+    pub async fn start_writer_with_store<S: IndexerStore>(
+        config: &IngestionConfig,
+        store: S,
+        metrics: IndexerMetrics,
+        snapshot_config: SnapshotLagConfig,
+        indexing_config: IndexingConfig,
         cancel: CancellationToken,
     ) -> Result<(), IndexerError> {
         info!(
@@ -45,6 +80,29 @@ impl Indexer {
             env!("CARGO_PKG_VERSION")
         );
         info!("Sui Indexer Writer config: {config:?}",);
+        let indexing_config = indexing_config.normalized();
+        info!("Sui Indexer Writer indexing config: {indexing_config:?}");
+
+        // Data already in the database was indexed with the recorded config (or with everything, if
+        // none was recorded), and readers rely on that config describing all of it, so it cannot
+        // change once the database holds checkpoints.
+        if store
+            .get_latest_checkpoint_sequence_number()
+            .await?
+            .is_some()
+        {
+            let recorded = store.get_indexing_config().await?.unwrap_or_default();
+            if recorded != indexing_config {
+                return Err(IndexerError::InvalidArgumentError(format!(
+                    "The database was indexed with a different indexing config ({recorded:?}) than \
+                     the one requested ({indexing_config:?}). Reset the database to change what is \
+                     indexed."
+                )));
+            }
+        }
+
+        // Record what is being indexed, so that readers can tell which queries they can serve.
+        store.persist_indexing_config(&indexing_config).await?;
 
         let primary_watermark = store
             .get_latest_checkpoint_sequence_number()
@@ -69,17 +127,6 @@ impl Indexer {
         )
         .await?;
 
-        if let Some(epochs_to_keep) = pruning_options.epochs_to_keep {
-            info!(
-                "Starting indexer pruner with epochs to keep: {}",
-                epochs_to_keep
-            );
-            assert!(epochs_to_keep > 0, "Epochs to keep must be positive");
-            let pruner = Pruner::new(store.clone(), epochs_to_keep, metrics.clone())?;
-            let cancel_clone = cancel.clone();
-            spawn_monitored_task!(pruner.start(cancel_clone));
-        }
-
         // If we already have chain identifier indexed (i.e. the first checkpoint has been indexed),
         // then we persist protocol configs for protocol versions not yet in the db.
         // Otherwise, we would do the persisting in `commit_checkpoint` while the first cp is
@@ -101,7 +148,14 @@ impl Indexer {
             2,
             DataIngestionMetrics::new(&Registry::new()),
         );
-        let worker = new_handlers(store, metrics, primary_watermark, cancel.clone()).await?;
+        let worker = new_handlers(
+            store,
+            metrics,
+            primary_watermark,
+            indexing_config,
+            cancel.clone(),
+        )
+        .await?;
         let worker_pool = WorkerPool::new(
             worker,
             "primary".to_string(),
@@ -8,7 +8,7 @@ use diesel::{
     OptionalExtension, QueryDsl, SelectableHelper, TextExpressionMethods,
 };
 use itertools::Itertools;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 use sui_types::dynamic_field::visitor as DFV;
 use sui_types::object::bounded_visitor::BoundedVisitor;
 use tap::{Pipe, TapFallible};
@@ -39,9 +39,12 @@ use sui_types::{
 };
 use sui_types::{coin::CoinMetadata, event::EventID};
 
+use crate::config::{IndexingConfig, TableGroup};
 use crate::database::ConnectionPool;
 use crate::db::ConnectionPoolConfig;
+use crate::models::indexing_config::StoredIndexingConfig;
 use crate::models::transactions::{stored_events_to_events, StoredTransactionEvents};
+use crate::schema::indexing_config;
 use crate::schema::pruner_cp_watermark;
 use crate::schema::tx_digests;
 use crate::{
@@ -68,6 +71,9 @@ pub const EVENT_SEQUENCE_NUMBER_STR: &str = "event_sequence_number";
 pub struct IndexerReader {
     pool: ConnectionPool,
     package_resolver: PackageResolver,
+    /// The writer's indexing config, loaded on first use. The writer refuses to change it once the
+    /// database holds data, so it does not need to be re-read.
+    indexing_config: Arc<OnceLock<IndexingConfig>>,
 }
 
 pub type PackageResolver = Arc<Resolver<PackageStoreWithLruCache<IndexerStorePackageResolver>>>;
@@ -81,6 +87,7 @@ impl IndexerReader {
         Self {
             pool,
             package_resolver,
+            indexing_config: Arc::new(OnceLock::new()),
         }
     }
 
@@ -98,6 +105,7 @@ impl IndexerReader {
         Ok(Self {
             pool,
             package_resolver,
+            indexing_config: Arc::new(OnceLock::new()),
         })
     }
 
@@ -108,6 +116,46 @@ impl IndexerReader {
 
 // Impl for reading data from the DB
 impl IndexerReader {
+    /// The configuration the writer is indexing with, or the default configuration (which indexes
+    /// everything) if the writer has not recorded one.
+    pub async fn get_indexing_config(&self) -> Result<IndexingConfig, IndexerError> {
+        use diesel_async::RunQueryDsl;
+
+        if let Some(config) = self.indexing_config.get() {
+            return Ok(config.clone());
+        }
+
+        let mut connection = self.pool.get().await?;
+
+        let stored = indexing_config::table
+            .select(StoredIndexingConfig::as_select())
+            .first::<StoredIndexingConfig>(&mut connection)
+            .await
+            .optional()?;
+
+        // Only cache a recorded config: until the writer starts, it may still record one.
+        let Some(stored) = stored else {
+            return Ok(IndexingConfig::default());
+        };
+
+        let config = IndexingConfig::try_from(stored)?;
+        Ok(self.indexing_config.get_or_init(|| config).clone())
+    }
+
+    /// Fail if the writer does not populate the tables in one of `groups`, which a query needs.
+    async fn check_indexed(&self, groups: &[TableGroup]) -> Result<(), IndexerError> {
+        let config = self.get_indexing_config().await?;
+        let Some(group) = groups.iter().find(|group| !config.is_enabled(**group)) else {
+            return Ok(());
+        };
+
+        Err(IndexerError::NotSupportedError(format!(
+            "This query is not supported because the indexer does not populate the {} tables \
+             (table group {group} is disabled).",
+            group.tables(),
+        )))
+    }
+
     async fn get_object_from_db(
         &self,
         object_id: &ObjectID,
@@ -779,6 +827,10 @@ impl IndexerReader {
             }
         };
 
+        if table_name != "transactions" {
+            self.check_indexed(&[TableGroup::TxIndices]).await?;
+        }
+
         let query = format!(
             "SELECT {TX_SEQUENCE_NUMBER_STR} FROM {} WHERE {} {} ORDER BY {TX_SEQUENCE_NUMBER_STR} {} LIMIT {}",
             table_name,
@@ -955,6 +1007,14 @@ impl IndexerReader {
     ) -> IndexerResult<Vec<SuiEvent>> {
         use diesel_async::RunQueryDsl;
 
+        // Events by sender are found by joining with tx_senders.
+        if let EventFilter::Sender(_) = &filter {
+            self.check_indexed(&[TableGroup::Events, TableGroup::TxIndices])
+                .await?;
+        } else {
+            self.check_indexed(&[TableGroup::Events]).await?;
+        }
+
         let mut connection = self.pool.get().await?;
 
         let (tx_seq, event_seq) = if let Some(cursor) = cursor {
@@ -29,6 +29,11 @@ async fn main() -> anyhow::Result<()> {
     mysten_metrics::init_metrics(&registry);
     let indexer_metrics = IndexerMetrics::new(&registry);
 
+    #[cfg(feature = "sqlite")]
+    if opts.database_url.scheme() == "sqlite" {
+        return start_sqlite_writer(opts, indexer_metrics).await;
+    }
+
     let pool = ConnectionPool::new(
         opts.database_url.clone(),
         opts.connection_pool_config.clone(),
@@ -42,6 +47,7 @@ async fn main() -> anyhow::Result<()> {
             snapshot_config,
             pruning_options,
             upload_options,
+            indexing_config,
         } => {
             // Make sure to run all migrations on startup, and also serve as a compatibility check.
             run_migrations(pool.dedicated_connection().await?).await?;
@@ -53,6 +59,7 @@ async fn main() -> anyhow::Result<()> {
                 indexer_metrics,
                 snapshot_config,
                 pruning_options,
+                indexing_config,
                 CancellationToken::new(),
             )
             .await?;
@@ -95,3 +102,42 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Run the indexer writer against an embedded SQLite database, given a URL like
+/// `sqlite:///path/to/indexer.db`, or `sqlite::memory:`. Only the `indexer` command is supported,
+/// because the other commands read from or manage a Postgres database.
+#[cfg(feature = "sqlite")]
+async fn start_sqlite_writer(
+    opts: sui_indexer::config::IndexerConfig,
+    indexer_metrics: IndexerMetrics,
+) -> anyhow::Result<()> {
+    use sui_indexer::store::SqliteIndexerStore;
+
+    let Command::Indexer {
+        ingestion_config,
+        snapshot_config,
+        pruning_options,
+        indexing_config,
+        ..
+    } = opts.command
+    else {
+        anyhow::bail!("Only the `indexer` command supports a SQLite database");
+    };
+
+    if pruning_options.epochs_to_keep.is_some() {
+        warn!("Pruning is not supported with a SQLite database, all epochs will be kept.");
+    }
+
+    let store = SqliteIndexerStore::new(opts.database_url.path(), indexer_metrics.clone())?;
+    Indexer::start_writer_with_store(
+        &ingestion_config,
+        store,
+        indexer_metrics,
+        snapshot_config,
+        indexing_config,
+        CancellationToken::new(),
+    )
+    .await?;
+
+    Ok(())
+}
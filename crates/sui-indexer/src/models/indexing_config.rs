@@ -0,0 +1,38 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::IndexingConfig;
+use crate::errors::IndexerError;
+use crate::schema::indexing_config;
+use diesel::prelude::*;
+
+#[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
+#[diesel(table_name = indexing_config)]
+pub struct StoredIndexingConfig {
+    /// Always 0: the table holds a single row.
+    pub id: i16,
+    /// JSON serialized IndexingConfig
+    pub config: String,
+}
+
+impl TryFrom<&IndexingConfig> for StoredIndexingConfig {
+    type Error = IndexerError;
+
+    fn try_from(config: &IndexingConfig) -> Result<Self, IndexerError> {
+        Ok(Self {
+            id: 0,
+            config: serde_json::to_string(config)
+                .map_err(|e| IndexerError::SerdeError(e.to_string()))?,
+        })
+    }
+}
+
+impl TryFrom<StoredIndexingConfig> for IndexingConfig {
+    type Error = IndexerError;
+
+    fn try_from(stored: StoredIndexingConfig) -> Result<Self, IndexerError> {
+        let config: IndexingConfig = serde_json::from_str(&stored.config)
+            .map_err(|e| IndexerError::SerdeError(e.to_string()))?;
+        Ok(config.normalized())
+    }
+}
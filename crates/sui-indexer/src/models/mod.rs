@@ -6,6 +6,7 @@ pub mod display;
 pub mod epoch;
 pub mod event_indices;
 pub mod events;
+pub mod indexing_config;
 pub mod obj_indices;
 pub mod objects;
 pub mod packages;
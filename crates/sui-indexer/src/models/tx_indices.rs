@@ -44,6 +44,7 @@ pub struct StoredTxAffectedObjects {
 pub struct StoredTxSenders {
     pub tx_sequence_number: i64,
     pub sender: Vec<u8>,
+    pub gas_used: Option<i64>,
 }
 
 #[derive(Queryable, Insertable, Selectable, Debug, Clone, Default)]
@@ -156,6 +157,7 @@ impl TxIndex {
         let tx_sender = StoredTxSenders {
             tx_sequence_number,
             sender: self.sender.to_vec(),
+            gas_used: Some(self.gas_used),
         };
 
         let tx_recipients = self
@@ -164,6 +164,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    indexing_config (id) {
+        id -> Int2,
+        config -> Text,
+    }
+}
+
 diesel::table! {
     objects (object_id) {
         object_id -> Bytea,
@@ -240,6 +247,13 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    persisted_queries (query_hash) {
+        query_hash -> Bytea,
+        query -> Text,
+    }
+}
+
 diesel::table! {
     protocol_configs (protocol_version, config_name) {
         protocol_version -> Int8,
@@ -365,6 +379,7 @@ diesel::table! {
     tx_senders (sender, tx_sequence_number) {
         tx_sequence_number -> Int8,
         sender -> Bytea,
+        gas_used -> Nullable<Int8>,
     }
 }
 
@@ -383,11 +398,13 @@ diesel::allow_tables_to_appear_in_same_query!(
     events,
     feature_flags,
     full_objects_history,
+    indexing_config,
     objects,
     objects_history,
     objects_snapshot,
     objects_version,
     packages,
+    persisted_queries,
     protocol_configs,
     pruner_cp_watermark,
     raw_checkpoints,
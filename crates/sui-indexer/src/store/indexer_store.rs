@@ -5,12 +5,14 @@ use std::collections::BTreeMap;
 
 use async_trait::async_trait;
 
+use crate::config::IndexingConfig;
 use crate::errors::IndexerError;
 use crate::handlers::{EpochToCommit, TransactionObjectChangesToCommit};
 use crate::models::display::StoredDisplay;
 use crate::models::obj_indices::StoredObjectVersion;
 use crate::models::objects::{StoredDeletedObject, StoredObject};
 use crate::models::raw_checkpoints::StoredRawCheckpoint;
+use crate::models::tx_indices::StoredTxDigest;
 use crate::types::{
     EventIndex, IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex,
 };
@@ -35,6 +37,9 @@ pub trait IndexerStore: Clone + Sync + Send + 'static {
 
     async fn get_chain_identifier(&self) -> Result<Option<Vec<u8>>, IndexerError>;
 
+    /// The configuration recorded by the last writer to run against this store, if any.
+    async fn get_indexing_config(&self) -> Result<Option<IndexingConfig>, IndexerError>;
+
     async fn persist_protocol_configs_and_feature_flags(
         &self,
         chain_id: Vec<u8>,
@@ -75,6 +80,9 @@ pub trait IndexerStore: Clone + Sync + Send + 'static {
         checkpoint_digest: Vec<u8>,
     ) -> Result<(), IndexerError>;
 
+    /// Record the configuration the writer indexes with, replacing any previous one.
+    async fn persist_indexing_config(&self, config: &IndexingConfig) -> Result<(), IndexerError>;
+
     async fn persist_transactions(
         &self,
         transactions: Vec<IndexedTransaction>,
@@ -82,6 +90,10 @@ pub trait IndexerStore: Clone + Sync + Send + 'static {
 
     async fn persist_tx_indices(&self, indices: Vec<TxIndex>) -> Result<(), IndexerError>;
 
+    /// Persist only the `tx_digests` index, for writers that do not populate the other `tx_*`
+    /// tables.
+    async fn persist_tx_digests(&self, digests: Vec<StoredTxDigest>) -> Result<(), IndexerError>;
+
     async fn persist_events(&self, events: Vec<IndexedEvent>) -> Result<(), IndexerError>;
     async fn persist_event_indices(
         &self,
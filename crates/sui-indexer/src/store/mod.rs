@@ -6,6 +6,8 @@ use std::time::Duration;
 use diesel_async::{scoped_futures::ScopedBoxFuture, AsyncPgConnection};
 pub(crate) use indexer_store::*;
 pub use pg_indexer_store::PgIndexerStore;
+#[cfg(feature = "sqlite")]
+pub use sqlite_indexer_store::SqliteIndexerStore;
 
 use crate::{database::ConnectionPool, errors::IndexerError};
 
@@ -13,6 +15,8 @@ pub mod indexer_store;
 pub mod package_resolver;
 mod pg_indexer_store;
 pub mod pg_partition_manager;
+#[cfg(feature = "sqlite")]
+mod sqlite_indexer_store;
 
 pub async fn transaction_with_retry<'a, Q, T>(
     pool: &ConnectionPool,
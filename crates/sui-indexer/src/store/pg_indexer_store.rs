@@ -12,6 +12,7 @@ use diesel::dsl::{max, min};
 use diesel::ExpressionMethods;
 use diesel::OptionalExtension;
 use diesel::QueryDsl;
+use diesel::SelectableHelper;
 use diesel_async::scoped_futures::ScopedFutureExt;
 use futures::future::Either;
 use itertools::Itertools;
@@ -24,7 +25,7 @@ use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_protocol_config::ProtocolConfig;
 use sui_storage::object_store::util::put;
 
-use crate::config::UploadOptions;
+use crate::config::{IndexingConfig, UploadOptions};
 use crate::database::ConnectionPool;
 use crate::errors::{Context, IndexerError};
 use crate::handlers::EpochToCommit;
@@ -37,6 +38,7 @@ use crate::models::display::StoredDisplay;
 use crate::models::epoch::StoredEpochInfo;
 use crate::models::epoch::{StoredFeatureFlag, StoredProtocolConfig};
 use crate::models::events::StoredEvent;
+use crate::models::indexing_config::StoredIndexingConfig;
 use crate::models::obj_indices::StoredObjectVersion;
 use crate::models::objects::{
     StoredDeletedObject, StoredFullHistoryObject, StoredHistoryObject, StoredObject,
@@ -44,14 +46,15 @@ use crate::models::objects::{
 };
 use crate::models::packages::StoredPackage;
 use crate::models::transactions::StoredTransaction;
+use crate::models::tx_indices::StoredTxDigest;
 use crate::schema::{
     chain_identifier, checkpoints, display, epochs, event_emit_module, event_emit_package,
     event_senders, event_struct_instantiation, event_struct_module, event_struct_name,
-    event_struct_package, events, feature_flags, full_objects_history, objects, objects_history,
-    objects_snapshot, objects_version, packages, protocol_configs, pruner_cp_watermark,
-    raw_checkpoints, transactions, tx_affected_addresses, tx_affected_objects, tx_calls_fun,
-    tx_calls_mod, tx_calls_pkg, tx_changed_objects, tx_digests, tx_input_objects, tx_kinds,
-    tx_recipients, tx_senders,
+    event_struct_package, events, feature_flags, full_objects_history, indexing_config, objects,
+    objects_history, objects_snapshot, objects_version, packages, protocol_configs,
+    pruner_cp_watermark, raw_checkpoints, transactions, tx_affected_addresses, tx_affected_objects,
+    tx_calls_fun, tx_calls_mod, tx_calls_pkg, tx_changed_objects, tx_digests, tx_input_objects,
+    tx_kinds, tx_recipients, tx_senders,
 };
 use crate::store::transaction_with_retry;
 use crate::types::{EventIndex, IndexedDeletedObject, IndexedObject};
@@ -1474,6 +1477,22 @@ impl IndexerStore for PgIndexerStore {
         self.get_chain_identifier().await
     }
 
+    async fn get_indexing_config(&self) -> Result<Option<IndexingConfig>, IndexerError> {
+        use diesel_async::RunQueryDsl;
+
+        let mut connection = self.pool.get().await?;
+
+        let stored = indexing_config::table
+            .select(StoredIndexingConfig::as_select())
+            .first::<StoredIndexingConfig>(&mut connection)
+            .await
+            .optional()
+            .map_err(Into::into)
+            .context("Failed reading indexing config from PostgresDB")?;
+
+        stored.map(IndexingConfig::try_from).transpose()
+    }
+
     async fn get_latest_object_snapshot_checkpoint_sequence_number(
         &self,
     ) -> Result<Option<u64>, IndexerError> {
@@ -1862,6 +1881,37 @@ impl IndexerStore for PgIndexerStore {
         Ok(())
     }
 
+    async fn persist_tx_digests(&self, digests: Vec<StoredTxDigest>) -> Result<(), IndexerError> {
+        use diesel_async::RunQueryDsl;
+
+        if digests.is_empty() {
+            return Ok(());
+        }
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_tx_indices
+            .start_timer();
+        transaction_with_retry(&self.pool, PG_DB_COMMIT_SLEEP_DURATION, |conn| {
+            async {
+                for digests_chunk in digests.chunks(PG_COMMIT_CHUNK_SIZE_INTRA_DB_TX) {
+                    diesel::insert_into(tx_digests::table)
+                        .values(digests_chunk)
+                        .on_conflict_do_nothing()
+                        .execute(conn)
+                        .await
+                        .map_err(IndexerError::from)
+                        .context("Failed to write to tx_digests table")?;
+                }
+                Ok::<(), IndexerError>(())
+            }
+            .scope_boxed()
+        })
+        .await?;
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} tx_digests", digests.len());
+        Ok(())
+    }
+
     async fn persist_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError> {
         self.persist_epoch(epoch).await
     }
@@ -2005,52 +2055,13 @@ impl IndexerStore for PgIndexerStore {
     ) -> Result<(), IndexerError> {
         use diesel_async::RunQueryDsl;
 
-        let chain_id = ChainIdentifier::from(
-            CheckpointDigest::try_from(chain_id).expect("Unable to convert chain id"),
-        );
-
-        let mut all_configs = vec![];
-        let mut all_flags = vec![];
-
         let (start_version, end_version) = self.get_protocol_version_index_range().await?;
         info!(
             "Persisting protocol configs with start_version: {}, end_version: {}",
             start_version, end_version
         );
-
-        // Gather all protocol configs and feature flags for all versions between start and end.
-        for version in start_version..=end_version {
-            let protocol_configs = ProtocolConfig::get_for_version_if_supported(
-                (version as u64).into(),
-                chain_id.chain(),
-            )
-            .ok_or(IndexerError::GenericError(format!(
-                "Unable to fetch protocol version {} and chain {:?}",
-                version,
-                chain_id.chain()
-            )))?;
-            let configs_vec = protocol_configs
-                .attr_map()
-                .into_iter()
-                .map(|(k, v)| StoredProtocolConfig {
-                    protocol_version: version,
-                    config_name: k,
-                    config_value: v.map(|v| v.to_string()),
-                })
-                .collect::<Vec<_>>();
-            all_configs.extend(configs_vec);
-
-            let feature_flags = protocol_configs
-                .feature_map()
-                .into_iter()
-                .map(|(k, v)| StoredFeatureFlag {
-                    protocol_version: version,
-                    flag_name: k,
-                    flag_value: v,
-                })
-                .collect::<Vec<_>>();
-            all_flags.extend(feature_flags);
-        }
+        let (all_configs, all_flags) =
+            protocol_configs_and_feature_flags(chain_id, start_version, end_version)?;
 
         // Now insert all of them into the db.
         // TODO: right now the size of these updates is manageable but later we may consider batching.
@@ -2104,6 +2115,28 @@ impl IndexerStore for PgIndexerStore {
         Ok(())
     }
 
+    async fn persist_indexing_config(&self, config: &IndexingConfig) -> Result<(), IndexerError> {
+        use diesel_async::RunQueryDsl;
+
+        let stored = StoredIndexingConfig::try_from(config)?;
+        transaction_with_retry(&self.pool, PG_DB_COMMIT_SLEEP_DURATION, |conn| {
+            async {
+                diesel::insert_into(indexing_config::table)
+                    .values(&stored)
+                    .on_conflict(indexing_config::id)
+                    .do_update()
+                    .set(indexing_config::config.eq(excluded(indexing_config::config)))
+                    .execute(conn)
+                    .await
+                    .map_err(IndexerError::from)
+                    .context("Failed to write to indexing_config table")?;
+                Ok::<(), IndexerError>(())
+            }
+            .scope_boxed()
+        })
+        .await
+    }
+
     async fn persist_raw_checkpoints(
         &self,
         checkpoints: Vec<StoredRawCheckpoint>,
@@ -2112,7 +2145,56 @@ impl IndexerStore for PgIndexerStore {
     }
 }
 
-fn make_objects_history_to_commit(
+/// Gather the protocol configs and feature flags of all protocol versions between `start_version`
+/// and `end_version` (inclusive), for the chain identified by `chain_id`.
+pub(crate) fn protocol_configs_and_feature_flags(
+    chain_id: Vec<u8>,
+    start_version: i64,
+    end_version: i64,
+) -> Result<(Vec<StoredProtocolConfig>, Vec<StoredFeatureFlag>), IndexerError> {
+    let chain_id = ChainIdentifier::from(
+        CheckpointDigest::try_from(chain_id).expect("Unable to convert chain id"),
+    );
+
+    let mut all_configs = vec![];
+    let mut all_flags = vec![];
+
+    // Gather all protocol configs and feature flags for all versions between start and end.
+    for version in start_version..=end_version {
+        let protocol_configs =
+            ProtocolConfig::get_for_version_if_supported((version as u64).into(), chain_id.chain())
+                .ok_or(IndexerError::GenericError(format!(
+                    "Unable to fetch protocol version {} and chain {:?}",
+                    version,
+                    chain_id.chain()
+                )))?;
+        let configs_vec = protocol_configs
+            .attr_map()
+            .into_iter()
+            .map(|(k, v)| StoredProtocolConfig {
+                protocol_version: version,
+                config_name: k,
+                config_value: v.map(|v| v.to_string()),
+            })
+            .collect::<Vec<_>>();
+        all_configs.extend(configs_vec);
+
+        let feature_flags = protocol_configs
+            .feature_map()
+            .into_iter()
+            .map(|(k, v)| StoredFeatureFlag {
+                protocol_version: version,
+                flag_name: k,
+                flag_value: v,
+            })
+            .collect::<Vec<_>>();
+        all_flags.extend(feature_flags);
+    }
+
+    Ok((all_configs, all_flags))
+}
+
+pub(crate) fn make_objects_history_to_commit(
     tx_object_changes: Vec<TransactionObjectChangesToCommit>,
 ) -> Vec<StoredHistoryObject> {
     let deleted_objects: Vec<StoredHistoryObject> = tx_object_changes
@@ -2133,7 +2215,7 @@ fn make_objects_history_to_commit(
 // within partition of mutations or deletions, retain the latest with highest version;
 // For overlappings of mutations and deletions, only keep one with higher version.
 // This is necessary b/c after this step, DB commit will be done in parallel and not in order.
-fn retain_latest_indexed_objects(
+pub(crate) fn retain_latest_indexed_objects(
     tx_object_changes: Vec<TransactionObjectChangesToCommit>,
 ) -> (Vec<IndexedObject>, Vec<IndexedDeletedObject>) {
     // Only the last deleted / mutated object will be in the map,
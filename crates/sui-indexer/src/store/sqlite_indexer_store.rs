@@ -0,0 +1,1511 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An [`IndexerStore`] backed by an embedded SQLite database, for running the indexer against a
+//! local network without a Postgres server. It writes the same tables as [`PgIndexerStore`], with
+//! two differences in representation: array columns are stored as BCS-serialized blobs, and JSON
+//! columns as text. Tables are not partitioned, so epochs cannot be pruned.
+//!
+//! [`PgIndexerStore`]: super::PgIndexerStore
+
+use std::collections::BTreeMap;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use csv::ReaderBuilder;
+use diesel::dsl::{max, min};
+use diesel::prelude::*;
+use diesel::upsert::excluded;
+use diesel::SqliteConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use tracing::{info, warn};
+
+use crate::config::IndexingConfig;
+use crate::errors::{Context, IndexerError};
+use crate::handlers::{EpochToCommit, TransactionObjectChangesToCommit};
+use crate::metrics::IndexerMetrics;
+use crate::models::checkpoints::{StoredChainIdentifier, StoredCheckpoint, StoredCpTx};
+use crate::models::display::StoredDisplay;
+use crate::models::epoch::StoredEpochInfo;
+use crate::models::events::StoredEvent;
+use crate::models::indexing_config::StoredIndexingConfig;
+use crate::models::obj_indices::StoredObjectVersion;
+use crate::models::objects::{
+    StoredDeletedObject, StoredFullHistoryObject, StoredObject, StoredObjectSnapshot,
+};
+use crate::models::packages::StoredPackage;
+use crate::models::raw_checkpoints::StoredRawCheckpoint;
+use crate::models::transactions::StoredTransaction;
+use crate::models::tx_indices::StoredTxDigest;
+use crate::schema::{
+    chain_identifier, display, event_emit_module, event_emit_package, event_senders,
+    event_struct_instantiation, event_struct_module, event_struct_name, event_struct_package,
+    feature_flags, full_objects_history, indexing_config, objects, objects_history,
+    objects_snapshot, objects_version, packages, protocol_configs, pruner_cp_watermark,
+    raw_checkpoints, tx_affected_addresses, tx_affected_objects, tx_calls_fun, tx_calls_mod,
+    tx_calls_pkg, tx_changed_objects, tx_digests, tx_input_objects, tx_kinds, tx_recipients,
+    tx_senders,
+};
+use crate::types::{
+    EventIndex, IndexedCheckpoint, IndexedEvent, IndexedPackage, IndexedTransaction, TxIndex,
+};
+
+use super::pg_indexer_store::{
+    make_objects_history_to_commit, protocol_configs_and_feature_flags,
+    retain_latest_indexed_objects,
+};
+use super::IndexerStore;
+
+const SQLITE_MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/sqlite");
+
+/// The tables in [`crate::schema`] that have array or JSON columns, re-declared with the types
+/// they are stored as in SQLite.
+mod sqlite_schema {
+    diesel::table! {
+        checkpoints (sequence_number) {
+            sequence_number -> Int8,
+            checkpoint_digest -> Bytea,
+            epoch -> Int8,
+            network_total_transactions -> Int8,
+            previous_checkpoint_digest -> Nullable<Bytea>,
+            end_of_epoch -> Bool,
+            tx_digests -> Bytea,
+            timestamp_ms -> Int8,
+            total_gas_cost -> Int8,
+            computation_cost -> Int8,
+            storage_cost -> Int8,
+            storage_rebate -> Int8,
+            non_refundable_storage_fee -> Int8,
+            checkpoint_commitments -> Bytea,
+            validator_signature -> Bytea,
+            end_of_epoch_data -> Nullable<Bytea>,
+            min_tx_sequence_number -> Nullable<Int8>,
+            max_tx_sequence_number -> Nullable<Int8>,
+        }
+    }
+
+    diesel::table! {
+        epochs (epoch) {
+            epoch -> Int8,
+            first_checkpoint_id -> Int8,
+            epoch_start_timestamp -> Int8,
+            reference_gas_price -> Int8,
+            protocol_version -> Int8,
+            total_stake -> Int8,
+            storage_fund_balance -> Int8,
+            system_state -> Nullable<Bytea>,
+            epoch_total_transactions -> Nullable<Int8>,
+            last_checkpoint_id -> Nullable<Int8>,
+            epoch_end_timestamp -> Nullable<Int8>,
+            storage_fund_reinvestment -> Nullable<Int8>,
+            storage_charge -> Nullable<Int8>,
+            storage_rebate -> Nullable<Int8>,
+            stake_subsidy_amount -> Nullable<Int8>,
+            total_gas_fees -> Nullable<Int8>,
+            total_stake_rewards_distributed -> Nullable<Int8>,
+            leftover_storage_fund_inflow -> Nullable<Int8>,
+            epoch_commitments -> Nullable<Bytea>,
+            system_state_summary_json -> Nullable<Text>,
+        }
+    }
+
+    diesel::table! {
+        events (tx_sequence_number, event_sequence_number) {
+            tx_sequence_number -> Int8,
+            event_sequence_number -> Int8,
+            transaction_digest -> Bytea,
+            senders -> Bytea,
+            package -> Bytea,
+            module -> Text,
+            event_type -> Text,
+            timestamp_ms -> Int8,
+            bcs -> Bytea,
+            sender -> Nullable<Bytea>,
+        }
+    }
+
+    diesel::table! {
+        transactions (tx_sequence_number) {
+            tx_sequence_number -> Int8,
+            transaction_digest -> Bytea,
+            raw_transaction -> Bytea,
+            raw_effects -> Bytea,
+            checkpoint_sequence_number -> Int8,
+            timestamp_ms -> Int8,
+            object_changes -> Bytea,
+            balance_changes -> Bytea,
+            events -> Bytea,
+            transaction_kind -> Int2,
+            success_command_count -> Int2,
+        }
+    }
+}
+
+use sqlite_schema::{checkpoints, epochs, events, transactions};
+
+/// Inserts each of `rows` into `table`, leaving existing rows with the same key untouched. Rows
+/// are inserted one at a time, because SQLite does not support `DEFAULT` in multi-row inserts.
+macro_rules! insert_or_ignore {
+    ($conn:expr, $table:expr, $rows:expr) => {
+        for row in $rows {
+            diesel::insert_into($table)
+                .values(row)
+                .on_conflict_do_nothing()
+                .execute(&mut *$conn)?;
+        }
+    };
+}
+
+#[derive(Clone)]
+pub struct SqliteIndexerStore {
+    connection: Arc<Mutex<SqliteConnection>>,
+    metrics: IndexerMetrics,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = checkpoints)]
+struct SqliteCheckpoint {
+    sequence_number: i64,
+    checkpoint_digest: Vec<u8>,
+    epoch: i64,
+    network_total_transactions: i64,
+    previous_checkpoint_digest: Option<Vec<u8>>,
+    end_of_epoch: bool,
+    tx_digests: Vec<u8>,
+    timestamp_ms: i64,
+    total_gas_cost: i64,
+    computation_cost: i64,
+    storage_cost: i64,
+    storage_rebate: i64,
+    non_refundable_storage_fee: i64,
+    checkpoint_commitments: Vec<u8>,
+    validator_signature: Vec<u8>,
+    end_of_epoch_data: Option<Vec<u8>>,
+    min_tx_sequence_number: Option<i64>,
+    max_tx_sequence_number: Option<i64>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = epochs)]
+struct SqliteEpochInfo {
+    epoch: i64,
+    first_checkpoint_id: i64,
+    epoch_start_timestamp: i64,
+    reference_gas_price: i64,
+    protocol_version: i64,
+    total_stake: i64,
+    storage_fund_balance: i64,
+    system_state: Option<Vec<u8>>,
+    epoch_total_transactions: Option<i64>,
+    last_checkpoint_id: Option<i64>,
+    epoch_end_timestamp: Option<i64>,
+    storage_fund_reinvestment: Option<i64>,
+    storage_charge: Option<i64>,
+    storage_rebate: Option<i64>,
+    stake_subsidy_amount: Option<i64>,
+    total_gas_fees: Option<i64>,
+    total_stake_rewards_distributed: Option<i64>,
+    leftover_storage_fund_inflow: Option<i64>,
+    epoch_commitments: Option<Vec<u8>>,
+    system_state_summary_json: Option<String>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = events)]
+struct SqliteEvent {
+    tx_sequence_number: i64,
+    event_sequence_number: i64,
+    transaction_digest: Vec<u8>,
+    senders: Vec<u8>,
+    package: Vec<u8>,
+    module: String,
+    event_type: String,
+    timestamp_ms: i64,
+    bcs: Vec<u8>,
+    sender: Option<Vec<u8>>,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = transactions)]
+struct SqliteTransaction {
+    tx_sequence_number: i64,
+    transaction_digest: Vec<u8>,
+    raw_transaction: Vec<u8>,
+    raw_effects: Vec<u8>,
+    checkpoint_sequence_number: i64,
+    timestamp_ms: i64,
+    object_changes: Vec<u8>,
+    balance_changes: Vec<u8>,
+    events: Vec<u8>,
+    transaction_kind: i16,
+    success_command_count: i16,
+}
+
+impl SqliteIndexerStore {
+    /// Open the SQLite database at `path` (creating it if it does not exist yet), and bring its
+    /// schema up to date. Pass `:memory:` for a database that only lives as long as the store.
+    pub fn new(path: &str, metrics: IndexerMetrics) -> Result<Self, IndexerError> {
+        let mut connection = SqliteConnection::establish(path).map_err(|e| {
+            IndexerError::SqliteError(format!("Failed to open database at {path}: {e}"))
+        })?;
+
+        // Wait for other connections to the same file (e.g. a reader inspecting the database)
+        // to release their locks, rather than failing immediately.
+        diesel::sql_query("PRAGMA busy_timeout = 5000")
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::SqliteError(format!("Failed to configure database: {e}")))?;
+
+        connection
+            .run_pending_migrations(SQLITE_MIGRATIONS)
+            .map_err(|e| IndexerError::SqliteError(format!("Failed to run migrations: {e}")))?;
+
+        Ok(Self {
+            connection: Arc::new(Mutex::new(connection)),
+            metrics,
+        })
+    }
+
+    /// Run `query` against the database, on a blocking thread. Queries are serialized, as the
+    /// store shares a single connection.
+    async fn run<T, F>(&self, query: F) -> Result<T, IndexerError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut SqliteConnection) -> Result<T, IndexerError> + Send + 'static,
+    {
+        let connection = self.connection.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut connection = connection
+                .lock()
+                .map_err(|e| IndexerError::SqliteError(e.to_string()))?;
+            query(&mut connection)
+        })
+        .await
+        .map_err(|e| IndexerError::SqliteError(format!("Query task failed: {e}")))?
+    }
+
+    /// Like [`Self::run`], but all the writes in `query` are committed in one transaction.
+    async fn transaction<T, F>(&self, query: F) -> Result<T, IndexerError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut SqliteConnection) -> Result<T, IndexerError> + Send + 'static,
+    {
+        self.run(|conn| conn.transaction(query)).await
+    }
+
+    async fn persist_display_updates(
+        &self,
+        display_updates: Vec<StoredDisplay>,
+    ) -> Result<(), IndexerError> {
+        self.transaction(move |conn| {
+            for display in &display_updates {
+                diesel::insert_into(display::table)
+                    .values(display)
+                    .on_conflict(display::object_type)
+                    .do_update()
+                    .set((
+                        display::id.eq(excluded(display::id)),
+                        display::version.eq(excluded(display::version)),
+                        display::bcs.eq(excluded(display::bcs)),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+        .await
+        .context("Failed to write to display table")
+    }
+}
+
+#[async_trait]
+impl IndexerStore for SqliteIndexerStore {
+    async fn get_latest_checkpoint_sequence_number(&self) -> Result<Option<u64>, IndexerError> {
+        self.run(|conn| {
+            checkpoints::table
+                .select(max(checkpoints::sequence_number))
+                .first::<Option<i64>>(conn)
+                .map_err(Into::into)
+                .map(|v| v.map(|v| v as u64))
+                .context("Failed reading latest checkpoint sequence number from SQLite")
+        })
+        .await
+    }
+
+    async fn get_available_epoch_range(&self) -> Result<(u64, u64), IndexerError> {
+        self.run(|conn| {
+            epochs::table
+                .select((min(epochs::epoch), max(epochs::epoch)))
+                .first::<(Option<i64>, Option<i64>)>(conn)
+                .map_err(Into::into)
+                .map(|(min, max)| {
+                    (
+                        min.unwrap_or_default() as u64,
+                        max.unwrap_or_default() as u64,
+                    )
+                })
+                .context("Failed reading min and max epoch numbers from SQLite")
+        })
+        .await
+    }
+
+    async fn get_available_checkpoint_range(&self) -> Result<(u64, u64), IndexerError> {
+        self.run(|conn| {
+            checkpoints::table
+                .select((
+                    min(checkpoints::sequence_number),
+                    max(checkpoints::sequence_number),
+                ))
+                .first::<(Option<i64>, Option<i64>)>(conn)
+                .map_err(Into::into)
+                .map(|(min, max)| {
+                    (
+                        min.unwrap_or_default() as u64,
+                        max.unwrap_or_default() as u64,
+                    )
+                })
+                .context("Failed reading min and max checkpoint sequence numbers from SQLite")
+        })
+        .await
+    }
+
+    async fn get_chain_identifier(&self) -> Result<Option<Vec<u8>>, IndexerError> {
+        self.run(|conn| {
+            chain_identifier::table
+                .select(chain_identifier::checkpoint_digest)
+                .first::<Vec<u8>>(conn)
+                .optional()
+                .map_err(Into::into)
+                .context("Failed reading chain id from SQLite")
+        })
+        .await
+    }
+
+    async fn get_indexing_config(&self) -> Result<Option<IndexingConfig>, IndexerError> {
+        self.run(|conn| {
+            let stored = indexing_config::table
+                .select(StoredIndexingConfig::as_select())
+                .first::<StoredIndexingConfig>(conn)
+                .optional()
+                .map_err(Into::into)
+                .context("Failed reading indexing config from SQLite")?;
+
+            stored.map(IndexingConfig::try_from).transpose()
+        })
+        .await
+    }
+
+    async fn get_latest_object_snapshot_checkpoint_sequence_number(
+        &self,
+    ) -> Result<Option<u64>, IndexerError> {
+        self.run(|conn| {
+            objects_snapshot::table
+                .select(max(objects_snapshot::checkpoint_sequence_number))
+                .first::<Option<i64>>(conn)
+                .map_err(Into::into)
+                .map(|v| v.map(|v| v as u64))
+                .context("Failed reading latest object snapshot checkpoint from SQLite")
+        })
+        .await
+    }
+
+    async fn persist_objects(
+        &self,
+        object_changes: Vec<TransactionObjectChangesToCommit>,
+    ) -> Result<(), IndexerError> {
+        if object_changes.is_empty() {
+            return Ok(());
+        }
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_objects
+            .start_timer();
+        let (indexed_mutations, indexed_deletions) = retain_latest_indexed_objects(object_changes);
+        let object_mutations = indexed_mutations
+            .into_iter()
+            .map(StoredObject::from)
+            .collect::<Vec<_>>();
+        let object_deletions = indexed_deletions
+            .into_iter()
+            .map(StoredDeletedObject::from)
+            .collect::<Vec<_>>();
+        let mutation_len = object_mutations.len();
+        let deletion_len = object_deletions.len();
+
+        self.transaction(move |conn| {
+            for object in &object_mutations {
+                diesel::insert_into(objects::table)
+                    .values(object)
+                    .on_conflict(objects::object_id)
+                    .do_update()
+                    .set((
+                        objects::object_id.eq(excluded(objects::object_id)),
+                        objects::object_version.eq(excluded(objects::object_version)),
+                        objects::object_digest.eq(excluded(objects::object_digest)),
+                        objects::owner_type.eq(excluded(objects::owner_type)),
+                        objects::owner_id.eq(excluded(objects::owner_id)),
+                        objects::object_type.eq(excluded(objects::object_type)),
+                        objects::serialized_object.eq(excluded(objects::serialized_object)),
+                        objects::coin_type.eq(excluded(objects::coin_type)),
+                        objects::coin_balance.eq(excluded(objects::coin_balance)),
+                        objects::df_kind.eq(excluded(objects::df_kind)),
+                    ))
+                    .execute(conn)?;
+            }
+
+            diesel::delete(
+                objects::table.filter(
+                    objects::object_id.eq_any(
+                        object_deletions
+                            .into_iter()
+                            .map(|o| o.object_id)
+                            .collect::<Vec<_>>(),
+                    ),
+                ),
+            )
+            .execute(conn)?;
+
+            Ok(())
+        })
+        .await
+        .context("Failed to write to objects table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(
+            elapsed,
+            "Persisted objects with {} mutations and {} deletions ", mutation_len, deletion_len,
+        );
+        Ok(())
+    }
+
+    async fn persist_objects_snapshot(
+        &self,
+        object_changes: Vec<TransactionObjectChangesToCommit>,
+    ) -> Result<(), IndexerError> {
+        if object_changes.is_empty() {
+            return Ok(());
+        }
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_objects_snapshot
+            .start_timer();
+        let (indexed_mutations, indexed_deletions) = retain_latest_indexed_objects(object_changes);
+        let objects_snapshot = indexed_mutations
+            .into_iter()
+            .map(StoredObjectSnapshot::from)
+            .chain(
+                indexed_deletions
+                    .into_iter()
+                    .map(StoredObjectSnapshot::from),
+            )
+            .collect::<Vec<_>>();
+        let len = objects_snapshot.len();
+
+        self.transaction(move |conn| {
+            for object in &objects_snapshot {
+                diesel::insert_into(objects_snapshot::table)
+                    .values(object)
+                    .on_conflict(objects_snapshot::object_id)
+                    .do_update()
+                    .set((
+                        objects_snapshot::object_version
+                            .eq(excluded(objects_snapshot::object_version)),
+                        objects_snapshot::object_status
+                            .eq(excluded(objects_snapshot::object_status)),
+                        objects_snapshot::object_digest
+                            .eq(excluded(objects_snapshot::object_digest)),
+                        objects_snapshot::owner_type.eq(excluded(objects_snapshot::owner_type)),
+                        objects_snapshot::owner_id.eq(excluded(objects_snapshot::owner_id)),
+                        objects_snapshot::object_type_package
+                            .eq(excluded(objects_snapshot::object_type_package)),
+                        objects_snapshot::object_type_module
+                            .eq(excluded(objects_snapshot::object_type_module)),
+                        objects_snapshot::object_type_name
+                            .eq(excluded(objects_snapshot::object_type_name)),
+                        objects_snapshot::object_type.eq(excluded(objects_snapshot::object_type)),
+                        objects_snapshot::serialized_object
+                            .eq(excluded(objects_snapshot::serialized_object)),
+                        objects_snapshot::coin_type.eq(excluded(objects_snapshot::coin_type)),
+                        objects_snapshot::coin_balance.eq(excluded(objects_snapshot::coin_balance)),
+                        objects_snapshot::df_kind.eq(excluded(objects_snapshot::df_kind)),
+                        objects_snapshot::checkpoint_sequence_number
+                            .eq(excluded(objects_snapshot::checkpoint_sequence_number)),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+        .await
+        .context("Failed to write to objects_snapshot table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} objects snapshot", len);
+        Ok(())
+    }
+
+    async fn persist_object_history(
+        &self,
+        object_changes: Vec<TransactionObjectChangesToCommit>,
+    ) -> Result<(), IndexerError> {
+        if object_changes.is_empty() {
+            return Ok(());
+        }
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_objects_history
+            .start_timer();
+        let objects = make_objects_history_to_commit(object_changes);
+        let len = objects.len();
+
+        self.transaction(move |conn| {
+            insert_or_ignore!(conn, objects_history::table, &objects);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to objects_history table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} objects history", len);
+        Ok(())
+    }
+
+    async fn persist_full_objects_history(
+        &self,
+        object_changes: Vec<TransactionObjectChangesToCommit>,
+    ) -> Result<(), IndexerError> {
+        if object_changes.is_empty() {
+            return Ok(());
+        }
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_full_objects_history
+            .start_timer();
+        let objects: Vec<StoredFullHistoryObject> = object_changes
+            .into_iter()
+            .flat_map(|c| {
+                let TransactionObjectChangesToCommit {
+                    changed_objects,
+                    deleted_objects,
+                } = c;
+                changed_objects
+                    .into_iter()
+                    .map(|o| o.into())
+                    .chain(deleted_objects.into_iter().map(|o| o.into()))
+            })
+            .collect();
+        let len = objects.len();
+
+        self.transaction(move |conn| {
+            insert_or_ignore!(conn, full_objects_history::table, &objects);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to full_objects_history table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} full objects history", len);
+        Ok(())
+    }
+
+    async fn persist_object_versions(
+        &self,
+        object_versions: Vec<StoredObjectVersion>,
+    ) -> Result<(), IndexerError> {
+        if object_versions.is_empty() {
+            return Ok(());
+        }
+        let len = object_versions.len();
+
+        self.transaction(move |conn| {
+            insert_or_ignore!(conn, objects_version::table, &object_versions);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to objects_version table")?;
+
+        info!("Persisted {} object versions", len);
+        Ok(())
+    }
+
+    async fn persist_checkpoints(
+        &self,
+        checkpoints: Vec<IndexedCheckpoint>,
+    ) -> Result<(), IndexerError> {
+        let Some(first_checkpoint) = checkpoints.first() else {
+            return Ok(());
+        };
+
+        // If the first checkpoint has sequence number 0, we need to persist the digest as
+        // chain identifier.
+        if first_checkpoint.sequence_number == 0 {
+            let checkpoint_digest = first_checkpoint.checkpoint_digest.into_inner().to_vec();
+            self.persist_protocol_configs_and_feature_flags(checkpoint_digest.clone())
+                .await?;
+            self.persist_chain_identifier(checkpoint_digest).await?;
+        }
+
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_checkpoints
+            .start_timer();
+
+        let stored_cp_txs = checkpoints.iter().map(StoredCpTx::from).collect::<Vec<_>>();
+        let stored_checkpoints = checkpoints
+            .iter()
+            .map(StoredCheckpoint::from)
+            .map(SqliteCheckpoint::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let len = stored_checkpoints.len();
+
+        let committed = self
+            .transaction(move |conn| {
+                insert_or_ignore!(conn, pruner_cp_watermark::table, &stored_cp_txs);
+                insert_or_ignore!(conn, checkpoints::table, &stored_checkpoints);
+                Ok(stored_checkpoints
+                    .into_iter()
+                    .map(|cp| (cp.sequence_number, cp.timestamp_ms))
+                    .collect::<Vec<_>>())
+            })
+            .await
+            .context("Failed to write to checkpoints table")?;
+
+        let time_now_ms = chrono::Utc::now().timestamp_millis();
+        for (sequence_number, timestamp_ms) in committed {
+            self.metrics
+                .db_commit_lag_ms
+                .set(time_now_ms - timestamp_ms);
+            self.metrics
+                .max_committed_checkpoint_sequence_number
+                .set(sequence_number);
+            self.metrics
+                .committed_checkpoint_timestamp_ms
+                .set(timestamp_ms);
+        }
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} checkpoints", len);
+        Ok(())
+    }
+
+    async fn persist_transactions(
+        &self,
+        transactions: Vec<IndexedTransaction>,
+    ) -> Result<(), IndexerError> {
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_transactions
+            .start_timer();
+        let transactions = transactions
+            .iter()
+            .map(StoredTransaction::from)
+            .map(SqliteTransaction::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let len = transactions.len();
+
+        self.transaction(move |conn| {
+            insert_or_ignore!(conn, transactions::table, &transactions);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to transactions table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} transactions", len);
+        Ok(())
+    }
+
+    async fn persist_events(&self, events: Vec<IndexedEvent>) -> Result<(), IndexerError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_events
+            .start_timer();
+        let events = events
+            .into_iter()
+            .map(StoredEvent::from)
+            .map(SqliteEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+        let len = events.len();
+
+        self.transaction(move |conn| {
+            insert_or_ignore!(conn, events::table, &events);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to events table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} events", len);
+        Ok(())
+    }
+
+    async fn persist_displays(
+        &self,
+        display_updates: BTreeMap<String, StoredDisplay>,
+    ) -> Result<(), IndexerError> {
+        if display_updates.is_empty() {
+            return Ok(());
+        }
+        self.persist_display_updates(display_updates.into_values().collect())
+            .await
+    }
+
+    async fn persist_packages(&self, packages: Vec<IndexedPackage>) -> Result<(), IndexerError> {
+        if packages.is_empty() {
+            return Ok(());
+        }
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_packages
+            .start_timer();
+        let packages = packages
+            .into_iter()
+            .map(StoredPackage::from)
+            .collect::<Vec<_>>();
+        let len = packages.len();
+
+        self.transaction(move |conn| {
+            for package in &packages {
+                diesel::insert_into(packages::table)
+                    .values(package)
+                    .on_conflict(packages::package_id)
+                    .do_update()
+                    .set((
+                        packages::package_id.eq(excluded(packages::package_id)),
+                        packages::move_package.eq(excluded(packages::move_package)),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+        .await
+        .context("Failed to write to packages table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} packages", len);
+        Ok(())
+    }
+
+    async fn persist_event_indices(&self, indices: Vec<EventIndex>) -> Result<(), IndexerError> {
+        if indices.is_empty() {
+            return Ok(());
+        }
+        let len = indices.len();
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_event_indices
+            .start_timer();
+
+        self.transaction(move |conn| {
+            for index in indices {
+                let (
+                    emit_package,
+                    emit_module,
+                    sender,
+                    struct_package,
+                    struct_module,
+                    struct_name,
+                    struct_instantiation,
+                ) = index.split();
+                insert_or_ignore!(conn, event_emit_package::table, [&emit_package]);
+                insert_or_ignore!(conn, event_emit_module::table, [&emit_module]);
+                insert_or_ignore!(conn, event_senders::table, [&sender]);
+                insert_or_ignore!(conn, event_struct_package::table, [&struct_package]);
+                insert_or_ignore!(conn, event_struct_module::table, [&struct_module]);
+                insert_or_ignore!(conn, event_struct_name::table, [&struct_name]);
+                insert_or_ignore!(
+                    conn,
+                    event_struct_instantiation::table,
+                    [&struct_instantiation]
+                );
+            }
+            Ok(())
+        })
+        .await
+        .context("Failed to write to event indices tables")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} event_indices", len);
+        Ok(())
+    }
+
+    async fn persist_tx_indices(&self, indices: Vec<TxIndex>) -> Result<(), IndexerError> {
+        if indices.is_empty() {
+            return Ok(());
+        }
+        let len = indices.len();
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_tx_indices
+            .start_timer();
+
+        self.transaction(move |conn| {
+            for index in indices {
+                let (
+                    affected_addresses,
+                    affected_objects,
+                    senders,
+                    recipients,
+                    input_objects,
+                    changed_objects,
+                    pkgs,
+                    mods,
+                    funs,
+                    digests,
+                    kinds,
+                ) = index.split();
+                insert_or_ignore!(conn, tx_affected_addresses::table, &affected_addresses);
+                insert_or_ignore!(conn, tx_affected_objects::table, &affected_objects);
+                insert_or_ignore!(conn, tx_senders::table, &senders);
+                insert_or_ignore!(conn, tx_recipients::table, &recipients);
+                insert_or_ignore!(conn, tx_input_objects::table, &input_objects);
+                insert_or_ignore!(conn, tx_changed_objects::table, &changed_objects);
+                insert_or_ignore!(conn, tx_calls_pkg::table, &pkgs);
+                insert_or_ignore!(conn, tx_calls_mod::table, &mods);
+                insert_or_ignore!(conn, tx_calls_fun::table, &funs);
+                insert_or_ignore!(conn, tx_digests::table, &digests);
+                insert_or_ignore!(conn, tx_kinds::table, &kinds);
+            }
+            Ok(())
+        })
+        .await
+        .context("Failed to write to tx indices tables")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} tx_indices", len);
+        Ok(())
+    }
+
+    async fn persist_tx_digests(&self, digests: Vec<StoredTxDigest>) -> Result<(), IndexerError> {
+        if digests.is_empty() {
+            return Ok(());
+        }
+        let len = digests.len();
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_tx_indices
+            .start_timer();
+
+        self.transaction(move |conn| {
+            insert_or_ignore!(conn, tx_digests::table, &digests);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to tx_digests table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, "Persisted {} tx_digests", len);
+        Ok(())
+    }
+
+    async fn persist_epoch(&self, epoch: EpochToCommit) -> Result<(), IndexerError> {
+        let guard = self
+            .metrics
+            .checkpoint_db_commit_latency_epoch
+            .start_timer();
+        let epoch_id = epoch.new_epoch.epoch;
+
+        self.transaction(move |conn| {
+            if let Some(last_epoch) = &epoch.last_epoch {
+                // As in the Postgres store, `epoch_total_transactions` is recomputed here, where
+                // the checkpoints of the epoch before last are guaranteed to have been written.
+                let previous_epoch_network_total_transactions = match epoch_id {
+                    0 | 1 => 0,
+                    _ => checkpoints::table
+                        .filter(checkpoints::epoch.eq((epoch_id - 2) as i64))
+                        .select(max(checkpoints::network_total_transactions))
+                        .first::<Option<i64>>(conn)?
+                        .unwrap_or(0) as u64,
+                };
+
+                let mut last_epoch = StoredEpochInfo::from_epoch_end_info(last_epoch);
+                last_epoch.epoch_total_transactions = Some(
+                    (epoch.network_total_transactions - previous_epoch_network_total_transactions)
+                        as i64,
+                );
+                info!(last_epoch.epoch, "Persisting epoch end data.");
+                diesel::insert_into(epochs::table)
+                    .values(SqliteEpochInfo::from(last_epoch))
+                    .on_conflict(epochs::epoch)
+                    .do_update()
+                    .set((
+                        epochs::system_state.eq(excluded(epochs::system_state)),
+                        epochs::epoch_total_transactions
+                            .eq(excluded(epochs::epoch_total_transactions)),
+                        epochs::last_checkpoint_id.eq(excluded(epochs::last_checkpoint_id)),
+                        epochs::epoch_end_timestamp.eq(excluded(epochs::epoch_end_timestamp)),
+                        epochs::storage_fund_reinvestment
+                            .eq(excluded(epochs::storage_fund_reinvestment)),
+                        epochs::storage_charge.eq(excluded(epochs::storage_charge)),
+                        epochs::storage_rebate.eq(excluded(epochs::storage_rebate)),
+                        epochs::stake_subsidy_amount.eq(excluded(epochs::stake_subsidy_amount)),
+                        epochs::total_gas_fees.eq(excluded(epochs::total_gas_fees)),
+                        epochs::total_stake_rewards_distributed
+                            .eq(excluded(epochs::total_stake_rewards_distributed)),
+                        epochs::leftover_storage_fund_inflow
+                            .eq(excluded(epochs::leftover_storage_fund_inflow)),
+                        epochs::epoch_commitments.eq(excluded(epochs::epoch_commitments)),
+                    ))
+                    .execute(conn)?;
+            }
+
+            info!(epoch_id, "Persisting epoch beginning info");
+            let new_epoch = StoredEpochInfo::from_epoch_beginning_info(&epoch.new_epoch);
+            insert_or_ignore!(conn, epochs::table, [SqliteEpochInfo::from(new_epoch)]);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to epochs table")?;
+
+        let elapsed = guard.stop_and_record();
+        info!(elapsed, epoch_id, "Persisted epoch beginning info");
+        Ok(())
+    }
+
+    async fn advance_epoch(&self, _epoch: EpochToCommit) -> Result<(), IndexerError> {
+        // Tables are not partitioned by epoch, so there is nothing to do.
+        Ok(())
+    }
+
+    async fn prune_epoch(&self, _epoch: u64) -> Result<(), IndexerError> {
+        Err(IndexerError::NotSupportedError(
+            "Pruning is not supported by the SQLite store".to_string(),
+        ))
+    }
+
+    async fn upload_display(&self, _epoch: u64) -> Result<(), IndexerError> {
+        warn!("Display upload is not supported by the SQLite store, skipping.");
+        Ok(())
+    }
+
+    async fn restore_display(&self, bytes: bytes::Bytes) -> Result<(), IndexerError> {
+        let cursor = Cursor::new(bytes);
+        let mut csv_reader = ReaderBuilder::new().has_headers(true).from_reader(cursor);
+        let displays = csv_reader
+            .deserialize()
+            .collect::<Result<Vec<StoredDisplay>, csv::Error>>()
+            .map_err(|e| {
+                IndexerError::GcsError(format!("Failed to deserialize display records: {}", e))
+            })?;
+        self.persist_display_updates(displays).await
+    }
+
+    async fn get_network_total_transactions_by_end_of_epoch(
+        &self,
+        epoch: u64,
+    ) -> Result<u64, IndexerError> {
+        self.run(move |conn| {
+            checkpoints::table
+                .filter(checkpoints::epoch.eq(epoch as i64))
+                .select(checkpoints::network_total_transactions)
+                .order_by(checkpoints::sequence_number.desc())
+                .first::<i64>(conn)
+                .map_err(Into::into)
+                .context("Failed to get network total transactions in epoch")
+                .map(|v| v as u64)
+        })
+        .await
+    }
+
+    async fn persist_protocol_configs_and_feature_flags(
+        &self,
+        chain_id: Vec<u8>,
+    ) -> Result<(), IndexerError> {
+        self.transaction(move |conn| {
+            // Index from the version after the latest one stored, up to the protocol version of
+            // the latest epoch.
+            let start_version = protocol_configs::table
+                .select(max(protocol_configs::protocol_version))
+                .first::<Option<i64>>(conn)?
+                .map_or(1, |v| v + 1);
+            let end_version = epochs::table
+                .select(max(epochs::protocol_version))
+                .first::<Option<i64>>(conn)?
+                .unwrap_or(1);
+
+            info!(
+                "Persisting protocol configs with start_version: {}, end_version: {}",
+                start_version, end_version
+            );
+            let (configs, flags) =
+                protocol_configs_and_feature_flags(chain_id, start_version, end_version)?;
+            insert_or_ignore!(conn, protocol_configs::table, &configs);
+            insert_or_ignore!(conn, feature_flags::table, &flags);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to protocol_configs and feature_flags tables")
+    }
+
+    async fn persist_chain_identifier(
+        &self,
+        checkpoint_digest: Vec<u8>,
+    ) -> Result<(), IndexerError> {
+        self.transaction(move |conn| {
+            let chain_id = StoredChainIdentifier { checkpoint_digest };
+            insert_or_ignore!(conn, chain_identifier::table, [&chain_id]);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to chain_identifier table")
+    }
+
+    async fn persist_indexing_config(&self, config: &IndexingConfig) -> Result<(), IndexerError> {
+        let stored = StoredIndexingConfig::try_from(config)?;
+        self.run(move |conn| {
+            diesel::insert_into(indexing_config::table)
+                .values(&stored)
+                .on_conflict(indexing_config::id)
+                .do_update()
+                .set(indexing_config::config.eq(excluded(indexing_config::config)))
+                .execute(conn)?;
+            Ok(())
+        })
+        .await
+        .context("Failed to write to indexing_config table")
+    }
+
+    async fn persist_raw_checkpoints(
+        &self,
+        checkpoints: Vec<StoredRawCheckpoint>,
+    ) -> Result<(), IndexerError> {
+        self.transaction(move |conn| {
+            insert_or_ignore!(conn, raw_checkpoints::table, &checkpoints);
+            Ok(())
+        })
+        .await
+        .context("Failed to write to raw_checkpoints table")
+    }
+}
+
+impl TryFrom<StoredCheckpoint> for SqliteCheckpoint {
+    type Error = IndexerError;
+
+    fn try_from(c: StoredCheckpoint) -> Result<Self, IndexerError> {
+        Ok(Self {
+            sequence_number: c.sequence_number,
+            checkpoint_digest: c.checkpoint_digest,
+            epoch: c.epoch,
+            network_total_transactions: c.network_total_transactions,
+            previous_checkpoint_digest: c.previous_checkpoint_digest,
+            end_of_epoch: c.end_of_epoch,
+            tx_digests: bcs::to_bytes(&c.tx_digests)?,
+            timestamp_ms: c.timestamp_ms,
+            total_gas_cost: c.total_gas_cost,
+            computation_cost: c.computation_cost,
+            storage_cost: c.storage_cost,
+            storage_rebate: c.storage_rebate,
+            non_refundable_storage_fee: c.non_refundable_storage_fee,
+            checkpoint_commitments: c.checkpoint_commitments,
+            validator_signature: c.validator_signature,
+            end_of_epoch_data: c.end_of_epoch_data,
+            min_tx_sequence_number: c.min_tx_sequence_number,
+            max_tx_sequence_number: c.max_tx_sequence_number,
+        })
+    }
+}
+
+impl From<StoredEpochInfo> for SqliteEpochInfo {
+    fn from(e: StoredEpochInfo) -> Self {
+        Self {
+            epoch: e.epoch,
+            first_checkpoint_id: e.first_checkpoint_id,
+            epoch_start_timestamp: e.epoch_start_timestamp,
+            reference_gas_price: e.reference_gas_price,
+            protocol_version: e.protocol_version,
+            total_stake: e.total_stake,
+            storage_fund_balance: e.storage_fund_balance,
+            system_state: e.system_state,
+            epoch_total_transactions: e.epoch_total_transactions,
+            last_checkpoint_id: e.last_checkpoint_id,
+            epoch_end_timestamp: e.epoch_end_timestamp,
+            storage_fund_reinvestment: e.storage_fund_reinvestment,
+            storage_charge: e.storage_charge,
+            storage_rebate: e.storage_rebate,
+            stake_subsidy_amount: e.stake_subsidy_amount,
+            total_gas_fees: e.total_gas_fees,
+            total_stake_rewards_distributed: e.total_stake_rewards_distributed,
+            leftover_storage_fund_inflow: e.leftover_storage_fund_inflow,
+            epoch_commitments: e.epoch_commitments,
+            system_state_summary_json: e.system_state_summary_json.map(|json| json.to_string()),
+        }
+    }
+}
+
+impl TryFrom<StoredEvent> for SqliteEvent {
+    type Error = IndexerError;
+
+    fn try_from(e: StoredEvent) -> Result<Self, IndexerError> {
+        Ok(Self {
+            tx_sequence_number: e.tx_sequence_number,
+            event_sequence_number: e.event_sequence_number,
+            transaction_digest: e.transaction_digest,
+            senders: bcs::to_bytes(&e.senders)?,
+            package: e.package,
+            module: e.module,
+            event_type: e.event_type,
+            timestamp_ms: e.timestamp_ms,
+            bcs: e.bcs,
+            sender: e.sender,
+        })
+    }
+}
+
+impl TryFrom<StoredTransaction> for SqliteTransaction {
+    type Error = IndexerError;
+
+    fn try_from(t: StoredTransaction) -> Result<Self, IndexerError> {
+        Ok(Self {
+            tx_sequence_number: t.tx_sequence_number,
+            transaction_digest: t.transaction_digest,
+            raw_transaction: t.raw_transaction,
+            raw_effects: t.raw_effects,
+            checkpoint_sequence_number: t.checkpoint_sequence_number,
+            timestamp_ms: t.timestamp_ms,
+            object_changes: bcs::to_bytes(&t.object_changes)?,
+            balance_changes: bcs::to_bytes(&t.balance_changes)?,
+            events: bcs::to_bytes(&t.events)?,
+            transaction_kind: t.transaction_kind,
+            success_command_count: t.success_command_count,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use diesel::sql_types::{BigInt, Text};
+    use prometheus::Registry;
+    use sui_types::base_types::{ObjectID, SuiAddress};
+    use sui_types::digests::TransactionDigest;
+    use sui_types::object::Object;
+
+    use crate::types::{IndexedDeletedObject, IndexedObject, TransactionKind};
+
+    use super::*;
+
+    /// A column as SQLite reports it: its declared type, whether it can be NULL, and its position
+    /// in the primary key (0 if it is not part of it).
+    #[derive(QueryableByName, Debug)]
+    struct SqliteColumn {
+        #[diesel(sql_type = Text)]
+        name: String,
+        #[diesel(sql_type = Text)]
+        ty: String,
+        #[diesel(sql_type = BigInt)]
+        not_null: i64,
+        #[diesel(sql_type = BigInt)]
+        pk: i64,
+    }
+
+    fn store() -> SqliteIndexerStore {
+        let metrics = IndexerMetrics::new(&Registry::default());
+        SqliteIndexerStore::new(":memory:", metrics).unwrap()
+    }
+
+    fn checkpoint(sequence_number: u64, epoch: u64, total_txs: u64) -> IndexedCheckpoint {
+        IndexedCheckpoint {
+            sequence_number,
+            epoch,
+            network_total_transactions: total_txs,
+            tx_digests: vec![Default::default()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_persist_checkpoints() {
+        let store = store();
+        assert_eq!(
+            store.get_latest_checkpoint_sequence_number().await.unwrap(),
+            None
+        );
+
+        let checkpoints = vec![
+            checkpoint(1, 0, 2),
+            checkpoint(2, 0, 5),
+            checkpoint(3, 1, 8),
+        ];
+        store.persist_checkpoints(checkpoints).await.unwrap();
+
+        // Persisting the same checkpoint again is a no-op.
+        store
+            .persist_checkpoints(vec![checkpoint(3, 1, 100)])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.get_latest_checkpoint_sequence_number().await.unwrap(),
+            Some(3)
+        );
+        assert_eq!(
+            store.get_available_checkpoint_range().await.unwrap(),
+            (1, 3)
+        );
+        assert_eq!(
+            store
+                .get_network_total_transactions_by_end_of_epoch(0)
+                .await
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            store
+                .get_network_total_transactions_by_end_of_epoch(1)
+                .await
+                .unwrap(),
+            8
+        );
+    }
+
+    #[tokio::test]
+    async fn test_persist_chain_identifier() {
+        let store = store();
+        assert_eq!(store.get_chain_identifier().await.unwrap(), None);
+
+        store.persist_chain_identifier(vec![1; 32]).await.unwrap();
+        store.persist_chain_identifier(vec![1; 32]).await.unwrap();
+        assert_eq!(
+            store.get_chain_identifier().await.unwrap(),
+            Some(vec![1; 32])
+        );
+    }
+
+    /// The tables in the Postgres schema, parsed from `schema.rs`, mapped to their primary key and
+    /// their columns, with the SQLite type each column is expected to have, and whether it is
+    /// nullable.
+    fn postgres_tables() -> BTreeMap<String, (Vec<String>, BTreeMap<String, (String, bool)>)> {
+        let mut tables = BTreeMap::new();
+        let mut lines = include_str!("../schema.rs").lines();
+
+        while let Some(line) = lines.next() {
+            if line != "diesel::table! {" {
+                continue;
+            }
+
+            let header = lines.next().unwrap().trim().strip_suffix(" {").unwrap();
+            let (name, keys) = header.split_once(' ').unwrap();
+            let keys = keys
+                .trim_matches(|c| c == '(' || c == ')')
+                .split(", ")
+                .map(String::from)
+                .collect();
+
+            let mut columns = BTreeMap::new();
+            for line in lines.by_ref() {
+                let line = line.trim();
+                if line == "}" {
+                    break;
+                }
+
+                let (column, ty) = line.strip_suffix(',').unwrap().split_once(" -> ").unwrap();
+                let (ty, nullable) = match ty.strip_prefix("Nullable<") {
+                    Some(ty) => (ty.strip_suffix('>').unwrap(), true),
+                    None => (ty, false),
+                };
+
+                let ty = match ty {
+                    "Int2" => "SMALLINT",
+                    "Int4" => "INTEGER",
+                    "Int8" => "BIGINT",
+                    "Bool" => "BOOLEAN",
+                    "Text" | "Jsonb" => "TEXT",
+                    "Bytea" => "BLOB",
+                    ty if ty.starts_with("Array<") => "BLOB",
+                    ty => panic!("Unexpected type {ty} for {name}.{column}"),
+                };
+
+                columns.insert(column.to_owned(), (ty.to_owned(), nullable));
+            }
+
+            tables.insert(name.to_owned(), (keys, columns));
+        }
+
+        tables
+    }
+
+    #[tokio::test]
+    async fn test_schema_matches_postgres() {
+        let store = store();
+        let expected = postgres_tables();
+        let names: Vec<_> = expected.keys().cloned().collect();
+
+        let actual = store
+            .run(move |conn| {
+                let mut tables = BTreeMap::new();
+                for name in names {
+                    let mut columns: Vec<SqliteColumn> = diesel::sql_query(format!(
+                        "SELECT name, type AS ty, \"notnull\" AS not_null, pk \
+                         FROM pragma_table_info('{name}')"
+                    ))
+                    .load(conn)?;
+
+                    columns.sort_by_key(|c| c.pk);
+                    let keys = columns
+                        .iter()
+                        .filter(|c| c.pk > 0)
+                        .map(|c| c.name.clone())
+                        .collect();
+
+                    // Primary key columns are not nullable in Postgres, regardless of how they
+                    // are declared.
+                    let columns = columns
+                        .into_iter()
+                        .map(|c| (c.name, (c.ty, c.not_null == 0 && c.pk == 0)))
+                        .collect();
+
+                    tables.insert(name, (keys, columns));
+                }
+                Ok(tables)
+            })
+            .await
+            .unwrap();
+
+        for (name, expected) in &expected {
+            assert_eq!(
+                &actual[name], expected,
+                "Table {name} differs from Postgres"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_persist_events() {
+        let store = store();
+        let sender = SuiAddress::random_for_testing_only();
+        let event = |tx_sequence_number, event_sequence_number| IndexedEvent {
+            tx_sequence_number,
+            event_sequence_number,
+            checkpoint_sequence_number: 1,
+            transaction_digest: TransactionDigest::random(),
+            sender,
+            package: ObjectID::from_single_byte(2),
+            module: "m".to_owned(),
+            event_type: "0x2::m::E".to_owned(),
+            event_type_package: ObjectID::from_single_byte(2),
+            event_type_module: "m".to_owned(),
+            event_type_name: "E".to_owned(),
+            bcs: vec![],
+            timestamp_ms: 0,
+        };
+
+        let events = vec![event(1, 0), event(1, 1), event(2, 0)];
+        store.persist_events(events.clone()).await.unwrap();
+        store.persist_events(events).await.unwrap();
+
+        let stored: Vec<(i64, i64, Vec<u8>)> = store
+            .run(|conn| {
+                Ok(events::table
+                    .select((
+                        events::tx_sequence_number,
+                        events::event_sequence_number,
+                        events::senders,
+                    ))
+                    .order_by((events::tx_sequence_number, events::event_sequence_number))
+                    .load(conn)?)
+            })
+            .await
+            .unwrap();
+
+        let keys: Vec<_> = stored.iter().map(|(tx, ev, _)| (*tx, *ev)).collect();
+        assert_eq!(keys, vec![(1, 0), (1, 1), (2, 0)]);
+
+        // Senders are stored as a BCS-serialized array.
+        let senders: Vec<Option<Vec<u8>>> = bcs::from_bytes(&stored[0].2).unwrap();
+        assert_eq!(senders, vec![Some(sender.to_vec())]);
+    }
+
+    #[tokio::test]
+    async fn test_persist_tx_indices() {
+        let store = store();
+        let sender = SuiAddress::random_for_testing_only();
+        let recipient = SuiAddress::random_for_testing_only();
+        let index = |tx_sequence_number| TxIndex {
+            tx_sequence_number,
+            tx_kind: TransactionKind::ProgrammableTransaction,
+            transaction_digest: TransactionDigest::random(),
+            checkpoint_sequence_number: 1,
+            input_objects: vec![ObjectID::random()],
+            changed_objects: vec![ObjectID::random()],
+            affected_objects: vec![],
+            payers: vec![sender],
+            sender,
+            recipients: vec![recipient],
+            move_calls: vec![(
+                ObjectID::from_single_byte(2),
+                "m".to_owned(),
+                "f".to_owned(),
+            )],
+            gas_used: tx_sequence_number as i64 * 100,
+        };
+
+        let indices = vec![index(1), index(2)];
+        store.persist_tx_indices(indices.clone()).await.unwrap();
+        store.persist_tx_indices(indices).await.unwrap();
+
+        let (senders, funs, recipients, digests) = store
+            .run(|conn| {
+                let senders: Vec<(i64, Option<i64>)> = tx_senders::table
+                    .select((tx_senders::tx_sequence_number, tx_senders::gas_used))
+                    .order_by(tx_senders::tx_sequence_number)
+                    .load(conn)?;
+                let funs: i64 = tx_calls_fun::table.count().get_result(conn)?;
+                let recipients: i64 = tx_recipients::table.count().get_result(conn)?;
+                let digests: i64 = tx_digests::table.count().get_result(conn)?;
+                Ok((senders, funs, recipients, digests))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(senders, vec![(1, Some(100)), (2, Some(200))]);
+        assert_eq!((funs, recipients, digests), (2, 2, 2));
+    }
+
+    #[tokio::test]
+    async fn test_persist_objects() {
+        let store = store();
+        let owner = SuiAddress::random_for_testing_only();
+        let kept = ObjectID::random();
+        let deleted = ObjectID::random();
+
+        let changes = |changed: Vec<ObjectID>, deleted: Vec<ObjectID>| {
+            vec![TransactionObjectChangesToCommit {
+                changed_objects: changed
+                    .into_iter()
+                    .map(|id| {
+                        let object = Object::with_id_owner_for_testing(id, owner);
+                        IndexedObject::from_object(1, object, None)
+                    })
+                    .collect(),
+                deleted_objects: deleted
+                    .into_iter()
+                    .map(|object_id| IndexedDeletedObject {
+                        object_id,
+                        object_version: 2,
+                        checkpoint_sequence_number: 2,
+                    })
+                    .collect(),
+            }]
+        };
+
+        store
+            .persist_objects(changes(vec![kept, deleted], vec![]))
+            .await
+            .unwrap();
+
+        // Writing an object again replaces it, and deleting an object removes it.
+        store
+            .persist_objects(changes(vec![kept], vec![deleted]))
+            .await
+            .unwrap();
+
+        let ids: Vec<Vec<u8>> = store
+            .run(|conn| Ok(objects::table.select(objects::object_id).load(conn)?))
+            .await
+            .unwrap();
+
+        assert_eq!(ids, vec![kept.to_vec()]);
+    }
+}
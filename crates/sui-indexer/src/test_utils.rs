@@ -12,13 +12,15 @@ use std::sync::Arc;
 use std::time::Duration;
 use sui_json_rpc_types::SuiTransactionBlockResponse;
 
-use crate::config::{IngestionConfig, PruningOptions, SnapshotLagConfig, UploadOptions};
+use crate::config::{
+    IndexingConfig, IngestionConfig, PruningOptions, SnapshotLagConfig, UploadOptions,
+};
 use crate::database::Connection;
 use crate::database::ConnectionPool;
 use crate::db::ConnectionPoolConfig;
 use crate::errors::IndexerError;
 use crate::indexer::Indexer;
-use crate::store::PgIndexerStore;
+use crate::store::{IndexerStore, PgIndexerStore};
 use crate::tempdb::get_available_port;
 use crate::tempdb::TempDb;
 use crate::IndexerMetrics;
@@ -129,6 +131,7 @@ pub async fn start_indexer_writer_for_testing(
                 indexer_metrics,
                 snapshot_config,
                 pruning_options,
+                IndexingConfig::default(),
                 token_clone,
             )
             .await
@@ -256,17 +259,71 @@ pub async fn set_up(
     (server_handle, pg_store, pg_handle, database)
 }
 
+/// Like [`set_up`], but the indexer writes to a SQLite database in a temporary directory, so no
+/// Postgres server is needed. The database is at `indexer.db` in the returned directory, which
+/// must be kept alive for the duration of the test. The writer indexes what `indexing_config`
+/// selects.
+#[cfg(feature = "sqlite")]
+pub async fn set_up_sqlite(
+    sim: Arc<Simulacrum>,
+    data_ingestion_path: PathBuf,
+    indexing_config: IndexingConfig,
+) -> (
+    JoinHandle<()>,
+    crate::store::SqliteIndexerStore,
+    JoinHandle<Result<(), IndexerError>>,
+    tempfile::TempDir,
+) {
+    let database = tempfile::tempdir().unwrap();
+    let server_url: SocketAddr = format!("127.0.0.1:{}", get_available_port())
+        .parse()
+        .unwrap();
+
+    let server_handle = tokio::spawn(async move {
+        sui_rest_api::RestService::new_without_version(sim)
+            .start_service(server_url)
+            .await;
+    });
+
+    let registry = prometheus::Registry::default();
+    init_metrics(&registry);
+    let indexer_metrics = IndexerMetrics::new(&registry);
+
+    let db_path = database.path().join("indexer.db");
+    let store =
+        crate::store::SqliteIndexerStore::new(db_path.to_str().unwrap(), indexer_metrics.clone())
+            .unwrap();
+
+    let mut ingestion_config = IngestionConfig::default();
+    ingestion_config.sources.data_ingestion_path = Some(data_ingestion_path);
+
+    let store_clone = store.clone();
+    let handle = tokio::spawn(async move {
+        Indexer::start_writer_with_store(
+            &ingestion_config,
+            store_clone,
+            indexer_metrics,
+            SnapshotLagConfig {
+                snapshot_min_lag: 5,
+                sleep_duration: 0,
+            },
+            indexing_config,
+            CancellationToken::new(),
+        )
+        .await
+    });
+
+    (server_handle, store, handle, database)
+}
+
 /// Wait for the indexer to catch up to the given checkpoint sequence number.
 pub async fn wait_for_checkpoint(
-    pg_store: &PgIndexerStore,
+    store: &impl IndexerStore,
     checkpoint_sequence_number: u64,
 ) -> Result<(), IndexerError> {
     tokio::time::timeout(Duration::from_secs(30), async {
         while {
-            let cp_opt = pg_store
-                .get_latest_checkpoint_sequence_number()
-                .await
-                .unwrap();
+            let cp_opt = store.get_latest_checkpoint_sequence_number().await.unwrap();
             cp_opt.is_none() || (cp_opt.unwrap() < checkpoint_sequence_number)
         } {
             tokio::time::sleep(Duration::from_millis(100)).await;
@@ -279,12 +336,12 @@ pub async fn wait_for_checkpoint(
 
 /// Wait for the indexer to catch up to the given checkpoint sequence number for objects snapshot.
 pub async fn wait_for_objects_snapshot(
-    pg_store: &PgIndexerStore,
+    store: &impl IndexerStore,
     checkpoint_sequence_number: u64,
 ) -> Result<(), IndexerError> {
     tokio::time::timeout(Duration::from_secs(30), async {
         while {
-            let cp_opt = pg_store
+            let cp_opt = store
                 .get_latest_object_snapshot_checkpoint_sequence_number()
                 .await
                 .unwrap();
@@ -412,6 +412,8 @@ pub struct TxIndex {
     pub sender: SuiAddress,
     pub recipients: Vec<SuiAddress>,
     pub move_calls: Vec<(ObjectID, String, String)>,
+    /// Net gas used by the transaction: computation and storage costs, less the storage rebate.
+    pub gas_used: i64,
 }
 
 // ObjectChange is not bcs deserializable, IndexedObjectChange is.
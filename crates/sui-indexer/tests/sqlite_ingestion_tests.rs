@@ -0,0 +1,244 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Ingestion tests that run the indexer writer against an embedded SQLite database, so they do not
+//! need a Postgres server.
+#![cfg(feature = "sqlite")]
+
+use std::sync::Arc;
+
+use diesel::prelude::*;
+use diesel::SqliteConnection;
+use simulacrum::Simulacrum;
+use sui_indexer::config::{IndexingConfig, IngestionConfig, SnapshotLagConfig};
+use sui_indexer::errors::IndexerError;
+use sui_indexer::indexer::Indexer;
+use sui_indexer::metrics::IndexerMetrics;
+use sui_indexer::schema::{objects, objects_snapshot, transactions, tx_digests, tx_senders};
+use sui_indexer::store::IndexerStore;
+use sui_indexer::test_utils::{set_up_sqlite, wait_for_checkpoint, wait_for_objects_snapshot};
+use sui_types::base_types::SuiAddress;
+use sui_types::effects::TransactionEffectsAPI;
+use sui_types::gas_coin::GasCoin;
+use tempfile::{tempdir, TempDir};
+use tokio_util::sync::CancellationToken;
+
+/// A separate connection to the database that the indexer is writing to.
+fn connect(database: &TempDir) -> SqliteConnection {
+    let path = database.path().join("indexer.db");
+    let mut connection = SqliteConnection::establish(path.to_str().unwrap()).unwrap();
+    diesel::sql_query("PRAGMA busy_timeout = 5000")
+        .execute(&mut connection)
+        .unwrap();
+    connection
+}
+
+#[tokio::test]
+pub async fn test_transaction_table() -> Result<(), IndexerError> {
+    let tempdir = tempdir().unwrap();
+    let mut sim = Simulacrum::new();
+    let data_ingestion_path = tempdir.path().to_path_buf();
+    sim.set_data_ingestion_path(data_ingestion_path.clone());
+
+    // Execute a simple transaction.
+    let transfer_recipient = SuiAddress::random_for_testing_only();
+    let (transaction, _) = sim.transfer_txn(transfer_recipient);
+    let (effects, err) = sim.execute_transaction(transaction.clone()).unwrap();
+    assert!(err.is_none());
+
+    // Create a checkpoint which should include the transaction we executed.
+    let checkpoint = sim.create_checkpoint();
+
+    let (_, store, _, database) = set_up_sqlite(
+        Arc::new(sim),
+        data_ingestion_path,
+        IndexingConfig::default(),
+    )
+    .await;
+
+    // Wait for the indexer to catch up to the checkpoint.
+    wait_for_checkpoint(&store, 1).await?;
+
+    let digest = effects.transaction_digest();
+    let mut connection = connect(&database);
+
+    let (tx_sequence_number, raw_transaction, timestamp_ms, checkpoint_sequence_number, kind): (
+        i64,
+        Vec<u8>,
+        i64,
+        i64,
+        i16,
+    ) = transactions::table
+        .filter(transactions::transaction_digest.eq(digest.inner().to_vec()))
+        .select((
+            transactions::tx_sequence_number,
+            transactions::raw_transaction,
+            transactions::timestamp_ms,
+            transactions::checkpoint_sequence_number,
+            transactions::transaction_kind,
+        ))
+        .first(&mut connection)
+        .expect("Failed reading transaction from SQLite");
+
+    assert_eq!(tx_sequence_number, 1);
+    assert_eq!(raw_transaction, bcs::to_bytes(&transaction.data()).unwrap());
+    assert_eq!(timestamp_ms, checkpoint.timestamp_ms as i64);
+    assert_eq!(checkpoint_sequence_number, 1);
+    assert_eq!(kind, 1);
+
+    // The transaction's sender is indexed, with the gas it used.
+    let gas_used: Option<i64> = tx_senders::table
+        .filter(tx_senders::tx_sequence_number.eq(1))
+        .select(tx_senders::gas_used)
+        .first(&mut connection)
+        .expect("Failed reading tx_senders from SQLite");
+
+    let gas = effects.gas_cost_summary();
+    assert_eq!(gas_used, Some(gas.net_gas_usage()));
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn test_objects_table() -> Result<(), IndexerError> {
+    let tempdir = tempdir().unwrap();
+    let mut sim = Simulacrum::new();
+    let data_ingestion_path = tempdir.path().to_path_buf();
+    sim.set_data_ingestion_path(data_ingestion_path.clone());
+
+    // Run enough transfers for the objects snapshot to catch up past the first checkpoint.
+    let transfer_recipient = SuiAddress::random_for_testing_only();
+    let mut last_transaction = None;
+    for _ in 0..7 {
+        let (transaction, _) = sim.transfer_txn(transfer_recipient);
+        let (_, err) = sim.execute_transaction(transaction.clone()).unwrap();
+        assert!(err.is_none());
+        last_transaction = Some(transaction);
+        let _ = sim.create_checkpoint();
+    }
+
+    let (_, store, _, database) = set_up_sqlite(
+        Arc::new(sim),
+        data_ingestion_path,
+        IndexingConfig::default(),
+    )
+    .await;
+
+    wait_for_checkpoint(&store, 7).await?;
+    wait_for_objects_snapshot(&store, 1).await?;
+
+    let gas_id = last_transaction.unwrap().gas()[0].0;
+    let mut connection = connect(&database);
+
+    let object_type: Option<String> = objects::table
+        .filter(objects::object_id.eq(gas_id.to_vec()))
+        .select(objects::object_type)
+        .first(&mut connection)
+        .expect("Failed reading object from SQLite");
+
+    assert_eq!(
+        object_type,
+        Some(GasCoin::type_().to_canonical_string(true))
+    );
+
+    // Each transfer sends a new coin to the recipient.
+    let received: i64 = objects::table
+        .filter(objects::owner_id.eq(transfer_recipient.to_vec()))
+        .count()
+        .get_result(&mut connection)
+        .expect("Failed counting objects in SQLite");
+
+    assert_eq!(received, 7);
+
+    // The objects snapshot, which lags behind, has caught up enough to include the gas coin.
+    let snapshotted: i64 = objects_snapshot::table
+        .filter(objects_snapshot::object_id.eq(gas_id.to_vec()))
+        .count()
+        .get_result(&mut connection)
+        .expect("Failed reading objects snapshot from SQLite");
+
+    assert_eq!(snapshotted, 1);
+    Ok(())
+}
+
+#[tokio::test]
+pub async fn test_filtered_indexing() -> Result<(), IndexerError> {
+    let tempdir = tempdir().unwrap();
+    let mut sim = Simulacrum::new();
+    let data_ingestion_path = tempdir.path().to_path_buf();
+    sim.set_data_ingestion_path(data_ingestion_path.clone());
+
+    // Send coins to two recipients, only one of which is indexed.
+    let indexed_recipient = SuiAddress::random_for_testing_only();
+    let other_recipient = SuiAddress::random_for_testing_only();
+    let mut indexed_digests = vec![];
+    for recipient in [indexed_recipient, other_recipient, indexed_recipient] {
+        let (transaction, _) = sim.transfer_txn(recipient);
+        let (effects, err) = sim.execute_transaction(transaction).unwrap();
+        assert!(err.is_none());
+        if recipient == indexed_recipient {
+            indexed_digests.push(effects.transaction_digest().inner().to_vec());
+        }
+        let _ = sim.create_checkpoint();
+    }
+
+    let indexing_config = IndexingConfig {
+        addresses: vec![indexed_recipient],
+        ..Default::default()
+    };
+
+    let (_, store, _, database) = set_up_sqlite(
+        Arc::new(sim),
+        data_ingestion_path.clone(),
+        indexing_config.clone(),
+    )
+    .await;
+
+    wait_for_checkpoint(&store, 3).await?;
+    let mut connection = connect(&database);
+
+    // Only the transactions sent to the indexed recipient are kept, along with their digests.
+    let mut stored: Vec<Vec<u8>> = transactions::table
+        .select(transactions::transaction_digest)
+        .load(&mut connection)
+        .expect("Failed reading transactions from SQLite");
+
+    let mut digests: Vec<Vec<u8>> = tx_digests::table
+        .select(tx_digests::tx_digest)
+        .load(&mut connection)
+        .expect("Failed reading tx_digests from SQLite");
+
+    stored.sort();
+    digests.sort();
+    indexed_digests.sort();
+    assert_eq!(stored, indexed_digests);
+    assert_eq!(digests, indexed_digests);
+
+    // All checkpoints are still indexed, and the config they were indexed with is recorded.
+    assert_eq!(
+        store.get_latest_checkpoint_sequence_number().await?,
+        Some(3)
+    );
+    assert_eq!(
+        store.get_indexing_config().await?,
+        Some(indexing_config.clone())
+    );
+
+    // A writer cannot change what is indexed once the database holds data.
+    let mut ingestion_config = IngestionConfig::default();
+    ingestion_config.sources.data_ingestion_path = Some(data_ingestion_path);
+    let registry = prometheus::Registry::default();
+    let err = Indexer::start_writer_with_store(
+        &ingestion_config,
+        store.clone(),
+        IndexerMetrics::new(&registry),
+        SnapshotLagConfig::default(),
+        IndexingConfig::default(),
+        CancellationToken::new(),
+    )
+    .await
+    .unwrap_err();
+
+    assert!(matches!(err, IndexerError::InvalidArgumentError(_)));
+    assert_eq!(store.get_indexing_config().await?, Some(indexing_config));
+    Ok(())
+}
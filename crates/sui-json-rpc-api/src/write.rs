@@ -6,11 +6,12 @@ use jsonrpsee::core::RpcResult;
 use jsonrpsee::proc_macros::rpc;
 
 use sui_json_rpc_types::{
-    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlockResponse,
+    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse,
+    SuiTransactionBlockLifecycleStatus, SuiTransactionBlockResponse,
     SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc_macros::open_rpc;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{SuiAddress, TransactionDigest};
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::sui_serde::BigInt;
 
@@ -64,4 +65,30 @@ pub trait WriteApi {
         &self,
         tx_bytes: Base64,
     ) -> RpcResult<DryRunTransactionBlockResponse>;
+
+    /// Return transaction execution effects as if the transaction had been executed against the
+    /// state at the end of the given checkpoint, rather than the latest state. Owned inputs are
+    /// read at the versions referenced by the transaction, and shared inputs and packages at
+    /// their versions as of the checkpoint. Fails if any of those versions have been pruned (and
+    /// the node has no historical data fallback configured), or if an input was modified too many
+    /// times since the checkpoint.
+    #[method(name = "dryRunTransactionBlockAtCheckpoint")]
+    async fn dry_run_transaction_block_at_checkpoint(
+        &self,
+        /// BCS serialized transaction data bytes without its type tag, as base-64 encoded string.
+        tx_bytes: Base64,
+        /// The sequence number of the checkpoint whose end state the transaction is executed against.
+        checkpoint: BigInt<u64>,
+    ) -> RpcResult<DryRunTransactionBlockResponse>;
+
+    /// Return how far a transaction block submitted to or executed by this node has progressed
+    /// towards finality: submitted, certified, waiting on inputs, executing, executed or finalized
+    /// in a checkpoint. Useful to find out what happened to a transaction after
+    /// `executeTransactionBlock` timed out. Returns null if the node has no record of it.
+    #[method(name = "getTransactionBlockStatus")]
+    async fn get_transaction_block_status(
+        &self,
+        /// the digest of the queried transaction
+        digest: TransactionDigest,
+    ) -> RpcResult<Option<SuiTransactionBlockLifecycleStatus>>;
 }
@@ -27,6 +27,7 @@ use sui_types::base_types::SequenceNumber;
 use sui_types::coin::{TreasuryCap, COIN_MODULE_NAME};
 use sui_types::digests::ObjectDigest;
 use sui_types::gas_coin::GAS;
+use sui_types::object::Owner;
 use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
 use sui_types::{parse_sui_struct_tag, SUI_FRAMEWORK_ADDRESS};
 use test_cluster::TestClusterBuilder;
@@ -140,6 +141,81 @@ async fn test_public_transfer_object() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
+#[sim_test]
+async fn test_dry_run_at_checkpoint_after_gas_changed() -> Result<(), anyhow::Error> {
+    let cluster = TestClusterBuilder::new().build().await;
+    let http_client = cluster.rpc_client();
+    let address = cluster.get_address_0();
+    let recipient = cluster.get_address_1();
+
+    let coins: CoinPage = http_client.get_coins(address, None, None, None).await?;
+    let gas = &coins.data[0];
+
+    // A transfer paid for by the gas coin as it is at this checkpoint...
+    let transfer: TransactionBlockBytes = http_client
+        .transfer_sui(
+            address,
+            gas.coin_object_id,
+            100_000_000.into(),
+            recipient,
+            Some(1_000.into()),
+        )
+        .await?;
+    let checkpoint = http_client.get_latest_checkpoint_sequence_number().await?;
+
+    // ...which is then spent from after the checkpoint.
+    let spend: TransactionBlockBytes = http_client
+        .transfer_sui(
+            address,
+            gas.coin_object_id,
+            100_000_000.into(),
+            recipient,
+            Some(2_000.into()),
+        )
+        .await?;
+    let tx = cluster.wallet.sign_transaction(&spend.to_data()?);
+    let (tx_bytes, signatures) = tx.to_tx_bytes_and_signatures();
+    http_client
+        .execute_transaction_block(
+            tx_bytes,
+            signatures,
+            None,
+            Some(ExecuteTransactionRequestType::WaitForLocalExecution),
+        )
+        .await?;
+
+    let response = http_client
+        .dry_run_transaction_block_at_checkpoint(transfer.tx_bytes, checkpoint)
+        .await?;
+    assert_eq!(*response.effects.status(), SuiExecutionStatus::Success);
+
+    // Changes are relative to the gas coin at the checkpoint, not its latest version.
+    let gas_used = response.effects.gas_cost_summary().net_gas_usage() as i128;
+    let balance_change = |owner| {
+        response
+            .balance_changes
+            .iter()
+            .find(|change| change.owner == Owner::AddressOwner(owner))
+            .map(|change| change.amount)
+    };
+    assert_eq!(balance_change(address), Some(-1_000 - gas_used));
+    assert_eq!(balance_change(recipient), Some(1_000));
+
+    let previous_gas_version = response
+        .object_changes
+        .iter()
+        .find_map(|change| match change {
+            ObjectChange::Mutated {
+                object_id,
+                previous_version,
+                ..
+            } if *object_id == gas.coin_object_id => Some(*previous_version),
+            _ => None,
+        });
+    assert_eq!(previous_gas_version, Some(gas.version));
+    Ok(())
+}
+
 fn assert_same_object_changes_ignoring_version_and_digest(
     expected: Vec<ObjectChange>,
     actual: Vec<ObjectChange>,
@@ -28,7 +28,8 @@ use sui_types::base_types::{
 };
 use sui_types::crypto::SuiSignature;
 use sui_types::digests::{
-    CheckpointDigest, ConsensusCommitDigest, ObjectDigest, TransactionEventsDigest,
+    CheckpointDigest, ConsensusCommitDigest, ObjectDigest, TransactionEffectsDigest,
+    TransactionEventsDigest,
 };
 use sui_types::effects::{TransactionEffects, TransactionEffectsAPI, TransactionEvents};
 use sui_types::error::{ExecutionError, SuiError, SuiResult};
@@ -39,9 +40,9 @@ use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::messages_consensus::ConsensusDeterminedVersionAssignments;
 use sui_types::object::Owner;
 use sui_types::parse_sui_type_tag;
-use sui_types::quorum_driver_types::ExecuteTransactionRequestType;
+use sui_types::quorum_driver_types::{ExecuteTransactionRequestType, TransactionLifecycleStatus};
 use sui_types::signature::GenericSignature;
-use sui_types::storage::{DeleteKind, WriteKind};
+use sui_types::storage::{DeleteKind, InputKey, WriteKind};
 use sui_types::sui_serde::Readable;
 use sui_types::sui_serde::{
     BigInt, SequenceNumber as AsSequenceNumber, SuiTypeTag as AsSuiTypeTag,
@@ -1337,6 +1338,97 @@ impl From<ExecutionStatus> for SuiExecutionStatus {
     }
 }
 
+/// The furthest stage a transaction block has reached on its way to finality, as observed by the
+/// fullnode it was submitted to.
+#[serde_as]
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(
+    rename = "TransactionBlockLifecycleStatus",
+    rename_all = "camelCase",
+    tag = "stage"
+)]
+pub enum SuiTransactionBlockLifecycleStatus {
+    /// The transaction is being submitted to validators to collect a certificate.
+    Submitted {
+        /// Number of submission attempts so far, including retries.
+        attempts: u32,
+    },
+    /// A certificate was formed and the node is waiting for validators to return effects.
+    Certified {
+        /// Number of submission attempts so far, including retries.
+        attempts: u32,
+    },
+    /// The transaction is enqueued for local execution, but some inputs are not available yet.
+    #[serde(rename_all = "camelCase")]
+    WaitingOnInputs { missing_inputs: Vec<SuiInputKey> },
+    /// All inputs are available and the transaction is executing locally.
+    Executing,
+    /// The transaction was executed locally but is not part of an executed checkpoint yet.
+    #[serde(rename_all = "camelCase")]
+    Executed {
+        effects_digest: TransactionEffectsDigest,
+    },
+    /// The transaction is part of a checkpoint that the node has executed.
+    #[serde(rename_all = "camelCase")]
+    Finalized {
+        effects_digest: TransactionEffectsDigest,
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        epoch: EpochId,
+        #[schemars(with = "BigInt<u64>")]
+        #[serde_as(as = "BigInt<u64>")]
+        checkpoint: CheckpointSequenceNumber,
+    },
+}
+
+impl From<TransactionLifecycleStatus> for SuiTransactionBlockLifecycleStatus {
+    fn from(status: TransactionLifecycleStatus) -> Self {
+        match status {
+            TransactionLifecycleStatus::Submitted { attempts } => Self::Submitted { attempts },
+            TransactionLifecycleStatus::Certified { attempts } => Self::Certified { attempts },
+            TransactionLifecycleStatus::WaitingOnInputs { missing_inputs } => {
+                Self::WaitingOnInputs {
+                    missing_inputs: missing_inputs.into_iter().map(SuiInputKey::from).collect(),
+                }
+            }
+            TransactionLifecycleStatus::Executing => Self::Executing,
+            TransactionLifecycleStatus::Executed { effects_digest } => {
+                Self::Executed { effects_digest }
+            }
+            TransactionLifecycleStatus::Finalized {
+                effects_digest,
+                epoch,
+                checkpoint,
+            } => Self::Finalized {
+                effects_digest,
+                epoch,
+                checkpoint,
+            },
+        }
+    }
+}
+
+/// An input that a transaction is waiting for before it can execute.
+#[serde_as]
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "InputKey", rename_all = "camelCase")]
+pub struct SuiInputKey {
+    pub object_id: ObjectID,
+    /// The version of the object that is waiting to become available. Not set for packages.
+    #[schemars(with = "Option<BigInt<u64>>")]
+    #[serde_as(as = "Option<AsSequenceNumber>")]
+    pub version: Option<SequenceNumber>,
+}
+
+impl From<InputKey> for SuiInputKey {
+    fn from(key: InputKey) -> Self {
+        Self {
+            object_id: key.id(),
+            version: key.version(),
+        }
+    }
+}
+
 fn to_sui_object_ref(refs: Vec<ObjectRef>) -> Vec<SuiObjectRef> {
     refs.into_iter().map(SuiObjectRef::from).collect()
 }
@@ -2123,6 +2215,10 @@ impl SuiTypeTag {
     pub fn new(tag: String) -> Self {
         Self(tag)
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
 }
 
 impl TryInto<TypeTag> for SuiTypeTag {
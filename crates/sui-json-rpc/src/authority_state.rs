@@ -8,6 +8,7 @@ use move_core_types::language_storage::TypeTag;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use sui_core::authority::authority_per_epoch_store::AuthorityPerEpochStore;
+use sui_core::authority::historical_object_store::HistoricalObjectStore;
 use sui_core::authority::AuthorityState;
 use sui_core::execution_cache::ObjectCacheRead;
 use sui_core::subscription_handler::SubscriptionHandler;
@@ -94,6 +95,12 @@ pub trait StateRead: Send + Sync {
 
     fn get_backing_package_store(&self) -> &Arc<dyn BackingPackageStore + Send + Sync>;
 
+    /// Reads objects as of the end of `checkpoint`, like dry runs at that checkpoint do.
+    fn get_historical_object_store(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> HistoricalObjectStore;
+
     fn get_owner_objects(
         &self,
         owner: SuiAddress,
@@ -124,6 +131,19 @@ pub trait StateRead: Send + Sync {
         Option<ObjectID>,
     )>;
 
+    #[allow(clippy::type_complexity)]
+    async fn dry_exec_transaction_at_checkpoint(
+        &self,
+        transaction: TransactionData,
+        transaction_digest: TransactionDigest,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> StateReadResult<(
+        DryRunTransactionBlockResponse,
+        BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
+        TransactionEffects,
+        Option<ObjectID>,
+    )>;
+
     async fn dev_inspect_transaction_block(
         &self,
         sender: SuiAddress,
@@ -317,6 +337,13 @@ impl StateRead for AuthorityState {
         self.get_backing_package_store()
     }
 
+    fn get_historical_object_store(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> HistoricalObjectStore {
+        self.get_historical_object_store(checkpoint)
+    }
+
     fn get_owner_objects(
         &self,
         owner: SuiAddress,
@@ -358,6 +385,23 @@ impl StateRead for AuthorityState {
             .await?)
     }
 
+    #[allow(clippy::type_complexity)]
+    async fn dry_exec_transaction_at_checkpoint(
+        &self,
+        transaction: TransactionData,
+        transaction_digest: TransactionDigest,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> StateReadResult<(
+        DryRunTransactionBlockResponse,
+        BTreeMap<ObjectID, (ObjectRef, Object, WriteKind)>,
+        TransactionEffects,
+        Option<ObjectID>,
+    )> {
+        Ok(self
+            .dry_exec_transaction_at_checkpoint(transaction, transaction_digest, checkpoint)
+            .await?)
+    }
+
     async fn dev_inspect_transaction_block(
         &self,
         sender: SuiAddress,
@@ -626,6 +670,31 @@ impl<S: ?Sized + StateRead> ObjectProvider for (Arc<S>, Arc<TransactionKeyValueS
     }
 }
 
+#[async_trait]
+impl ObjectProvider for HistoricalObjectStore {
+    type Error = StateReadError;
+
+    async fn get_object(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Object, Self::Error> {
+        let object = self.get_object_version(id, *version)?;
+        Ok(object.ok_or(UserInputError::ObjectNotFound {
+            object_id: *id,
+            version: Some(*version),
+        })?)
+    }
+
+    async fn find_object_lt_or_eq_version(
+        &self,
+        id: &ObjectID,
+        version: &SequenceNumber,
+    ) -> Result<Option<Object>, Self::Error> {
+        Ok(self.find_object_lt_or_eq_version(id, *version)?)
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum StateReadInternalError {
     #[error(transparent)]
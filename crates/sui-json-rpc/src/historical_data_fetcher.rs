@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::future::Future;
+
+use jsonrpsee::http_client::{HttpClient, HttpClientBuilder};
+use sui_core::authority::historical_object_store::HistoricalDataFetcher;
+use sui_json_rpc_api::ReadApiClient;
+use sui_json_rpc_types::{
+    SuiObjectDataOptions, SuiPastObjectResponse, SuiTransactionBlockResponseOptions,
+};
+use sui_types::base_types::{ObjectID, SequenceNumber, TransactionDigest};
+use sui_types::effects::TransactionEffects;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Object;
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// Serves historical data that has been pruned from this node by reading it from another full
+/// node's JSON-RPC API, the same way the replay tool does.
+pub struct RpcHistoricalDataFetcher {
+    client: HttpClient,
+}
+
+impl RpcHistoricalDataFetcher {
+    pub fn new(rpc_url: &str) -> anyhow::Result<Self> {
+        Ok(Self {
+            client: HttpClientBuilder::default().build(rpc_url)?,
+        })
+    }
+
+    /// Runs `future` to completion from the synchronous context the object store is read in.
+    /// This blocks the current worker thread, which is only possible on a multi-threaded runtime.
+    fn block_on<F: Future>(future: F) -> SuiResult<F::Output> {
+        let handle = Handle::try_current().map_err(Self::rpc_error)?;
+        if handle.runtime_flavor() == RuntimeFlavor::CurrentThread {
+            return Err(SuiError::UnsupportedFeatureError {
+                error: "fetching historical data requires a multi-threaded runtime".to_string(),
+            });
+        }
+        Ok(tokio::task::block_in_place(|| handle.block_on(future)))
+    }
+
+    fn rpc_error(error: impl std::fmt::Display) -> SuiError {
+        SuiError::Storage(format!("historical data fetch failed: {error}"))
+    }
+}
+
+impl HistoricalDataFetcher for RpcHistoricalDataFetcher {
+    fn get_transaction_checkpoint(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        let response = Self::block_on(self.client.get_transaction_block(*digest, None))?
+            .map_err(Self::rpc_error)?;
+        Ok(response.checkpoint)
+    }
+
+    fn get_executed_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionEffects>> {
+        let options = SuiTransactionBlockResponseOptions::new().with_raw_effects();
+        let response = Self::block_on(self.client.get_transaction_block(*digest, Some(options)))?
+            .map_err(Self::rpc_error)?;
+        if response.raw_effects.is_empty() {
+            return Ok(None);
+        }
+        bcs::from_bytes(&response.raw_effects)
+            .map(Some)
+            .map_err(Self::rpc_error)
+    }
+
+    fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        let response = Self::block_on(self.client.try_get_past_object(
+            *object_id,
+            version,
+            Some(SuiObjectDataOptions::bcs_lossless()),
+        ))?
+        .map_err(Self::rpc_error)?;
+        match response {
+            SuiPastObjectResponse::VersionFound(data) => {
+                data.try_into().map(Some).map_err(Self::rpc_error)
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_current_thread_runtime() {
+        let fetcher = RpcHistoricalDataFetcher::new("http://127.0.0.1:9000").unwrap();
+        let err = fetcher
+            .get_object_by_key(&ObjectID::ZERO, SequenceNumber::new())
+            .unwrap_err();
+        assert!(matches!(err, SuiError::UnsupportedFeatureError { .. }));
+    }
+}
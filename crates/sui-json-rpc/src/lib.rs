@@ -40,6 +40,7 @@ pub mod bridge_api;
 pub mod coin_api;
 pub mod error;
 pub mod governance_api;
+pub mod historical_data_fetcher;
 pub mod indexer_api;
 pub mod logger;
 mod metrics;
@@ -10,11 +10,11 @@ use fastcrypto::traits::ToFromBytes;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::RpcModule;
 
-use crate::authority_state::StateRead;
+use crate::authority_state::{StateRead, StateReadError};
 use crate::error::{Error, SuiRpcInputError};
 use crate::{
-    get_balance_changes_from_effect, get_object_changes, with_tracing, ObjectProviderCache,
-    SuiRpcModule,
+    get_balance_changes_from_effect, get_object_changes, with_tracing, ObjectProvider,
+    ObjectProviderCache, SuiRpcModule,
 };
 use mysten_metrics::spawn_monitored_task;
 use shared_crypto::intent::{AppId, Intent, IntentMessage, IntentScope, IntentVersion};
@@ -23,14 +23,16 @@ use sui_core::authority_client::NetworkAuthorityClient;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_json_rpc_api::{JsonRpcMetrics, WriteApiOpenRpc, WriteApiServer};
 use sui_json_rpc_types::{
-    DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, SuiTransactionBlock,
-    SuiTransactionBlockEvents, SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    BalanceChange, DevInspectArgs, DevInspectResults, DryRunTransactionBlockResponse, ObjectChange,
+    SuiTransactionBlock, SuiTransactionBlockEvents, SuiTransactionBlockLifecycleStatus,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
 };
 use sui_open_rpc::Module;
-use sui_types::base_types::SuiAddress;
+use sui_types::base_types::{ObjectID, SuiAddress};
 use sui_types::crypto::default_hash;
 use sui_types::digests::TransactionDigest;
-use sui_types::effects::TransactionEffectsAPI;
+use sui_types::effects::{TransactionEffects, TransactionEffectsAPI};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::quorum_driver_types::{
     ExecuteTransactionRequestType, ExecuteTransactionRequestV3, ExecuteTransactionResponseV3,
 };
@@ -281,33 +283,77 @@ impl TransactionExecutionApi {
         Ok((intent_msg.value, txn_digest, input_objs))
     }
 
+    /// The balance and object changes of a dry run, read from `object_provider`.
+    async fn dry_run_changes<P: ObjectProvider<Error = StateReadError> + Sync>(
+        object_provider: &P,
+        sender: SuiAddress,
+        effects: &TransactionEffects,
+        input_objs: Vec<InputObjectKind>,
+        mock_gas: Option<ObjectID>,
+    ) -> Result<(Vec<BalanceChange>, Vec<ObjectChange>), Error> {
+        let balance_changes =
+            get_balance_changes_from_effect(object_provider, effects, input_objs, mock_gas).await?;
+        let object_changes = get_object_changes(
+            object_provider,
+            sender,
+            effects.modified_at_versions(),
+            effects.all_changed_objects(),
+            effects.all_removed_objects(),
+        )
+        .await?;
+        Ok((balance_changes, object_changes))
+    }
+
     async fn dry_run_transaction_block(
         &self,
         tx_bytes: Base64,
+        checkpoint: Option<CheckpointSequenceNumber>,
     ) -> Result<DryRunTransactionBlockResponse, Error> {
         let (txn_data, txn_digest, input_objs) =
             self.prepare_dry_run_transaction_block(tx_bytes)?;
         let sender = txn_data.sender();
-        let (resp, written_objects, transaction_effects, mock_gas) = self
-            .state
-            .dry_exec_transaction(txn_data.clone(), txn_digest)
-            .await?;
-        let object_cache = ObjectProviderCache::new_with_cache(self.state.clone(), written_objects);
-        let balance_changes = get_balance_changes_from_effect(
-            &object_cache,
-            &transaction_effects,
-            input_objs,
-            mock_gas,
-        )
-        .await?;
-        let object_changes = get_object_changes(
-            &object_cache,
-            sender,
-            transaction_effects.modified_at_versions(),
-            transaction_effects.all_changed_objects(),
-            transaction_effects.all_removed_objects(),
-        )
-        .await?;
+        let (resp, written_objects, transaction_effects, mock_gas) = match checkpoint {
+            Some(checkpoint) => {
+                self.state
+                    .dry_exec_transaction_at_checkpoint(txn_data.clone(), txn_digest, checkpoint)
+                    .await?
+            }
+            None => {
+                self.state
+                    .dry_exec_transaction(txn_data.clone(), txn_digest)
+                    .await?
+            }
+        };
+        // Changes are described in terms of the objects the transaction read, so they must come
+        // from the same view of the store that it executed against.
+        let (balance_changes, object_changes) = match checkpoint {
+            Some(checkpoint) => {
+                let object_cache = ObjectProviderCache::new_with_cache(
+                    self.state.get_historical_object_store(checkpoint),
+                    written_objects,
+                );
+                Self::dry_run_changes(
+                    &object_cache,
+                    sender,
+                    &transaction_effects,
+                    input_objs,
+                    mock_gas,
+                )
+                .await?
+            }
+            None => {
+                let object_cache =
+                    ObjectProviderCache::new_with_cache(self.state.clone(), written_objects);
+                Self::dry_run_changes(
+                    &object_cache,
+                    sender,
+                    &transaction_effects,
+                    input_objs,
+                    mock_gas,
+                )
+                .await?
+            }
+        };
 
         Ok(DryRunTransactionBlockResponse {
             effects: resp.effects,
@@ -374,7 +420,32 @@ impl WriteApiServer for TransactionExecutionApi {
         &self,
         tx_bytes: Base64,
     ) -> RpcResult<DryRunTransactionBlockResponse> {
-        with_tracing!(async move { self.dry_run_transaction_block(tx_bytes).await })
+        with_tracing!(async move { self.dry_run_transaction_block(tx_bytes, None).await })
+    }
+
+    #[instrument(skip(self))]
+    async fn dry_run_transaction_block_at_checkpoint(
+        &self,
+        tx_bytes: Base64,
+        checkpoint: BigInt<u64>,
+    ) -> RpcResult<DryRunTransactionBlockResponse> {
+        with_tracing!(async move {
+            self.dry_run_transaction_block(tx_bytes, Some(*checkpoint))
+                .await
+        })
+    }
+
+    #[instrument(skip(self))]
+    async fn get_transaction_block_status(
+        &self,
+        digest: TransactionDigest,
+    ) -> RpcResult<Option<SuiTransactionBlockLifecycleStatus>> {
+        with_tracing!(async move {
+            Ok(self
+                .transaction_orchestrator
+                .get_transaction_status(&digest)?
+                .map(SuiTransactionBlockLifecycleStatus::from))
+        })
     }
 }
 
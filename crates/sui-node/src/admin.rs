@@ -23,7 +23,7 @@ use sui_types::{
 };
 use telemetry_subscribers::TracingHandle;
 use tokio::sync::oneshot;
-use tracing::info;
+use tracing::{error, info};
 
 // Example commands:
 //
@@ -48,6 +48,27 @@ use tracing::info;
 //
 //   $ curl 'http://127.0.0.1:1337/node-config'
 //
+// View hit ratios, dirty-set sizes and eviction counts of the execution cache:
+//
+//   $ curl 'http://127.0.0.1:1337/execution-cache-stats'
+//
+// Write the ids of the objects currently in the execution cache to the configured
+// hot-objects-path, so that they are prefetched on the next start:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/persist-execution-cache-hot-objects'
+//
+// Check the owner, coin and dynamic field indexes of a fullnode against the live object set and
+// rebuild inconsistent entries in the background. Progress is reported through the
+// index_repair_* metrics and the result is logged. Pass dry_run=true to only count them:
+//
+//   $ curl -X POST 'http://127.0.0.1:1337/repair-indexes?dry_run=true'
+//
+// View the consensus health of a validator as JSON: the round propagation matrix from the last
+// round probe, local vs quorum commit index, leader timeout and the outcomes of the most recently
+// decided leaders. Pass leaders=N to only include the last N leaders:
+//
+//   $ curl 'http://127.0.0.1:1337/consensus-health?leaders=20'
+//
 // Set a time-limited tracing config. After the duration expires, tracing will be disabled
 // automatically.
 //
@@ -77,6 +98,10 @@ const CLEAR_BUFFER_STAKE_ROUTE: &str = "/clear-override-buffer-stake";
 const FORCE_CLOSE_EPOCH: &str = "/force-close-epoch";
 const CAPABILITIES: &str = "/capabilities";
 const NODE_CONFIG: &str = "/node-config";
+const EXECUTION_CACHE_STATS: &str = "/execution-cache-stats";
+const PERSIST_EXECUTION_CACHE_HOT_OBJECTS: &str = "/persist-execution-cache-hot-objects";
+const REPAIR_INDEXES: &str = "/repair-indexes";
+const CONSENSUS_HEALTH: &str = "/consensus-health";
 const RANDOMNESS_PARTIAL_SIGS_ROUTE: &str = "/randomness-partial-sigs";
 const RANDOMNESS_INJECT_PARTIAL_SIGS_ROUTE: &str = "/randomness-inject-partial-sigs";
 const RANDOMNESS_INJECT_FULL_SIG_ROUTE: &str = "/randomness-inject-full-sig";
@@ -98,6 +123,13 @@ pub async fn run_admin_server(node: Arc<SuiNode>, port: u16, tracing_handle: Tra
         .route(LOGGING_ROUTE, get(get_filter))
         .route(CAPABILITIES, get(capabilities))
         .route(NODE_CONFIG, get(node_config))
+        .route(EXECUTION_CACHE_STATS, get(execution_cache_stats))
+        .route(
+            PERSIST_EXECUTION_CACHE_HOT_OBJECTS,
+            post(persist_execution_cache_hot_objects),
+        )
+        .route(REPAIR_INDEXES, post(repair_indexes))
+        .route(CONSENSUS_HEALTH, get(consensus_health))
         .route(LOGGING_ROUTE, post(set_filter))
         .route(
             SET_BUFFER_STAKE_ROUTE,
@@ -260,6 +292,121 @@ async fn node_config(State(state): State<Arc<AppState>>) -> (StatusCode, String)
     (StatusCode::OK, format!("{:#?}\n", node_config))
 }
 
+async fn execution_cache_stats(State(state): State<Arc<AppState>>) -> (StatusCode, String) {
+    let stats = state.node.state().get_cache_stats_api().cache_stats();
+
+    let mut output = String::new();
+    for (name, cache) in [
+        ("objects", &stats.objects),
+        ("packages", &stats.packages),
+        ("markers", &stats.markers),
+    ] {
+        output.push_str(&format!(
+            "{name}: hits={} misses={} hit_ratio={:.4} dirty_entries={} cached_entries={} evictions={}\n",
+            cache.hits,
+            cache.misses,
+            cache.hit_ratio(),
+            cache.dirty_entries,
+            cache.cached_entries,
+            cache.evictions,
+        ));
+    }
+
+    (StatusCode::OK, output)
+}
+
+async fn persist_execution_cache_hot_objects(
+    State(state): State<Arc<AppState>>,
+) -> (StatusCode, String) {
+    if state
+        .node
+        .config
+        .execution_cache
+        .hot_objects_path()
+        .is_none()
+    {
+        return (
+            StatusCode::BAD_REQUEST,
+            "execution cache hot-objects-path is not configured\n".to_string(),
+        );
+    }
+
+    match state.node.persist_execution_cache_hot_objects() {
+        Ok(()) => (
+            StatusCode::OK,
+            "execution cache hot objects persisted\n".to_string(),
+        ),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+#[derive(Deserialize)]
+struct RepairIndexes {
+    #[serde(default)]
+    dry_run: bool,
+}
+
+async fn repair_indexes(
+    State(state): State<Arc<AppState>>,
+    args: Query<RepairIndexes>,
+) -> (StatusCode, String) {
+    let Query(RepairIndexes { dry_run }) = args;
+    let authority_state = state.node.state();
+    if authority_state.indexes.is_none() {
+        return (
+            StatusCode::BAD_REQUEST,
+            "secondary indexes are not enabled on this node\n".to_string(),
+        );
+    }
+    if authority_state.is_index_repair_in_progress() {
+        return (
+            StatusCode::CONFLICT,
+            "an index repair is already in progress\n".to_string(),
+        );
+    }
+
+    tokio::spawn(async move {
+        if let Err(err) = authority_state.repair_indexes(dry_run).await {
+            error!("Index repair failed: {err}");
+        }
+    });
+    (
+        StatusCode::OK,
+        format!("index repair started (dry_run={dry_run})\n"),
+    )
+}
+
+#[derive(Deserialize)]
+struct ConsensusHealthArgs {
+    leaders: Option<usize>,
+}
+
+async fn consensus_health(
+    State(state): State<Arc<AppState>>,
+    args: Query<ConsensusHealthArgs>,
+) -> (StatusCode, String) {
+    let Query(ConsensusHealthArgs { leaders }) = args;
+    let mut health = match state.node.consensus_health().await {
+        Ok(Some(health)) => health,
+        Ok(None) => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "consensus is not running\n".to_string(),
+            )
+        }
+        Err(err) => return (StatusCode::BAD_REQUEST, err.to_string()),
+    };
+    if let Some(leaders) = leaders {
+        let skipped = health.recent_leaders.len().saturating_sub(leaders);
+        health.recent_leaders.drain(..skipped);
+    }
+
+    match serde_json::to_string_pretty(&health) {
+        Ok(output) => (StatusCode::OK, output + "\n"),
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
 #[derive(Deserialize)]
 struct Epoch {
     epoch: u64,
@@ -9,6 +9,7 @@ use anemo_tower::trace::TraceLayer;
 use anyhow::anyhow;
 use anyhow::Result;
 use arc_swap::ArcSwap;
+use consensus_core::ConsensusHealth;
 use fastcrypto_zkp::bn254::zk_login::JwkId;
 use fastcrypto_zkp::bn254::zk_login::OIDCProvider;
 use futures::TryFutureExt;
@@ -29,7 +30,9 @@ use sui_core::authority::CHAIN_IDENTIFIER;
 use sui_core::consensus_adapter::SubmitToConsensus;
 use sui_core::consensus_manager::ConsensusClient;
 use sui_core::epoch::randomness::RandomnessManager;
-use sui_core::execution_cache::build_execution_cache;
+use sui_core::execution_cache::{
+    build_execution_cache, persist_hot_objects, warm_up_execution_cache,
+};
 use sui_core::state_accumulator::StateAccumulatorMetrics;
 use sui_core::storage::RestReadStore;
 use sui_core::traffic_controller::metrics::TrafficControllerMetrics;
@@ -485,6 +488,13 @@ impl SuiNode {
         let cache_traits =
             build_execution_cache(&epoch_start_configuration, &prometheus_registry, &store);
 
+        if let Some(path) = config.execution_cache.hot_objects_path() {
+            // A failed warmup only costs us latency, so it must not prevent the node from starting.
+            if let Err(err) = warm_up_execution_cache(cache_traits.cache_stats_api.as_ref(), path) {
+                warn!("failed to warm up execution cache: {err:?}");
+            }
+        }
+
         let auth_agg = {
             let safe_client_metrics_base = SafeClientMetricsBase::new(&prometheus_registry);
             let auth_agg_metrics = Arc::new(AuthAggMetrics::new(&prometheus_registry));
@@ -691,6 +701,13 @@ impl SuiNode {
             validator_tx_finalizer,
         )
         .await;
+
+        if let Some(rpc_url) = &config.historical_data_fallback_rpc_url {
+            state.set_historical_data_fetcher(Arc::new(
+                sui_json_rpc::historical_data_fetcher::RpcHistoricalDataFetcher::new(rpc_url)?,
+            ));
+        }
+
         // ensure genesis txn was executed
         if epoch_store.epoch() == 0 {
             let txn = &genesis.transaction();
@@ -886,11 +903,32 @@ impl SuiNode {
         Ok(())
     }
 
+    /// Returns the health of consensus, or None if consensus is not running on this node.
+    pub async fn consensus_health(&self) -> SuiResult<Option<ConsensusHealth>> {
+        Ok(self
+            .validator_components
+            .lock()
+            .await
+            .as_ref()
+            .ok_or_else(|| SuiError::from("Node is not a validator"))?
+            .consensus_manager
+            .consensus_health())
+    }
+
     pub fn clear_override_protocol_upgrade_buffer_stake(&self, epoch: EpochId) -> SuiResult {
         self.state
             .clear_override_protocol_upgrade_buffer_stake(epoch)
     }
 
+    /// Write the ids of the objects currently in the execution cache to the configured
+    /// `hot-objects-path`, if any. Called on shutdown so that the next start can warm up the cache.
+    pub fn persist_execution_cache_hot_objects(&self) -> anyhow::Result<()> {
+        if let Some(path) = self.config.execution_cache.hot_objects_path() {
+            persist_hot_objects(self.state.get_cache_stats_api().as_ref(), path)?;
+        }
+        Ok(())
+    }
+
     pub fn set_override_protocol_upgrade_buffer_stake(
         &self,
         epoch: EpochId,
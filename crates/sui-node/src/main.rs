@@ -170,8 +170,9 @@ fn main() {
         sui_node::admin::run_admin_server(node, admin_interface_port, filter_handle).await
     });
 
+    let node_once_cell_clone = node_once_cell.clone();
     runtimes.metrics.spawn(async move {
-        let node = node_once_cell.get().await;
+        let node = node_once_cell_clone.get().await;
         let state = node.state();
         loop {
             send_telemetry_event(state.clone(), is_validator).await;
@@ -180,11 +181,20 @@ fn main() {
     });
 
     // wait for SIGINT on the main thread
-    tokio::runtime::Builder::new_current_thread()
+    let main_runtime = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
-        .unwrap()
-        .block_on(wait_termination(runtime_shutdown_rx));
+        .unwrap();
+    main_runtime.block_on(wait_termination(runtime_shutdown_rx));
+
+    // The node may never have finished starting, so don't wait for it indefinitely.
+    main_runtime.block_on(async {
+        if let Ok(node) = tokio::time::timeout(Duration::from_secs(1), node_once_cell.get()).await {
+            if let Err(err) = node.persist_execution_cache_hot_objects() {
+                error!("Failed to persist execution cache hot objects: {err:?}");
+            }
+        }
+    });
 
     // Drop and wait all runtimes on main thread
     drop(runtimes);
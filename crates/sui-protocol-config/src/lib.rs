@@ -546,6 +546,14 @@ struct FeatureFlags {
     // Makes the event's sending module version-aware.
     #[serde(skip_serializing_if = "is_false")]
     relocate_event_module: bool,
+
+    // Attach misbehavior reports (equivocations, invalid blocks) to consensus block proposals.
+    #[serde(skip_serializing_if = "is_false")]
+    consensus_misbehavior_reports: bool,
+
+    // Strategy used to score leaders in consensus, when distributed vote scoring is enabled.
+    #[serde(skip_serializing_if = "ConsensusLeaderScoringStrategy::is_vote")]
+    consensus_leader_scoring_strategy: ConsensusLeaderScoringStrategy,
 }
 
 fn is_false(b: &bool) -> bool {
@@ -616,6 +624,25 @@ impl ConsensusNetwork {
     }
 }
 
+// Rule used by consensus to turn the votes for committed leaders into reputation scores.
+#[derive(Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug)]
+pub enum ConsensusLeaderScoringStrategy {
+    /// Score a vote with the stake of the blocks that include it.
+    #[default]
+    Vote,
+    /// Score a vote only once it is included by a quorum of stake.
+    CertifiedVote,
+    /// Score votes like `Vote`, scaled down by how many rounds late the authority's blocks
+    /// are included by the committed blocks of other authorities.
+    LatencyWeightedVote,
+}
+
+impl ConsensusLeaderScoringStrategy {
+    pub fn is_vote(&self) -> bool {
+        matches!(self, ConsensusLeaderScoringStrategy::Vote)
+    }
+}
+
 /// Constants that change the behavior of the protocol.
 ///
 /// The value of each constant here must be fixed for a given protocol version. To change the value
@@ -1624,6 +1651,14 @@ impl ProtocolConfig {
     pub fn relocate_event_module(&self) -> bool {
         self.feature_flags.relocate_event_module
     }
+
+    pub fn consensus_misbehavior_reports(&self) -> bool {
+        self.feature_flags.consensus_misbehavior_reports
+    }
+
+    pub fn consensus_leader_scoring_strategy(&self) -> ConsensusLeaderScoringStrategy {
+        self.feature_flags.consensus_leader_scoring_strategy
+    }
 }
 
 #[cfg(not(msim))]
@@ -2819,6 +2854,11 @@ impl ProtocolConfig {
                 }
                 62 => {
                     cfg.feature_flags.relocate_event_module = true;
+
+                    if chain != Chain::Mainnet && chain != Chain::Testnet {
+                        // Enable consensus misbehavior reports for devnet
+                        cfg.feature_flags.consensus_misbehavior_reports = true;
+                    }
                 }
                 // Use this template when making changes:
                 //
@@ -2988,6 +3028,17 @@ impl ProtocolConfig {
     pub fn set_gc_depth_for_testing(&mut self, val: u32) {
         self.consensus_gc_depth = Some(val);
     }
+
+    pub fn set_consensus_misbehavior_reports_for_testing(&mut self, val: bool) {
+        self.feature_flags.consensus_misbehavior_reports = val;
+    }
+
+    pub fn set_consensus_leader_scoring_strategy_for_testing(
+        &mut self,
+        val: ConsensusLeaderScoringStrategy,
+    ) {
+        self.feature_flags.consensus_leader_scoring_strategy = val;
+    }
 }
 
 type OverrideFn = dyn Fn(ProtocolVersion, ProtocolConfig) -> ProtocolConfig + Send;
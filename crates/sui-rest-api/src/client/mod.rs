@@ -6,14 +6,14 @@ use sdk::Result;
 
 pub use reqwest;
 
-use crate::transactions::ExecuteTransactionQueryParameters;
+use crate::transactions::{ExecuteTransactionQueryParameters, SimulateTransactionQueryParameters};
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress};
 use sui_types::crypto::AuthorityStrongQuorumSignInfo;
 use sui_types::effects::{TransactionEffects, TransactionEvents};
 use sui_types::full_checkpoint_content::CheckpointData;
 use sui_types::messages_checkpoint::{CertifiedCheckpointSummary, CheckpointSequenceNumber};
 use sui_types::object::Object;
-use sui_types::transaction::Transaction;
+use sui_types::transaction::{Transaction, TransactionData};
 use sui_types::TypeTag;
 
 use self::sdk::Response;
@@ -119,6 +119,34 @@ impl Client {
 
         self.inner.bcs(response).await.map(Response::into_inner)
     }
+
+    pub async fn simulate_transaction(
+        &self,
+        parameters: &SimulateTransactionQueryParameters,
+        transaction: &TransactionData,
+    ) -> Result<TransactionSimulationResponse> {
+        let url = self.inner.url().join("transactions/simulate")?;
+        let body = bcs::to_bytes(transaction)?;
+
+        let response = self
+            .inner
+            .client()
+            .post(url)
+            .query(parameters)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_BCS)
+            .header(reqwest::header::CONTENT_TYPE, crate::APPLICATION_BCS)
+            .body(body)
+            .send()
+            .await?;
+
+        self.inner.bcs(response).await.map(Response::into_inner)
+    }
+}
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct TransactionSimulationResponse {
+    pub effects: TransactionEffects,
+    pub events: Option<TransactionEvents>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -35,6 +35,7 @@ use crate::system::X_SUI_MIN_SUPPORTED_PROTOCOL_VERSION;
 use crate::transactions::ListTransactionsQueryParameters;
 use crate::transactions::TransactionExecutionResponse;
 use crate::transactions::TransactionResponse;
+use crate::transactions::TransactionStatus;
 use crate::types::X_SUI_CHAIN;
 use crate::types::X_SUI_CHAIN_ID;
 use crate::types::X_SUI_CHECKPOINT_HEIGHT;
@@ -400,6 +401,24 @@ impl Client {
         self.bcs(response).await
     }
 
+    pub async fn get_transaction_status(
+        &self,
+        transaction: &TransactionDigest,
+    ) -> Result<Response<TransactionStatus>> {
+        let url = self
+            .url()
+            .join(&format!("transactions/{transaction}/status"))?;
+
+        let response = self
+            .inner
+            .get(url)
+            .header(reqwest::header::ACCEPT, crate::APPLICATION_JSON)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
     async fn check_response(
         &self,
         response: reqwest::Response,
@@ -56,6 +56,15 @@ impl From<sui_types::sui_sdk2_conversions::SdkTypeConversionError> for RestError
     }
 }
 
+impl From<sui_types::error::SuiError> for RestError {
+    fn from(value: sui_types::error::SuiError) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: Some(value.to_string()),
+        }
+    }
+}
+
 impl From<bcs::Error> for RestError {
     fn from(value: bcs::Error) -> Self {
         Self {
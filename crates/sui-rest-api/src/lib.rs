@@ -33,7 +33,7 @@ pub use client::Client;
 pub use error::{RestError, Result};
 pub use metrics::RestMetrics;
 pub use sui_types::full_checkpoint_content::{CheckpointData, CheckpointTransaction};
-pub use transactions::ExecuteTransactionQueryParameters;
+pub use transactions::{ExecuteTransactionQueryParameters, SimulateTransactionQueryParameters};
 
 pub const TEXT_PLAIN_UTF_8: &str = "text/plain; charset=utf-8";
 pub const APPLICATION_BCS: &str = "application/bcs";
@@ -84,6 +84,8 @@ const ENDPOINTS: &[&dyn ApiEndpoint<RestService>] = &[
     &system::GetProtocolConfig,
     &system::GetGasInfo,
     &transactions::ExecuteTransaction,
+    &transactions::GetTransactionStatus,
+    &transactions::SimulateTransaction,
     &coins::GetCoinInfo,
 ];
 
@@ -7,6 +7,16 @@ pub use execution::ExecuteTransaction;
 pub use execution::ExecuteTransactionQueryParameters;
 pub use execution::TransactionExecutionResponse;
 
+mod simulation;
+pub use simulation::SimulateTransaction;
+pub use simulation::SimulateTransactionQueryParameters;
+pub use simulation::TransactionSimulationResponse;
+
+mod status;
+pub use status::GetTransactionStatus;
+pub use status::InputKey;
+pub use status::TransactionStatus;
+
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use sui_sdk2::types::CheckpointSequenceNumber;
@@ -0,0 +1,178 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use schemars::JsonSchema;
+use sui_sdk2::types::{
+    CheckpointSequenceNumber, Transaction, TransactionEffects, TransactionEvents,
+};
+use sui_types::error::{SuiError, UserInputError};
+use sui_types::transaction_executor::TransactionExecutor;
+use tap::Pipe;
+
+use crate::openapi::{
+    ApiEndpoint, OperationBuilder, RequestBodyBuilder, ResponseBuilder, RouteHandler,
+};
+use crate::response::Bcs;
+use crate::{accept::AcceptFormat, response::ResponseContent};
+use crate::{RestError, RestService, Result};
+
+pub struct SimulateTransaction;
+
+impl ApiEndpoint<RestService> for SimulateTransaction {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::POST
+    }
+
+    fn path(&self) -> &'static str {
+        "/transactions/simulate"
+    }
+
+    fn operation(
+        &self,
+        generator: &mut schemars::gen::SchemaGenerator,
+    ) -> openapiv3::v3_1::Operation {
+        generator.subschema_for::<Transaction>();
+
+        OperationBuilder::new()
+            .tag("Transactions")
+            .operation_id("SimulateTransaction")
+            .query_parameters::<SimulateTransactionQueryParameters>(generator)
+            .request_body(RequestBodyBuilder::new().bcs_content().build())
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<TransactionSimulationResponse>(generator)
+                    .bcs_content()
+                    .build(),
+            )
+            .response(400, ResponseBuilder::new().build())
+            .response(404, ResponseBuilder::new().build())
+            .build()
+    }
+
+    fn handler(&self) -> RouteHandler<RestService> {
+        RouteHandler::new(self.method(), simulate_transaction)
+    }
+}
+
+/// Simulate Transaction REST endpoint.
+///
+/// Executes the provided (unsigned) transaction against the object versions as of the end of the
+/// requested checkpoint, without submitting it, and returns the effects and events it would have
+/// produced. Only checkpoints whose history is still available to this node can be simulated at.
+///
+/// A client can signal, using the `Accept` header, the response format as either JSON or BCS.
+async fn simulate_transaction(
+    State(state): State<Option<Arc<dyn TransactionExecutor>>>,
+    Query(parameters): Query<SimulateTransactionQueryParameters>,
+    accept: AcceptFormat,
+    Bcs(transaction): Bcs<Transaction>,
+) -> Result<ResponseContent<TransactionSimulationResponse>> {
+    let executor = state.ok_or_else(|| anyhow::anyhow!("No Transaction Executor"))?;
+    let transaction = transaction.try_into()?;
+
+    // Historical reads can walk through many versions of an object, so keep them off the async
+    // worker threads.
+    let (effects, events) = tokio::task::spawn_blocking(move || {
+        executor.simulate_transaction_at_checkpoint(transaction, parameters.checkpoint)
+    })
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?
+    .map_err(simulation_error)?;
+
+    let response = TransactionSimulationResponse {
+        effects: effects.try_into()?,
+        events: (!events.data.is_empty())
+            .then(|| events.try_into())
+            .transpose()?,
+    };
+
+    match accept {
+        AcceptFormat::Json => ResponseContent::Json(response),
+        AcceptFormat::Bcs => ResponseContent::Bcs(response),
+    }
+    .pipe(Ok)
+}
+
+/// Distinguishes simulations that can't be served because of what was asked (an unknown or
+/// pruned checkpoint, an input that didn't exist, an unsupported transaction) from failures of
+/// the node itself.
+fn simulation_error(error: SuiError) -> RestError {
+    use axum::http::StatusCode;
+
+    let status = match &error {
+        SuiError::UserInputError {
+            error:
+                UserInputError::ObjectNotFound { .. }
+                | UserInputError::DependentPackageNotFound { .. }
+                | UserInputError::VerifiedCheckpointNotFound(_),
+        } => StatusCode::NOT_FOUND,
+        SuiError::UserInputError { .. } | SuiError::UnsupportedFeatureError { .. } => {
+            StatusCode::BAD_REQUEST
+        }
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    };
+
+    RestError::new(status, error.to_string())
+}
+
+/// Query parameters for the simulate transaction endpoint
+#[derive(Debug, serde::Serialize, serde::Deserialize, JsonSchema)]
+pub struct SimulateTransactionQueryParameters {
+    /// The checkpoint whose end state the transaction is executed against.
+    pub checkpoint: CheckpointSequenceNumber,
+}
+
+/// Response type for the simulate transaction endpoint
+#[derive(Debug, serde::Serialize, serde::Deserialize, JsonSchema)]
+pub struct TransactionSimulationResponse {
+    effects: TransactionEffects,
+    events: Option<TransactionEvents>,
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use axum::response::IntoResponse;
+    use sui_types::base_types::ObjectID;
+
+    use super::*;
+
+    fn status(error: SuiError) -> StatusCode {
+        simulation_error(error).into_response().status()
+    }
+
+    #[test]
+    fn test_simulation_error_status() {
+        let not_found = UserInputError::ObjectNotFound {
+            object_id: ObjectID::ZERO,
+            version: None,
+        };
+        assert_eq!(status(not_found.into()), StatusCode::NOT_FOUND);
+        assert_eq!(
+            status(UserInputError::VerifiedCheckpointNotFound(1).into()),
+            StatusCode::NOT_FOUND
+        );
+
+        assert_eq!(
+            status(
+                UserInputError::GasBudgetTooLow {
+                    gas_budget: 1,
+                    min_budget: 2
+                }
+                .into()
+            ),
+            StatusCode::BAD_REQUEST
+        );
+        let unsupported = SuiError::UnsupportedFeatureError {
+            error: "object was modified too many times".to_string(),
+        };
+        assert_eq!(status(unsupported), StatusCode::BAD_REQUEST);
+
+        let storage = SuiError::Storage("corrupted".to_string());
+        assert_eq!(status(storage), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
@@ -0,0 +1,154 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use schemars::JsonSchema;
+use sui_sdk2::types::{
+    CheckpointSequenceNumber, EpochId, ObjectId, TransactionDigest, TransactionEffectsDigest,
+    Version,
+};
+use sui_types::quorum_driver_types::TransactionLifecycleStatus;
+use sui_types::transaction_executor::TransactionExecutor;
+use tap::Pipe;
+
+use super::TransactionNotFoundError;
+use crate::openapi::{ApiEndpoint, OperationBuilder, ResponseBuilder, RouteHandler};
+use crate::{RestService, Result};
+
+pub struct GetTransactionStatus;
+
+impl ApiEndpoint<RestService> for GetTransactionStatus {
+    fn method(&self) -> axum::http::Method {
+        axum::http::Method::GET
+    }
+
+    fn path(&self) -> &'static str {
+        "/transactions/{transaction}/status"
+    }
+
+    fn operation(
+        &self,
+        generator: &mut schemars::gen::SchemaGenerator,
+    ) -> openapiv3::v3_1::Operation {
+        OperationBuilder::new()
+            .tag("Transactions")
+            .operation_id("GetTransactionStatus")
+            .path_parameter::<TransactionDigest>("transaction", generator)
+            .response(
+                200,
+                ResponseBuilder::new()
+                    .json_content::<TransactionStatus>(generator)
+                    .build(),
+            )
+            .response(404, ResponseBuilder::new().build())
+            .build()
+    }
+
+    fn handler(&self) -> RouteHandler<RestService> {
+        RouteHandler::new(self.method(), get_transaction_status)
+    }
+}
+
+/// Transaction Status REST endpoint.
+///
+/// Reports how far a transaction that was submitted to, or executed by, this node has progressed
+/// towards finality. Returns a `404` if the node has no record of the transaction.
+async fn get_transaction_status(
+    State(state): State<Option<Arc<dyn TransactionExecutor>>>,
+    Path(transaction_digest): Path<TransactionDigest>,
+) -> Result<axum::Json<TransactionStatus>> {
+    let executor = state.ok_or_else(|| anyhow::anyhow!("No Transaction Executor"))?;
+
+    executor
+        .transaction_status(&transaction_digest.into())?
+        .ok_or(TransactionNotFoundError(transaction_digest))?
+        .pipe(TransactionStatus::from)
+        .pipe(axum::Json)
+        .pipe(Ok)
+}
+
+/// The furthest stage a transaction has reached on its way to finality
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, JsonSchema)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum TransactionStatus {
+    /// The transaction is being submitted to validators to collect a certificate.
+    Submitted {
+        /// Number of submission attempts so far, including retries.
+        attempts: u32,
+    },
+    /// A certificate was formed and the node is waiting for validators to return effects.
+    Certified {
+        /// Number of submission attempts so far, including retries.
+        attempts: u32,
+    },
+    /// The transaction is enqueued for local execution, but some inputs are not available yet.
+    WaitingOnInputs { missing_inputs: Vec<InputKey> },
+    /// All inputs are available and the transaction is executing locally.
+    Executing,
+    /// The transaction was executed locally but is not part of an executed checkpoint yet.
+    Executed {
+        effects_digest: TransactionEffectsDigest,
+    },
+    /// The transaction is part of a checkpoint that the node has executed.
+    Finalized {
+        effects_digest: TransactionEffectsDigest,
+        #[serde_as(as = "sui_types::sui_serde::Readable<sui_types::sui_serde::BigInt<u64>, _>")]
+        #[schemars(with = "crate::_schemars::U64")]
+        epoch: EpochId,
+        #[serde_as(as = "sui_types::sui_serde::Readable<sui_types::sui_serde::BigInt<u64>, _>")]
+        #[schemars(with = "crate::_schemars::U64")]
+        checkpoint: CheckpointSequenceNumber,
+    },
+}
+
+/// An input that a transaction is waiting for before it can execute
+#[serde_with::serde_as]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, JsonSchema)]
+pub struct InputKey {
+    pub object_id: ObjectId,
+    /// Not set for packages, which are only ever waited on by id.
+    #[serde_as(
+        as = "Option<sui_types::sui_serde::Readable<sui_types::sui_serde::BigInt<u64>, _>>"
+    )]
+    #[schemars(with = "Option<crate::_schemars::U64>")]
+    pub version: Option<Version>,
+}
+
+impl From<sui_types::storage::InputKey> for InputKey {
+    fn from(value: sui_types::storage::InputKey) -> Self {
+        Self {
+            object_id: value.id().into(),
+            version: value.version().map(|version| version.value()),
+        }
+    }
+}
+
+impl From<TransactionLifecycleStatus> for TransactionStatus {
+    fn from(value: TransactionLifecycleStatus) -> Self {
+        match value {
+            TransactionLifecycleStatus::Submitted { attempts } => Self::Submitted { attempts },
+            TransactionLifecycleStatus::Certified { attempts } => Self::Certified { attempts },
+            TransactionLifecycleStatus::WaitingOnInputs { missing_inputs } => {
+                Self::WaitingOnInputs {
+                    missing_inputs: missing_inputs.into_iter().map(Into::into).collect(),
+                }
+            }
+            TransactionLifecycleStatus::Executing => Self::Executing,
+            TransactionLifecycleStatus::Executed { effects_digest } => Self::Executed {
+                effects_digest: TransactionEffectsDigest::new(effects_digest.into_inner()),
+            },
+            TransactionLifecycleStatus::Finalized {
+                effects_digest,
+                epoch,
+                checkpoint,
+            } => Self::Finalized {
+                effects_digest: TransactionEffectsDigest::new(effects_digest.into_inner()),
+                epoch,
+                checkpoint,
+            },
+        }
+    }
+}
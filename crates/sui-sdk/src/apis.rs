@@ -26,9 +26,9 @@ use sui_json_rpc_types::{
     DryRunTransactionBlockResponse, DynamicFieldPage, EventFilter, EventPage, ObjectsPage,
     ProtocolConfigResponse, SuiCoinMetadata, SuiCommittee, SuiEvent, SuiGetPastObjectRequest,
     SuiMoveNormalizedModule, SuiObjectDataOptions, SuiObjectResponse, SuiObjectResponseQuery,
-    SuiPastObjectResponse, SuiTransactionBlockEffects, SuiTransactionBlockResponse,
-    SuiTransactionBlockResponseOptions, SuiTransactionBlockResponseQuery, TransactionBlocksPage,
-    TransactionFilter,
+    SuiPastObjectResponse, SuiTransactionBlockEffects, SuiTransactionBlockLifecycleStatus,
+    SuiTransactionBlockResponse, SuiTransactionBlockResponseOptions,
+    SuiTransactionBlockResponseQuery, TransactionBlocksPage, TransactionFilter,
 };
 use sui_types::balance::Supply;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
@@ -646,6 +646,24 @@ impl ReadApi {
             .await?)
     }
 
+    /// Like [dry_run_transaction_block](ReadApi::dry_run_transaction_block), but runs the
+    /// transaction against the state at the end of the given checkpoint instead of the latest
+    /// state, e.g. to check whether a transaction would have succeeded at some point in the past.
+    pub async fn dry_run_transaction_block_at_checkpoint(
+        &self,
+        tx: TransactionData,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiRpcResult<DryRunTransactionBlockResponse> {
+        Ok(self
+            .api
+            .http
+            .dry_run_transaction_block_at_checkpoint(
+                Base64::from_bytes(&bcs::to_bytes(&tx)?),
+                checkpoint.into(),
+            )
+            .await?)
+    }
+
     /// Return the inspection of the transaction block, or an error upon failure.
     ///
     /// Use this function to inspect the current state of the network by running a programmable
@@ -1182,6 +1200,15 @@ impl QuorumDriverApi {
         poll_response.confirmed_local_execution = Some(true);
         Ok(poll_response)
     }
+
+    /// Return how far the transaction with the given digest has progressed towards finality on
+    /// the FullNode, or `None` if the FullNode has no record of it.
+    pub async fn get_transaction_block_status(
+        &self,
+        digest: TransactionDigest,
+    ) -> SuiRpcResult<Option<SuiTransactionBlockLifecycleStatus>> {
+        Ok(self.api.http.get_transaction_block_status(digest).await?)
+    }
 }
 
 /// Governance API provides the staking functionality.
@@ -5,6 +5,7 @@
 #[cfg(test)]
 mod tests;
 
+pub mod portable;
 pub mod reader;
 pub mod uploader;
 mod writer;
@@ -0,0 +1,334 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{compute_sha3_checksum, MAGIC_BYTES, SHA3_BYTES};
+use anyhow::{anyhow, Context, Result};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use fastcrypto::hash::{HashFunction, MultisetHash, Sha3_256};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use sui_core::authority::authority_store_tables::{AuthorityPerpetualTables, LiveObject};
+use sui_core::authority::AuthorityStore;
+use sui_core::state_accumulator::StateAccumulator;
+use sui_storage::blob::{Blob, BlobEncoding};
+use sui_types::accumulator::Accumulator;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointCommitment, CheckpointSummary, ECMHLiveObjectSetDigest,
+};
+use tracing::info;
+
+/// A portable snapshot holds the live object set at the end of an epoch in a single file, together
+/// with the certified last checkpoint of every epoch up to and including that one, so that the
+/// chain of committees can be verified from genesis on import. Unlike a formal snapshot it does
+/// not need an object store layout to be restored from, which makes it convenient for moving state
+/// between machines and for test fixtures. The header and every object are written as blobs, using
+/// the same encoding as the objects in *.obj files.
+///
+/// Portable Snapshot File Disk Format
+///┌──────────────────────────────┐
+///│  magic(0x5EA1ED05) <4 byte>  │
+///├──────────────────────────────┤
+///│        header <blob>         │
+///├──────────────────────────────┤
+///│ ┌──────────────────────────┐ │
+///│ │     LiveObject 1 <blob>  │ │
+///│ ├──────────────────────────┤ │
+///│ │          ...             │ │
+///│ ├──────────────────────────┤ │
+///│ │     LiveObject N <blob>  │ │
+///│ └──────────────────────────┘ │
+///├──────────────────────────────┤
+///│       N <8 byte>             │
+///├──────────────────────────────┤
+///│      sha3 <32 bytes>         │
+///└──────────────────────────────┘
+/// The trailing sha3 digest covers every byte that precedes it.
+const PORTABLE_SNAPSHOT_FILE_MAGIC: u32 = 0x5EA1ED05;
+const OBJECT_COUNT_BYTES: usize = 8;
+const FOOTER_BYTES: usize = OBJECT_COUNT_BYTES + SHA3_BYTES;
+const IMPORT_BATCH_SIZE: usize = 10_000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PortableSnapshotHeaderV1 {
+    pub epoch: u64,
+    pub include_wrapped_tombstone: bool,
+    /// Last checkpoint of each epoch from 0 to `epoch`. The end of epoch data of the last one
+    /// commits to the exported live object set.
+    pub end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum PortableSnapshotHeader {
+    V1(PortableSnapshotHeaderV1),
+}
+
+impl PortableSnapshotHeader {
+    pub fn epoch(&self) -> u64 {
+        match self {
+            Self::V1(header) => header.epoch,
+        }
+    }
+    pub fn include_wrapped_tombstone(&self) -> bool {
+        match self {
+            Self::V1(header) => header.include_wrapped_tombstone,
+        }
+    }
+    pub fn end_of_epoch_checkpoints(&self) -> &[CertifiedCheckpointSummary] {
+        match self {
+            Self::V1(header) => &header.end_of_epoch_checkpoints,
+        }
+    }
+    pub fn last_checkpoint(&self) -> &CertifiedCheckpointSummary {
+        self.end_of_epoch_checkpoints()
+            .last()
+            .expect("Header must contain at least one checkpoint")
+    }
+    pub fn root_state_hash(&self) -> Result<ECMHLiveObjectSetDigest> {
+        state_hash_commitment(self.last_checkpoint())
+    }
+
+    fn validate(&self) -> Result<()> {
+        let checkpoints = self.end_of_epoch_checkpoints();
+        if checkpoints.len() as u64 != self.epoch() + 1 {
+            return Err(anyhow!(
+                "Expected {} end of epoch checkpoints for epoch {} but found {}",
+                self.epoch() + 1,
+                self.epoch(),
+                checkpoints.len()
+            ));
+        }
+        for (epoch, checkpoint) in checkpoints.iter().enumerate() {
+            if checkpoint.epoch != epoch as u64 || checkpoint.end_of_epoch_data.is_none() {
+                return Err(anyhow!(
+                    "Checkpoint {} is not the last checkpoint of epoch {}",
+                    checkpoint.sequence_number,
+                    epoch
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn state_hash_commitment(checkpoint: &CheckpointSummary) -> Result<ECMHLiveObjectSetDigest> {
+    let end_of_epoch_data = checkpoint.end_of_epoch_data.as_ref().ok_or_else(|| {
+        anyhow!(
+            "Checkpoint {} is not the last checkpoint of epoch {}",
+            checkpoint.sequence_number,
+            checkpoint.epoch
+        )
+    })?;
+    match end_of_epoch_data.epoch_commitments.last() {
+        Some(CheckpointCommitment::ECMHLiveObjectSetDigest(digest)) => Ok(digest.clone()),
+        None => Err(anyhow!(
+            "End of epoch checkpoint {} has no state commitment",
+            checkpoint.sequence_number
+        )),
+    }
+}
+
+/// Writes the live object set of `perpetual_db` to a portable snapshot file at `path`, and returns
+/// the number of objects written. The database must hold the state as of the end of the last epoch
+/// in `end_of_epoch_checkpoints` (e.g. a db checkpoint taken at epoch change), which is verified
+/// against the state commitment in its last checkpoint before the file is made visible.
+pub fn write_portable_snapshot(
+    path: &Path,
+    perpetual_db: &AuthorityPerpetualTables,
+    end_of_epoch_checkpoints: Vec<CertifiedCheckpointSummary>,
+    include_wrapped_tombstone: bool,
+) -> Result<u64> {
+    let epoch = end_of_epoch_checkpoints
+        .last()
+        .context("No end of epoch checkpoint provided")?
+        .epoch;
+    let header = PortableSnapshotHeader::V1(PortableSnapshotHeaderV1 {
+        epoch,
+        include_wrapped_tombstone,
+        end_of_epoch_checkpoints,
+    });
+    header.validate()?;
+    let root_state_hash = header.root_state_hash()?;
+
+    let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+    let mut wbuf = BufWriter::new(File::create(&tmp_path)?);
+    wbuf.write_u32::<BigEndian>(PORTABLE_SNAPSHOT_FILE_MAGIC)?;
+    Blob::encode(&header, BlobEncoding::Bcs)?.write(&mut wbuf)?;
+
+    let mut acc = Accumulator::default();
+    let mut num_objects = 0u64;
+    for object in perpetual_db.iter_live_object_set(include_wrapped_tombstone) {
+        StateAccumulator::accumulate_live_object(&mut acc, &object);
+        Blob::encode(&object, BlobEncoding::Bcs)?.write(&mut wbuf)?;
+        num_objects += 1;
+    }
+    let local_digest = ECMHLiveObjectSetDigest::from(acc.digest());
+    if local_digest != root_state_hash {
+        drop(wbuf);
+        fs::remove_file(&tmp_path)?;
+        return Err(anyhow!(
+            "Live object set digest {} does not match end of epoch {} root state digest {}",
+            local_digest.digest,
+            header.epoch(),
+            root_state_hash.digest,
+        ));
+    }
+    wbuf.write_u64::<BigEndian>(num_objects)?;
+    wbuf.flush()?;
+    wbuf.get_ref().sync_data()?;
+    drop(wbuf);
+
+    let sha3_digest = compute_sha3_checksum(&tmp_path)?;
+    let mut f = OpenOptions::new().append(true).open(&tmp_path)?;
+    f.write_all(&sha3_digest)?;
+    f.sync_data()?;
+    drop(f);
+    fs::rename(&tmp_path, path)?;
+    info!(
+        "Wrote {} live objects at end of epoch {} to {}",
+        num_objects,
+        header.epoch(),
+        path.display()
+    );
+    Ok(num_objects)
+}
+
+/// PortableSnapshotReader validates a portable snapshot file and loads its objects into a database.
+pub struct PortableSnapshotReader {
+    path: PathBuf,
+    header: PortableSnapshotHeader,
+    objects_offset: u64,
+    objects_len: u64,
+    num_objects: u64,
+}
+
+impl PortableSnapshotReader {
+    /// Opens the file at `path`, verifying its checksum before anything else is read from it.
+    pub fn open(path: &Path) -> Result<Self> {
+        let mut file =
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let file_len = file.metadata()?.len();
+        if file_len < (MAGIC_BYTES + FOOTER_BYTES) as u64 {
+            return Err(anyhow!(
+                "File {} is too short to be a portable snapshot",
+                path.display()
+            ));
+        }
+
+        let mut hasher = Sha3_256::default();
+        io::copy(
+            &mut (&mut file).take(file_len - SHA3_BYTES as u64),
+            &mut hasher,
+        )?;
+        let mut sha3_digest = [0u8; SHA3_BYTES];
+        file.read_exact(&mut sha3_digest)?;
+        if hasher.finalize().digest != sha3_digest {
+            return Err(anyhow!(
+                "Checksum mismatch in portable snapshot file {}",
+                path.display()
+            ));
+        }
+
+        let objects_end = file_len - FOOTER_BYTES as u64;
+        file.seek(SeekFrom::Start(objects_end))?;
+        let num_objects = file.read_u64::<BigEndian>()?;
+
+        file.rewind()?;
+        let mut reader = BufReader::new(file);
+        let magic = reader.read_u32::<BigEndian>()?;
+        if magic != PORTABLE_SNAPSHOT_FILE_MAGIC {
+            return Err(anyhow!(
+                "Unexpected magic string in portable snapshot file: {:?}",
+                magic
+            ));
+        }
+        let header_blob = Blob::read(&mut reader)?;
+        let objects_offset = (MAGIC_BYTES + header_blob.size()) as u64;
+        if objects_offset > objects_end {
+            return Err(anyhow!("Portable snapshot header overlaps with the footer"));
+        }
+        let header: PortableSnapshotHeader = header_blob.decode()?;
+        header.validate()?;
+        Ok(PortableSnapshotReader {
+            path: path.to_path_buf(),
+            header,
+            objects_offset,
+            objects_len: objects_end - objects_offset,
+            num_objects,
+        })
+    }
+
+    pub fn header(&self) -> &PortableSnapshotHeader {
+        &self.header
+    }
+
+    pub fn num_objects(&self) -> u64 {
+        self.num_objects
+    }
+
+    /// Inserts all objects into `perpetual_db` and returns the accumulator of the imported live
+    /// object set, once it has been checked against the state commitment in the header.
+    pub fn import(
+        &self,
+        perpetual_db: &AuthorityPerpetualTables,
+        indirect_objects_threshold: usize,
+    ) -> Result<Accumulator> {
+        let root_state_hash = self.header.root_state_hash()?;
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.objects_offset))?;
+        let mut reader = BufReader::new(file).take(self.objects_len);
+
+        let mut acc = Accumulator::default();
+        let mut num_objects = 0u64;
+        let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+        while reader.limit() > 0 {
+            let object: LiveObject = Blob::read(&mut reader)?.decode()?;
+            StateAccumulator::accumulate_live_object(&mut acc, &object);
+            batch.push(object);
+            num_objects += 1;
+            if batch.len() == IMPORT_BATCH_SIZE {
+                Self::insert_batch(perpetual_db, &mut batch, indirect_objects_threshold)?;
+            }
+        }
+        Self::insert_batch(perpetual_db, &mut batch, indirect_objects_threshold)?;
+
+        if num_objects != self.num_objects {
+            return Err(anyhow!(
+                "Expected {} objects in portable snapshot but found {}",
+                self.num_objects,
+                num_objects
+            ));
+        }
+        let local_digest = ECMHLiveObjectSetDigest::from(acc.digest());
+        if local_digest != root_state_hash {
+            return Err(anyhow!(
+                "End of epoch {} root state digest {} does not match \
+                local root state hash {} computed from portable snapshot",
+                self.header.epoch(),
+                root_state_hash.digest,
+                local_digest.digest,
+            ));
+        }
+        Ok(acc)
+    }
+
+    fn insert_batch(
+        perpetual_db: &AuthorityPerpetualTables,
+        batch: &mut Vec<LiveObject>,
+        indirect_objects_threshold: usize,
+    ) -> Result<()> {
+        let mut hasher = Sha3_256::default();
+        for object in batch.iter() {
+            hasher.update(object.object_reference().2.inner());
+        }
+        let sha3_digest = hasher.finalize().digest;
+        AuthorityStore::bulk_insert_live_objects(
+            perpetual_db,
+            batch.drain(..),
+            indirect_objects_threshold,
+            &sha3_digest,
+        )?;
+        Ok(())
+    }
+}
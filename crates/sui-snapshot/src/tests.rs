@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::portable::{write_portable_snapshot, PortableSnapshotReader};
 use crate::reader::StateSnapshotReaderV1;
 use crate::writer::StateSnapshotWriterV1;
 use crate::FileCompression;
@@ -8,15 +9,21 @@ use fastcrypto::hash::MultisetHash;
 use futures::future::AbortHandle;
 use indicatif::MultiProgress;
 use std::collections::HashSet;
+use std::fs;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
 use sui_core::state_accumulator::StateAccumulator;
-use sui_protocol_config::ProtocolConfig;
+use sui_protocol_config::{ProtocolConfig, ProtocolVersion};
 use sui_types::accumulator::Accumulator;
 use sui_types::base_types::ObjectID;
-use sui_types::messages_checkpoint::ECMHLiveObjectSetDigest;
+use sui_types::committee::Committee;
+use sui_types::gas::GasCostSummary;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointCommitment, CheckpointContents, CheckpointSummary,
+    ECMHLiveObjectSetDigest, EndOfEpochData,
+};
 use sui_types::object::Object;
 use tempfile::tempdir;
 
@@ -68,6 +75,33 @@ fn accumulate_live_object_set(
     acc
 }
 
+fn end_of_epoch_checkpoint(
+    epoch: u64,
+    root_state_hash: ECMHLiveObjectSetDigest,
+) -> CertifiedCheckpointSummary {
+    let (committee, keypairs) = Committee::new_simple_test_committee();
+    let protocol_config = ProtocolConfig::get_for_max_version_UNSAFE();
+    let summary = CheckpointSummary::new(
+        &protocol_config,
+        epoch,
+        0,
+        0,
+        &CheckpointContents::new_with_digests_and_signatures(vec![], vec![]),
+        None,
+        GasCostSummary::default(),
+        Some(EndOfEpochData {
+            next_epoch_committee: committee.voting_rights.clone(),
+            next_epoch_protocol_version: ProtocolVersion::MAX,
+            epoch_commitments: vec![CheckpointCommitment::ECMHLiveObjectSetDigest(
+                root_state_hash,
+            )],
+        }),
+        0,
+        vec![],
+    );
+    CertifiedCheckpointSummary::new_from_keypairs_for_testing(summary, &keypairs, &committee)
+}
+
 #[tokio::test]
 async fn test_snapshot_basic() -> Result<(), anyhow::Error> {
     let db_path = temp_dir();
@@ -181,3 +215,64 @@ async fn test_snapshot_empty_db() -> Result<(), anyhow::Error> {
     )?;
     Ok(())
 }
+
+#[tokio::test]
+async fn test_portable_snapshot_roundtrip() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let restored_db_path = temp_dir();
+    let snapshot_file = temp_dir().join("epoch_0.snapshot");
+    let perpetual_db = AuthorityPerpetualTables::open(&db_path, None);
+    insert_keys(&perpetual_db, 1000)?;
+    let root_state_hash =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&perpetual_db, true).digest());
+    let num_objects = write_portable_snapshot(
+        &snapshot_file,
+        &perpetual_db,
+        vec![end_of_epoch_checkpoint(0, root_state_hash.clone())],
+        true,
+    )?;
+    assert_eq!(num_objects, 1000);
+
+    let reader = PortableSnapshotReader::open(&snapshot_file)?;
+    assert_eq!(reader.header().epoch(), 0);
+    assert_eq!(reader.num_objects(), 1000);
+    let restored_perpetual_db = AuthorityPerpetualTables::open(&restored_db_path, None);
+    let acc = reader.import(&restored_perpetual_db, usize::MAX)?;
+    assert_eq!(ECMHLiveObjectSetDigest::from(acc.digest()), root_state_hash);
+    compare_live_objects(&perpetual_db, &restored_perpetual_db, true)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_portable_snapshot_rejects_corruption() -> Result<(), anyhow::Error> {
+    let db_path = temp_dir();
+    let snapshot_file = temp_dir().join("epoch_0.snapshot");
+    let perpetual_db = AuthorityPerpetualTables::open(&db_path, None);
+    insert_keys(&perpetual_db, 10)?;
+
+    // A checkpoint that commits to a different state must not produce a file.
+    let wrong_hash = ECMHLiveObjectSetDigest::from(Accumulator::default().digest());
+    assert!(write_portable_snapshot(
+        &snapshot_file,
+        &perpetual_db,
+        vec![end_of_epoch_checkpoint(0, wrong_hash)],
+        true,
+    )
+    .is_err());
+    assert!(!snapshot_file.exists());
+
+    let root_state_hash =
+        ECMHLiveObjectSetDigest::from(accumulate_live_object_set(&perpetual_db, true).digest());
+    write_portable_snapshot(
+        &snapshot_file,
+        &perpetual_db,
+        vec![end_of_epoch_checkpoint(0, root_state_hash)],
+        true,
+    )?;
+    let mut bytes = fs::read(&snapshot_file)?;
+    let mid = bytes.len() / 2;
+    bytes[mid] ^= 0xff;
+    fs::write(&snapshot_file, bytes)?;
+    assert!(PortableSnapshotReader::open(&snapshot_file).is_err());
+    Ok(())
+}
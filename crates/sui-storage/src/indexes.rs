@@ -30,7 +30,7 @@ use sui_types::effects::TransactionEvents;
 use sui_types::error::{SuiError, SuiResult, UserInputError};
 use sui_types::inner_temporary_store::TxCoins;
 use sui_types::object::{Object, Owner};
-use sui_types::parse_sui_struct_tag;
+use sui_types::{parse_sui_struct_tag, parse_sui_type_tag};
 use tokio::task::spawn_blocking;
 use tracing::{debug, trace};
 use typed_store::rocks::{
@@ -223,6 +223,10 @@ impl IndexStoreTables {
     pub fn coin_index(&self) -> &DBMap<CoinIndexKey, CoinInfo> {
         &self.coin_index
     }
+
+    pub fn dynamic_field_index(&self) -> &DBMap<DynamicFieldKey, DynamicFieldInfo> {
+        &self.dynamic_field_index
+    }
 }
 
 pub struct IndexStore {
@@ -1303,6 +1307,60 @@ impl IndexStore {
         Ok(())
     }
 
+    /// Overwrites or removes individual owner, coin and dynamic field index entries, as computed
+    /// by index repair. Cached balances of every address whose coins changed are invalidated.
+    pub async fn repair_object_indexes(
+        &self,
+        object_index_changes: ObjectIndexChanges,
+        deleted_coins: Vec<CoinIndexKey>,
+        new_coins: Vec<(CoinIndexKey, CoinInfo)>,
+    ) -> SuiResult {
+        let coin_keys: HashSet<(SuiAddress, String)> = deleted_coins
+            .iter()
+            .chain(new_coins.iter().map(|(key, _)| key))
+            .map(|(owner, coin_type, _)| (*owner, coin_type.clone()))
+            .collect();
+        let addresses: HashSet<SuiAddress> = coin_keys.iter().map(|(owner, _)| *owner).collect();
+        // Hold the same locks as transaction indexing so that balances are not recomputed from a
+        // half-repaired coin index.
+        let _locks = self
+            .caches
+            .locks
+            .acquire_locks(addresses.iter().cloned())
+            .await;
+
+        let mut batch = self.tables.owner_index.batch();
+        batch.delete_batch(
+            &self.tables.owner_index,
+            object_index_changes.deleted_owners.into_iter(),
+        )?;
+        batch.delete_batch(
+            &self.tables.dynamic_field_index,
+            object_index_changes.deleted_dynamic_fields.into_iter(),
+        )?;
+        batch.delete_batch(&self.tables.coin_index, deleted_coins.into_iter())?;
+        batch.insert_batch(
+            &self.tables.owner_index,
+            object_index_changes.new_owners.into_iter(),
+        )?;
+        batch.insert_batch(
+            &self.tables.dynamic_field_index,
+            object_index_changes.new_dynamic_fields.into_iter(),
+        )?;
+        batch.insert_batch(&self.tables.coin_index, new_coins.into_iter())?;
+        batch.write()?;
+
+        self.invalidate_per_coin_type_cache(coin_keys.into_iter().filter_map(
+            |(owner, coin_type)| {
+                parse_sui_type_tag(&coin_type)
+                    .ok()
+                    .map(|type_tag| (owner, type_tag))
+            },
+        ))
+        .await?;
+        self.invalidate_all_balance_cache(addresses).await
+    }
+
     pub fn is_empty(&self) -> bool {
         self.tables.owner_index.is_empty()
     }
@@ -82,6 +82,10 @@ impl WritePathPendingTransactionLog {
         write_batch.write().map_err(SuiError::from)
     }
 
+    pub fn is_pending(&self, tx: &TransactionDigest) -> SuiResult<bool> {
+        Ok(self.pending_transactions.logs.contains_key(tx)?)
+    }
+
     pub fn load_all_pending_transactions(&self) -> Vec<VerifiedTransaction> {
         self.pending_transactions
             .logs
@@ -116,10 +120,12 @@ mod tests {
 
         let loaded_txes = pending_txes.load_all_pending_transactions();
         assert_eq!(vec![tx], loaded_txes);
+        assert!(pending_txes.is_pending(&tx_digest).unwrap());
 
         pending_txes.finish_transaction(&tx_digest).unwrap();
         let loaded_txes = pending_txes.load_all_pending_transactions();
         assert!(loaded_txes.is_empty());
+        assert!(!pending_txes.is_pending(&tx_digest).unwrap());
 
         // It's ok to finish an already finished transaction
         pending_txes.finish_transaction(&tx_digest).unwrap();
@@ -243,6 +243,7 @@ impl ValidatorConfigBuilder {
             enable_validator_tx_finalizer: true,
             verifier_signing_config: VerifierSigningConfig::default(),
             enable_db_write_stall: None,
+            historical_data_fallback_rpc_url: None,
         }
     }
 
@@ -540,6 +541,7 @@ impl FullnodeConfigBuilder {
             enable_validator_tx_finalizer: false,
             verifier_signing_config: VerifierSigningConfig::default(),
             enable_db_write_stall: None,
+            historical_data_fallback_rpc_url: None,
         }
     }
 }
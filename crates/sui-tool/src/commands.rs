@@ -5,11 +5,14 @@ use crate::{
     check_completed_snapshot,
     db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand},
     download_db_snapshot, download_formal_snapshot, dump_checkpoints_from_archive,
-    get_latest_available_epoch, get_object, get_transaction_block, make_clients,
+    export_state_snapshot, get_latest_available_epoch, get_object, get_transaction_block,
+    import_state_snapshot, inspect_consensus_dag, make_clients, replay_consensus,
     restore_from_db_checkpoint, verify_archive, verify_archive_by_checksum, ConciseObjectOutput,
-    GroupedObjectOutput, SnapshotVerifyMode, VerboseObjectOutput,
+    ConsensusDagFormat, ConsensusDagRange, GroupedObjectOutput, SnapshotVerifyMode,
+    VerboseObjectOutput,
 };
 use anyhow::Result;
+use consensus_core::{CommitIndex, Round};
 use futures::{future::join_all, StreamExt};
 use std::path::PathBuf;
 use std::{collections::BTreeMap, env, sync::Arc};
@@ -378,6 +381,92 @@ pub enum ToolCommand {
         all_checkpoints: bool,
     },
 
+    #[clap(
+        name = "export-state-snapshot",
+        about = "Exports the live object set at the end of an epoch from a local db into a single file"
+    )]
+    ExportStateSnapshot {
+        /// Path to the db holding the end of epoch state, e.g. a db checkpoint taken at
+        /// epoch change. Must contain the `store` and `checkpoints` tables.
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        #[clap(long = "epoch")]
+        epoch: u64,
+        /// File to write the snapshot to
+        #[clap(long = "output")]
+        output: PathBuf,
+    },
+
+    #[clap(
+        name = "import-state-snapshot",
+        about = "Restores a db from a file written by `export-state-snapshot`, outputs to local disk"
+    )]
+    ImportStateSnapshot {
+        #[clap(long = "snapshot-file")]
+        snapshot_file: PathBuf,
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+        #[clap(long = "path")]
+        path: PathBuf,
+        /// Verification mode to employ. The file checksum and the live object set are always
+        /// checked, `normal` additionally verifies the end of epoch checkpoint signatures.
+        #[clap(long = "verify", default_value = "normal")]
+        verify: Option<SnapshotVerifyMode>,
+        /// Network the snapshot was taken from. Defaults to "mainnet".
+        #[clap(long = "network", default_value = "mainnet")]
+        network: Chain,
+    },
+
+    #[clap(
+        name = "replay-consensus",
+        about = "Replays the blocks in the consensus db of the current epoch through the commit rule, \
+        and compares the resulting commits with the recorded ones"
+    )]
+    ReplayConsensus {
+        /// Path to the validator config, used to locate the dbs and the own authority
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+        /// Consensus parameters to replay with, as a yaml file. Defaults to the parameters in
+        /// the validator config.
+        #[clap(long = "parameters")]
+        parameters: Option<PathBuf>,
+        /// Overrides the number of leaders per round of the epoch protocol config
+        #[clap(long = "num-leaders-per-round")]
+        num_leaders_per_round: Option<usize>,
+        /// Overrides the number of commits after which the leader schedule is updated
+        #[clap(long = "commits-per-schedule")]
+        commits_per_schedule: Option<u64>,
+    },
+
+    #[clap(
+        name = "inspect-consensus-dag",
+        about = "Renders a range of the DAG in the consensus db of the current epoch, with the \
+        decisions on its leaders"
+    )]
+    InspectConsensusDag {
+        /// Path to the validator config, used to locate the dbs
+        #[clap(long = "config-path")]
+        config_path: PathBuf,
+        #[clap(
+            long = "start-round",
+            requires = "end_round",
+            conflicts_with = "start_commit"
+        )]
+        start_round: Option<Round>,
+        #[clap(long = "end-round", requires = "start_round")]
+        end_round: Option<Round>,
+        /// Inspects the rounds of the leaders of a range of commits, instead of a range of rounds
+        #[clap(long = "start-commit", requires = "end_commit")]
+        start_commit: Option<CommitIndex>,
+        #[clap(long = "end-commit", requires = "start_commit")]
+        end_commit: Option<CommitIndex>,
+        #[clap(long = "format", value_enum, default_value = "text")]
+        format: ConsensusDagFormat,
+        /// File to write the rendering to. Defaults to stdout.
+        #[clap(long = "output")]
+        output: Option<PathBuf>,
+    },
+
     #[clap(name = "replay")]
     Replay {
         #[arg(long = "rpc")]
@@ -881,6 +970,52 @@ impl ToolCommand {
                 )
                 .await?;
             }
+            ToolCommand::ExportStateSnapshot {
+                db_path,
+                epoch,
+                output,
+            } => {
+                export_state_snapshot(&db_path, epoch, &output).await?;
+            }
+            ToolCommand::ImportStateSnapshot {
+                snapshot_file,
+                genesis,
+                path,
+                verify,
+                network,
+            } => {
+                let verify = verify.unwrap_or_default();
+                import_state_snapshot(&path, &snapshot_file, &genesis, network, verify).await?;
+            }
+            ToolCommand::ReplayConsensus {
+                config_path,
+                parameters,
+                num_leaders_per_round,
+                commits_per_schedule,
+            } => {
+                replay_consensus(
+                    &config_path,
+                    parameters.as_deref(),
+                    num_leaders_per_round,
+                    commits_per_schedule,
+                )?;
+            }
+            ToolCommand::InspectConsensusDag {
+                config_path,
+                start_round,
+                end_round,
+                start_commit,
+                end_commit,
+                format,
+                output,
+            } => {
+                let range = match (start_round, end_round, start_commit, end_commit) {
+                    (Some(start), Some(end), None, None) => ConsensusDagRange::Rounds(start..=end),
+                    (None, None, Some(start), Some(end)) => ConsensusDagRange::Commits(start..=end),
+                    _ => anyhow::bail!("Either a round range or a commit range must be given"),
+                };
+                inspect_consensus_dag(&config_path, range, format, output.as_deref())?;
+            }
             ToolCommand::DownloadDBSnapshot {
                 epoch,
                 path,
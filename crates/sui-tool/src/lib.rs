@@ -3,15 +3,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use fastcrypto::traits::ToFromBytes;
+use consensus_config::{Parameters as ConsensusParameters, ProtocolPublicKey};
+use consensus_core::{CommitIndex, ConsensusReplayer, DagInspector, Round};
+use fastcrypto::traits::{KeyPair as _, ToFromBytes};
 use futures::future::join_all;
 use futures::future::AbortHandle;
 use itertools::Itertools;
 use std::collections::BTreeMap;
 use std::fmt::Write;
+use std::io::Write as _;
 use std::num::NonZeroUsize;
-use std::ops::Range;
+use std::ops::{Range, RangeInclusive};
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
@@ -20,7 +24,7 @@ use sui_config::{genesis::Genesis, NodeConfig};
 use sui_core::authority_client::{AuthorityAPI, NetworkAuthorityClient};
 use sui_core::execution_cache::build_execution_cache_from_env;
 use sui_network::default_mysten_network_config;
-use sui_protocol_config::Chain;
+use sui_protocol_config::{Chain, ProtocolConfig};
 use sui_sdk::SuiClient;
 use sui_sdk::SuiClientBuilder;
 use sui_storage::object_store::http::HttpDownloaderBuilder;
@@ -28,16 +32,17 @@ use sui_storage::object_store::util::Manifest;
 use sui_storage::object_store::util::PerEpochManifest;
 use sui_storage::object_store::util::MANIFEST_FILENAME;
 use sui_types::accumulator::Accumulator;
-use sui_types::committee::QUORUM_THRESHOLD;
+use sui_types::committee::{Committee, QUORUM_THRESHOLD};
 use sui_types::crypto::AuthorityPublicKeyBytes;
 use sui_types::messages_grpc::LayoutGenerationOption;
 use sui_types::multiaddr::Multiaddr;
+use sui_types::sui_system_state::epoch_start_sui_system_state::EpochStartSystemStateTrait;
 use sui_types::{base_types::*, object::Owner};
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tokio::time::Instant;
 
-use anyhow::anyhow;
+use anyhow::{anyhow, bail};
 use clap::ValueEnum;
 use eyre::ContextCompat;
 use fastcrypto::hash::MultisetHash;
@@ -50,16 +55,21 @@ use sui_archival::{verify_archive_with_checksums, verify_archive_with_genesis_co
 use sui_config::node::ArchiveReaderConfig;
 use sui_config::object_storage_config::{ObjectStoreConfig, ObjectStoreType};
 use sui_core::authority::authority_store_tables::AuthorityPerpetualTables;
+use sui_core::authority::epoch_start_configuration::EpochStartConfigTrait;
 use sui_core::authority::AuthorityStore;
 use sui_core::checkpoints::CheckpointStore;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_core::storage::RocksDbStore;
+use sui_snapshot::portable::{write_portable_snapshot, PortableSnapshotReader};
 use sui_snapshot::reader::StateSnapshotReaderV1;
 use sui_snapshot::setup_db_state;
 use sui_storage::object_store::util::{copy_file, exists, get_path};
 use sui_storage::object_store::ObjectStoreGetExt;
 use sui_storage::verify_checkpoint_range;
-use sui_types::messages_checkpoint::{CheckpointCommitment, ECMHLiveObjectSetDigest};
+use sui_types::digests::ChainIdentifier;
+use sui_types::messages_checkpoint::{
+    CheckpointCommitment, ECMHLiveObjectSetDigest, VerifiedCheckpoint,
+};
 use sui_types::messages_grpc::{
     ObjectInfoRequest, ObjectInfoRequestKind, ObjectInfoResponse, TransactionInfoRequest,
     TransactionStatus,
@@ -998,6 +1008,338 @@ pub async fn download_formal_snapshot(
     Ok(())
 }
 
+pub async fn export_state_snapshot(
+    db_path: &Path,
+    epoch: EpochId,
+    output: &Path,
+) -> Result<(), anyhow::Error> {
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&db_path.join("store"), None));
+    let checkpoint_store = CheckpointStore::open_tables_read_write(
+        db_path.join("checkpoints"),
+        MetricConf::default(),
+        None,
+        None,
+    );
+    let mut end_of_epoch_checkpoints = Vec::with_capacity(epoch as usize + 1);
+    for e in 0..=epoch {
+        let checkpoint = checkpoint_store
+            .get_epoch_last_checkpoint(e)?
+            .ok_or(anyhow!("Last checkpoint of epoch {} not found in db", e))?;
+        end_of_epoch_checkpoints.push(checkpoint.into_inner());
+    }
+    let genesis_checkpoint = checkpoint_store
+        .get_checkpoint_by_sequence_number(0)?
+        .ok_or(anyhow!("Genesis checkpoint not found in db"))?;
+    let chain = ChainIdentifier::from(*genesis_checkpoint.digest()).chain();
+    let next_epoch_protocol_version = end_of_epoch_checkpoints
+        .last()
+        .and_then(|checkpoint| checkpoint.end_of_epoch_data.as_ref())
+        .map(|data| data.next_epoch_protocol_version)
+        .ok_or(anyhow!("Missing end of epoch data for epoch {}", epoch))?;
+    let protocol_config = ProtocolConfig::get_for_version(next_epoch_protocol_version, chain);
+    let include_wrapped_tombstone = !protocol_config.simplified_unwrap_then_delete();
+
+    let output = output.to_path_buf();
+    let num_objects = tokio::task::spawn_blocking(move || {
+        write_portable_snapshot(
+            &output,
+            &perpetual_db,
+            end_of_epoch_checkpoints,
+            include_wrapped_tombstone,
+        )
+    })
+    .await??;
+    println!(
+        "Exported {} live objects at end of epoch {}",
+        num_objects, epoch
+    );
+    Ok(())
+}
+
+pub async fn import_state_snapshot(
+    path: &Path,
+    snapshot_file: &Path,
+    genesis: &Path,
+    network: Chain,
+    verify: SnapshotVerifyMode,
+) -> Result<(), anyhow::Error> {
+    let reader = PortableSnapshotReader::open(snapshot_file)?;
+    let epoch = reader.header().epoch();
+    println!(
+        "Importing {} live objects at end of epoch {}, network: {:?}, verification mode: {:?}",
+        reader.num_objects(),
+        epoch,
+        network,
+        verify,
+    );
+    let path = path.join("staging").to_path_buf();
+    if path.exists() {
+        fs::remove_dir_all(path.clone())?;
+    }
+    let perpetual_db = Arc::new(AuthorityPerpetualTables::open(&path.join("store"), None));
+    let genesis = Genesis::load(genesis).unwrap();
+    let genesis_committee = genesis.committee()?;
+    let committee_store = Arc::new(CommitteeStore::new(
+        path.join("epochs"),
+        &genesis_committee,
+        None,
+    ));
+    let checkpoint_store = Arc::new(CheckpointStore::open_tables_read_write(
+        path.join("checkpoints"),
+        MetricConf::default(),
+        None,
+        None,
+    ));
+    checkpoint_store.insert_checkpoint_contents(genesis.checkpoint_contents().clone())?;
+    checkpoint_store.insert_verified_checkpoint(&genesis.checkpoint())?;
+    checkpoint_store.update_highest_synced_checkpoint(&genesis.checkpoint())?;
+
+    // Walk the committee chain from genesis, the same way end of epoch checkpoints are verified
+    // when restoring from a formal snapshot without `--all-checkpoints`.
+    let mut committee = genesis_committee;
+    for checkpoint in reader.header().end_of_epoch_checkpoints() {
+        if verify != SnapshotVerifyMode::None {
+            checkpoint.verify_authority_signatures(&committee)?;
+        }
+        let checkpoint = VerifiedCheckpoint::new_unchecked(checkpoint.clone());
+        let next_epoch_committee = checkpoint
+            .next_epoch_committee()
+            .ok_or(anyhow!("Expected end of epoch checkpoint"))?;
+        committee = Committee::new(
+            checkpoint.epoch + 1,
+            next_epoch_committee.iter().cloned().collect(),
+        );
+        committee_store.insert_new_committee(&committee)?;
+        checkpoint_store.insert_verified_checkpoint(&checkpoint)?;
+        checkpoint_store.insert_epoch_last_checkpoint(checkpoint.epoch, &checkpoint)?;
+    }
+    if verify == SnapshotVerifyMode::None {
+        println!(
+            "WARNING: Skipping checkpoint signature verification! \
+            This is highly discouraged unless you fully trust the source of this snapshot file."
+        );
+    }
+
+    let last_checkpoint =
+        VerifiedCheckpoint::new_unchecked(reader.header().last_checkpoint().clone());
+    let num_live_objects = reader.num_objects();
+    let perpetual_db_clone = perpetual_db.clone();
+    let root_accumulator =
+        tokio::task::spawn_blocking(move || reader.import(&perpetual_db_clone, usize::MAX))
+            .await??;
+
+    checkpoint_store.update_highest_synced_checkpoint(&last_checkpoint)?;
+    checkpoint_store.update_highest_executed_checkpoint(&last_checkpoint)?;
+    checkpoint_store.update_highest_pruned_checkpoint(&last_checkpoint)?;
+
+    setup_db_state(
+        epoch,
+        root_accumulator,
+        perpetual_db.clone(),
+        checkpoint_store,
+        committee_store,
+        network,
+        verify == SnapshotVerifyMode::Strict,
+        num_live_objects,
+        MultiProgress::new(),
+    )
+    .await?;
+
+    let new_path = path.parent().unwrap().join("live");
+    if new_path.exists() {
+        fs::remove_dir_all(new_path.clone())?;
+    }
+    fs::rename(&path, &new_path)?;
+    println!(
+        "Successfully imported state snapshot at end of epoch {}",
+        epoch
+    );
+    Ok(())
+}
+
+/// Replays the blocks in the consensus db of the current epoch with the given overrides, and
+/// prints how the resulting commits differ from the ones recorded by the validator.
+pub fn replay_consensus(
+    config_path: &Path,
+    parameters_path: Option<&Path>,
+    num_leaders_per_round: Option<usize>,
+    commits_per_schedule: Option<u64>,
+) -> Result<(), anyhow::Error> {
+    let config = NodeConfig::load(config_path)?;
+    let consensus_config = config.consensus_config().ok_or(anyhow!(
+        "Consensus config not found, the node must be a validator"
+    ))?;
+    let db_path = config.db_path();
+    let perpetual_db = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    let epoch_start_configuration = perpetual_db
+        .get_epoch_start_configuration()?
+        .ok_or(anyhow!("Epoch start configuration not found in db"))?;
+    let epoch_start_state = epoch_start_configuration.epoch_start_state();
+    let epoch = epoch_start_state.epoch();
+
+    let checkpoint_store = CheckpointStore::open_tables_read_write(
+        db_path.join("checkpoints"),
+        MetricConf::default(),
+        None,
+        None,
+    );
+    let genesis_checkpoint = checkpoint_store
+        .get_checkpoint_by_sequence_number(0)?
+        .ok_or(anyhow!("Genesis checkpoint not found in db"))?;
+    let chain = ChainIdentifier::from(*genesis_checkpoint.digest()).chain();
+    let mut protocol_config =
+        ProtocolConfig::get_for_version(epoch_start_state.protocol_version(), chain);
+    if let Some(num_leaders_per_round) = num_leaders_per_round {
+        protocol_config
+            .set_mysticeti_num_leaders_per_round_for_testing(Some(num_leaders_per_round));
+    }
+
+    let committee = epoch_start_state.get_consensus_committee();
+    let own_protocol_key = ProtocolPublicKey::new(config.worker_key_pair().public().clone());
+    let (own_index, _) = committee
+        .authorities()
+        .find(|(_, authority)| authority.protocol_key == own_protocol_key)
+        .ok_or(anyhow!(
+            "Own authority not found in the committee of epoch {}",
+            epoch
+        ))?;
+
+    let consensus_db_path = consensus_config.db_path().join(epoch.to_string());
+    let parameters = match parameters_path {
+        Some(path) => serde_yaml::from_reader(fs::File::open(path)?)?,
+        None => consensus_config.parameters.clone().unwrap_or_default(),
+    };
+    let parameters = ConsensusParameters {
+        db_path: consensus_db_path.clone(),
+        ..parameters
+    };
+
+    let mut replayer = ConsensusReplayer::new(own_index, committee, parameters, protocol_config);
+    if let Some(commits_per_schedule) = commits_per_schedule {
+        replayer = replayer.with_num_commits_per_schedule(commits_per_schedule);
+    }
+    println!(
+        "Replaying consensus db of epoch {} at {}",
+        epoch,
+        consensus_db_path.display()
+    );
+    let report = replayer.replay_store(&consensus_db_path)?;
+    println!("{}", report);
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ConsensusDagFormat {
+    /// The DAG format understood by the consensus test DAG parser
+    Text,
+    Dot,
+    /// Rendered by the `dot` command of Graphviz, which must be installed
+    Svg,
+    Json,
+}
+
+/// Part of the consensus DAG to inspect.
+pub enum ConsensusDagRange {
+    Rounds(RangeInclusive<Round>),
+    /// The rounds spanned by the leaders of these commits.
+    Commits(RangeInclusive<CommitIndex>),
+}
+
+/// Renders a range of the DAG in the consensus db of the current epoch, with the decisions on
+/// the leaders of its rounds.
+pub fn inspect_consensus_dag(
+    config_path: &Path,
+    range: ConsensusDagRange,
+    format: ConsensusDagFormat,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let config = NodeConfig::load(config_path)?;
+    let consensus_config = config.consensus_config().ok_or(anyhow!(
+        "Consensus config not found, the node must be a validator"
+    ))?;
+    let db_path = config.db_path();
+    let perpetual_db = AuthorityPerpetualTables::open(&db_path.join("store"), None);
+    let epoch_start_configuration = perpetual_db
+        .get_epoch_start_configuration()?
+        .ok_or(anyhow!("Epoch start configuration not found in db"))?;
+    let epoch_start_state = epoch_start_configuration.epoch_start_state();
+
+    let checkpoint_store = CheckpointStore::open_tables_read_write(
+        db_path.join("checkpoints"),
+        MetricConf::default(),
+        None,
+        None,
+    );
+    let genesis_checkpoint = checkpoint_store
+        .get_checkpoint_by_sequence_number(0)?
+        .ok_or(anyhow!("Genesis checkpoint not found in db"))?;
+    let chain = ChainIdentifier::from(*genesis_checkpoint.digest()).chain();
+    let protocol_config =
+        ProtocolConfig::get_for_version(epoch_start_state.protocol_version(), chain);
+
+    let consensus_db_path = consensus_config
+        .db_path()
+        .join(epoch_start_state.epoch().to_string());
+    let inspector = DagInspector::new(
+        epoch_start_state.get_consensus_committee(),
+        protocol_config,
+        &consensus_db_path,
+    );
+    let view = match range {
+        ConsensusDagRange::Rounds(rounds) => {
+            if rounds.is_empty() {
+                bail!(
+                    "Start round {} is after end round {}",
+                    rounds.start(),
+                    rounds.end()
+                );
+            }
+            inspector.inspect(rounds)?
+        }
+        ConsensusDagRange::Commits(commits) => {
+            if commits.is_empty() {
+                bail!(
+                    "Start commit {} is after end commit {}",
+                    commits.start(),
+                    commits.end()
+                );
+            }
+            inspector.inspect_commits(commits)?
+        }
+    };
+
+    let rendered = match format {
+        ConsensusDagFormat::Text => view.to_string().into_bytes(),
+        ConsensusDagFormat::Dot => view.to_dot().into_bytes(),
+        ConsensusDagFormat::Svg => dot_to_svg(&view.to_dot())?,
+        ConsensusDagFormat::Json => serde_json::to_vec_pretty(&view)?,
+    };
+    match output {
+        Some(path) => fs::write(path, rendered)?,
+        None => io::stdout().write_all(&rendered)?,
+    }
+    Ok(())
+}
+
+fn dot_to_svg(dot: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let mut child = Command::new("dot")
+        .arg("-Tsvg")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Failed to run Graphviz `dot`, is it installed? {}", e))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(dot.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("Graphviz `dot` exited with {}", output.status);
+    }
+    Ok(output.stdout)
+}
+
 pub async fn download_db_snapshot(
     path: &Path,
     epoch: u64,
@@ -34,6 +34,10 @@ use sui_types::transaction::{
 };
 use sui_types::{coin, fp_ensure, SUI_FRAMEWORK_PACKAGE_ID, SUI_SYSTEM_PACKAGE_ID};
 
+use crate::move_registry::LocalMoveRegistry;
+
+pub mod move_registry;
+
 #[async_trait]
 pub trait DataReader {
     async fn get_owned_objects(
@@ -52,11 +56,42 @@ pub trait DataReader {
 }
 
 #[derive(Clone)]
-pub struct TransactionBuilder(Arc<dyn DataReader + Sync + Send>);
+pub struct TransactionBuilder(
+    Arc<dyn DataReader + Sync + Send>,
+    Option<Arc<LocalMoveRegistry>>,
+);
 
 impl TransactionBuilder {
     pub fn new(data_reader: Arc<dyn DataReader + Sync + Send>) -> Self {
-        Self(data_reader)
+        Self(data_reader, None)
+    }
+
+    /// Resolve Move Registry names (e.g. `@org/app`) in the type arguments of Move calls, and in
+    /// [`Self::resolve_package_name`], using `registry`.
+    pub fn with_move_registry(self, registry: LocalMoveRegistry) -> Self {
+        Self(self.0, Some(Arc::new(registry)))
+    }
+
+    /// The ID of the package that the Move Registry name `name` refers to, for use as the package
+    /// of a Move call. Fails if the builder has no registry, or `name` is not in it.
+    pub fn resolve_package_name(&self, name: &str) -> anyhow::Result<ObjectID> {
+        let Some(registry) = &self.1 else {
+            bail!("Cannot resolve Move Registry name {name} without a registry");
+        };
+
+        Ok(registry.address_for(name)?.into())
+    }
+
+    /// Parse `type_args`, replacing any Move Registry names in them with package addresses first,
+    /// if the builder has a registry.
+    fn resolve_type_args(&self, type_args: Vec<SuiTypeTag>) -> anyhow::Result<Vec<TypeTag>> {
+        type_args
+            .into_iter()
+            .map(|ty| match &self.1 {
+                Some(registry) => SuiTypeTag::new(registry.replace_names(ty.as_str())?).try_into(),
+                None => ty.try_into(),
+            })
+            .collect()
     }
 
     async fn select_gas(
@@ -440,10 +475,7 @@ impl TransactionBuilder {
         let module = Identifier::from_str(module)?;
         let function = Identifier::from_str(function)?;
 
-        let type_args = type_args
-            .into_iter()
-            .map(|ty| ty.try_into())
-            .collect::<Result<Vec<_>, _>>()?;
+        let type_args = self.resolve_type_args(type_args)?;
 
         let call_args = self
             .resolve_and_checks_json_args(
@@ -1083,3 +1115,76 @@ impl TransactionBuilder {
         Ok((object.object_ref(), object.object_type()?))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APP: &str = "0x0000000000000000000000000000000000000000000000000000000000000a01";
+
+    struct NoData;
+
+    #[async_trait]
+    impl DataReader for NoData {
+        async fn get_owned_objects(
+            &self,
+            _address: SuiAddress,
+            _object_type: StructTag,
+        ) -> Result<Vec<ObjectInfo>, anyhow::Error> {
+            unimplemented!()
+        }
+
+        async fn get_object_with_options(
+            &self,
+            _object_id: ObjectID,
+            _options: SuiObjectDataOptions,
+        ) -> Result<SuiObjectResponse, anyhow::Error> {
+            unimplemented!()
+        }
+
+        async fn get_reference_gas_price(&self) -> Result<u64, anyhow::Error> {
+            unimplemented!()
+        }
+    }
+
+    fn builder() -> TransactionBuilder {
+        let registry =
+            LocalMoveRegistry::from_toml(&format!("[packages]\n\"@org/app\" = \"{APP}\"")).unwrap();
+        TransactionBuilder::new(Arc::new(NoData)).with_move_registry(registry)
+    }
+
+    #[test]
+    fn resolve_package_names() {
+        assert_eq!(
+            builder().resolve_package_name("@org/app").unwrap(),
+            ObjectID::from_str(APP).unwrap(),
+        );
+
+        assert!(builder().resolve_package_name("@org/other").is_err());
+        assert!(TransactionBuilder::new(Arc::new(NoData))
+            .resolve_package_name("@org/app")
+            .is_err());
+    }
+
+    #[test]
+    fn resolve_type_arg_names() {
+        let type_args = builder()
+            .resolve_type_args(vec![
+                SuiTypeTag::new("@org/app::m::T<vector<@org/app::m::U>>".to_owned()),
+                SuiTypeTag::new("u64".to_owned()),
+            ])
+            .unwrap();
+
+        assert_eq!(
+            type_args,
+            vec![
+                TypeTag::from_str(&format!("{APP}::m::T<vector<{APP}::m::U>>")).unwrap(),
+                TypeTag::U64,
+            ],
+        );
+
+        assert!(builder()
+            .resolve_type_args(vec![SuiTypeTag::new("@org/other::m::T".to_owned())])
+            .is_err());
+    }
+}
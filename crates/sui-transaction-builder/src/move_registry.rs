@@ -0,0 +1,201 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Resolution of Move Registry (MVR) names, like `@org/app` or `@org/app/2`, to package addresses
+//! using a registry read from a local TOML file, rather than from the on-chain registry. This is
+//! meant for testing named packages on networks where the registry is not deployed (e.g. localnet).
+//!
+//! The file maps names to package addresses:
+//!
+//! ```toml
+//! [packages]
+//! "@org/app" = "0x..."
+//! "@org/app/1" = "0x..."
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use sui_types::base_types::SuiAddress;
+
+/// Matches a name in the format `@org/app` or `@org/app/1` (with an optional version), possibly
+/// preceded by sub-domains of the organization (`sub@org/app`).
+const NAME_PATTERN: &str = r"[a-z0-9.\-]*@[a-z0-9\-]+/[a-z0-9\-]+(?:/\d+)?";
+
+/// Detects all the names in a string (e.g. a type or a Move call target).
+static NAME_UNBOUND_REG: Lazy<Regex> = Lazy::new(|| Regex::new(NAME_PATTERN).unwrap());
+
+/// Detects a string that is a single name.
+static NAME_REG: Lazy<Regex> = Lazy::new(|| Regex::new(&format!("^{NAME_PATTERN}$")).unwrap());
+
+/// Detects a name at the start of a string.
+static NAME_PREFIX_REG: Lazy<Regex> =
+    Lazy::new(|| Regex::new(&format!("^{NAME_PATTERN}")).unwrap());
+
+/// The name at the start of `s`, if `s` starts with one (e.g. the `@org/app` in
+/// `@org/app::module::function`).
+pub fn name_prefix(s: &str) -> Option<&str> {
+    NAME_PREFIX_REG.find(s).map(|m| m.as_str())
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+pub struct LocalMoveRegistry {
+    /// Package addresses, keyed by name. A versioned name refers to that version of the package
+    /// exactly, while an unversioned name refers to the package it is resolved against.
+    #[serde(default)]
+    packages: BTreeMap<String, SuiAddress>,
+}
+
+impl LocalMoveRegistry {
+    /// Read the registry from the TOML file at `path`.
+    pub fn from_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read Move Registry from {}", path.display()))?;
+
+        Self::from_toml(&contents)
+            .with_context(|| format!("Failed to parse Move Registry from {}", path.display()))
+    }
+
+    pub fn from_toml(contents: &str) -> anyhow::Result<Self> {
+        let registry: Self = toml::from_str(contents)?;
+        if let Some(name) = registry.packages.keys().find(|n| !NAME_REG.is_match(n)) {
+            bail!("Invalid Move Registry name: {name}");
+        }
+
+        Ok(registry)
+    }
+
+    /// Resolve `name` to a package address. If there is an entry for `name` itself, its address
+    /// is returned, with no version. Otherwise, if `name` is versioned, the address of its
+    /// unversioned entry is returned alongside the version, which remains to be resolved against
+    /// the package at that address.
+    pub fn resolve(&self, name: &str) -> Option<(SuiAddress, Option<u64>)> {
+        if let Some(address) = self.packages.get(name) {
+            return Some((*address, None));
+        }
+
+        let (unversioned, version) = name.rsplit_once('/')?;
+        let version = version.parse().ok()?;
+        let address = self.packages.get(unversioned)?;
+        Some((*address, Some(version)))
+    }
+
+    /// Replace every name in `s` (e.g. a type, or a `package::module::function` path) with the
+    /// address of the package it refers to. Fails if a name is not in the registry, or only its
+    /// unversioned entry is, because versions cannot be resolved without querying the network.
+    pub fn replace_names(&self, s: &str) -> anyhow::Result<String> {
+        let mut replaced = String::with_capacity(s.len());
+        let mut last_match = 0;
+
+        for m in NAME_UNBOUND_REG.find_iter(s) {
+            replaced.push_str(&s[last_match..m.start()]);
+            replaced.push_str(&self.address_for(m.as_str())?.to_string());
+            last_match = m.end();
+        }
+
+        replaced.push_str(&s[last_match..]);
+        Ok(replaced)
+    }
+
+    /// The address of exactly the package that `name` refers to. Fails if `name` is not in the
+    /// registry, or only its unversioned entry is, because versions cannot be resolved without
+    /// querying the network.
+    pub fn address_for(&self, name: &str) -> anyhow::Result<SuiAddress> {
+        match self.resolve(name) {
+            Some((address, None)) => Ok(address),
+            Some((_, Some(version))) => Err(anyhow!(
+                "Move Registry: Version {version} of {name} is not in the local registry"
+            )),
+            None => Err(anyhow!(
+                "Move Registry: The name {name} was not found in the local registry"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APP_V1: &str = "0x0000000000000000000000000000000000000000000000000000000000000a01";
+    const APP_V2: &str = "0x0000000000000000000000000000000000000000000000000000000000000a02";
+    const OTHER: &str = "0x00000000000000000000000000000000000000000000000000000000000000b1";
+
+    fn registry() -> LocalMoveRegistry {
+        LocalMoveRegistry::from_toml(&format!(
+            r#" [packages]
+                "@org/app" = "{APP_V2}"
+                "@org/app/1" = "{APP_V1}"
+                "sub@org/other-app" = "{OTHER}"
+            "#
+        ))
+        .unwrap()
+    }
+
+    fn address(s: &str) -> SuiAddress {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn resolve_names() {
+        let registry = registry();
+        assert_eq!(registry.resolve("@org/app"), Some((address(APP_V2), None)));
+        assert_eq!(
+            registry.resolve("@org/app/1"),
+            Some((address(APP_V1), None))
+        );
+        assert_eq!(
+            registry.resolve("@org/app/3"),
+            Some((address(APP_V2), Some(3)))
+        );
+        assert_eq!(registry.resolve("@org/unknown"), None);
+        assert_eq!(registry.resolve("sub@org/other-app/x"), None);
+    }
+
+    #[test]
+    fn replace_names_in_type() {
+        let registry = registry();
+        let replaced = registry
+            .replace_names("@org/app::m::T<@org/app/1::m::U, sub@org/other-app::n::V>")
+            .unwrap();
+
+        assert_eq!(
+            replaced,
+            format!("{APP_V2}::m::T<{APP_V1}::m::U, {OTHER}::n::V>")
+        );
+
+        // Strings without names are left alone.
+        assert_eq!(
+            registry.replace_names("0x2::coin::Coin").unwrap(),
+            "0x2::coin::Coin"
+        );
+    }
+
+    #[test]
+    fn names_at_start() {
+        assert_eq!(name_prefix("@org/app::m::f"), Some("@org/app"));
+        assert_eq!(name_prefix("@org/app/2::m::T"), Some("@org/app/2"));
+        assert_eq!(name_prefix("sub@org/other-app"), Some("sub@org/other-app"));
+        assert_eq!(name_prefix("@0x2"), None);
+        assert_eq!(name_prefix("0x2::coin::Coin<@org/app::m::T>"), None);
+    }
+
+    #[test]
+    fn replace_names_errors() {
+        let registry = registry();
+        assert!(registry.replace_names("@org/unknown::m::f").is_err());
+        assert!(registry.replace_names("@org/app/3::m::f").is_err());
+    }
+
+    #[test]
+    fn invalid_names() {
+        for name in ["org/app", "@org", "@Org/app", "@org/app/v1"] {
+            let toml = format!("[packages]\n\"{name}\" = \"{APP_V1}\"");
+            assert!(LocalMoveRegistry::from_toml(&toml).is_err(), "{name}");
+        }
+    }
+}
@@ -133,6 +133,8 @@ pub struct SuiTestAdapter {
     default_account: TestAccount,
     default_syntax: SyntaxChoice,
     object_enumeration: BiBTreeMap<ObjectID, FakeID>,
+    /// Digests of the transactions executed by each task, by task number.
+    digest_enumeration: BTreeMap<u64, TransactionDigest>,
     next_fake: (u64, u64),
     gas_price: u64,
     pub(crate) staged_modules: BTreeMap<Symbol, StagedPackage>,
@@ -367,6 +369,7 @@ impl<'a> MoveTestAdapter<'a> for SuiTestAdapter {
             default_account,
             default_syntax,
             object_enumeration: BiBTreeMap::new(),
+            digest_enumeration: BTreeMap::new(),
             next_fake: (0, 0),
             // TODO: make this configurable
             gas_price: default_gas_price.unwrap_or(DEFAULT_GAS_PRICE),
@@ -1203,6 +1206,10 @@ impl<'a> SuiTestAdapter {
             }
         }
 
+        for (task, digest) in &self.digest_enumeration {
+            variables.insert(format!("digest_{task}"), digest.to_string());
+        }
+
         for (idx, s) in cursors.iter().enumerate() {
             // an object cursor may be either @{obj_x_y} or @{obj_x_y,n}
             // if the former, then use highest_checkpoint
@@ -1466,6 +1473,7 @@ impl<'a> SuiTestAdapter {
             .contains_shared_object();
         let (effects, error_opt) = self.executor.execute_txn(transaction).await?;
         let digest = effects.transaction_digest();
+        self.digest_enumeration.insert(self.next_fake.0, *digest);
         let mut created_ids: Vec<_> = effects
             .created()
             .iter()
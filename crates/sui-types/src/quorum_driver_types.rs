@@ -7,6 +7,7 @@ use std::collections::BTreeMap;
 use crate::base_types::{AuthorityName, EpochId, ObjectRef, TransactionDigest};
 use crate::committee::StakeUnit;
 use crate::crypto::{AuthorityStrongQuorumSignInfo, ConciseAuthorityPublicKeyBytes};
+use crate::digests::TransactionEffectsDigest;
 use crate::effects::{
     CertifiedTransactionEffects, TransactionEffects, TransactionEvents,
     VerifiedCertifiedTransactionEffects,
@@ -14,6 +15,7 @@ use crate::effects::{
 use crate::error::SuiError;
 use crate::messages_checkpoint::CheckpointSequenceNumber;
 use crate::object::Object;
+use crate::storage::InputKey;
 use crate::transaction::{Transaction, VerifiedTransaction};
 use serde::{Deserialize, Serialize};
 use strum::AsRefStr;
@@ -203,3 +205,29 @@ impl FinalizedEffects {
         }
     }
 }
+
+/// The furthest stage a transaction has reached on its way to finality, as observed by the node
+/// it was submitted to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionLifecycleStatus {
+    /// The transaction is being submitted to validators to collect a certificate. `attempts` is
+    /// the number of times it has been sent so far, including retries.
+    Submitted { attempts: u32 },
+    /// A certificate was formed and the node is waiting for validators to return effects.
+    Certified { attempts: u32 },
+    /// The certificate is enqueued for local execution but some of its inputs are not available
+    /// on this node yet.
+    WaitingOnInputs { missing_inputs: Vec<InputKey> },
+    /// All inputs are available and the certificate is executing locally.
+    Executing,
+    /// The transaction was executed locally but is not part of an executed checkpoint yet.
+    Executed {
+        effects_digest: TransactionEffectsDigest,
+    },
+    /// The transaction is part of a checkpoint that this node has executed.
+    Finalized {
+        effects_digest: TransactionEffectsDigest,
+        epoch: EpochId,
+        checkpoint: CheckpointSequenceNumber,
+    },
+}
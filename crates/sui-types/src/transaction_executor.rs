@@ -1,9 +1,15 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::base_types::TransactionDigest;
+use crate::effects::{TransactionEffects, TransactionEvents};
+use crate::error::SuiResult;
+use crate::messages_checkpoint::CheckpointSequenceNumber;
 use crate::quorum_driver_types::ExecuteTransactionRequestV3;
 use crate::quorum_driver_types::ExecuteTransactionResponseV3;
 use crate::quorum_driver_types::QuorumDriverError;
+use crate::quorum_driver_types::TransactionLifecycleStatus;
+use crate::transaction::TransactionData;
 
 /// Trait to define the interface for how the REST service interacts with a a QuorumDriver or a
 /// simulated transaction executor.
@@ -14,4 +20,19 @@ pub trait TransactionExecutor: Send + Sync {
         request: ExecuteTransactionRequestV3,
         client_addr: Option<std::net::SocketAddr>,
     ) -> Result<ExecuteTransactionResponseV3, QuorumDriverError>;
+
+    /// Returns how far `digest` has progressed towards finality, or `None` if the transaction is
+    /// not known to this executor.
+    fn transaction_status(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<Option<TransactionLifecycleStatus>>;
+
+    /// Executes `transaction` against the object versions as of the end of `checkpoint`, without
+    /// committing anything, and returns the effects and events it would have produced.
+    fn simulate_transaction_at_checkpoint(
+        &self,
+        transaction: TransactionData,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<(TransactionEffects, TransactionEvents)>;
 }
@@ -37,6 +37,7 @@ pub const JSON: &str = "json";
 pub const DRY_RUN: &str = "dry-run";
 pub const SERIALIZE_UNSIGNED: &str = "serialize-unsigned-transaction";
 pub const SERIALIZE_SIGNED: &str = "serialize-signed-transaction";
+pub const MOVE_REGISTRY: &str = "move-registry";
 
 // Types
 pub const U8: &str = "u8";
@@ -76,6 +77,7 @@ pub const COMMANDS: &[&str] = &[
     DRY_RUN,
     SERIALIZE_UNSIGNED,
     SERIALIZE_SIGNED,
+    MOVE_REGISTRY,
 ];
 
 pub fn is_keyword(s: &str) -> bool {
@@ -112,6 +114,8 @@ pub struct ProgramMetadata {
     pub json_set: bool,
     pub dry_run_set: bool,
     pub gas_budget: Option<Spanned<u64>>,
+    /// Path to the file that Move Registry names in the program are resolved with.
+    pub move_registry: Option<Spanned<String>>,
 }
 
 /// A parsed module access consisting of the address, module name, and function name.
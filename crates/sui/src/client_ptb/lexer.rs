@@ -1,6 +1,8 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use sui_transaction_builder::move_registry::name_prefix;
+
 use crate::sp;
 
 use super::{
@@ -215,6 +217,13 @@ impl<'l, I: Iterator<Item = &'l str>> Iterator for Lexer<'l, I> {
             return Some(self.done(T::Eof));
         };
 
+        // Move Registry names contain characters that are not allowed elsewhere (like '/'), so
+        // they are recognised as a whole before anything else.
+        let buf = self.buf;
+        if let Some(name) = name_prefix(buf).and_then(|n| self.eat_prefix(n)) {
+            return Some(name.map(|src| Lexeme(T::MvrName, src)));
+        }
+
         macro_rules! token {
             ($t:expr) => {{
                 self.bump();
@@ -318,6 +327,18 @@ impl<'l, I: Iterator<Item = &'l str>> Iterator for Lexer<'l, I> {
                         file.widen(c).map(|src| Lexeme(T::Upgrade, src))
                     }
 
+                    sp!(_, "move-registry") => {
+                        if let Some(next) = self.peek() {
+                            break 'command self.unexpected(next);
+                        }
+
+                        let Some(file) = self.eat_token() else {
+                            break 'command self.done(T::EarlyEof);
+                        };
+
+                        file.widen(c).map(|src| Lexeme(T::MoveRegistry, src))
+                    }
+
                     sp!(_, _) => ident.widen(c).map(|src| Lexeme(T::Command, src)),
                 }
             }
@@ -510,6 +531,19 @@ mod tests {
         insta::assert_debug_snapshot!(lex(unexpected));
     }
 
+    #[test]
+    fn mvr_names() {
+        let names = vec![
+            "@org/app::m::f<@org/app/2::m::T>",
+            "sub@org/other-app::m::T",
+            "'@org/app'",
+            "--move-registry",
+            "registry.toml",
+        ];
+
+        insta::assert_debug_snapshot!(lex(names));
+    }
+
     #[test]
     fn unexpected_random_chars() {
         let unexpected = vec!["4 * 5"];
@@ -43,6 +43,7 @@ struct ProgramParsingState {
     dry_run_set: bool,
     gas_object_id: Option<Spanned<ObjectID>>,
     gas_budget: Option<Spanned<u64>>,
+    move_registry: Option<Spanned<String>>,
 }
 
 impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
@@ -65,6 +66,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 dry_run_set: false,
                 gas_object_id: None,
                 gas_budget: None,
+                move_registry: None,
             },
         })
     }
@@ -144,6 +146,21 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     Ok(sp.wrap(ParsedPTBCommand::Publish(src)))
                 }),
 
+                L(T::MoveRegistry, file) => {
+                    let file = sp.wrap(file.to_owned());
+                    if let Some(other) = self.state.move_registry.replace(file.clone()) {
+                        self.state.errors.extend([
+                            err!(
+                                other.span,
+                                "Multiple Move Registry files found. File first set here.",
+                            ),
+                            err!(file.span => help: {
+                                "PTBs can be resolved against at most one Move Registry file."
+                            }, "File set again here."),
+                        ]);
+                    }
+                }
+
                 L(T::Upgrade, src) => command!({
                     let src = sp.wrap(src.to_owned());
                     let cap = try_!(self.parse_argument());
@@ -208,6 +225,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                     json_set: self.state.json_set,
                     dry_run_set: self.state.dry_run_set,
                     gas_budget: self.state.gas_budget,
+                    move_registry: self.state.move_registry,
                 },
             ))
         } else {
@@ -482,7 +500,7 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
                 sp.wrap(ParsedType::Vector(Box::new(ty)))
             }
 
-            L(T::Ident | T::Number | T::HexNumber, _) => 'fq: {
+            L(T::Ident | T::Number | T::HexNumber | T::MvrName, _) => 'fq: {
                 let sp!(_, module_access) = self.parse_module_access()?;
                 let sp!(_, address) = module_access.address;
                 let sp!(_, module_name) = module_access.module_name;
@@ -646,7 +664,9 @@ impl<'a, I: Iterator<Item = &'a str>> ProgramParser<'a, I> {
 
         let sp!(sp, lexeme) = self.peek();
         let addr = match lexeme {
-            L(T::Ident, name) => {
+            // Move Registry names are kept as named addresses until the program is resolved
+            // against a registry.
+            L(T::Ident | T::MvrName, name) => {
                 self.bump();
                 ParsedAddress::Named(name.to_owned())
             }
@@ -4,23 +4,30 @@
 use crate::{
     client_commands::{dry_run_or_execute_or_serialize, Opts, OptsWithGas, SuiClientCommandResult},
     client_ptb::{
-        ast::{ParsedProgram, Program},
+        ast::{ParsedPTBCommand, ParsedProgram, Program},
         builder::PTBBuilder,
-        error::{build_error_reports, PTBError},
+        error::{build_error_reports, PTBError, Span, Spanned},
         token::{Lexeme, Token},
     },
     displays::Pretty,
-    sp,
+    err, sp,
 };
 
 use super::{ast::ProgramMetadata, lexer::Lexer, parser::ProgramParser};
 use anyhow::{anyhow, ensure, Error};
 use clap::{arg, Args, ValueHint};
+use move_command_line_common::{
+    address::{NumericalAddress, ParsedAddress},
+    parser::NumberFormat,
+    types::{ParsedStructType, ParsedType},
+};
 use move_core_types::account_address::AccountAddress;
 use serde::Serialize;
+use std::path::Path;
 use sui_json_rpc_types::{SuiExecutionStatus, SuiTransactionBlockEffectsAPI};
 use sui_keys::keystore::AccountKeystore;
 use sui_sdk::{wallet_context::WalletContext, SuiClient};
+use sui_transaction_builder::move_registry::LocalMoveRegistry;
 use sui_types::{
     digests::TransactionDigest,
     gas::GasCostSummary,
@@ -67,7 +74,7 @@ impl PTB {
         }
 
         // Tokenize and parse to get the program
-        let (program, program_metadata) = match ProgramParser::new(tokens)
+        let (mut program, program_metadata) = match ProgramParser::new(tokens)
             .map_err(|e| vec![e])
             .and_then(|parser| parser.parse())
         {
@@ -99,6 +106,18 @@ impl PTB {
             return Ok(());
         }
 
+        if let Err(errors) =
+            resolve_move_registry_names(&mut program, program_metadata.move_registry.as_ref())
+        {
+            let suffix = if errors.len() > 1 { "s" } else { "" };
+            let rendered = build_error_reports(&source_string, errors);
+            eprintln!("Encountered error{suffix} when resolving Move Registry names in PTB:");
+            for e in rendered.iter() {
+                eprintln!("{:?}", e);
+            }
+            anyhow::bail!("Could not build PTB due to previous error{suffix}");
+        }
+
         let client = context.get_client().await?;
 
         let (res, warnings) = Self::build_ptb(program, context, client).await;
@@ -242,6 +261,91 @@ impl PTB {
     }
 }
 
+/// Replaces the Move Registry names (e.g. `@org/app`) that `program` uses as addresses, in the
+/// packages of its move calls and in its types, with the package addresses they map to in the
+/// `registry` file. Names can only appear in these positions, so string arguments that look like
+/// names are left untouched.
+pub fn resolve_move_registry_names(
+    program: &mut Program,
+    registry: Option<&Spanned<String>>,
+) -> Result<(), Vec<PTBError>> {
+    let registry = match registry {
+        None => None,
+        Some(sp!(file_loc, file)) => Some(
+            LocalMoveRegistry::from_file(Path::new(file))
+                .map_err(|e| vec![err!(*file_loc, "Failed to load Move Registry file: {e}")])?,
+        ),
+    };
+
+    let mut errors = vec![];
+    let mut resolve = |address: &mut ParsedAddress, loc: Span| {
+        // Identifiers can't contain an '@', so any named address that does is a registry name.
+        let ParsedAddress::Named(name) = address else {
+            return;
+        };
+
+        if !name.contains('@') {
+            return;
+        }
+
+        let Some(registry) = &registry else {
+            errors.push(err!(loc => help: {
+                "Pass a registry file with --move-registry <FILE>"
+            }, "Cannot resolve Move Registry name '{name}' without a registry file"));
+            return;
+        };
+
+        match registry.address_for(name) {
+            Ok(resolved) => {
+                *address = ParsedAddress::Numerical(NumericalAddress::new(
+                    resolved.to_inner(),
+                    NumberFormat::Hex,
+                ));
+            }
+            Err(e) => errors.push(err!(loc, "{e}")),
+        }
+    };
+
+    for sp!(_, command) in &mut program.commands {
+        match command {
+            ParsedPTBCommand::MakeMoveVec(sp!(ty_loc, ty), _) => {
+                resolve_type_addresses(ty, &mut |a| resolve(a, *ty_loc));
+            }
+
+            ParsedPTBCommand::MoveCall(sp!(_, access), ty_args, _) => {
+                resolve(&mut access.address.value, access.address.span);
+                if let Some(sp!(ty_loc, ty_args)) = ty_args {
+                    for ty in ty_args {
+                        resolve_type_addresses(ty, &mut |a| resolve(a, *ty_loc));
+                    }
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Call `f` on every address that appears in `ty`.
+fn resolve_type_addresses(ty: &mut ParsedType, f: &mut impl FnMut(&mut ParsedAddress)) {
+    match ty {
+        ParsedType::Vector(ty) => resolve_type_addresses(ty, f),
+        ParsedType::Struct(ParsedStructType { fq_name, type_args }) => {
+            f(&mut fq_name.module.address);
+            for ty in type_args {
+                resolve_type_addresses(ty, f);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Convert a vector of shell tokens into a single string, with each shell token separated by a
 /// space with each command starting on a new line.
 /// NB: we add a space to the end of the source string to ensure that for unexpected EOF
@@ -411,4 +515,92 @@ pub fn ptb_description() -> clap::Command {
             --"json"
             "Return command outputs in json format."
         ))
+        .arg(arg!(
+            --"move-registry" <FILE>
+            "Resolve Move Registry names (e.g. @org/app or @org/app/2) in the PTB to the package \
+            addresses they map to in the given TOML file."
+        )
+        .long_help(
+            "Resolve Move Registry names (e.g. @org/app or @org/app/2) in the PTB to the package \
+            addresses they map to in the given TOML file, instead of looking them up on-chain. \
+            This is useful for testing named packages on localnet.\
+            \n\nExamples:\
+            \n --move-registry registry.toml --move-call @org/app::module::function <@org/app::module::Type>\
+            \n\nWhere registry.toml contains:\
+            \n [packages]\
+            \n \"@org/app\" = \"0x...\""
+        ).value_hint(ValueHint::FilePath))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use crate::client_ptb::ast::Argument;
+
+    const APP: &str = "0x0000000000000000000000000000000000000000000000000000000000000a01";
+
+    fn parse(input: &str) -> (Program, ProgramMetadata) {
+        let args = shlex::split(input).unwrap();
+        PTB::parse_ptb_commands(args).unwrap()
+    }
+
+    fn registry_file() -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "[packages]\n\"@org/app\" = \"{APP}\"").unwrap();
+        file
+    }
+
+    #[test]
+    fn resolve_names_in_addresses_and_types() {
+        let file = registry_file();
+        let (mut program, metadata) = parse(&format!(
+            "--move-registry {} \
+             --move-call @org/app::m::f <@org/app::m::T<vector<@org/app::m::U>>> '\"@org/app\"' \
+             --make-move-vec <@org/app::m::T> []",
+            file.path().display(),
+        ));
+
+        resolve_move_registry_names(&mut program, metadata.move_registry.as_ref()).unwrap();
+
+        let app = ParsedAddress::Numerical(NumericalAddress::parse_str(APP).unwrap());
+        let ty = |t: &str| ParsedType::parse(&t.replace("@org/app", APP)).unwrap();
+
+        let ParsedPTBCommand::MoveCall(access, Some(ty_args), args) = &program.commands[0].value
+        else {
+            panic!("Expected a move call");
+        };
+
+        assert_eq!(access.value.address.value, app);
+        assert_eq!(
+            ty_args.value,
+            vec![ty("@org/app::m::T<vector<@org/app::m::U>>")]
+        );
+        assert!(matches!(&args[0].value, Argument::String(s) if s == "@org/app"));
+
+        let ParsedPTBCommand::MakeMoveVec(elem, _) = &program.commands[1].value else {
+            panic!("Expected make-move-vec");
+        };
+
+        assert_eq!(elem.value, ty("@org/app::m::T"));
+    }
+
+    #[test]
+    fn resolve_names_errors() {
+        let (mut program, metadata) = parse("--move-call @org/app::m::f");
+        let errors =
+            resolve_move_registry_names(&mut program, metadata.move_registry.as_ref()).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].help.is_some());
+
+        let file = registry_file();
+        let (mut program, metadata) = parse(&format!(
+            "--move-registry {} --move-call @org/missing::m::f <@org/missing::m::T>",
+            file.path().display(),
+        ));
+        let errors =
+            resolve_move_registry_names(&mut program, metadata.move_registry.as_ref()).unwrap_err();
+        assert_eq!(errors.len(), 2);
+    }
 }
@@ -45,6 +45,8 @@ pub enum Token {
     At,
     /// .
     Dot,
+    /// [a-z0-9.-]*@[a-z0-9-]+/[a-z0-9-]+(/[0-9]+)?
+    MvrName,
 
     /// End of input.
     Eof,
@@ -55,11 +57,13 @@ pub enum Token {
     EarlyEof,
 
     // The following tokens are special -- they consume multiple shell tokens, to ensure we capture
-    // the path for a publish or an upgrade command.
+    // the path for a publish or an upgrade command, or for a Move Registry file.
     /// --publish \<shell-token\>
     Publish,
     /// --upgraded \<shell-token\>
     Upgrade,
+    /// --move-registry \<shell-token\>
+    MoveRegistry,
 }
 
 impl<'l> Lexeme<'l> {
@@ -76,7 +80,14 @@ impl<'l> Lexeme<'l> {
 
     /// Returns true if this lexeme signifies the end of the current command.
     pub fn is_command_end(&self) -> bool {
-        self.is_terminal() || [Token::Command, Token::Publish, Token::Upgrade].contains(&self.0)
+        self.is_terminal()
+            || [
+                Token::Command,
+                Token::Publish,
+                Token::Upgrade,
+                Token::MoveRegistry,
+            ]
+            .contains(&self.0)
     }
 }
 
@@ -101,11 +112,13 @@ impl<'a> fmt::Display for Lexeme<'a> {
             T::RAngle => write!(f, "'>'"),
             T::At => write!(f, "'@'"),
             T::Dot => write!(f, "'.'"),
+            T::MvrName => write!(f, "Move Registry name '{}'", self.1),
             T::Unexpected => write!(f, "input {:?}", self.1),
             T::UnfinishedString => write!(f, "unfinished string {:?}", format!("{}...", self.1)),
             T::EarlyEof | T::Eof => write!(f, "end of input"),
             T::Publish => write!(f, "command '--publish {:?}'", self.1),
             T::Upgrade => write!(f, "command '--upgrade {:?}'", self.1),
+            T::MoveRegistry => write!(f, "command '--move-registry {:?}'", self.1),
         }
     }
 }
@@ -130,12 +143,14 @@ impl fmt::Display for Token {
             T::RAngle => write!(f, "'>'"),
             T::At => write!(f, "'@'"),
             T::Dot => write!(f, "'.'"),
+            T::MvrName => write!(f, "a Move Registry name"),
             T::Eof => write!(f, "end of input"),
             T::Unexpected => write!(f, "unexpected input"),
             T::UnfinishedString => write!(f, "an unfinished string"),
             T::EarlyEof => write!(f, "unexpected end of input"),
             T::Publish => write!(f, "a '--publish' command"),
             T::Upgrade => write!(f, "an '--upgrade' command"),
+            T::MoveRegistry => write!(f, "a '--move-registry' command"),
         }
     }
 }
@@ -3,7 +3,7 @@
 
 use crate::{
     client_ptb::{
-        ast::{GAS_BUDGET, GAS_COIN, JSON, SUMMARY, WARN_SHADOWS},
+        ast::{GAS_BUDGET, GAS_COIN, JSON, MOVE_REGISTRY, SUMMARY, WARN_SHADOWS},
         ptb::PTBPreview,
     },
     sp,
@@ -30,6 +30,9 @@ impl<'a> Display for PTBPreview<'a> {
         if let Some(gas_coin_id) = self.program_metadata.gas_object_id {
             builder.push_record([GAS_COIN, gas_coin_id.value.to_string().as_str()]);
         }
+        if let Some(move_registry) = &self.program_metadata.move_registry {
+            builder.push_record([MOVE_REGISTRY, move_registry.value.as_str()]);
+        }
         if self.program_metadata.json_set {
             builder.push_record([JSON, "true"]);
         }